@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use ofkt::core::search::SearchEngine;
-use ofkt::data::models::FileAlias;
+use ofkt::data::models::{FileAlias, OpenAction};
 use std::path::PathBuf;
 use chrono::{Utc, Duration};
 
@@ -16,6 +16,14 @@ fn create_test_alias(alias: &str, path: &str) -> FileAlias {
         created_at: now,
         last_accessed: now - Duration::days(100),
         is_favorite: false,
+        open_action: OpenAction::Open,
+        volume: None,
+        open_count: 0,
+        note: None,
+        reading: None,
+        order: 0,
+        is_broken: false,
+        is_unreachable: false,
     }
 }
 
@@ -1,14 +1,24 @@
 pub mod state;
 
-use state::{AppState, BrowseMode, FocusArea};
+use state::{selection_summary, AppState, BrowseMode, DirectoryViewMode, FocusArea, HistoryNavigation, SelectionClick};
 use eframe::egui;
 use log::info;
-use crate::ui::theme::Theme;
 use crate::ui::search_bar::SearchBar;
-use crate::ui::file_tree::FileTreeView;
+use crate::ui::file_tree::{EntryRowContext, FileTreeView};
+use crate::ui::file_table::{sort_for_header_click, FileTableView};
+use crate::ui::row_columns::parse_row_columns;
 use crate::ui::context_menu::{ContextMenu, MenuAction};
-use crate::core::file_manager::FileManager;
-use crate::platform::{theme_detector, TrayEvent};
+use crate::ui::command_palette::{CommandPalette, PaletteAction, PaletteOutcome};
+use crate::ui::settings::{Settings, SettingsAction};
+use crate::ui::detail_strip::AliasDetail;
+use crate::ui::keybindings::{FileAction, FileKeybindings};
+use crate::core::activation::{activate, ActivationContext, ActivationResult};
+use crate::core::alias::sorted_for_sidebar;
+use crate::core::file_manager::{open_with_action, FileManager};
+use crate::core::dir_size::SizeTaskState;
+use crate::core::thumbnail::{Thumbnail, ThumbnailStatus};
+use crate::data::models::OpenAction;
+use crate::platform::TrayEvent;
 use crate::utils::path::paths_equal;
 
 /// Ofkt アプリケーション
@@ -16,6 +26,15 @@ pub struct OfktApp {
     state: AppState,
     search_bar: SearchBar,
     file_tree: FileTreeView,
+    file_table: FileTableView,
+    command_palette: CommandPalette,
+    settings_view: Option<Settings>,
+    /// プロパティダイアログに表示中のサムネイルとそのテクスチャ
+    properties_thumbnail: Option<(std::sync::Arc<Thumbnail>, egui::TextureHandle)>,
+    /// 詳細表示の帯の内容と、その元にした選択中のエイリアス
+    alias_detail: Option<(crate::data::models::FileAlias, AliasDetail)>,
+    /// ファイル操作（コピー・切り取り・貼り付け）のキー割り当て
+    file_keybindings: FileKeybindings,
 }
 
 impl Default for OfktApp {
@@ -41,49 +60,168 @@ impl OfktApp {
             state,
             search_bar: SearchBar::new(),
             file_tree: FileTreeView::new(),
+            file_table: FileTableView::new(),
+            command_palette: CommandPalette::new(),
+            settings_view: None,
+            properties_thumbnail: None,
+            alias_detail: None,
+            file_keybindings: FileKeybindings::default(),
         }
     }
 
     /// テーマを適用
+    ///
+    /// テーマが変わった場合のみ egui に適用し直します（毎フレームの再設定によるちらつきを避ける）。
     fn apply_theme(&mut self, ctx: &egui::Context) {
-        let theme = if let Some(ref config) = self.state.config {
-            match config.theme.mode.as_str() {
-                "system" => {
-                    // システムテーマを検出
-                    theme_detector::detect_system_theme()
-                }
-                "light" => Theme::Light,
-                "dark" => Theme::Dark,
-                _ => Theme::Dark, // デフォルトはダーク
-            }
-        } else {
-            Theme::Dark
-        };
-
-        // テーマを状態に保存
-        self.state.current_theme = theme;
+        let visuals_dark_mode = ctx.style().visuals.dark_mode;
+        if let Some(theme) = self.state.theme_to_apply(std::time::Instant::now(), visuals_dark_mode) {
+            ctx.set_visuals(theme.to_visuals());
+        }
 
-        // egui にテーマを適用
-        ctx.set_visuals(theme.to_visuals());
+        // システム設定に従う場合は、OSのテーマ変更を拾えるよう定期的に再描画
+        if self.state.follows_system_theme() {
+            ctx.request_repaint_after(self.state.system_theme_cache.interval());
+        }
     }
 
     /// ウィンドウの表示/非表示を切り替える
     fn toggle_window_visibility(&mut self, ctx: &egui::Context) {
-        self.state.is_window_visible = !self.state.is_window_visible;
-
-        if self.state.is_window_visible {
+        if self.state.toggle_window_visible() {
             // ウィンドウを表示（最小化を解除）
             ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
             ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
         } else {
             // ウィンドウを非表示（最小化）
             ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            self.persist_last_queries();
         }
 
         log::info!("ウィンドウ表示切り替え: {}",
             if self.state.is_window_visible { "表示" } else { "非表示" });
     }
 
+    /// 最後の検索クエリを設定ファイルに保存（設定で有効な場合のみ）
+    fn persist_last_queries(&mut self) {
+        if self.state.capture_last_queries() {
+            self.state.save_config_or_report();
+        }
+    }
+
+    /// サイドバーの幅を設定ファイルに保存（幅の変更をドラッグし終えたときのみ）
+    fn persist_sidebar_width(&mut self, ctx: &egui::Context, width: f32) {
+        if ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        if self.state.record_sidebar_width(width) {
+            self.state.save_config_or_report();
+        }
+    }
+
+    /// サイドバーのエイリアスを1件描画（クリックでそのパスに移動）
+    fn render_sidebar_alias(&mut self, ui: &mut egui::Ui, alias: &crate::data::models::FileAlias, alias_index: usize) {
+        let button_text = if alias.is_favorite {
+            format!("⭐ {}", alias.alias)
+        } else {
+            alias.alias.clone()
+        };
+        // パスが見つからないエイリアスは警告色で表示する
+        let button_text = if alias.is_broken {
+            egui::RichText::new(format!("⚠ {}", button_text)).color(ui.visuals().error_fg_color)
+        } else {
+            egui::RichText::new(button_text)
+        };
+
+        let button = egui::Button::new(button_text)
+            .selected(self.state.current_focus_area == FocusArea::Sidebar
+                && self.state.selected_sidebar_index == Some(alias_index));
+
+        if ui.add(button).clicked() {
+            // エイリアスのパスに移動
+            if let Err(e) = self.state.init_directory_browser(alias.path.clone()) {
+                log::error!("エイリアスパスへの移動に失敗: {}", e);
+            } else {
+                // 検索バーをクリア（設定によっては絞り込みを続ける）
+                self.state.update_directory_search_after_navigation();
+                log::info!("エイリアス「{}」を開きました", alias.alias);
+            }
+        }
+    }
+
+    /// 選択モードの一括操作バーを描画
+    ///
+    /// # 引数
+    /// * `visible_paths` - 「すべて選択」の対象となる表示中の項目のパス
+    fn render_selection_toolbar(&mut self, ui: &mut egui::Ui, visible_paths: &[std::path::PathBuf]) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("{} 件選択中", self.state.checked_paths.len()));
+            if ui.button("すべて選択").clicked() {
+                self.state.check_all(visible_paths.iter().cloned());
+            }
+            if ui.button("選択解除").clicked() {
+                self.state.uncheck_all();
+            }
+
+            ui.separator();
+
+            let has_checked = !self.state.checked_paths.is_empty();
+            if ui.add_enabled(has_checked, egui::Button::new("コピー")).clicked() {
+                let count = self.state.bulk_copy();
+                self.state.toasts.push(crate::app::state::OperationResultMessage::success(
+                    format!("{} 件をコピーしました", count)
+                ));
+            }
+            if ui.add_enabled(has_checked, egui::Button::new("切り取り")).clicked() {
+                let count = self.state.bulk_cut();
+                self.state.toasts.push(crate::app::state::OperationResultMessage::success(
+                    format!("{} 件を切り取りました", count)
+                ));
+            }
+            if ui.add_enabled(has_checked, egui::Button::new("削除")).clicked() {
+                self.state.bulk_delete();
+            }
+
+            ui.separator();
+
+            ui.add(
+                egui::TextEdit::singleline(&mut self.state.bulk_tag_input)
+                    .hint_text("タグ")
+                    .desired_width(80.0)
+            );
+            let can_tag = has_checked && !self.state.bulk_tag_input.trim().is_empty();
+            if ui.add_enabled(can_tag, egui::Button::new("タグ付け")).clicked() {
+                let tag = std::mem::take(&mut self.state.bulk_tag_input);
+                let updated = self.state.bulk_tag(&tag);
+                if updated == 0 {
+                    self.state.toasts.push(crate::app::state::OperationResultMessage::warning(
+                        "タグを付けるエイリアスがありません（エイリアスに登録されていない項目は対象外です）".to_string()
+                    ));
+                } else if let Err(e) = self.state.alias_manager.save() {
+                    log::error!("エイリアスの保存に失敗: {}", e);
+                    self.state.toasts.push(crate::app::state::OperationResultMessage::error(
+                        format!("エイリアスの保存に失敗しました: {}", e)
+                    ));
+                } else {
+                    self.state.toasts.push(crate::app::state::OperationResultMessage::success(
+                        format!("{} 件のエイリアスにタグ「{}」を付けました", updated, tag.trim())
+                    ));
+                }
+            }
+        });
+    }
+
+    /// 選択モードの各行をチェックボックス付きで描画
+    ///
+    /// # 引数
+    /// * `rows` - (パス, 表示名) のリスト
+    fn render_checkbox_rows(&mut self, ui: &mut egui::Ui, rows: &[(std::path::PathBuf, String)]) {
+        for (path, label) in rows {
+            let mut checked = self.state.is_checked(path);
+            if ui.checkbox(&mut checked, label.as_str()).changed() {
+                self.state.set_checked(path, checked);
+            }
+        }
+    }
+
     /// クリップボードからファイルをペースト（ディレクトリモード用）
     fn handle_paste(&mut self) {
         let current_dir = if let Some(ref browser) = self.state.directory_browser {
@@ -96,11 +234,37 @@ impl OfktApp {
         self.handle_paste_to_dir(current_dir);
 
         // ディレクトリをリロード
-        if let Some(ref mut browser) = self.state.directory_browser {
-            if let Err(e) = browser.reload() {
-                log::error!("ディレクトリリロード失敗: {}", e);
-            }
+        if let Err(e) = self.state.reload_directory() {
+            log::error!("ディレクトリリロード失敗: {}", e);
+        }
+    }
+
+    /// テキストクリップボードのパスをペースト元として取り込む
+    ///
+    /// 存在するパスをコピーモードでクリップボードに入れ、通常のペースト処理に渡します。
+    /// パスとして扱えない行は警告を出して無視します。
+    fn paste_text_paths(&mut self, text: &str) {
+        let parsed = crate::core::clipboard::parse_clipboard_paths(text);
+
+        for line in &parsed.invalid_lines {
+            log::warn!("パスとして扱えない行を無視しました: {}", line);
+        }
+        if !parsed.invalid_lines.is_empty() {
+            self.state.toasts.push(
+                crate::app::state::OperationResultMessage::warning(
+                    format!("{}行をパスとして認識できなかったため無視しました", parsed.invalid_lines.len())
+                )
+            );
         }
+
+        if parsed.paths.is_empty() {
+            log::info!("テキストクリップボードに有効なパスがありません");
+            return;
+        }
+
+        log::info!("テキストクリップボードから {} 個のパスを取り込み", parsed.paths.len());
+        self.state.clipboard_state.copy(parsed.paths);
+        self.state.pending_file_paste = true;
     }
 
     /// 指定ディレクトリにクリップボードからファイルをペースト
@@ -152,8 +316,8 @@ impl OfktApp {
             log::warn!("=== 事前検証フェーズ失敗 === エラー数: {}", validation_errors.len());
             log::warn!("検証エラー: {}", validation_errors.join(", "));
             let error_message = format!("ペースト操作を実行できません:\n{}", validation_errors.join("\n"));
-            self.state.paste_result_message = Some(
-                crate::app::state::PasteResultMessage::new(
+            self.state.toasts.push(
+                crate::app::state::OperationResultMessage::with_type(
                     error_message,
                     crate::app::state::MessageType::Error
                 )
@@ -165,28 +329,49 @@ impl OfktApp {
 
         // ペースト前に上書きされるファイルをチェック
         let mut files_to_overwrite = Vec::new();
+        let destinations = crate::core::file_manager::paste_destinations(
+            &paths, &dest_dir, self.state.preserve_paste_structure(),
+        );
 
-        for src_path in paths.iter() {
-            if let Some(file_name) = src_path.file_name() {
-                let dest_path = dest_dir.join(file_name);
-                if dest_path.exists() && src_path != &dest_path {
-                    files_to_overwrite.push(dest_path);
-                }
+        for (src_path, dest_path) in paths.iter().zip(destinations) {
+            if dest_path.exists() && src_path != &dest_path {
+                files_to_overwrite.push(crate::app::state::OverwriteConflict::from_paths(
+                    src_path.clone(),
+                    dest_path,
+                ));
             }
         }
 
+        // 切り取りの場合、システム・隠しファイルの移動は確認を求める
+        let sensitive_files: Vec<std::path::PathBuf> = if mode == crate::core::clipboard::ClipboardMode::Cut {
+            paths.iter()
+                .filter(|p| crate::core::file_manager::is_system_or_hidden(p))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // 作成される項目数が多すぎる場合は確認を求める
+        let large_item_count = self.state.large_paste_count(&paths);
+
         // 上書き対象がある場合、確認ダイアログを表示
-        if !files_to_overwrite.is_empty() {
-            log::info!("上書き確認ダイアログ表示: {} 個のファイルが上書き対象", files_to_overwrite.len());
+        if !files_to_overwrite.is_empty() || !sensitive_files.is_empty() || large_item_count.is_some() {
+            log::info!("上書き確認ダイアログ表示: {} 個のファイルが上書き対象, システム・隠しファイル {} 個, 項目数 {:?}",
+                files_to_overwrite.len(), sensitive_files.len(), large_item_count);
             self.state.overwrite_confirmation_dialog = Some(
-                crate::app::state::OverwriteConfirmationDialog {
-                    files: files_to_overwrite,
-                    pending_paste: crate::app::state::PendingPasteOperation {
+                crate::app::state::OverwriteConfirmationDialog::new(
+                    files_to_overwrite,
+                    sensitive_files,
+                    large_item_count,
+                    crate::app::state::PendingPasteOperation {
                         src_paths: paths.clone(),
                         dest_dir: dest_dir.clone(),
                         mode,
+                        conflict_policy: self.state.conflict_policy(),
+                        resolutions: std::collections::HashMap::new(),
                     },
-                }
+                )
             );
             return; // 確認待ちで処理を保留
         }
@@ -198,100 +383,96 @@ impl OfktApp {
             src_paths: paths,
             dest_dir,
             mode,
+            conflict_policy: self.state.conflict_policy(),
+            resolutions: std::collections::HashMap::new(),
         });
     }
 
     /// ペースト操作を実行（上書き確認をスキップ）
+    ///
+    /// 貼り付けはバックグラウンドで行い、完了すると `finish_paste_operation` で結果を反映する。
     fn execute_paste_operation(&mut self, operation: crate::app::state::PendingPasteOperation) {
-        use crate::core::clipboard::{ClipboardMode, generate_copy_name};
-
-        let file_manager = FileManager::new();
-        let paths = operation.src_paths;
-        let dest_dir = operation.dest_dir;
-        let mode = operation.mode;
-
-        log::info!("=== ペースト実行開始 === モード: {:?}, ファイル数: {}, 宛先: {}",
-            mode, paths.len(), dest_dir.display());
-
-        let mut pasted_paths = Vec::new();
-        let mut success_count = 0;
-        let mut error_count = 0;
-        let mut errors = Vec::new();
-
-        for (idx, src_path) in paths.iter().enumerate() {
-            log::debug!("[{}/{}] 処理開始: {}", idx + 1, paths.len(), src_path.display());
-            let file_name = match src_path.file_name() {
-                Some(name) => name,
-                None => {
-                    log::error!("ファイル名の取得に失敗: {}", src_path.display());
-                    error_count += 1;
-                    errors.push(format!("ファイル名の取得に失敗: {}", src_path.display()));
-                    continue;
-                }
-            };
-
-            let mut dest_path = dest_dir.join(file_name);
-
-            if src_path == &dest_path {
-                dest_path = generate_copy_name(src_path, &dest_dir);
-            }
-
-            if dest_path.exists() && src_path != &dest_path {
-                log::warn!("「{}」は既に存在します。上書きします。", file_name.to_string_lossy());
-            }
+        if self.state.paste_task.is_some() {
+            log::warn!("貼り付けを実行中のため、新しい貼り付けを受け付けません");
+            self.state.toasts.push(crate::app::state::OperationResultMessage::warning(
+                "貼り付けを実行中です。完了してから再度お試しください".to_string()
+            ));
+            return;
+        }
 
-            let file_size = src_path.metadata()
-                .map(|m| m.len())
-                .unwrap_or(0);
-            let start_time = std::time::Instant::now();
+        let destinations = crate::core::file_manager::paste_destinations(
+            &operation.src_paths, &operation.dest_dir, self.state.preserve_paste_structure(),
+        );
+        let job = crate::core::paste_task::PasteJob {
+            src_paths: operation.src_paths,
+            destinations,
+            dest_dir: operation.dest_dir,
+            mode: operation.mode,
+            conflict_policy: operation.conflict_policy,
+            resolutions: operation.resolutions,
+            copy_name_pattern: self.state.copy_name_pattern().to_string(),
+            preserve_attributes: self.state.preserve_paste_attributes(),
+        };
+        self.state.paste_task = Some(crate::core::paste_task::PasteTask::spawn(job));
+    }
 
-            match mode {
-                ClipboardMode::Copy => {
-                    log::debug!("コピー開始: {} -> {} (サイズ: {} bytes)",
-                        src_path.display(), dest_path.display(), file_size);
-                    if let Err(e) = file_manager.copy_recursive(src_path, &dest_path) {
-                        let elapsed = start_time.elapsed();
-                        log::error!("コピー失敗: {} (経過時間: {:?})", e, elapsed);
-                        error_count += 1;
-                        errors.push(format!("「{}」のコピーに失敗: {}", file_name.to_string_lossy(), e));
-                    } else {
-                        let elapsed = start_time.elapsed();
-                        log::info!("「{}」をコピーしました (サイズ: {} bytes, 時間: {:?})",
-                            file_name.to_string_lossy(), file_size, elapsed);
-                        pasted_paths.push(dest_path.clone());
-                        success_count += 1;
-                    }
-                }
-                ClipboardMode::Cut => {
-                    log::debug!("移動開始: {} -> {} (サイズ: {} bytes)",
-                        src_path.display(), dest_path.display(), file_size);
-                    if let Err(e) = file_manager.move_file(src_path, &dest_path) {
-                        let elapsed = start_time.elapsed();
-                        log::error!("移動失敗: {} (経過時間: {:?})", e, elapsed);
-                        error_count += 1;
-                        errors.push(format!("「{}」の移動に失敗: {}", file_name.to_string_lossy(), e));
-                    } else {
-                        let elapsed = start_time.elapsed();
-                        log::info!("「{}」を移動しました (サイズ: {} bytes, 時間: {:?})",
-                            file_name.to_string_lossy(), file_size, elapsed);
-                        pasted_paths.push(dest_path.clone());
-                        success_count += 1;
-                    }
-                }
-            }
+    /// バックグラウンドの貼り付けの進み具合を確認し、完了していれば結果を反映
+    fn poll_paste_task(&mut self) {
+        let Some(task) = self.state.paste_task.as_mut() else {
+            return;
+        };
+        let mode = task.mode();
+        if let Some(outcome) = task.poll() {
+            self.state.paste_task = None;
+            self.finish_paste_operation(mode, outcome);
         }
+    }
 
-        // 切り取りモードで全て成功した場合のみクリップボードをクリア
+    /// 完了した貼り付けの結果を反映（クリップボード・ハイライト・結果メッセージ）
+    fn finish_paste_operation(
+        &mut self,
+        mode: crate::core::clipboard::ClipboardMode,
+        outcome: crate::core::paste_task::PasteOutcome,
+    ) {
+        use crate::core::clipboard::ClipboardMode;
+
+        let crate::core::paste_task::PasteOutcome {
+            pasted_paths,
+            undoable_items,
+            failed_paths,
+            success_count,
+            error_count,
+            errors,
+            skipped_count,
+            declined_count,
+            cancelled,
+            files_completed,
+        } = outcome;
+
+        // 切り取りモードで全て成功した場合のみクリップボードをクリア（スキップ・取り消しがあれば保持）
         if mode == ClipboardMode::Cut {
-            if error_count == 0 {
+            if error_count == 0 && skipped_count == 0 && declined_count == 0 && !cancelled {
                 log::info!("Cutモード: 全てのファイル移動が成功したため、クリップボードをクリア");
                 self.state.clipboard_state.clear();
             } else {
-                log::warn!("Cutモード: {}個のファイル移動に失敗したため、クリップボードを保持", error_count);
+                log::warn!("Cutモード: {}個のファイル移動に失敗、{}個をスキップしたため、クリップボードを保持", error_count, skipped_count + declined_count);
             }
         }
 
-        log::info!("=== ペースト実行完了 === 成功: {}, 失敗: {}", success_count, error_count);
+        // 貼り付けた項目をまとめて1件の操作として記録（取り消し・やり直し用）
+        if !undoable_items.is_empty() {
+            let operation = match mode {
+                ClipboardMode::Copy => {
+                    let (sources, created) = undoable_items.into_iter().unzip();
+                    crate::core::operation_history::FileOperation::Copy { sources, created }
+                }
+                ClipboardMode::Cut => crate::core::operation_history::FileOperation::Move { moved: undoable_items },
+            };
+            self.state.operation_history.push(operation);
+        }
+
+        // 失敗した行にエラーバッジを表示（コピー元・コピー先のどちらが表示されていても分かるように両方）
+        self.state.record_failed_paths(failed_paths);
 
         // ペーストハイライトを設定
         if !pasted_paths.is_empty() {
@@ -300,7 +481,7 @@ impl OfktApp {
         }
 
         // 結果メッセージを設定
-        let message = if error_count == 0 {
+        let mut message = if error_count == 0 {
             format!("{}個のファイルを{}しました", success_count, if mode == ClipboardMode::Copy { "コピー" } else { "移動" })
         } else if success_count == 0 {
             format!("すべてのファイルの{}に失敗しました:\n{}", if mode == ClipboardMode::Copy { "コピー" } else { "移動" }, errors.join("\n"))
@@ -309,15 +490,32 @@ impl OfktApp {
                 success_count, if mode == ClipboardMode::Copy { "コピー" } else { "移動" }, error_count, errors.join("\n"))
         };
 
-        let message_type = if error_count == 0 {
+        if skipped_count > 0 {
+            message.push_str(&format!("\n貼り付け先の方が新しい{}個のファイルはスキップしました", skipped_count));
+        }
+        if declined_count > 0 {
+            message.push_str(&format!("\n同名のファイルがある{}個のファイルは選択どおりスキップしました", declined_count));
+        }
+        if cancelled {
+            message.push_str(&format!("\nキャンセルしました（{}個のファイルを処理済み）", files_completed));
+        }
+
+        let message_type = if error_count == 0 && skipped_count == 0 && !cancelled {
             crate::app::state::MessageType::Success
-        } else if success_count == 0 {
+        } else if error_count == 0 {
+            crate::app::state::MessageType::Warning
+        } else if success_count == 0 && !cancelled {
             crate::app::state::MessageType::Error
         } else {
             crate::app::state::MessageType::Warning
         };
 
-        self.state.paste_result_message = Some(crate::app::state::PasteResultMessage::new(message, message_type));
+        self.state.toasts.push(crate::app::state::OperationResultMessage::with_type(message, message_type));
+
+        // ディレクトリをリロード
+        if let Err(e) = self.state.reload_directory() {
+            log::error!("ディレクトリリロード失敗: {}", e);
+        }
     }
 
     /// 削除処理を実行するヘルパーメソッド
@@ -329,39 +527,337 @@ impl OfktApp {
         let file_manager = FileManager::new();
         let mut success_count = 0;
         let mut errors = Vec::new();
+        let mut failed_paths = Vec::new();
 
-        for path in paths {
-            if let Err(e) = file_manager.delete(path, permanent) {
-                log::error!("削除に失敗: {}", e);
-                errors.push(format!("{}: {}", path.file_name().unwrap_or_default().to_string_lossy(), e));
-            } else {
-                success_count += 1;
+        if permanent {
+            for path in paths {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+                // フォルダの完全削除は途中で失敗しても残りを削除し続ける
+                if path.is_dir() {
+                    let result = file_manager.delete_recursive_with_progress(path, |done, total, current| {
+                        if done % 500 == 0 || done == total {
+                            log::debug!("削除中: {}/{} ({})", done, total, current.display());
+                        }
+                    });
+                    match result {
+                        Ok(result) if result.is_complete() => success_count += 1,
+                        Ok(result) => {
+                            for (failed_path, e) in &result.failed {
+                                log::error!("削除に失敗: {} ({})", failed_path.display(), e);
+                            }
+                            errors.push(format!("{}: {}/{} 件を削除できませんでした",
+                                name, result.failed.len(), result.total()));
+                            failed_paths.push(path.clone());
+                            failed_paths.extend(result.failed.into_iter().map(|(failed_path, _)| failed_path));
+                        }
+                        Err(e) => {
+                            log::error!("削除に失敗: {}", e);
+                            errors.push(format!("{}: {}", name, e));
+                            failed_paths.push(path.clone());
+                        }
+                    }
+                    continue;
+                }
+
+                if let Err(e) = file_manager.delete(path, true) {
+                    log::error!("削除に失敗: {}", e);
+                    errors.push(format!("{}: {}", name, e));
+                    failed_paths.push(path.clone());
+                } else {
+                    success_count += 1;
+                }
+            }
+        } else {
+            // ゴミ箱への移動はまとめて行い、失敗した項目だけを報告する
+            let result = file_manager.delete_many_to_trash(paths);
+            success_count = result.succeeded;
+            for (failed_path, e) in &result.failed {
+                log::error!("削除に失敗: {} ({})", failed_path.display(), e);
+                let name = failed_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                errors.push(format!("{}: {}", name, e));
             }
+            failed_paths.extend(result.failed.into_iter().map(|(failed_path, _)| failed_path));
         }
 
         self.state.delete_confirmation_dialog = None;
 
         // ディレクトリをリロード
-        if let Some(ref mut browser) = self.state.directory_browser {
-            let _ = browser.reload();
-        }
+        let _ = self.state.reload_directory();
+
+        // 失敗した行にエラーバッジを表示
+        self.state.record_failed_paths(failed_paths);
 
         // 結果メッセージを設定
         let action = if permanent { "完全に削除" } else { "ゴミ箱に移動" };
         if errors.is_empty() {
-            self.state.operation_result_message = Some(
+            self.state.toasts.push(
                 crate::app::state::OperationResultMessage::success(
                     format!("{} 個のアイテムを{}しました", success_count, action)
                 )
             );
         } else {
-            self.state.operation_result_message = Some(
+            self.state.toasts.push(
                 crate::app::state::OperationResultMessage::error(
                     format!("削除に失敗: {}", errors.join(", "))
                 )
             );
         }
     }
+
+    /// 表示設定（エイリアス行の列・既定のソート方法）とキー割り当てをビューに反映
+    fn apply_display_config(&mut self) {
+        if let Some(ref config) = self.state.config {
+            self.file_tree.set_row_columns(parse_row_columns(&config.display.row_columns));
+            self.file_keybindings = FileKeybindings::from_config(&config.keybindings);
+        }
+
+        let (key, order) = self.state.default_sort();
+        if let Some(ref mut browser) = self.state.directory_browser {
+            browser.set_default_sort(key, order);
+        }
+    }
+
+    /// クイックアクセスセクションへのドロップを処理
+    ///
+    /// OS からのファイルドロップと、ディレクトリツリーからのドラッグの両方を受け付けます。
+    /// フォルダ以外がドロップされた場合は追加せずにメッセージを表示します。
+    ///
+    /// # 引数
+    /// * `ctx` - egui コンテキスト
+    /// * `section` - クイックアクセスセクションの領域
+    fn handle_quick_access_drop(&mut self, ctx: &egui::Context, section: &egui::Response) {
+        let mut dropped: Vec<std::path::PathBuf> = Vec::new();
+
+        // ディレクトリツリーからのドラッグ
+        if let Some(path) = section.dnd_release_payload::<std::path::PathBuf>() {
+            dropped.push((*path).clone());
+        }
+
+        // OS からのドロップ（ポインタがセクション上にある場合のみ）
+        let over_section = ctx
+            .input(|i| i.pointer.latest_pos())
+            .is_some_and(|pos| section.rect.contains(pos));
+        if over_section {
+            ctx.input(|i| {
+                dropped.extend(i.raw.dropped_files.iter().filter_map(|f| f.path.clone()));
+            });
+        }
+
+        for path in dropped {
+            match self.state.add_dropped_to_quick_access(&path) {
+                Ok(name) => {
+                    log::info!("「{}」をクイックアクセスに追加しました", name);
+                    self.state.toasts.push(
+                        crate::app::state::OperationResultMessage::success(
+                            format!("「{}」をクイックアクセスに追加しました", name)
+                        )
+                    );
+                }
+                Err(e) => {
+                    log::warn!("クイックアクセスへの追加を中止: {}", e);
+                    self.state.toasts.push(
+                        crate::app::state::OperationResultMessage::warning(e)
+                    );
+                }
+            }
+        }
+    }
+
+    /// 選択中のエイリアスに合わせて詳細表示の帯の内容を更新する
+    ///
+    /// パスの解決と存在確認は、選択中のエイリアスが変わったときだけ行います。
+    fn refresh_alias_detail(&mut self) {
+        let Some(alias) = self.state.selected_index.and_then(|idx| self.state.filtered_items.get(idx)) else {
+            self.alias_detail = None;
+            return;
+        };
+        if self.alias_detail.as_ref().is_some_and(|(source, _)| source == alias) {
+            return;
+        }
+
+        let resolved = crate::platform::resolve_alias_path(
+            &alias.path,
+            alias.volume.as_ref(),
+            &crate::platform::SystemDriveEnumerator,
+        );
+        let detail = AliasDetail::new(alias, &resolved, resolved.exists());
+        self.alias_detail = Some((alias.clone(), detail));
+    }
+
+    /// 「最近」タブで選んだ項目を開く
+    ///
+    /// フォルダはディレクトリモードで開き、ファイルは既定のアプリで開きます。
+    fn open_recent(&mut self, path: std::path::PathBuf) {
+        let Some(result) = self.activate_path(&path, &OpenAction::Open) else {
+            return;
+        };
+
+        if let ActivationResult::Navigate(dir) = result {
+            if let Err(e) = self.state.init_directory_browser(dir) {
+                log::error!("最近の項目を開けませんでした: {}", e);
+                self.state.toasts.push(
+                    crate::app::state::OperationResultMessage::error(format!("フォルダを開けませんでした: {}", e))
+                );
+                return;
+            }
+            self.state.set_browse_mode(BrowseMode::Directory);
+        }
+        self.state.record_recent(&path);
+    }
+
+    /// パスを開き、呼び出し元で行う後処理を返す
+    ///
+    /// Enter・ダブルクリックで項目を開くときの共通の入口です。
+    /// 開けなかった場合はエラーを表示して None を返します。
+    ///
+    /// # 引数
+    /// * `path` - 開くパス
+    /// * `open_action` - 開くときの動作
+    fn activate_path(&mut self, path: &std::path::Path, open_action: &OpenAction) -> Option<ActivationResult> {
+        let file_manager = FileManager::new();
+        let context = ActivationContext::new(&file_manager, open_action);
+        let result = activate(path, &context).and_then(|result| match result {
+            // アーカイブの中の表示には未対応のため、既定のアプリで開く
+            ActivationResult::EnterArchive(archive) => open_with_action(&file_manager, &archive, open_action)
+                .map(|()| ActivationResult::Opened(archive)),
+            result => Ok(result),
+        });
+
+        match result {
+            Ok(result) => Some(result),
+            Err(e) => {
+                log::error!("項目を開けませんでした: {}", e);
+                self.state.toasts.push(
+                    crate::app::state::OperationResultMessage::error(e)
+                );
+                None
+            }
+        }
+    }
+
+    /// 操作を取り消す・やり直し、結果をトーストで通知する
+    ///
+    /// # 引数
+    /// * `undo` - 取り消す場合は true、やり直す場合は false
+    fn step_operation_history(&mut self, undo: bool) {
+        let result = if undo {
+            self.state.undo_operation()
+        } else {
+            self.state.redo_operation()
+        };
+        match result {
+            Ok(msg) => {
+                self.state.toasts.push(
+                    crate::app::state::OperationResultMessage::success(msg)
+                );
+            }
+            Err(msg) => {
+                self.state.toasts.push(
+                    crate::app::state::OperationResultMessage::warning(msg)
+                );
+            }
+        }
+    }
+
+    /// ディレクトリ一覧の項目を開く（フォルダは移動、ファイルは既定のアプリで開く）
+    ///
+    /// 再帰検索の結果の場合は、項目があるフォルダへ移動して選択します。
+    fn activate_directory_entry(&mut self, path: std::path::PathBuf) {
+        if self.state.is_recursive_search_active() {
+            if let Err(e) = self.state.reveal_directory_entry(&path) {
+                log::error!("検索結果の表示に失敗: {}", e);
+                self.state.toasts.push(
+                    crate::app::state::OperationResultMessage::error(e)
+                );
+            }
+            return;
+        }
+
+        match self.activate_path(&path, &OpenAction::Open) {
+            Some(ActivationResult::Navigate(dir)) => {
+                let Some(browser) = self.state.directory_browser.as_mut() else {
+                    return;
+                };
+                if let Err(e) = browser.navigate_to(dir) {
+                    log::error!("ディレクトリの移動に失敗: {}", e);
+                } else {
+                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                    self.state.update_directory_search_after_navigation();
+                }
+            }
+            Some(result) => self.state.record_recent(result.path()),
+            None => {}
+        }
+    }
+
+    /// エイリアスを開く
+    ///
+    /// フォルダで動作が `Open` の場合はディレクトリモードで移動し、
+    /// それ以外はエイリアスの `open_action` に従って開きます。
+    ///
+    /// # 引数
+    /// * `alias` - 開くエイリアス
+    fn activate_alias(&mut self, alias: &crate::data::models::FileAlias) {
+        // 環境変数を展開し、リムーバブルドライブのドライブレターが変わっていても現在の位置に解決する
+        let path = crate::platform::resolve_alias_path(
+            &alias.path,
+            alias.volume.as_ref(),
+            &crate::platform::SystemDriveEnumerator,
+        );
+
+        let Some(result) = self.activate_path(&path, &alias.open_action) else {
+            return;
+        };
+
+        if let ActivationResult::Navigate(dir) = result {
+            if let Err(e) = self.state.init_directory_browser(dir) {
+                log::error!("エイリアスパスへの移動に失敗: {}", e);
+                return;
+            }
+            self.state.browse_mode = BrowseMode::Directory;
+            // 検索バーをクリア
+            self.state.search_query.clear();
+            self.state.selected_index = None;
+        }
+        self.state.record_alias_open(&alias.id);
+        self.state.record_recent(&path);
+    }
+
+    /// コマンドパレットで選択されたアクションを実行
+    ///
+    /// # 引数
+    /// * `ctx` - egui コンテキスト
+    /// * `action` - 実行するアクション
+    fn execute_palette_action(&mut self, ctx: &egui::Context, action: PaletteAction) {
+        match self.state.dispatch_palette_action(action) {
+            Ok(msg) => {
+                if action == PaletteAction::ToggleTheme {
+                    // テーマ変更を保存して即座に反映
+                    self.state.save_config_or_report();
+                    self.apply_theme(ctx);
+                }
+                if action == PaletteAction::ToggleAlwaysOnTop {
+                    // 最前面表示の変更を保存して即座に反映
+                    self.state.save_config_or_report();
+                    ctx.send_viewport_cmd(self.state.window_level_command());
+                }
+
+                // 設定画面や追加ダイアログを開くだけのアクションはメッセージを出さない
+                if !matches!(action, PaletteAction::OpenSettings | PaletteAction::AddAlias | PaletteAction::ImportProfile) {
+                    self.state.toasts.push(
+                        crate::app::state::OperationResultMessage::success(msg)
+                    );
+                }
+            }
+            Err(msg) => {
+                log::warn!("コマンドの実行に失敗: {}", msg);
+                self.state.toasts.push(
+                    crate::app::state::OperationResultMessage::error(msg)
+                );
+            }
+        }
+    }
 }
 
 impl eframe::App for OfktApp {
@@ -391,35 +887,33 @@ impl eframe::App for OfktApp {
             }
         }
 
-        // Ctrl+C/X/V の検出
+        // コピー・切り取り・貼り付けのキーの検出（既定は Ctrl+C/X/V、設定で変更可能）
         // ファイルが選択されている場合はファイル操作を優先
         let has_file_selection = match self.state.browse_mode {
             BrowseMode::Alias => self.state.selected_index.is_some(),
             BrowseMode::Directory => self.state.selected_directory_index.is_some(),
+            BrowseMode::Recent => false,
         };
 
         // egui::Eventを直接チェックする方式（Windows互換性のため）
         let mut copy_pressed = false;
         let mut cut_pressed = false;
         let mut paste_pressed = false;
+        let mut text_paths_paste: Option<String> = None;
 
         ctx.input(|i| {
             for event in &i.events {
                 match event {
-                    egui::Event::Key { key, pressed: true, modifiers, .. } => {
-                        if modifiers.ctrl {
-                            match key {
-                                egui::Key::C => copy_pressed = true,
-                                egui::Key::X => cut_pressed = true,
-                                egui::Key::V => paste_pressed = true,
-                                _ => {}
-                            }
-                        }
+                    // Ctrl+Shift+V: テキストとしてコピーされたパスを貼り付け
+                    egui::Event::Paste(text) if i.modifiers.command && i.modifiers.shift => {
+                        text_paths_paste = Some(text.clone());
                     }
-                    egui::Event::Copy => copy_pressed = true,
-                    egui::Event::Cut => cut_pressed = true,
-                    egui::Event::Paste(_) => paste_pressed = true,
-                    _ => {}
+                    _ => match self.file_keybindings.action_for_event(event, i.modifiers) {
+                        Some(FileAction::Copy) => copy_pressed = true,
+                        Some(FileAction::Cut) => cut_pressed = true,
+                        Some(FileAction::Paste) => paste_pressed = true,
+                        None => {}
+                    },
                 }
             }
         });
@@ -446,6 +940,10 @@ impl eframe::App for OfktApp {
             log::info!("[KEYBOARD] Ctrl+V detected! (browse_mode={:?})", self.state.browse_mode);
             self.state.pending_file_paste = true;
         }
+        if let Some(text) = text_paths_paste {
+            log::info!("[KEYBOARD] Ctrl+Shift+V detected! (browse_mode={:?})", self.state.browse_mode);
+            self.paste_text_paths(&text);
+        }
 
         // Ctrl+Z: Undo
         let undo_pressed = ctx.input(|i| {
@@ -463,43 +961,45 @@ impl eframe::App for OfktApp {
         });
 
         if undo_pressed {
-            match self.state.operation_history.undo() {
-                Ok(msg) => {
-                    self.state.operation_result_message = Some(
-                        crate::app::state::OperationResultMessage::success(msg)
-                    );
-                    // ディレクトリをリロード
-                    if let Some(ref mut browser) = self.state.directory_browser {
-                        let _ = browser.reload();
-                    }
-                }
-                Err(msg) => {
-                    self.state.operation_result_message = Some(
-                        crate::app::state::OperationResultMessage::warning(msg)
-                    );
-                }
-            }
+            self.step_operation_history(true);
         }
 
         if redo_pressed {
-            match self.state.operation_history.redo() {
-                Ok(msg) => {
-                    self.state.operation_result_message = Some(
-                        crate::app::state::OperationResultMessage::success(msg)
-                    );
-                    if let Some(ref mut browser) = self.state.directory_browser {
-                        let _ = browser.reload();
-                    }
-                }
-                Err(msg) => {
-                    self.state.operation_result_message = Some(
-                        crate::app::state::OperationResultMessage::warning(msg)
-                    );
-                }
-            }
+            self.step_operation_history(false);
         }
 
-        // ペーストハイライトの期限チェック
+        // Ctrl+Shift+P: コマンドパレット
+        let palette_pressed = ctx.input(|i| {
+            i.events.iter().any(|e| {
+                matches!(e, egui::Event::Key { key: egui::Key::P, pressed: true, modifiers, .. } if modifiers.ctrl && modifiers.shift)
+            })
+        });
+
+        if palette_pressed {
+            if self.state.command_palette.is_some() {
+                self.state.command_palette = None;
+            } else if !self.state.is_any_dialog_open() {
+                log::info!("[KEYBOARD] Ctrl+Shift+P: コマンドパレットを開きます");
+                self.state.command_palette = Some(crate::app::state::CommandPaletteDialog::new());
+            }
+        }
+
+        // F1 / ?: ショートカット一覧
+        if crate::ui::shortcut_help::help_requested(ctx) {
+            if self.state.show_shortcut_help {
+                self.state.show_shortcut_help = false;
+            } else if !self.state.is_any_dialog_open() {
+                self.state.show_shortcut_help = true;
+            }
+        }
+
+        // エラーバッジの期限切れ・フォルダ移動によるクリア
+        if !self.state.failed_path_badges.is_empty() {
+            let current_dir = self.state.directory_browser.as_ref().map(|b| b.current_path().to_path_buf());
+            self.state.failed_path_badges.prune(current_dir.as_deref(), std::time::Instant::now());
+        }
+
+        // ペーストハイライトの期限チェック
         if let Some(ref highlight) = self.state.pasted_files_highlight {
             if highlight.is_expired() {
                 self.state.pasted_files_highlight = None;
@@ -538,8 +1038,26 @@ impl eframe::App for OfktApp {
             if let Err(e) = self.state.lazy_initialize() {
                 log::error!("遅延初期化に失敗: {}", e);
             }
+            self.apply_display_config();
+            // 設定の「常に最前面に表示」を反映
+            ctx.send_viewport_cmd(self.state.window_level_command());
+        }
+
+        // エイリアスファイルの外部変更を反映
+        if self.state.poll_alias_file_changes() {
+            self.state.toasts.push(
+                crate::app::state::OperationResultMessage::success(
+                    "エイリアスを再読み込みしました".to_string()
+                )
+            );
         }
 
+        // フォルダ一覧の先読みとキャッシュの無効化を反映
+        self.state.poll_directory_cache();
+
+        // バックグラウンドの貼り付けの進み具合と完了を反映
+        self.poll_paste_task();
+
         // テーマを適用
         self.apply_theme(ctx);
 
@@ -575,7 +1093,7 @@ impl eframe::App for OfktApp {
                 }
                 TrayEvent::Settings => {
                     log::info!("トレイメニュー「設定」が選択されました");
-                    // TODO: 設定画面を開く（将来実装）
+                    self.state.show_settings = true;
                 }
                 TrayEvent::Exit => {
                     log::info!("トレイメニュー「終了」が選択されました");
@@ -584,12 +1102,23 @@ impl eframe::App for OfktApp {
             }
         }
 
-        // Ctrl+Tabでエイリアス/ディレクトリモード切り替え
+        // Esc: 1回目で選択と検索をクリアし、素早く2回押すとウィンドウを隠す
+        if !self.state.is_any_dialog_open() && ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            let threshold = self.state.double_escape_threshold();
+            if self.state.escape_tracker.press(Instant::now(), threshold) {
+                if self.state.is_window_visible {
+                    // 1回目でクリアした選択を戻してから隠す（再表示時に元の画面に戻す）
+                    self.state.restore_escape_cleared_selection();
+                    self.toggle_window_visibility(ctx);
+                }
+            } else {
+                self.state.clear_selection_and_search();
+            }
+        }
+
+        // Ctrl+Tabで表示中のタブを順に切り替え
         if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Tab)) {
-            self.state.browse_mode = match self.state.browse_mode {
-                BrowseMode::Alias => BrowseMode::Directory,
-                BrowseMode::Directory => BrowseMode::Alias,
-            };
+            self.state.switch_to_next_browse_mode();
 
             // モード切り替え時にフォーカスをメインパネルに設定
             self.state.current_focus_area = FocusArea::Main;
@@ -612,12 +1141,54 @@ impl eframe::App for OfktApp {
         egui::TopBottomPanel::top("top_bar").show(ctx, |ui| {
             ui.heading("Ofkt - ファイル管理ツール");
 
+            // データフォルダに書き込めない場合の警告（保存できないため常に表示する）
+            if let Some(ref warning) = self.state.data_dir_warning {
+                ui.colored_label(ui.visuals().error_fg_color, format!("⚠ {}", warning));
+            }
+
+            // ホットキー登録失敗の警告（登録できるまで表示し続ける）
+            if self.state.hotkey_warning_visible() {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(220, 140, 0),
+                        "⚠ グローバルホットキーを登録できませんでした。他のアプリと重複していないか確認し、設定でキーを変更してください。",
+                    );
+                    if ui.button("設定を開く").clicked() {
+                        self.state.show_settings = true;
+                    }
+                });
+            }
+
             ui.separator();
 
-            // モード切替タブバー
+            // モード切替タブバー（設定の並び順で表示）
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut self.state.browse_mode, BrowseMode::Alias, "エイリアス");
-                ui.selectable_value(&mut self.state.browse_mode, BrowseMode::Directory, "ディレクトリ");
+                let current_mode = self.state.browse_mode;
+                for mode in self.state.browse_tabs() {
+                    if ui.selectable_label(current_mode == mode, mode.label()).clicked() {
+                        self.state.set_browse_mode(mode);
+                    }
+                }
+
+                // 元に戻す/やり直すボタン（履歴がない間は押せない）
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let redo = self.state.redo_button_state();
+                    if ui.add_enabled(redo.enabled, egui::Button::new("↷"))
+                        .on_hover_text(&redo.tooltip)
+                        .on_disabled_hover_text(&redo.tooltip)
+                        .clicked()
+                    {
+                        self.step_operation_history(false);
+                    }
+                    let undo = self.state.undo_button_state();
+                    if ui.add_enabled(undo.enabled, egui::Button::new("↶"))
+                        .on_hover_text(&undo.tooltip)
+                        .on_disabled_hover_text(&undo.tooltip)
+                        .clicked()
+                    {
+                        self.step_operation_history(true);
+                    }
+                });
             });
         });
 
@@ -625,12 +1196,20 @@ impl eframe::App for OfktApp {
         match self.state.browse_mode {
             BrowseMode::Alias => {
                 // エイリアスモードUI
+                // 選択中のエイリアスの詳細表示（一覧の下の帯）
+                self.refresh_alias_detail();
+                if let Some((_, detail)) = &self.alias_detail {
+                    egui::TopBottomPanel::bottom("alias_detail_strip").show(ctx, |ui| {
+                        crate::ui::detail_strip::render(ui, detail);
+                    });
+                }
+
                 let mut central_panel = egui::CentralPanel::default();
 
                 // メインパネルにフォーカスがある場合は枠線を表示
                 if self.state.current_focus_area == FocusArea::Main {
                     central_panel = central_panel.frame(egui::Frame {
-                        stroke: egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 150, 255)),  // 青色の枠線
+                        stroke: self.state.focus_stroke(),
                         ..Default::default()
                     });
                 }
@@ -712,25 +1291,60 @@ impl eframe::App for OfktApp {
 
                     if self.state.search_query.is_empty() {
                         ui.label(format!("エイリアス: {} 件", total_count));
+                    } else if self.state.alias_search_truncated {
+                        // 時間内に走査しきれなかった場合は途中までの結果であることを示す
+                        ui.horizontal(|ui| {
+                            ui.label(format!("検索結果: {} / {} 件（途中まで）", filtered_count, total_count));
+                            if ui.small_button("すべて検索").clicked() {
+                                self.state.complete_alias_search();
+                            }
+                        });
                     } else {
                         ui.label(format!("検索結果: {} / {} 件", filtered_count, total_count));
                     }
 
                     ui.separator();
 
-                    // エイリアス追加ボタン
-                    if ui.button("+ エイリアス追加").clicked() {
-                        self.state.show_add_alias_dialog = true;
-                        self.state.new_alias_name.clear();
-                        self.state.new_alias_path.clear();
-                    }
+                    ui.horizontal(|ui| {
+                        // エイリアス追加ボタン
+                        if ui.button("+ エイリアス追加").clicked() {
+                            self.state.show_add_alias_dialog = true;
+                            self.state.new_alias_name.clear();
+                            self.state.new_alias_path.clear();
+                            self.state.add_alias_path_warning = None;
+                        }
+                        if ui.selectable_label(self.state.selection_mode, "☑ 選択モード").clicked() {
+                            self.state.toggle_selection_mode();
+                        }
+                        if ui.selectable_label(self.state.group_by_tag, "🏷 タグ別").clicked() {
+                            self.state.group_by_tag = !self.state.group_by_tag;
+                        }
+                    });
 
                     ui.separator();
 
+                    // 選択モードの一括操作バー
+                    if self.state.selection_mode {
+                        let visible_paths: Vec<_> = self.state.filtered_items.iter()
+                            .map(|alias| alias.path.clone())
+                            .collect();
+                        self.render_selection_toolbar(ui, &visible_paths);
+                        ui.separator();
+                    }
+
                     // スクロール可能なエリアでファイルツリーを表示
                     egui::ScrollArea::vertical()
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
+                            // 選択モードではチェックボックス付きの一覧を表示
+                            if self.state.selection_mode {
+                                let rows: Vec<_> = self.state.filtered_items.iter()
+                                    .map(|alias| (alias.path.clone(), format!("{}  ({})", alias.alias, alias.path.display())))
+                                    .collect();
+                                self.render_checkbox_rows(ui, &rows);
+                                return;
+                            }
+
                             // ファイルツリー
                             // メインパネルにフォーカスがある場合のみハイライト表示
                             let display_selected_index = if self.state.current_focus_area == FocusArea::Main {
@@ -739,37 +1353,50 @@ impl eframe::App for OfktApp {
                                 None
                             };
 
-                            let (selected_index, open_index) = self.file_tree.render(
-                                ui,
-                                &self.state.filtered_items,
-                                display_selected_index,
-                            );
+                            let (selected_index, open_index) = if self.state.group_by_tag {
+                                // タグ別表示（折りたたみ状態は設定に保存する）
+                                let collapsed_tags = self.state.config
+                                    .as_ref()
+                                    .map(|c| c.display.collapsed_tags.clone())
+                                    .unwrap_or_default();
+                                let (selected_index, open_index, toggled_tag) = self.file_tree.render_grouped_by_tag(
+                                    ui,
+                                    &self.state.filtered_items,
+                                    display_selected_index,
+                                    &collapsed_tags,
+                                );
+                                if let Some(tag) = toggled_tag {
+                                    if self.state.toggle_tag_collapsed(&tag) {
+                                        self.state.save_config_or_report();
+                                    }
+                                }
+                                (selected_index, open_index)
+                            } else {
+                                self.file_tree.render(
+                                    ui,
+                                    &self.state.filtered_items,
+                                    display_selected_index,
+                                )
+                            };
 
                             // シングルクリック → 選択のみ
                             if let Some(idx) = selected_index {
                                 self.state.selected_index = Some(idx);
                             }
 
+                            // 右クリックメニューの「修復」
+                            if let Some(alias_id) = self.file_tree.take_repair_request() {
+                                if let Err(e) = self.state.open_alias_repair_dialog(&alias_id) {
+                                    log::error!("エイリアス修復ダイアログを開けません: {}", e);
+                                }
+                            }
+
                             // ダブルクリック → ファイルを開く / ディレクトリに移動
                             if let Some(idx) = open_index {
                                 self.state.selected_index = Some(idx);
 
-                                if let Some(alias) = self.state.filtered_items.get(idx) {
-                                    if alias.path.is_dir() {
-                                        if let Err(e) = self.state.init_directory_browser(alias.path.clone()) {
-                                            log::error!("エイリアスパスへの移動に失敗: {}", e);
-                                        } else {
-                                            self.state.browse_mode = BrowseMode::Directory;
-                                            // 検索バーをクリア
-                                            self.state.search_query.clear();
-                                            self.state.selected_index = None;
-                                        }
-                                    } else {
-                                        let file_manager = FileManager::new();
-                                        if let Err(e) = file_manager.open(&alias.path) {
-                                            log::error!("ファイルを開けませんでした: {}", e);
-                                        }
-                                    }
+                                if let Some(alias) = self.state.filtered_items.get(idx).cloned() {
+                                    self.activate_alias(&alias);
                                 }
                             }
 
@@ -777,6 +1404,50 @@ impl eframe::App for OfktApp {
                             if self.state.selected_index.is_some() {
                                 // 右クリックでコンテキストメニューを表示
                                 ui.menu_button("操作", |ui| {
+                                    if ui.button("編集").clicked() {
+                                        if let Some(alias_id) = self.state.selected_index
+                                            .and_then(|idx| self.state.filtered_items.get(idx))
+                                            .map(|alias| alias.id.clone())
+                                        {
+                                            if let Err(e) = self.state.open_alias_edit_dialog(&alias_id) {
+                                                log::error!("エイリアス編集ダイアログを開けません: {}", e);
+                                            }
+                                        }
+                                        ui.close_menu();
+                                    }
+                                    let repair_target = self.state.selected_index
+                                        .and_then(|idx| self.state.filtered_items.get(idx))
+                                        .filter(|alias| alias.is_broken || alias.is_unreachable)
+                                        .map(|alias| alias.id.clone());
+                                    if ui.add_enabled(repair_target.is_some(), egui::Button::new("修復")).clicked() {
+                                        if let Some(alias_id) = repair_target {
+                                            if let Err(e) = self.state.open_alias_repair_dialog(&alias_id) {
+                                                log::error!("エイリアス修復ダイアログを開けません: {}", e);
+                                            }
+                                        }
+                                        ui.close_menu();
+                                    }
+                                    // 手動の並び順を変更
+                                    let move_target = self.state.selected_index
+                                        .and_then(|idx| self.state.filtered_items.get(idx))
+                                        .map(|alias| alias.id.clone());
+                                    for (label, up) in [("上へ移動", true), ("下へ移動", false)] {
+                                        if ui.button(label).clicked() {
+                                            if let Some(ref alias_id) = move_target {
+                                                match self.state.move_alias(alias_id, up) {
+                                                    Ok(()) => {
+                                                        if let Err(e) = self.state.alias_manager.save() {
+                                                            self.state.report_save_error("エイリアス", e);
+                                                        }
+                                                    }
+                                                    Err(e) => {
+                                                        log::error!("エイリアスの移動に失敗: {}", e);
+                                                    }
+                                                }
+                                            }
+                                            ui.close_menu();
+                                        }
+                                    }
                                     if ui.button("削除").clicked() {
                                         // 選択されたエイリアスを削除
                                         if let Some(idx) = self.state.selected_index {
@@ -788,12 +1459,10 @@ impl eframe::App for OfktApp {
                                                     Ok(()) => {
                                                         // 保存
                                                         if let Err(e) = self.state.alias_manager.save() {
-                                                            log::error!("エイリアスの保存に失敗: {}", e);
+                                                            self.state.report_save_error("エイリアス", e);
                                                         } else {
-                                                            // file_aliasesとfiltered_itemsを更新
-                                                            self.state.file_aliases = self.state.alias_manager.get_aliases().to_vec();
-                                                            self.state.filter_aliases();
-                                                            self.state.selected_index = None;
+                                                            // 表示中の一覧を検索し直し、選択を範囲内に収める
+                                                            self.state.refresh_after_alias_change();
                                                             log::info!("エイリアス「{}」を削除しました", alias_name);
                                                         }
                                                     }
@@ -821,7 +1490,7 @@ impl eframe::App for OfktApp {
                         if let Some(alias) = self.state.filtered_items.get(idx) {
                             self.state.clipboard_state.copy(vec![alias.path.clone()]);
                             log::info!("「{}」をコピーしました", alias.alias);
-                            self.state.operation_result_message = Some(
+                            self.state.toasts.push(
                                 crate::app::state::OperationResultMessage::success(
                                     format!("「{}」をコピーしました", alias.alias)
                                 )
@@ -842,7 +1511,7 @@ impl eframe::App for OfktApp {
                         if let Some(alias) = self.state.filtered_items.get(idx) {
                             self.state.clipboard_state.cut(vec![alias.path.clone()]);
                             log::info!("「{}」を切り取りました", alias.alias);
-                            self.state.operation_result_message = Some(
+                            self.state.toasts.push(
                                 crate::app::state::OperationResultMessage::success(
                                     format!("「{}」を切り取りました", alias.alias)
                                 )
@@ -871,6 +1540,24 @@ impl eframe::App for OfktApp {
                 // メインパネルにフォーカスがある場合のみキーイベント処理を実行
                 // ダイアログ表示中はキー入力をスキップ
                 if self.state.current_focus_area == FocusArea::Main && !self.state.is_any_dialog_open() {
+                    // タイプアヘッド: 文字を入力すると、その文字列で始まるエイリアスへジャンプ
+                    if !ctx.wants_keyboard_input() {
+                        let typed: Vec<char> = ctx.input(|i| {
+                            i.events.iter()
+                                .filter_map(|event| match event {
+                                    egui::Event::Text(text) => Some(text.clone()),
+                                    _ => None,
+                                })
+                                .flat_map(|text| text.chars().collect::<Vec<_>>())
+                                .filter(|ch| !ch.is_control())
+                                .collect()
+                        });
+                        let now = std::time::Instant::now();
+                        for ch in typed {
+                            self.state.type_ahead_input(ch, now);
+                        }
+                    }
+
                     if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
                         let max_index = self.state.filtered_items.len().saturating_sub(1);
                         self.state.selected_index = Some(
@@ -887,21 +1574,8 @@ impl eframe::App for OfktApp {
 
                     if ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
                         if let Some(idx) = self.state.selected_index {
-                            if let Some(alias) = self.state.filtered_items.get(idx) {
-                                if alias.path.is_dir() {
-                                    if let Err(e) = self.state.init_directory_browser(alias.path.clone()) {
-                                        log::error!("エイリアスパスへの移動に失敗: {}", e);
-                                    } else {
-                                        self.state.browse_mode = BrowseMode::Directory;
-                                        self.state.search_query.clear();
-                                        self.state.selected_index = None;
-                                    }
-                                } else {
-                                    let file_manager = FileManager::new();
-                                    if let Err(e) = file_manager.open(&alias.path) {
-                                        log::error!("ファイルを開けませんでした: {}", e);
-                                    }
-                                }
+                            if let Some(alias) = self.state.filtered_items.get(idx).cloned() {
+                                self.activate_alias(&alias);
                             }
                         }
                     }
@@ -924,17 +1598,19 @@ impl eframe::App for OfktApp {
             }
             BrowseMode::Directory => {
                 // サイドバー
-                let mut sidebar_panel = egui::SidePanel::left("drive_panel");
+                let mut sidebar_panel = egui::SidePanel::left("drive_panel")
+                    .default_width(self.state.sidebar_width())
+                    .width_range(crate::ui::layout::MIN_SIDEBAR_WIDTH..=crate::ui::layout::MAX_SIDEBAR_WIDTH);
 
                 // サイドバーにフォーカスがある場合は枠線を表示
                 if self.state.current_focus_area == FocusArea::Sidebar {
                     sidebar_panel = sidebar_panel.frame(egui::Frame {
-                        stroke: egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 150, 255)),  // 青色の枠線
+                        stroke: self.state.focus_stroke(),
                         ..Default::default()
                     });
                 }
 
-                sidebar_panel.show(ctx, |ui| {
+                let sidebar_response = sidebar_panel.show(ctx, |ui| {
                     egui::ScrollArea::vertical()
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
@@ -945,15 +1621,10 @@ impl eframe::App for OfktApp {
                             ui.label("エイリアス");
 
                             // お気に入りエイリアスを優先表示
-                            let mut aliases = self.state.file_aliases.clone();
-                            aliases.sort_by(|a, b| {
-                                // お気に入りを優先、その後名前順
-                                match (b.is_favorite, a.is_favorite) {
-                                    (true, false) => std::cmp::Ordering::Greater,
-                                    (false, true) => std::cmp::Ordering::Less,
-                                    _ => a.alias.cmp(&b.alias),
-                                }
-                            });
+                            let aliases: Vec<_> = sorted_for_sidebar(&self.state.file_aliases)
+                                .into_iter()
+                                .cloned()
+                                .collect();
 
                             // 検索クエリでフィルタリング
                             let filtered_aliases: Vec<_> = if self.state.directory_search_query.is_empty() {
@@ -965,36 +1636,45 @@ impl eframe::App for OfktApp {
                                     .collect()
                             };
 
-                            // エイリアスリストを表示（最大10件）
-                            let displayed_aliases: Vec<_> = filtered_aliases.iter().take(10).collect();
+                            // エイリアスリストを表示（設定の件数まで。「もっと見る」で残りを表示）
+                            let alias_limit = self.state.sidebar_alias_limit().max(1);
+                            let aliases_expanded = self.state.sidebar_aliases_expanded();
+                            let displayed_aliases: Vec<_> = filtered_aliases
+                                .iter()
+                                .take(crate::ui::layout::sidebar_alias_display_count(
+                                    filtered_aliases.len(), alias_limit, aliases_expanded,
+                                ))
+                                .collect();
                             let displayed_aliases_count = displayed_aliases.len();
 
-                            for (alias_index, alias) in displayed_aliases.iter().enumerate() {
-                                let button_text = if alias.is_favorite {
-                                    format!("⭐ {}", alias.alias)
+                            for (alias_index, alias) in displayed_aliases.iter().take(alias_limit).enumerate() {
+                                self.render_sidebar_alias(ui, alias, alias_index);
+                            }
+                            if displayed_aliases_count > alias_limit {
+                                egui::ScrollArea::vertical()
+                                    .id_salt("sidebar_more_aliases")
+                                    .max_height(200.0)
+                                    .show(ui, |ui| {
+                                        for (alias_index, alias) in displayed_aliases.iter().enumerate().skip(alias_limit) {
+                                            self.render_sidebar_alias(ui, alias, alias_index);
+                                        }
+                                    });
+                            }
+                            if filtered_aliases.len() > alias_limit {
+                                let label = if aliases_expanded {
+                                    "折りたたむ".to_string()
                                 } else {
-                                    alias.alias.clone()
+                                    format!("もっと見る（残り {} 件）", filtered_aliases.len() - alias_limit)
                                 };
-
-                                let button = egui::Button::new(&button_text)
-                                    .selected(self.state.current_focus_area == FocusArea::Sidebar
-                                        && self.state.selected_sidebar_index == Some(alias_index));
-
-                                if ui.add(button).clicked() {
-                                    // エイリアスのパスに移動
-                                    if let Err(e) = self.state.init_directory_browser(alias.path.clone()) {
-                                        log::error!("エイリアスパスへの移動に失敗: {}", e);
-                                    } else {
-                                        // 検索バーをクリア
-                                        self.state.directory_search_query.clear();
-                                        log::info!("エイリアス「{}」を開きました", alias.alias);
-                                    }
+                                if ui.small_button(label).clicked() && self.state.toggle_sidebar_aliases_expanded() {
+                                    self.state.save_config_or_report();
                                 }
                             }
 
                             ui.separator();
 
-                            // クイックアクセスセクション
+                            // クイックアクセスセクション（フォルダをドロップして追加できる）
+                            let quick_access_top = ui.cursor().min.y;
                             ui.label("クイックアクセス");
 
                             // 借用エラーを避けるため、先にclone
@@ -1008,16 +1688,76 @@ impl eframe::App for OfktApp {
                                         && self.state.browse_mode == BrowseMode::Directory
                                         && self.state.selected_sidebar_index == Some(sidebar_index));
 
-                                if ui.add(button).clicked() {
+                                let response = ui.add(button);
+                                if response.clicked() {
                                     // クリック時の処理
                                     if let Err(e) = self.state.init_directory_browser(entry.path.clone()) {
                                         log::error!("ナビゲーション失敗: {}", e);
                                     } else {
-                                        // 検索バーをクリア
-                                        self.state.directory_search_query.clear();
+                                        // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                        self.state.update_directory_search_after_navigation();
                                     }
                                 }
+
+                                // 右クリックメニュー
+                                response.context_menu(|ui| {
+                                    if ui.button("名前変更").clicked() {
+                                        self.state.rename_quick_access_dialog = Some(
+                                            crate::app::state::RenameQuickAccessDialog::new(
+                                                entry.id.clone(),
+                                                entry.name.clone()
+                                            )
+                                        );
+                                        ui.close_menu();
+                                    }
+                                    if ui.button("削除").clicked() {
+                                        if entry.is_system {
+                                            self.state.toasts.push(
+                                                crate::app::state::OperationResultMessage::warning(
+                                                    format!("「{}」はシステム項目のため削除できません", entry.name)
+                                                )
+                                            );
+                                        } else {
+                                            match self.state.remove_from_quick_access(&entry.id) {
+                                                Ok(()) => {
+                                                    log::info!("「{}」をクイックアクセスから削除しました", entry.name);
+                                                    self.state.toasts.push(
+                                                        crate::app::state::OperationResultMessage::success(
+                                                            format!("「{}」をクイックアクセスから削除しました", entry.name)
+                                                        )
+                                                    );
+                                                }
+                                                Err(e) => {
+                                                    log::error!("クイックアクセスからの削除に失敗: {}", e);
+                                                    self.state.toasts.push(
+                                                        crate::app::state::OperationResultMessage::error(
+                                                            format!("クイックアクセスからの削除に失敗しました: {}", e)
+                                                        )
+                                                    );
+                                                }
+                                            }
+                                        }
+                                        ui.close_menu();
+                                    }
+                                });
+                            }
+
+                            // セクション全体をドロップ先にする
+                            let quick_access_rect = egui::Rect::from_x_y_ranges(
+                                ui.max_rect().x_range(),
+                                quick_access_top..=ui.cursor().min.y,
+                            );
+                            let quick_access_drop = ui.interact(
+                                quick_access_rect,
+                                ui.id().with("quick_access_drop"),
+                                egui::Sense::hover(),
+                            );
+                            if egui::DragAndDrop::has_payload_of_type::<std::path::PathBuf>(ctx)
+                                && quick_access_drop.contains_pointer()
+                            {
+                                ui.painter().rect_stroke(quick_access_rect, 4.0, ui.visuals().selection.stroke);
                             }
+                            self.handle_quick_access_drop(ctx, &quick_access_drop);
 
                             ui.separator();
 
@@ -1042,8 +1782,8 @@ impl eframe::App for OfktApp {
                                     if let Err(e) = self.state.init_directory_browser(drive.path.clone()) {
                                         log::error!("ディレクトリブラウザ初期化失敗: {}", e);
                                     } else {
-                                        // 検索バーをクリア
-                                        self.state.directory_search_query.clear();
+                                        // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                        self.state.update_directory_search_after_navigation();
                                     }
                                 }
                             }
@@ -1065,8 +1805,8 @@ impl eframe::App for OfktApp {
                                         if let Err(e) = self.state.init_directory_browser(dist.path.clone()) {
                                             log::error!("ディレクトリブラウザ初期化失敗: {}", e);
                                         } else {
-                                            // 検索バーをクリア
-                                            self.state.directory_search_query.clear();
+                                            // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                            self.state.update_directory_search_after_navigation();
                                         }
                                     }
                                 }
@@ -1118,7 +1858,7 @@ impl eframe::App for OfktApp {
                                                     if let Err(e) = self.state.init_directory_browser(alias.path.clone()) {
                                                         log::error!("エイリアスパスへの移動に失敗: {}", e);
                                                     } else {
-                                                        self.state.directory_search_query.clear();
+                                                        self.state.update_directory_search_after_navigation();
                                                     }
                                                 }
                                             } else {
@@ -1131,7 +1871,7 @@ impl eframe::App for OfktApp {
                                                         if let Err(e) = self.state.init_directory_browser(entry.path.clone()) {
                                                             log::error!("クイックアクセスへの移動に失敗: {}", e);
                                                         } else {
-                                                            self.state.directory_search_query.clear();
+                                                            self.state.update_directory_search_after_navigation();
                                                         }
                                                     }
                                                 } else {
@@ -1144,7 +1884,7 @@ impl eframe::App for OfktApp {
                                                             if let Err(e) = self.state.init_directory_browser(drive.path.clone()) {
                                                                 log::error!("ドライブへの移動に失敗: {}", e);
                                                             } else {
-                                                                self.state.directory_search_query.clear();
+                                                                self.state.update_directory_search_after_navigation();
                                                             }
                                                         }
                                                     } else {
@@ -1157,7 +1897,7 @@ impl eframe::App for OfktApp {
                                                                 if let Err(e) = self.state.init_directory_browser(dist.path.clone()) {
                                                                     log::error!("WSL分布版への移動に失敗: {}", e);
                                                                 } else {
-                                                                    self.state.directory_search_query.clear();
+                                                                    self.state.update_directory_search_after_navigation();
                                                                 }
                                                             }
                                                         }
@@ -1170,6 +1910,7 @@ impl eframe::App for OfktApp {
                             }
                         });
                 });
+                self.persist_sidebar_width(ctx, sidebar_response.response.rect.width());
 
                 // メインパネル
                 let mut central_panel = egui::CentralPanel::default();
@@ -1177,7 +1918,7 @@ impl eframe::App for OfktApp {
                 // メインパネルにフォーカスがある場合は枠線を表示
                 if self.state.current_focus_area == FocusArea::Main {
                     central_panel = central_panel.frame(egui::Frame {
-                        stroke: egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 150, 255)),  // 青色の枠線
+                        stroke: self.state.focus_stroke(),
                         ..Default::default()
                     });
                 }
@@ -1191,34 +1932,20 @@ impl eframe::App for OfktApp {
                     if self.state.pending_file_copy {
                         self.state.pending_file_copy = false;
                         log::info!("[DIRECTORY] Ctrl+C処理開始 (focus={:?})", self.state.current_focus_area);
-                        if let Some(ref browser) = self.state.directory_browser {
-                            let entries = self.state.get_current_entries();
-                            // 検索クエリでフィルタリング
-                            let filtered_entries: Vec<_> = if self.state.directory_search_query.is_empty() {
-                                entries
-                            } else {
-                                let query = self.state.directory_search_query.to_lowercase();
-                                entries.into_iter()
-                                    .filter(|e| e.name.to_lowercase().contains(&query))
-                                    .collect()
-                            };
-                            log::debug!("[DEBUG] selected_directory_index={:?}", self.state.selected_directory_index);
-                            if let Some(idx) = self.state.selected_directory_index {
-                                if let Some(entry) = filtered_entries.get(idx) {
-                                    self.state.clipboard_state.copy(vec![entry.path.clone()]);
-                                    log::info!("「{}」をコピーしました", entry.name);
-                                    self.state.operation_result_message = Some(
-                                        crate::app::state::OperationResultMessage::success(
-                                            format!("「{}」をコピーしました", entry.name)
-                                        )
-                                    );
-                                } else {
-                                    log::debug!("[DIRECTORY] selected_directory_index is Some but entry not found");
-                                }
+                        if self.state.directory_browser.is_some() {
+                            // 複数選択があればその全体、なければフォーカス中の項目
+                            let paths = self.state.directory_selection_paths(&self.state.visible_directory_paths());
+                            log::debug!("[DEBUG] selected_directory_index={:?}, 選択 {} 件",
+                                self.state.selected_directory_index, paths.len());
+                            if let Some(message) = selection_summary(&paths, "コピーしました") {
+                                self.state.clipboard_state.copy(paths);
+                                log::info!("{}", message);
+                                self.state.toasts.push(
+                                    crate::app::state::OperationResultMessage::success(message)
+                                );
                             } else {
-                                log::debug!("[DIRECTORY] selected_directory_index is None");
+                                log::debug!("[DIRECTORY] コピーする項目が選択されていません");
                             }
-                            let _ = browser; // 借用を明示的に終了
                         } else {
                             log::warn!("[DIRECTORY] Ctrl+C: ディレクトリブラウザが初期化されていません");
                         }
@@ -1228,29 +1955,16 @@ impl eframe::App for OfktApp {
                     if self.state.pending_file_cut {
                         self.state.pending_file_cut = false;
                         log::info!("[DIRECTORY] Ctrl+X処理開始 (focus={:?})", self.state.current_focus_area);
-                        if let Some(ref browser) = self.state.directory_browser {
-                            let entries = self.state.get_current_entries();
-                            // 検索クエリでフィルタリング
-                            let filtered_entries: Vec<_> = if self.state.directory_search_query.is_empty() {
-                                entries
-                            } else {
-                                let query = self.state.directory_search_query.to_lowercase();
-                                entries.into_iter()
-                                    .filter(|e| e.name.to_lowercase().contains(&query))
-                                    .collect()
-                            };
-                            if let Some(idx) = self.state.selected_directory_index {
-                                if let Some(entry) = filtered_entries.get(idx) {
-                                    self.state.clipboard_state.cut(vec![entry.path.clone()]);
-                                    log::info!("「{}」を切り取りました", entry.name);
-                                    self.state.operation_result_message = Some(
-                                        crate::app::state::OperationResultMessage::success(
-                                            format!("「{}」を切り取りました", entry.name)
-                                        )
-                                    );
-                                }
+                        if self.state.directory_browser.is_some() {
+                            // 複数選択があればその全体、なければフォーカス中の項目
+                            let paths = self.state.directory_selection_paths(&self.state.visible_directory_paths());
+                            if let Some(message) = selection_summary(&paths, "切り取りました") {
+                                self.state.clipboard_state.cut(paths);
+                                log::info!("{}", message);
+                                self.state.toasts.push(
+                                    crate::app::state::OperationResultMessage::success(message)
+                                );
                             }
-                            let _ = browser; // 借用を明示的に終了
                         } else {
                             log::warn!("[DIRECTORY] Ctrl+X: ディレクトリブラウザが初期化されていません");
                         }
@@ -1323,21 +2037,26 @@ impl eframe::App for OfktApp {
                     ui.separator();
 
                     if self.state.directory_browser.is_some() {
-                        let entries = self.state.get_current_entries();
+                        // `**クエリ` の場合は配下を再帰検索（結果はバックグラウンドで届く）
+                        self.state.update_recursive_search();
+                        let recursive_search = self.state.is_recursive_search_active();
+                        let recursive_pending = self.state.recursive_search
+                            .as_ref()
+                            .is_some_and(|task| task.is_pending());
 
                         // 検索クエリでフィルタリング
-                        let filtered_entries: Vec<_> = if self.state.directory_search_query.is_empty() {
-                            entries
-                        } else {
-                            let query = self.state.directory_search_query.to_lowercase();
-                            entries.into_iter()
-                                .filter(|e| e.name.to_lowercase().contains(&query))
-                                .collect()
-                        };
+                        let filtered_entries = self.state.filtered_directory_entries();
+
+                        // 再読み込みや検索で表示されなくなった項目を複数選択から外す
+                        let visible_paths: Vec<_> = filtered_entries.iter()
+                            .map(|entry| entry.path.clone())
+                            .collect();
+                        self.state.retain_visible_selection(&visible_paths);
 
                         // 現在のパス表示
                         let current_path = self.state.directory_browser.as_ref().unwrap().current_path().to_path_buf();
-                        ui.label(format!("パス: {}", current_path.display()));
+                        ui.label(format!("パス: {}", crate::utils::path::display_path(&current_path)))
+                            .on_hover_text(current_path.display().to_string());
 
                         // ナビゲーションボタンの状態を取得
                         let can_back = self.state.directory_browser.as_ref().unwrap().can_go_back();
@@ -1349,56 +2068,78 @@ impl eframe::App for OfktApp {
                                 if let Err(e) = self.state.directory_browser.as_mut().unwrap().go_back() {
                                     log::error!("戻るに失敗: {}", e);
                                 } else {
-                                    // 検索バーをクリア
-                                    self.state.directory_search_query.clear();
+                                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                    self.state.update_directory_search_after_navigation();
                                 }
                             }
                             if ui.add_enabled(can_forward, egui::Button::new("進む →")).clicked() {
                                 if let Err(e) = self.state.directory_browser.as_mut().unwrap().go_forward() {
                                     log::error!("進むに失敗: {}", e);
                                 } else {
-                                    // 検索バーをクリア
-                                    self.state.directory_search_query.clear();
+                                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                    self.state.update_directory_search_after_navigation();
                                 }
                             }
                             if ui.button("親フォルダ ↑").clicked() {
                                 if let Err(e) = self.state.directory_browser.as_mut().unwrap().parent() {
                                     log::error!("親フォルダへの移動に失敗: {}", e);
                                 } else {
-                                    // 検索バーをクリア
-                                    self.state.directory_search_query.clear();
+                                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                    self.state.update_directory_search_after_navigation();
                                 }
                             }
                         });
 
                         ui.separator();
 
-                        // フィルタリングされたエントリ数を表示
-                        ui.label(format!("エントリ: {} 件", filtered_entries.len()));
+                        // フィルタリングされたエントリ数と表示形式の切り替え
+                        ui.horizontal(|ui| {
+                            if recursive_pending {
+                                ui.spinner();
+                                ui.label("検索中...");
+                            } else if recursive_search {
+                                ui.label(format!("配下の一致: {} 件", filtered_entries.len()));
+                            } else {
+                                ui.label(format!("エントリ: {} 件", filtered_entries.len()));
+                            }
+                            let (toggle_label, next_mode) = match self.state.directory_view_mode {
+                                DirectoryViewMode::Tree => ("▦ テーブル", DirectoryViewMode::Table),
+                                DirectoryViewMode::Table => ("🌲 ツリー", DirectoryViewMode::Tree),
+                            };
+                            if ui.button(toggle_label).clicked() {
+                                self.state.directory_view_mode = next_mode;
+                                log::info!("表示形式を切り替え: {:?}", next_mode);
+                            }
+                            if ui.selectable_label(self.state.selection_mode, "☑ 選択モード").clicked() {
+                                self.state.toggle_selection_mode();
+                            }
+                        });
 
                         ui.separator();
 
+                        // 選択モードの一括操作バー
+                        if self.state.selection_mode {
+                            self.render_selection_toolbar(ui, &visible_paths);
+                            ui.separator();
+                        }
+
+                        // マウスのサイドボタンで戻る/進む（フォーカス位置に関係なく有効）
+                        if !self.state.is_any_dialog_open() {
+                            if let Some(navigation) = ctx.input(|i| HistoryNavigation::from_events(&i.events)) {
+                                if let Err(e) = self.state.navigate_history(navigation) {
+                                    log::error!("履歴の移動に失敗: {}", e);
+                                }
+                            }
+                        }
+
                         // メインパネルにフォーカスがある場合のみキーイベント処理を実行
                         // ダイアログ表示中はキー入力をスキップ
                         if self.state.current_focus_area == FocusArea::Main && !self.state.is_any_dialog_open() {
                             if ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
                                 if let Some(idx) = self.state.selected_directory_index {
                                     if let Some(entry) = filtered_entries.get(idx) {
-                                        if entry.is_directory {
-                                            // ディレクトリの場合は移動
-                                            if let Err(e) = self.state.directory_browser.as_mut().unwrap().navigate_to(entry.path.clone()) {
-                                                log::error!("ディレクトリの移動に失敗: {}", e);
-                                            } else {
-                                                // 検索バーをクリア
-                                                self.state.directory_search_query.clear();
-                                            }
-                                        } else {
-                                            // ファイルの場合は開く
-                                            let file_manager = FileManager::new();
-                                            if let Err(e) = file_manager.open(&entry.path) {
-                                                log::error!("ファイルを開くのに失敗: {}", e);
-                                            }
-                                        }
+                                        // ディレクトリは移動、ファイルは開く
+                                        self.activate_directory_entry(entry.path.clone());
                                     }
                                 }
                             }
@@ -1409,24 +2150,24 @@ impl eframe::App for OfktApp {
                                 if let Err(e) = self.state.directory_browser.as_mut().unwrap().parent() {
                                     log::error!("親フォルダへの移動に失敗: {}", e);
                                 } else {
-                                    // 検索バーをクリア
-                                    self.state.directory_search_query.clear();
+                                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                    self.state.update_directory_search_after_navigation();
                                 }
                             }
                             if ctx.input(|i| i.modifiers.alt && i.key_pressed(egui::Key::ArrowLeft)) {
                                 if let Err(e) = self.state.directory_browser.as_mut().unwrap().go_back() {
                                     log::error!("戻るに失敗: {}", e);
                                 } else {
-                                    // 検索バーをクリア
-                                    self.state.directory_search_query.clear();
+                                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                    self.state.update_directory_search_after_navigation();
                                 }
                             }
                             if ctx.input(|i| i.modifiers.alt && i.key_pressed(egui::Key::ArrowRight)) {
                                 if let Err(e) = self.state.directory_browser.as_mut().unwrap().go_forward() {
                                     log::error!("進むに失敗: {}", e);
                                 } else {
-                                    // 検索バーをクリア
-                                    self.state.directory_search_query.clear();
+                                    // 検索バーをクリア（設定によっては絞り込みを続ける）
+                                    self.state.update_directory_search_after_navigation();
                                 }
                             }
 
@@ -1468,8 +2209,8 @@ impl eframe::App for OfktApp {
                                                             self.state.directory_search_query.clear();
 
                                                             // 警告メッセージを表示
-                                                            self.state.paste_result_message = Some(
-                                                                crate::app::state::PasteResultMessage::new(
+                                                            self.state.toasts.push(
+                                                                crate::app::state::OperationResultMessage::with_type(
                                                                     format!("親ディレクトリ「{}」は検索結果に含まれていないため、検索をクリアしました",
                                                                         parent_path.display()),
                                                                     crate::app::state::MessageType::Warning
@@ -1503,6 +2244,21 @@ impl eframe::App for OfktApp {
                                 }
                             }
 
+                            // Ctrl+A: 表示中の項目をすべて選択
+                            if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::A)) {
+                                self.state.select_all_directory_entries(&visible_paths);
+                            }
+
+                            // Delete: 選択中の項目の削除確認ダイアログを表示
+                            if ctx.input(|i| i.key_pressed(egui::Key::Delete)) {
+                                let paths = self.state.directory_selection_paths(&visible_paths);
+                                if !paths.is_empty() {
+                                    self.state.delete_confirmation_dialog = Some(
+                                        crate::app::state::DeleteConfirmationDialog::new(paths)
+                                    );
+                                }
+                            }
+
                             // Ctrl+D: クイックアクセスに追加（確認ダイアログを表示）
                             if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::D)) {
                                 if let Some(idx) = self.state.selected_directory_index {
@@ -1521,88 +2277,148 @@ impl eframe::App for OfktApp {
                             }
                         }
 
-                        // スクロール可能なエリアでファイルツリーを表示
-                        egui::ScrollArea::vertical()
-                            .auto_shrink([false, false])
-                            .show(ui, |ui| {
-                                // ファイルツリー表示（filtered_entriesを使用）
-                                // メインパネルにフォーカスがある場合のみハイライト表示
-                                let display_selected_index = if self.state.current_focus_area == FocusArea::Main {
-                                    self.state.selected_directory_index
-                                } else {
-                                    None
-                                };
+                        // メインパネルにフォーカスがある場合のみハイライト表示
+                        let display_selected_index = if self.state.current_focus_area == FocusArea::Main {
+                            self.state.selected_directory_index
+                        } else {
+                            None
+                        };
+
+                        // 再帰検索の結果は相対パスを並べるためテーブル表示にする
+                        let view_mode = if recursive_search {
+                            DirectoryViewMode::Table
+                        } else {
+                            self.state.directory_view_mode
+                        };
+                        let (selected_path, open_path, is_right_click, total_items) = match view_mode {
+                            // 選択モードではチェックボックス付きの一覧を表示
+                            _ if self.state.selection_mode => {
+                                let rows: Vec<_> = filtered_entries.iter()
+                                    .map(|entry| {
+                                        let icon = if entry.is_directory { "📁" } else { "📄" };
+                                        (entry.path.clone(), format!("{} {}", icon, entry.name))
+                                    })
+                                    .collect();
+                                egui::ScrollArea::vertical()
+                                    .auto_shrink([false, false])
+                                    .show(ui, |ui| self.render_checkbox_rows(ui, &rows));
+                                (None, None, false, filtered_entries.len())
+                            }
+                            DirectoryViewMode::Table => {
+                                // テーブル表示（列見出しクリックでソート）
+                                let browser = self.state.directory_browser.as_ref().unwrap();
+                                let (sort_key, sort_order) = (browser.sort_key(), browser.sort_order());
 
-                                let (selected_path, open_path, is_right_click, total_items) = self.file_tree.render_directory_tree(
+                                let response = self.file_table.render(
                                     ui,
                                     &filtered_entries,
-                                    &mut self.state.expanded_directories,
-                                    display_selected_index,
-                                    self.state.pasted_files_highlight.as_ref()
+                                    sort_key,
+                                    sort_order,
+                                    EntryRowContext {
+                                        selected_index: display_selected_index,
+                                        multi_selected: &self.state.multi_selected_paths,
+                                        pasted_highlight: self.state.pasted_files_highlight.as_ref(),
+                                        clipboard: &self.state.clipboard_state,
+                                        failed_badges: &self.state.failed_path_badges,
+                                    },
                                 );
 
-                                // キーボードナビゲーション（ArrowDown/ArrowUp）
-                                // total_items（展開されたツリー全体）を使用
-                                if self.state.current_focus_area == FocusArea::Main {
-                                    if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
-                                        let max_index = total_items.saturating_sub(1);
-                                        self.state.selected_directory_index = Some(
-                                            self.state.selected_directory_index.map(|i| (i + 1).min(max_index)).unwrap_or(0)
-                                        );
-                                    }
-                                    if ctx.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
-                                        self.state.selected_directory_index = self.state.selected_directory_index.and_then(|i| i.checked_sub(1));
-                                    }
+                                if let Some(column) = response.clicked_column {
+                                    let (new_key, new_order) = sort_for_header_click(sort_key, sort_order, column);
+                                    self.state.directory_browser.as_mut().unwrap().set_sort(new_key, new_order);
+                                    log::info!("ソートを変更: {:?} {:?}", new_key, new_order);
                                 }
 
-                                // シングルクリック → 選択のみ
-                                if let Some(ref path) = selected_path {
-                                    // パスからインデックスを検索
-                                    self.state.selected_directory_index = filtered_entries.iter()
-                                        .position(|e| paths_equal(&e.path, path));
-
-                                    if is_right_click {
-                                        // 右クリックの場合、コンテキストメニュー状態を設定
-                                        if let Some(entry) = filtered_entries.iter().find(|e| paths_equal(&e.path, path)) {
-                                            let pointer_pos = ctx.input(|i| i.pointer.hover_pos().unwrap_or(egui::Pos2::ZERO));
-                                            self.state.context_menu_state = Some(
-                                                crate::app::state::ContextMenuState::new(
-                                                    pointer_pos,
-                                                    entry.path.clone(),
-                                                    entry.name.clone(),
-                                                    entry.is_directory,
-                                                )
-                                            );
-                                        }
-                                    }
-                                }
+                                (response.selected_path, response.open_path, response.is_right_click, filtered_entries.len())
+                            }
+                            DirectoryViewMode::Tree => {
+                                // スクロール可能なエリアでファイルツリーを表示
+                                egui::ScrollArea::vertical()
+                                    .auto_shrink([false, false])
+                                    .show(ui, |ui| {
+                                        // ファイルツリー表示（filtered_entriesを使用）
+                                        self.file_tree.render_directory_tree(
+                                            ui,
+                                            &filtered_entries,
+                                            &mut self.state.expanded_directories,
+                                            EntryRowContext {
+                                                selected_index: display_selected_index,
+                                                multi_selected: &self.state.multi_selected_paths,
+                                                pasted_highlight: self.state.pasted_files_highlight.as_ref(),
+                                                clipboard: &self.state.clipboard_state,
+                                                failed_badges: &self.state.failed_path_badges,
+                                            },
+                                        )
+                                    })
+                                    .inner
+                            }
+                        };
 
-                                // ダブルクリック → ファイルを開く / ディレクトリに移動
-                                if let Some(ref path) = open_path {
-                                    if let Some(entry) = filtered_entries.iter().find(|e| paths_equal(&e.path, path)) {
-                                        if entry.is_directory {
-                                            // ディレクトリをダブルクリックで移動
-                                            if let Err(e) = self.state.directory_browser.as_mut().unwrap().navigate_to(entry.path.clone()) {
-                                                log::error!("ディレクトリの移動に失敗: {}", e);
-                                            } else {
-                                                // 検索バーをクリア
-                                                self.state.directory_search_query.clear();
-                                            }
-                                        } else {
-                                            // ファイルをダブルクリックで開く
-                                            let file_manager = FileManager::new();
-                                            if let Err(e) = file_manager.open(&entry.path) {
-                                                log::error!("ファイルを開くのに失敗: {}", e);
-                                            }
-                                        }
-                                    }
+                        // キーボードナビゲーション（ArrowDown/ArrowUp）
+                        // total_items（展開されたツリー全体）を使用
+                        if self.state.current_focus_area == FocusArea::Main {
+                            if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+                                let max_index = total_items.saturating_sub(1);
+                                self.state.selected_directory_index = Some(
+                                    self.state.selected_directory_index.map(|i| (i + 1).min(max_index)).unwrap_or(0)
+                                );
+                            }
+                            if ctx.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
+                                self.state.selected_directory_index = self.state.selected_directory_index.and_then(|i| i.checked_sub(1));
+                            }
+                        }
+
+                        // シングルクリック → 選択のみ（Ctrl/Shift+クリックで複数選択）
+                        if let Some(ref path) = selected_path {
+                            if is_right_click {
+                                // 右クリックは複数選択を変えずにフォーカスだけ移す
+                                self.state.selected_directory_index = filtered_entries.iter()
+                                    .position(|e| paths_equal(&e.path, path));
+                            } else {
+                                let click = SelectionClick::from_modifiers(ctx.input(|i| i.modifiers));
+                                self.state.click_directory_entry(&visible_paths, path, click);
+                            }
+
+                            if is_right_click {
+                                // 右クリックの場合、コンテキストメニュー状態を設定
+                                if let Some(entry) = filtered_entries.iter().find(|e| paths_equal(&e.path, path)) {
+                                    let pointer_pos = ctx.input(|i| i.pointer.hover_pos().unwrap_or(egui::Pos2::ZERO));
+                                    self.state.context_menu_state = Some(
+                                        crate::app::state::ContextMenuState::new(
+                                            pointer_pos,
+                                            entry.path.clone(),
+                                            entry.name.clone(),
+                                            entry.is_directory,
+                                        )
+                                    );
                                 }
-                            });
+                            }
+                        }
+
+                        // ダブルクリック → ファイルを開く / ディレクトリに移動
+                        if let Some(ref path) = open_path {
+                            if let Some(entry) = filtered_entries.iter().find(|e| paths_equal(&e.path, path)) {
+                                self.activate_directory_entry(entry.path.clone());
+                            }
+                        }
                     } else {
                         ui.label("ディレクトリブラウザが初期化されていません");
                     }
                 });
             }
+            BrowseMode::Recent => {
+                // 最近開いたファイル・フォルダ
+                let recent = self.state.recent_entries();
+                let mut clicked_path = None;
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        clicked_path = crate::ui::history::HistoryView::new().render_interactive(ui, &recent);
+                    });
+                });
+                if let Some(path) = clicked_path {
+                    self.open_recent(path);
+                }
+            }
         }
 
         // エイリアス追加ダイアログ
@@ -1616,150 +2432,315 @@ impl eframe::App for OfktApp {
 
                     ui.label("パス:");
                     ui.horizontal(|ui| {
-                        ui.text_edit_singleline(&mut self.state.new_alias_path);
+                        if ui.text_edit_singleline(&mut self.state.new_alias_path).changed() {
+                            // パスを変えたら同じパスの警告は確認し直す
+                            self.state.add_alias_path_warning = None;
+                        }
                         if ui.button("...").clicked() {
                             // ディレクトリ選択ダイアログ（将来実装）
                             log::info!("ディレクトリ選択ダイアログ（未実装）");
                         }
                     });
 
-                    ui.separator();
+                    // ショートカットファイルはリンク先をエイリアスにできるようにする
+                    let path = std::path::PathBuf::from(&self.state.new_alias_path);
+                    if crate::core::file_manager::is_shortcut_file(&path) {
+                        ui.horizontal(|ui| {
+                            ui.label("ショートカットファイルです");
+                            if ui.button("リンク先を使う").clicked() {
+                                match crate::core::file_manager::resolve_lnk(&path) {
+                                    Ok(target) => {
+                                        self.state.new_alias_path = target.display().to_string();
+                                    }
+                                    Err(e) => {
+                                        log::warn!("ショートカットの解決に失敗: {}", e);
+                                        self.state.toasts.push(
+                                            crate::app::state::OperationResultMessage::error(e)
+                                        );
+                                    }
+                                }
+                            }
+                        });
+                    }
+
+                    // 同じパスを指すエイリアスがある場合は、続けるか確認する
+                    let mut add_anyway = false;
+                    if let Some(ref warning) = self.state.add_alias_path_warning {
+                        ui.colored_label(ui.visuals().warn_fg_color, warning);
+                        ui.horizontal(|ui| {
+                            if ui.button("このまま追加").clicked() {
+                                add_anyway = true;
+                            }
+                        });
+                    }
+
+                    ui.separator();
 
                     ui.horizontal(|ui| {
+                        let mut should_add = add_anyway;
                         if ui.button("追加").clicked() {
                             // バリデーション
                             if self.state.new_alias_name.is_empty() {
                                 log::warn!("エイリアス名が空です");
                             } else if self.state.new_alias_path.is_empty() {
                                 log::warn!("パスが空です");
-                            } else {
-                                // エイリアスを追加
-                                match self.state.alias_manager.add_alias(
-                                    self.state.new_alias_name.clone(),
-                                    std::path::PathBuf::from(&self.state.new_alias_path),
-                                    vec![],
-                                    None,
-                                    false,
-                                ) {
-                                    Ok(()) => {
-                                        // 保存
-                                        if let Err(e) = self.state.alias_manager.save() {
-                                            log::error!("エイリアスの保存に失敗: {}", e);
-                                        } else {
-                                            // file_aliasesとfiltered_itemsを更新
-                                            self.state.file_aliases = self.state.alias_manager.get_aliases().to_vec();
-                                            self.state.filter_aliases();
-                                            log::info!("エイリアス「{}」を追加しました", self.state.new_alias_name);
-                                            self.state.show_add_alias_dialog = false;
+                            } else if self.state.add_alias_path_warning.is_none() {
+                                self.state.add_alias_path_warning = self.state.new_alias_duplicate_warning();
+                                should_add = self.state.add_alias_path_warning.is_none();
+                            }
+                        }
+
+                        if should_add {
+                            // エイリアスを追加
+                            let path = std::path::PathBuf::from(&self.state.new_alias_path);
+                            match self.state.alias_manager.add_alias(
+                                self.state.new_alias_name.clone(),
+                                path.clone(),
+                                vec![],
+                                None,
+                                false,
+                            ) {
+                                Ok(()) => {
+                                    // リムーバブルドライブ上ならボリューム情報を記録
+                                    let volume = crate::platform::volume_for_path(
+                                        &path,
+                                        &crate::platform::SystemDriveEnumerator,
+                                    );
+                                    if volume.is_some() {
+                                        if let Err(e) = self.state.alias_manager
+                                            .set_volume(&self.state.new_alias_name, volume)
+                                        {
+                                            log::warn!("ボリューム情報の記録に失敗: {}", e);
                                         }
                                     }
-                                    Err(e) => {
-                                        log::error!("エイリアスの追加に失敗: {}", e);
+
+                                    // 保存
+                                    if let Err(e) = self.state.alias_manager.save() {
+                                        self.state.report_save_error("エイリアス", e);
+                                    } else {
+                                        // 表示中の一覧を検索し直し、選択を範囲内に収める
+                                        self.state.refresh_after_alias_change();
+                                        log::info!("エイリアス「{}」を追加しました", self.state.new_alias_name);
+                                        self.state.show_add_alias_dialog = false;
+                                        self.state.add_alias_path_warning = None;
                                     }
                                 }
+                                Err(e) => {
+                                    log::error!("エイリアスの追加に失敗: {}", e);
+                                }
                             }
                         }
 
                         if ui.button("キャンセル").clicked() {
                             self.state.show_add_alias_dialog = false;
+                            self.state.add_alias_path_warning = None;
                         }
                     });
                 });
         }
 
-        // ペースト結果メッセージの表示
-        if let Some(ref msg) = self.state.paste_result_message {
-            // 表示から500ms経過後、任意のキー押下で閉じる
-            let can_dismiss = msg.timestamp.elapsed() > std::time::Duration::from_millis(500);
-            let any_key_pressed = ctx.input(|i| {
-                i.key_pressed(egui::Key::Enter)
-                    || i.key_pressed(egui::Key::Escape)
-                    || i.key_pressed(egui::Key::Space)
-                    || i.key_pressed(egui::Key::ArrowUp)
-                    || i.key_pressed(egui::Key::ArrowDown)
-                    || i.key_pressed(egui::Key::ArrowLeft)
-                    || i.key_pressed(egui::Key::ArrowRight)
-            });
-
-            if msg.is_expired() || (can_dismiss && any_key_pressed) {
-                self.state.paste_result_message = None;
-            } else {
-                let title = match msg.message_type {
-                    crate::app::state::MessageType::Success => "✓ 成功",
-                    crate::app::state::MessageType::Error => "✗ エラー",
-                    crate::app::state::MessageType::Warning => "⚠ 警告",
-                };
+        // 通知トーストの表示（古い順に上から積み重ねる）
+        // 複数行のトーストも重ならないよう、1つの領域に縦に並べて実際の高さで配置する
+        self.state.toasts.remove_expired();
+        let mut dismissed_index = None;
+        if !self.state.toasts.is_empty() {
+            egui::Area::new(egui::Id::new("toasts"))
+                .anchor(egui::Align2::CENTER_TOP, [0.0, 50.0])
+                .order(egui::Order::Foreground)
+                .show(ctx, |ui| {
+                    ui.vertical_centered(|ui| {
+                        for (index, toast) in self.state.toasts.iter().enumerate() {
+                            let color = match toast.message_type {
+                                crate::app::state::MessageType::Success => egui::Color32::from_rgb(200, 255, 200),
+                                crate::app::state::MessageType::Error => egui::Color32::from_rgb(255, 200, 200),
+                                crate::app::state::MessageType::Warning => egui::Color32::from_rgb(255, 255, 200),
+                            };
 
-                let message_clone = msg.message.clone();
-                let mut open = true;
-                let mut should_close = false;
-                egui::Window::new(title)
-                    .open(&mut open)
-                    .resizable(false)
-                    .collapsible(false)
-                    .show(ctx, |ui| {
-                        ui.label(&message_clone);
-                        ui.add_space(10.0);
-                        if ui.button("OK").clicked() {
-                            should_close = true;
+                            egui::Frame::window(ui.style()).fill(color).show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.colored_label(egui::Color32::BLACK, &toast.message);
+                                    if ui.small_button("✖").clicked() {
+                                        dismissed_index = Some(index);
+                                    }
+                                });
+                            });
                         }
                     });
-
-                if !open || should_close {
-                    self.state.paste_result_message = None;
-                }
-            }
+                });
+        }
+        if let Some(index) = dismissed_index {
+            self.state.toasts.dismiss(index);
         }
 
-        // 操作結果メッセージの表示
-        if let Some(ref msg) = self.state.operation_result_message {
-            if msg.is_expired() {
-                self.state.operation_result_message = None;
-            } else {
-                let color = match msg.message_type {
-                    crate::app::state::MessageType::Success => egui::Color32::from_rgb(200, 255, 200),
-                    crate::app::state::MessageType::Error => egui::Color32::from_rgb(255, 200, 200),
-                    crate::app::state::MessageType::Warning => egui::Color32::from_rgb(255, 255, 200),
-                };
+        // 貼り付けの進捗ダイアログ
+        if let Some(ref task) = self.state.paste_task {
+            let progress = task.progress();
+            let action = if task.mode() == crate::core::clipboard::ClipboardMode::Copy { "コピー" } else { "移動" };
 
-                let message_clone = msg.message.clone();
-                egui::Window::new("操作結果")
-                    .collapsible(false)
-                    .resizable(false)
-                    .anchor(egui::Align2::CENTER_TOP, [0.0, 50.0])
-                    .frame(egui::Frame::window(&ctx.style()).fill(color))
-                    .show(ctx, |ui| {
-                        ui.label(&message_clone);
-                    });
-            }
+            egui::Window::new(format!("{}中", action))
+                .id(egui::Id::new("paste_progress_dialog"))
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "処理中: {}",
+                        progress.current_file.as_deref().unwrap_or("準備中...")
+                    ));
+                    ui.label(format!(
+                        "{} / {} 個のファイル（{} / {}）",
+                        progress.files_completed,
+                        progress.total_files,
+                        crate::ui::file_table::format_size(progress.bytes_copied),
+                        crate::ui::file_table::format_size(progress.total_bytes),
+                    ));
+                    ui.add_space(5.0);
+                    ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage());
+                    ui.add_space(10.0);
+
+                    if task.is_cancelling() {
+                        ui.add_enabled(false, egui::Button::new("キャンセル中..."));
+                    } else if ui.button("キャンセル").clicked() {
+                        log::info!("貼り付け: ユーザーが「キャンセル」を選択");
+                        task.cancel();
+                    }
+                });
+
+            // 進み具合を反映するため、完了するまで再描画を続ける
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
         }
 
         // 上書き確認ダイアログ
-        if let Some(ref dialog) = self.state.overwrite_confirmation_dialog {
+        if let Some(ref mut dialog) = self.state.overwrite_confirmation_dialog {
             log::debug!("上書き確認ダイアログを描画中: {} 個のファイル", dialog.files.len());
             let mut should_close = false;
             let mut should_proceed = false;
 
-            egui::Window::new("⚠ 上書き確認")
+            let title = if dialog.files.is_empty() { "⚠ 貼り付けの確認" } else { "⚠ 上書き確認" };
+            egui::Window::new(title)
+                .id(egui::Id::new("overwrite_confirmation_dialog"))
                 .resizable(false)
                 .collapsible(false)
                 .show(ctx, |ui| {
-                    ui.label(format!("{}個のファイルが既に存在します。上書きしますか？", dialog.files.len()));
-                    ui.add_space(10.0);
-
-                    // ファイル一覧（最大5件表示）
-                    for (_i, file) in dialog.files.iter().take(5).enumerate() {
-                        ui.label(format!("• {}", file.file_name().unwrap_or_default().to_string_lossy()));
+                    if let Some(count) = dialog.large_item_count {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            format!("{} 件以上の項目を貼り付けようとしています。続行しますか？", count),
+                        );
+                        ui.add_space(10.0);
                     }
-                    if dialog.files.len() > 5 {
-                        ui.label(format!("...他{}個", dialog.files.len() - 5));
+
+                    if !dialog.files.is_empty() {
+                        ui.label(format!("{}個のファイルが既に存在します。ファイルごとに処理を選んでください。", dialog.files.len()));
+                        ui.add_space(10.0);
+
+                        // ファイル一覧（貼り付け元と貼り付け先のサイズ・更新日時を比較し、ファイルごとに処理を選ぶ）
+                        let mut chosen = None;
+                        let mut apply_toggled = None;
+                        egui::ScrollArea::vertical()
+                            .max_height(240.0)
+                            .show(ui, |ui| {
+                                egui::Grid::new("overwrite_conflicts")
+                                    .num_columns(5)
+                                    .spacing([12.0, 4.0])
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.strong("名前");
+                                        ui.strong("貼り付け元");
+                                        ui.strong("貼り付け先");
+                                        ui.strong("処理");
+                                        ui.strong("");
+                                        ui.end_row();
+
+                                        for (index, conflict) in dialog.files.iter().enumerate() {
+                                            let newer = conflict.newer_side();
+                                            ui.label(format!("• {}", conflict.file_name()));
+                                            for (info, side) in [
+                                                (conflict.source_info, crate::app::state::NewerSide::Source),
+                                                (conflict.destination_info, crate::app::state::NewerSide::Destination),
+                                            ] {
+                                                let text = info
+                                                    .map(|info| info.summary())
+                                                    .unwrap_or_else(|| "情報を取得できません".to_string());
+                                                if newer == Some(side) {
+                                                    ui.label(egui::RichText::new(format!("{}（新しい）", text)).strong());
+                                                } else {
+                                                    ui.label(text);
+                                                }
+                                            }
+
+                                            let mut resolution = dialog.resolutions[index];
+                                            ui.horizontal(|ui| {
+                                                for choice in crate::core::file_manager::ConflictResolution::ALL {
+                                                    ui.radio_value(&mut resolution, choice, choice.label());
+                                                }
+                                            });
+                                            if resolution != dialog.resolutions[index] {
+                                                chosen = Some((index, resolution));
+                                            }
+
+                                            // 最後の項目には「以降」がないため出さない
+                                            if index + 1 < dialog.files.len() {
+                                                let mut apply = dialog.apply_to_remaining[index];
+                                                if ui.checkbox(&mut apply, "以降すべてに適用").changed() {
+                                                    apply_toggled = Some((index, apply));
+                                                }
+                                            } else {
+                                                ui.label("");
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                        if let Some((index, resolution)) = chosen {
+                            dialog.set_resolution(index, resolution);
+                        }
+                        if let Some((index, apply)) = apply_toggled {
+                            dialog.set_apply_to_remaining(index, apply);
+                        }
+
+                        // 貼り付け先の方が新しい項目は、初期値をスキップにしている
+                        let newer_count = dialog.newer_destination_count();
+                        if newer_count > 0 {
+                            ui.add_space(5.0);
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                format!("{}個は貼り付け先の方が新しいため、初期値をスキップにしています。", newer_count),
+                            );
+                        }
+
+                        ui.add_space(5.0);
+                        ui.label(format!(
+                            "上書き {}個・スキップ {}個・両方残す {}個",
+                            dialog.resolution_count(crate::core::file_manager::ConflictResolution::Overwrite),
+                            dialog.resolution_count(crate::core::file_manager::ConflictResolution::Skip),
+                            dialog.resolution_count(crate::core::file_manager::ConflictResolution::KeepBoth),
+                        ));
+                        ui.add_space(10.0);
                     }
 
-                    ui.add_space(10.0);
+                    if !dialog.sensitive_files.is_empty() {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            "システムファイルまたは隠しファイルを移動しようとしています。OSやアプリが正しく動かなくなる可能性があります。",
+                        );
+                        for file in dialog.sensitive_files.iter().take(5) {
+                            ui.label(format!("• {}", file.file_name().unwrap_or_default().to_string_lossy()));
+                        }
+                        if dialog.sensitive_files.len() > 5 {
+                            ui.label(format!("...他{}個", dialog.sensitive_files.len() - 5));
+                        }
+
+                        ui.add_space(10.0);
+                    }
 
+                    let proceed_label = if !dialog.sensitive_files.is_empty() {
+                        "移動する"
+                    } else {
+                        "貼り付ける"
+                    };
                     ui.horizontal(|ui| {
-                        if ui.button("上書きする").clicked() {
-                            log::info!("上書き確認: ユーザーが「上書きする」を選択");
+                        if ui.button(proceed_label).clicked() {
+                            log::info!("上書き確認: ユーザーが「{}」を選択", proceed_label);
                             should_proceed = true;
                             should_close = true;
                         }
@@ -1772,16 +2753,15 @@ impl eframe::App for OfktApp {
 
             if should_proceed {
                 log::info!("上書き確認後、ペースト処理を実行");
-                let pending = dialog.pending_paste.clone();
+                let mut pending = dialog.pending_paste.clone();
+                pending.resolutions = dialog.resolution_map();
                 self.state.overwrite_confirmation_dialog = None;
                 // 実際のペースト処理を実行（上書きを許可）
                 self.execute_paste_operation(pending);
 
                 // ディレクトリをリロード
-                if let Some(ref mut browser) = self.state.directory_browser {
-                    if let Err(e) = browser.reload() {
-                        log::error!("ディレクトリリロード失敗: {}", e);
-                    }
+                if let Err(e) = self.state.reload_directory() {
+                    log::error!("ディレクトリリロード失敗: {}", e);
                 }
             } else if should_close {
                 self.state.overwrite_confirmation_dialog = None;
@@ -1833,8 +2813,8 @@ impl eframe::App for OfktApp {
                         log::info!("「{}」をクイックアクセスに追加しました", name);
 
                         // 成功メッセージを表示
-                        self.state.paste_result_message = Some(
-                            crate::app::state::PasteResultMessage::new(
+                        self.state.toasts.push(
+                            crate::app::state::OperationResultMessage::with_type(
                                 format!("「{}」をクイックアクセスに追加しました", name),
                                 crate::app::state::MessageType::Success
                             )
@@ -1844,8 +2824,8 @@ impl eframe::App for OfktApp {
                         log::error!("クイックアクセスへの追加に失敗: {}", e);
 
                         // エラーメッセージを表示
-                        self.state.paste_result_message = Some(
-                            crate::app::state::PasteResultMessage::new(
+                        self.state.toasts.push(
+                            crate::app::state::OperationResultMessage::with_type(
                                 format!("クイックアクセスへの追加に失敗しました: {}", e),
                                 crate::app::state::MessageType::Error
                             )
@@ -1859,13 +2839,67 @@ impl eframe::App for OfktApp {
             }
         }
 
+        // クイックアクセス名前変更ダイアログ
+        if let Some(ref mut dialog) = self.state.rename_quick_access_dialog {
+            let mut should_close = false;
+            let mut should_rename = false;
+
+            egui::Window::new("クイックアクセスの名前変更")
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("名前:");
+                        let response = ui.text_edit_singleline(&mut dialog.name);
+                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            should_rename = true;
+                        }
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("変更").clicked() {
+                            should_rename = true;
+                        }
+                        if ui.button("キャンセル").clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+
+            if should_rename {
+                let id = dialog.id.clone();
+                let name = dialog.name.clone();
+
+                match self.state.rename_quick_access(&id, &name) {
+                    Ok(()) => {
+                        log::info!("クイックアクセスの名前を「{}」に変更しました", name.trim());
+                        should_close = true;
+                    }
+                    Err(e) => {
+                        log::error!("クイックアクセスの名前変更に失敗: {}", e);
+                        self.state.toasts.push(
+                            crate::app::state::OperationResultMessage::error(
+                                format!("名前変更に失敗しました: {}", e)
+                            )
+                        );
+                    }
+                }
+            }
+
+            if should_close {
+                self.state.rename_quick_access_dialog = None;
+            }
+        }
+
         // 削除確認ダイアログの表示
         let mut delete_action: Option<bool> = None; // Some(true): 完全削除、Some(false): ゴミ箱
         let mut delete_paths: Vec<std::path::PathBuf> = Vec::new();
         let mut should_cancel_delete = false;
 
-        if let Some(ref dialog) = self.state.delete_confirmation_dialog {
-            let dialog_clone = dialog.clone();
+        if let Some(ref mut dialog) = self.state.delete_confirmation_dialog {
+            let mut dialog_clone = dialog.clone();
             delete_paths = dialog_clone.paths.clone();
 
             egui::Window::new("削除の確認")
@@ -1887,14 +2921,26 @@ impl eframe::App for OfktApp {
                             }
                         }
 
+                        // システム・隠しファイルは了承のチェックを求める
+                        if !dialog_clone.sensitive_names.is_empty() {
+                            ui.add_space(8.0);
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 165, 0),
+                                format!("⚠ システムファイルまたは隠しファイルが含まれています: {}",
+                                    dialog_clone.sensitive_names.join(", ")),
+                            );
+                            ui.checkbox(&mut dialog_clone.sensitive_acknowledged, "OSやアプリに影響する可能性を理解した上で削除する");
+                        }
+
                         ui.add_space(16.0);
 
+                        let can_confirm = dialog_clone.can_confirm();
                         ui.horizontal(|ui| {
-                            if ui.button("ゴミ箱に移動").clicked() {
+                            if ui.add_enabled(can_confirm, egui::Button::new("ゴミ箱に移動")).clicked() {
                                 delete_action = Some(false);
                             }
 
-                            if ui.button("完全に削除").clicked() {
+                            if ui.add_enabled(can_confirm, egui::Button::new("完全に削除")).clicked() {
                                 delete_action = Some(true);
                             }
 
@@ -1904,6 +2950,7 @@ impl eframe::App for OfktApp {
                         });
                     });
                 });
+            dialog.sensitive_acknowledged = dialog_clone.sensitive_acknowledged;
         }
 
         // 削除アクションの実行（ダイアログ表示後）
@@ -1932,7 +2979,19 @@ impl eframe::App for OfktApp {
                         ui.label(format!("「{}」の新しい名前:", dialog.original_name));
                         ui.add_space(8.0);
 
-                        let response = ui.text_edit_singleline(&mut dialog.new_name);
+                        let mut output = egui::TextEdit::singleline(&mut dialog.new_name).show(ui);
+                        let response = output.response;
+                        // 開いた直後は拡張子を除いた部分だけを選択する
+                        if !dialog.selection_applied {
+                            dialog.selection_applied = true;
+                            let range = dialog.initial_selection();
+                            output.state.cursor.set_char_range(Some(egui::text::CCursorRange::two(
+                                egui::text::CCursor::new(range.start),
+                                egui::text::CCursor::new(range.end),
+                            )));
+                            output.state.store(ui.ctx(), response.id);
+                            response.request_focus();
+                        }
                         if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
                             should_rename = true;
                         }
@@ -1961,7 +3020,7 @@ impl eframe::App for OfktApp {
 
                 if let Err(e) = std::fs::rename(&target_path, &new_path) {
                     log::error!("リネームに失敗: {}", e);
-                    self.state.operation_result_message = Some(
+                    self.state.toasts.push(
                         crate::app::state::OperationResultMessage::error(
                             format!("リネームに失敗: {}", e)
                         )
@@ -1975,10 +3034,10 @@ impl eframe::App for OfktApp {
                             new_path: new_path.clone(),
                         }
                     );
-                    if let Some(ref mut browser) = self.state.directory_browser {
-                        let _ = browser.reload();
-                    }
-                    self.state.operation_result_message = Some(
+                    self.state.remap_expanded_directories(&target_path, &new_path);
+                    self.state.follow_renamed_path(&target_path, &new_path);
+                    let _ = self.state.reload_directory();
+                    self.state.toasts.push(
                         crate::app::state::OperationResultMessage::success(
                             format!("「{}」を「{}」に変更しました", original_name, new_name)
                         )
@@ -1994,25 +3053,134 @@ impl eframe::App for OfktApp {
         if self.state.properties_dialog.is_some() {
             let mut should_close = false;
 
-            if let Some(ref dialog) = self.state.properties_dialog {
-                let dialog_clone = dialog.clone();
+            if let Some(ref mut dialog) = self.state.properties_dialog {
+                let size_state = dialog.size_task.as_mut().map(|task| task.poll());
+                if size_state == Some(SizeTaskState::Pending) {
+                    // 計算完了を拾えるように定期的に再描画
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                }
+
+                // 画像ファイルのサムネイル（作成されるまではプレースホルダーを表示）
+                let thumbnail_status = if dialog.is_directory {
+                    ThumbnailStatus::Unavailable
+                } else {
+                    self.state.thumbnail_cache.request(&dialog.path)
+                };
+                match &thumbnail_status {
+                    ThumbnailStatus::Loading => {
+                        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                    }
+                    ThumbnailStatus::Ready(thumbnail) => {
+                        let is_current = self.properties_thumbnail
+                            .as_ref()
+                            .is_some_and(|(shown, _)| std::sync::Arc::ptr_eq(shown, thumbnail));
+                        if !is_current {
+                            let image = egui::ColorImage::from_rgba_unmultiplied(
+                                [thumbnail.width as usize, thumbnail.height as usize],
+                                &thumbnail.rgba,
+                            );
+                            let texture = ctx.load_texture("properties_thumbnail", image, egui::TextureOptions::LINEAR);
+                            self.properties_thumbnail = Some((thumbnail.clone(), texture));
+                        }
+                    }
+                    ThumbnailStatus::Unavailable => {
+                        self.properties_thumbnail = None;
+                    }
+                }
+
                 egui::Window::new("プロパティ")
                     .collapsible(false)
                     .resizable(false)
                     .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                     .show(ctx, |ui| {
                         ui.vertical(|ui| {
-                            ui.label(format!("名前: {}", dialog_clone.name));
-                            ui.label(format!("種類: {}", if dialog_clone.is_directory { "フォルダ" } else { "ファイル" }));
-                            ui.label(format!("サイズ: {} バイト", dialog_clone.size));
-                            ui.label(format!("読み取り専用: {}", if dialog_clone.is_readonly { "はい" } else { "いいえ" }));
+                            match (&thumbnail_status, &self.properties_thumbnail) {
+                                (ThumbnailStatus::Ready(_), Some((_, texture))) => {
+                                    ui.image(egui::load::SizedTexture::from_handle(texture));
+                                    ui.add_space(8.0);
+                                }
+                                (ThumbnailStatus::Loading, _) => {
+                                    ui.horizontal(|ui| {
+                                        ui.spinner();
+                                        ui.label("プレビューを作成中");
+                                    });
+                                    ui.add_space(8.0);
+                                }
+                                _ => {}
+                            }
+                            ui.label(format!("名前: {}", dialog.name));
+                            ui.label(format!("種類: {}", if dialog.is_directory { "フォルダ" } else { "ファイル" }));
+                            match size_state {
+                                None => {
+                                    ui.label(format!("サイズ: {} バイト", dialog.size));
+                                }
+                                Some(SizeTaskState::Done(size)) => {
+                                    ui.label(format!("サイズ: {} バイト", size));
+                                }
+                                Some(SizeTaskState::Pending) => {
+                                    ui.horizontal(|ui| {
+                                        ui.label("サイズ: 計算中");
+                                        ui.spinner();
+                                        if ui.small_button("中止").clicked() {
+                                            if let Some(task) = dialog.size_task.as_mut() {
+                                                task.cancel();
+                                            }
+                                        }
+                                    });
+                                }
+                                Some(SizeTaskState::Cancelled) => {
+                                    ui.label("サイズ: 計算を中止しました");
+                                }
+                            }
+                            ui.label(format!("読み取り専用: {}", if dialog.is_readonly { "はい" } else { "いいえ" }));
 
-                            if let Some(modified) = dialog_clone.modified {
+                            if let Some(modified) = dialog.modified {
                                 if let Ok(duration) = modified.duration_since(std::time::UNIX_EPOCH) {
                                     ui.label(format!("更新日時: {:?}", duration));
                                 }
                             }
 
+                            // テキストファイルの先頭部分（文字コードは自動判定）
+                            if let Some(ref preview) = dialog.text_preview {
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("文字コード: {}", preview.encoding));
+                                    if ui.small_button("内容をコピー").clicked() {
+                                        match crate::core::text_preview::read_text(
+                                            &dialog.path,
+                                            crate::core::text_preview::TEXT_COPY_MAX_BYTES,
+                                        ) {
+                                            Ok(Some(decoded)) => {
+                                                ui.ctx().copy_text(decoded.text);
+                                                let message = if decoded.truncated {
+                                                    crate::app::state::OperationResultMessage::warning("ファイルが大きいため先頭部分のみコピーしました".to_string())
+                                                } else {
+                                                    crate::app::state::OperationResultMessage::success("内容をクリップボードにコピーしました".to_string())
+                                                };
+                                                self.state.toasts.push(message);
+                                            }
+                                            Ok(None) => {
+                                                self.state.toasts.push(crate::app::state::OperationResultMessage::error(
+                                                    "テキストとして読み込めないファイルです".to_string()
+                                                ));
+                                            }
+                                            Err(e) => {
+                                                self.state.toasts.push(crate::app::state::OperationResultMessage::error(e));
+                                            }
+                                        }
+                                    }
+                                });
+                                egui::ScrollArea::vertical()
+                                    .id_salt("properties_text_preview")
+                                    .max_height(200.0)
+                                    .show(ui, |ui| {
+                                        ui.add(egui::Label::new(egui::RichText::new(&preview.text).monospace()).wrap());
+                                        if preview.truncated {
+                                            ui.weak("（以下省略）");
+                                        }
+                                    });
+                            }
+
                             ui.add_space(16.0);
                             if ui.button("閉じる").clicked() {
                                 should_close = true;
@@ -2023,6 +3191,7 @@ impl eframe::App for OfktApp {
 
             if should_close {
                 self.state.properties_dialog = None;
+                self.properties_thumbnail = None;
             }
         }
 
@@ -2075,6 +3244,21 @@ impl eframe::App for OfktApp {
                                 should_close = true;
                             }
                             ui.separator();
+                            if ui.button("ターミナルを開く").clicked() {
+                                action_to_execute = Some(MenuAction::OpenTerminal);
+                                should_close = true;
+                            }
+                            let link_label = if menu_state.is_directory { "ジャンクションを作成" } else { "ハードリンクを作成" };
+                            if ui.button(link_label).clicked() {
+                                action_to_execute = Some(MenuAction::CreateLink);
+                                should_close = true;
+                            }
+                            if !menu_state.is_directory
+                                && ui.button("親フォルダをクイックアクセスに追加").clicked()
+                            {
+                                action_to_execute = Some(MenuAction::AddParentToQuickAccess);
+                                should_close = true;
+                            }
                             if ui.button("プロパティ").clicked() {
                                 action_to_execute = Some(MenuAction::Properties);
                                 should_close = true;
@@ -2108,18 +3292,11 @@ impl eframe::App for OfktApp {
                     let file_manager = FileManager::new();
                     match action {
                         MenuAction::Open => {
-                            if menu_state.is_directory {
-                                if let Some(ref mut browser) = self.state.directory_browser {
-                                    let _ = browser.navigate_to(menu_state.entry_path.clone());
-                                    self.state.directory_search_query.clear();
-                                }
-                            } else {
-                                let _ = file_manager.open(&menu_state.entry_path);
-                            }
+                            self.activate_directory_entry(menu_state.entry_path.clone());
                         }
                         MenuAction::Copy => {
                             self.state.clipboard_state.copy(vec![menu_state.entry_path.clone()]);
-                            self.state.operation_result_message = Some(
+                            self.state.toasts.push(
                                 crate::app::state::OperationResultMessage::success(
                                     format!("「{}」をコピーしました", menu_state.entry_name)
                                 )
@@ -2127,7 +3304,7 @@ impl eframe::App for OfktApp {
                         }
                         MenuAction::Cut => {
                             self.state.clipboard_state.cut(vec![menu_state.entry_path.clone()]);
-                            self.state.operation_result_message = Some(
+                            self.state.toasts.push(
                                 crate::app::state::OperationResultMessage::success(
                                     format!("「{}」を切り取りました", menu_state.entry_name)
                                 )
@@ -2152,6 +3329,78 @@ impl eframe::App for OfktApp {
                                 crate::app::state::PropertiesDialog::new(menu_state.entry_path.clone())
                             );
                         }
+                        MenuAction::OpenTerminal => {
+                            // ファイルの場合は親フォルダで開く
+                            let dir = if menu_state.is_directory {
+                                Some(menu_state.entry_path.as_path())
+                            } else {
+                                menu_state.entry_path.parent()
+                            };
+                            let result = dir
+                                .ok_or_else(|| "親ディレクトリが見つかりません".to_string())
+                                .and_then(|dir| file_manager.open_terminal(dir));
+                            if let Err(e) = result {
+                                log::error!("ターミナルを開けませんでした: {}", e);
+                                self.state.toasts.push(
+                                    crate::app::state::OperationResultMessage::error(
+                                        format!("ターミナルを開けませんでした: {}", e)
+                                    )
+                                );
+                            }
+                        }
+                        MenuAction::CreateLink => {
+                            let target = &menu_state.entry_path;
+                            let result = crate::core::file_manager::link_path_for(target)
+                                .ok_or_else(|| "親ディレクトリが見つかりません".to_string())
+                                .and_then(|link| {
+                                    let created = if menu_state.is_directory {
+                                        file_manager.create_junction(&link, target)
+                                    } else {
+                                        file_manager.create_hardlink(&link, target)
+                                    };
+                                    created.map(|()| link)
+                                });
+                            match result {
+                                Ok(link) => {
+                                    let name = link.file_name().unwrap_or_default().to_string_lossy().to_string();
+                                    log::info!("リンクを作成しました: {} -> {}", link.display(), target.display());
+                                    self.state.toasts.push(
+                                        crate::app::state::OperationResultMessage::success(
+                                            format!("「{}」を作成しました", name)
+                                        )
+                                    );
+                                    if let Err(e) = self.state.reload_directory() {
+                                        log::error!("ディレクトリリロード失敗: {}", e);
+                                    }
+                                }
+                                Err(e) => {
+                                    log::error!("リンクの作成に失敗: {}", e);
+                                    self.state.toasts.push(
+                                        crate::app::state::OperationResultMessage::error(e)
+                                    );
+                                }
+                            }
+                        }
+                        MenuAction::AddParentToQuickAccess => {
+                            match self.state.add_parent_to_quick_access(&menu_state.entry_path) {
+                                Ok(name) => {
+                                    log::info!("「{}」をクイックアクセスに追加しました", name);
+                                    self.state.toasts.push(
+                                        crate::app::state::OperationResultMessage::success(
+                                            format!("「{}」をクイックアクセスに追加しました", name)
+                                        )
+                                    );
+                                }
+                                Err(e) => {
+                                    log::error!("クイックアクセスへの追加に失敗: {}", e);
+                                    self.state.toasts.push(
+                                        crate::app::state::OperationResultMessage::error(
+                                            format!("クイックアクセスへの追加に失敗しました: {}", e)
+                                        )
+                                    );
+                                }
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -2162,12 +3411,445 @@ impl eframe::App for OfktApp {
             }
         }
 
+        // コマンドパレットの表示
+        if let Some(ref mut palette_state) = self.state.command_palette {
+            let outcome = self.command_palette.render(
+                ctx,
+                &mut palette_state.query,
+                &mut palette_state.selected_index,
+            );
+
+            match outcome {
+                Some(PaletteOutcome::Execute(action)) => {
+                    self.state.command_palette = None;
+                    self.execute_palette_action(ctx, action);
+                }
+                Some(PaletteOutcome::Cancel) => {
+                    self.state.command_palette = None;
+                }
+                None => {}
+            }
+        }
+
+        // 重複エイリアス解決ダイアログ
+        if let Some(ref mut dialog) = self.state.duplicate_alias_dialog {
+            let mut rename_request: Option<(String, String)> = None;
+            let mut delete_request: Option<String> = None;
+            let mut should_close = false;
+
+            egui::Window::new("重複しているエイリアス")
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label("同じ名前のエイリアスがあります。名前を変更するか削除してください。");
+                    ui.add_space(8.0);
+
+                    egui::Grid::new("duplicate_alias_grid")
+                        .num_columns(4)
+                        .spacing([8.0, 4.0])
+                        .show(ui, |ui| {
+                            for entry in dialog.entries.iter_mut() {
+                                ui.text_edit_singleline(&mut entry.new_name);
+                                ui.label(entry.path.display().to_string());
+                                if ui.button("名前変更").clicked() {
+                                    rename_request = Some((entry.id.clone(), entry.new_name.clone()));
+                                }
+                                if ui.button("削除").clicked() {
+                                    delete_request = Some(entry.id.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.add_space(8.0);
+                    if ui.button("後で").clicked() {
+                        should_close = true;
+                    }
+                });
+
+            let result = if let Some((id, name)) = rename_request {
+                Some(self.state.rename_duplicate_alias(&id, &name))
+            } else {
+                delete_request.map(|id| self.state.delete_duplicate_alias(&id))
+            };
+
+            match result {
+                Some(Ok(())) if self.state.duplicate_alias_dialog.is_none() => {
+                    self.state.toasts.push(
+                        crate::app::state::OperationResultMessage::success(
+                            "重複しているエイリアスを解決しました".to_string()
+                        )
+                    );
+                }
+                Some(Err(e)) => {
+                    log::error!("重複エイリアスの解決に失敗: {}", e);
+                    self.state.toasts.push(
+                        crate::app::state::OperationResultMessage::error(e)
+                    );
+                }
+                _ => {}
+            }
+
+            if should_close {
+                self.state.duplicate_alias_dialog = None;
+            }
+        }
+
+        // 壊れたエイリアスの修復ダイアログ
+        if let Some(ref mut dialog) = self.state.alias_repair_dialog {
+            let mut should_close = false;
+            let mut should_repoint = false;
+            let mut should_delete = false;
+            let current_folder = self.state.directory_browser
+                .as_ref()
+                .map(|browser| browser.current_path().to_path_buf());
+
+            egui::Window::new(format!("エイリアスの修復: {}", dialog.alias_name))
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label(format!("見つからないパス: {}", dialog.old_path.display()));
+                    ui.add_space(8.0);
+
+                    ui.label("新しいパスに付け替える:");
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut dialog.new_path);
+                        if let Some(ref folder) = current_folder {
+                            if ui.button("表示中のフォルダ")
+                                .on_hover_text(folder.display().to_string())
+                                .clicked()
+                            {
+                                dialog.new_path = folder.display().to_string();
+                            }
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.button("近くを探す")
+                            .on_hover_text("元の親フォルダと同じ階層のフォルダから、同じ名前の項目を探します")
+                            .clicked()
+                        {
+                            dialog.search_nearby();
+                        }
+                    });
+                    match dialog.candidates {
+                        Some(ref candidates) if candidates.is_empty() => {
+                            ui.label("候補は見つかりませんでした");
+                        }
+                        Some(ref candidates) => {
+                            let mut picked = None;
+                            for candidate in candidates {
+                                let text = candidate.display().to_string();
+                                if ui.selectable_label(dialog.new_path == text, text.as_str()).clicked() {
+                                    picked = Some(text);
+                                }
+                            }
+                            if let Some(text) = picked {
+                                dialog.new_path = text;
+                            }
+                        }
+                        None => {}
+                    }
+
+                    if let Some(ref error) = dialog.error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("付け替え").clicked() {
+                            should_repoint = true;
+                        }
+                        if ui.button("エイリアスを削除").clicked() {
+                            should_delete = true;
+                        }
+                        if ui.button("キャンセル").clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+
+            if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                should_close = true;
+            }
+
+            let result = if should_close {
+                self.state.alias_repair_dialog = None;
+                None
+            } else if should_repoint {
+                Some(self.state.apply_alias_repair().map(|name| format!("エイリアス「{}」のパスを付け替えました", name)))
+            } else if should_delete {
+                Some(self.state.delete_repaired_alias().map(|name| format!("エイリアス「{}」を削除しました", name)))
+            } else {
+                None
+            };
+
+            match result {
+                Some(Ok(message)) => {
+                    if let Err(e) = self.state.alias_manager.save() {
+                        self.state.report_save_error("エイリアス", e);
+                    } else {
+                        log::info!("{}", message);
+                        self.state.toasts.push(
+                            crate::app::state::OperationResultMessage::success(message)
+                        );
+                    }
+                }
+                Some(Err(e)) => log::warn!("エイリアスの修復に失敗: {}", e),
+                None => {}
+            }
+        }
+
+        // プロファイルのインポート確認ダイアログ
+        if let Some(ref dialog) = self.state.profile_import_dialog {
+            let mut should_close = false;
+            let mut should_import = false;
+
+            egui::Window::new("プロファイルのインポート")
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label(format!("ファイル: {}", dialog.path.display()));
+                    ui.label(format!("バンドル形式のバージョン: {}", dialog.preview.version));
+                    if let Some(count) = dialog.preview.alias_count {
+                        ui.label(format!("エイリアス: {} 件", count));
+                    }
+                    if let Some(count) = dialog.preview.quick_access_count {
+                        ui.label(format!("クイックアクセス: {} 件", count));
+                    }
+                    ui.add_space(8.0);
+
+                    ui.label("インポートすると次のファイルが変更されます:");
+                    for (name, change) in &dialog.preview.changes {
+                        let change = match change {
+                            crate::core::profile::FileChange::Create => "新規作成",
+                            crate::core::profile::FileChange::Replace => "置き換え",
+                            crate::core::profile::FileChange::Unchanged => "変更なし",
+                        };
+                        ui.label(format!("  {}: {}", name, change));
+                    }
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("インポート").clicked() {
+                            should_import = true;
+                        }
+                        if ui.button("キャンセル").clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+
+            if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                should_close = true;
+            }
+
+            if should_close {
+                self.state.profile_import_dialog = None;
+            } else if should_import {
+                match self.state.apply_profile_import() {
+                    Ok(message) => {
+                        log::info!("{}", message);
+                        self.apply_theme(ctx);
+                        self.state.toasts.push(
+                            crate::app::state::OperationResultMessage::success(message)
+                        );
+                    }
+                    Err(e) => {
+                        log::error!("プロファイルのインポートに失敗: {}", e);
+                        self.state.toasts.push(
+                            crate::app::state::OperationResultMessage::error(e)
+                        );
+                    }
+                }
+            }
+        }
+
+        // エイリアス編集ダイアログ
+        if let Some(ref mut dialog) = self.state.alias_edit_dialog {
+            let mut should_close = false;
+            let mut should_save = false;
+
+            egui::Window::new("エイリアスの編集")
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    egui::Grid::new("alias_edit_grid")
+                        .num_columns(2)
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("エイリアス名:");
+                            ui.text_edit_singleline(&mut dialog.name);
+                            ui.end_row();
+
+                            ui.label("パス:");
+                            ui.text_edit_singleline(&mut dialog.path);
+                            ui.end_row();
+
+                            ui.label("タグ:");
+                            ui.add(egui::TextEdit::singleline(&mut dialog.tags).hint_text("カンマ区切り"));
+                            ui.end_row();
+
+                            ui.label("色:");
+                            ui.horizontal(|ui| {
+                                if ui.selectable_label(dialog.color.is_none(), "なし").clicked() {
+                                    dialog.color = None;
+                                }
+                                for hex in crate::ui::theme::ALIAS_COLOR_PALETTE {
+                                    let Some(color) = crate::ui::theme::parse_hex_color(hex) else {
+                                        continue;
+                                    };
+                                    let selected = dialog.color
+                                        .as_deref()
+                                        .is_some_and(|current| current.eq_ignore_ascii_case(hex));
+                                    let swatch = egui::Button::new("")
+                                        .fill(color)
+                                        .min_size(egui::vec2(18.0, 18.0))
+                                        .selected(selected);
+                                    if ui.add(swatch).on_hover_text(hex).clicked() {
+                                        dialog.color = Some(hex.to_string());
+                                    }
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("");
+                            ui.checkbox(&mut dialog.is_favorite, "お気に入り");
+                            ui.end_row();
+                        });
+
+                    if let Some(warning) = dialog.path_warning() {
+                        ui.colored_label(ui.visuals().warn_fg_color, warning);
+                    }
+                    if let Some(ref error) = dialog.error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("保存").clicked() {
+                            should_save = true;
+                        }
+                        if ui.button("キャンセル").clicked() {
+                            should_close = true;
+                        }
+                    });
+                });
+
+            // Escキーでは変更を破棄して閉じる
+            if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                should_close = true;
+            }
+
+            if should_close {
+                self.state.alias_edit_dialog = None;
+            } else if should_save {
+                match self.state.apply_alias_edit() {
+                    Ok(name) => {
+                        if let Err(e) = self.state.alias_manager.save() {
+                            self.state.report_save_error("エイリアス", e);
+                        } else {
+                            log::info!("エイリアス「{}」を更新しました", name);
+                            self.state.toasts.push(
+                                crate::app::state::OperationResultMessage::success(
+                                    format!("エイリアス「{}」を更新しました", name)
+                                )
+                            );
+                        }
+                    }
+                    Err(e) => log::warn!("エイリアスの更新に失敗: {}", e),
+                }
+            }
+        }
+
+        // ショートカット一覧の表示
+        if self.state.show_shortcut_help {
+            crate::ui::shortcut_help::render_shortcut_help(ctx, &mut self.state.show_shortcut_help);
+        }
+
+        // 設定画面の表示
+        if self.state.show_settings {
+            if self.settings_view.is_none() {
+                if let Some(ref config) = self.state.config {
+                    self.settings_view = Some(Settings::new(config.clone()));
+                } else {
+                    log::warn!("設定が読み込まれていないため、設定画面を開けません");
+                    self.state.show_settings = false;
+                }
+            }
+
+            let mut settings_action = None;
+            let mut open = true;
+            if let Some(ref mut settings) = self.settings_view {
+                egui::Window::new("設定")
+                    .open(&mut open)
+                    .collapsible(false)
+                    .vscroll(true)
+                    .show(ctx, |ui| {
+                        settings_action = settings.render(ui);
+                    });
+            }
+
+            match settings_action {
+                Some(SettingsAction::Save) => {
+                    if let Some(settings) = self.settings_view.take() {
+                        let config = settings.get_config().clone();
+                        if let Err(e) = crate::data::storage::save_config(&config) {
+                            log::error!("設定の保存に失敗: {}", e);
+                            self.state.toasts.push(
+                                crate::app::state::OperationResultMessage::error(
+                                    format!("設定の保存に失敗しました: {}", e)
+                                )
+                            );
+                        }
+                        let network_setting_changed = self.state.config
+                            .as_ref()
+                            .map(|c| c.display.network_path_as_unknown)
+                            != Some(config.display.network_path_as_unknown);
+                        self.state.config = Some(config);
+                        self.state.ensure_browse_mode_enabled();
+                        self.apply_display_config();
+                        ctx.send_viewport_cmd(self.state.window_level_command());
+                        self.state.apply_search_config();
+                        self.state.apply_history_config();
+                        if network_setting_changed {
+                            // ネットワークパスの扱いが変わったので印を付け直す
+                            self.state.check_broken_aliases();
+                            self.state.refresh_after_alias_change();
+                        }
+                        self.state.filter_aliases();
+
+                        // ホットキーを再登録
+                        if let Some(ref mut manager) = self.state.hotkey_manager {
+                            let _ = manager.unregister_all();
+                        }
+                        self.state.register_hotkey_with_fallback();
+                    }
+                    self.state.show_settings = false;
+                }
+                Some(SettingsAction::Cancel) => {
+                    self.settings_view = None;
+                    self.state.show_settings = false;
+                }
+                None => {
+                    if !open {
+                        self.settings_view = None;
+                        self.state.show_settings = false;
+                    }
+                }
+            }
+        }
+
         // 非アクティブ時でもホットキーを検出できるように定期的に再描画をリクエスト
-        ctx.request_repaint_after(Duration::from_millis(100));
+        // （非表示で処理待ちもない間は間隔を延ばす）
+        ctx.request_repaint_after(self.state.repaint_interval());
     }
 
     /// アプリケーション終了時の保存処理
     fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        self.persist_last_queries();
         info!("アプリケーション終了");
     }
 }
@@ -1,19 +1,30 @@
 use crate::core::alias::AliasManager;
+use crate::core::alias_watcher::{AliasFileWatcher, ReloadDebouncer};
+use crate::core::directory_cache::{
+    prewarm_targets, DirectoryCacheWatcher, DirectoryPrewarmer, SharedDirectoryCache, PREWARM_LIMIT,
+};
 use crate::core::clipboard::ClipboardState;
-use crate::core::directory_browser::DirectoryBrowser;
-use crate::core::operation_history::OperationHistoryManager;
+use crate::core::dir_size::DirectorySizeTask;
+use crate::core::file_manager::{ConflictPolicy, ConflictResolution};
+use crate::core::directory_browser::{DirectoryBrowser, SortKey, SortOrder};
+use crate::core::operation_history::{FileOperation, OperationHistoryManager};
+use crate::core::history::HistoryManager;
 use crate::core::quick_access::QuickAccessManager;
-use crate::core::search::SearchEngine;
-use crate::data::models::{Config, FileAlias, QuickAccessEntry};
+use crate::core::search::{SearchEngine, StalePenalty};
+use crate::core::text_preview::{read_text, DecodedText, TEXT_PREVIEW_MAX_BYTES};
+use crate::core::thumbnail::ThumbnailCache;
+use crate::data::models::{Config, FileAlias, FileHistory, QuickAccessEntry};
 use crate::platform::hotkey::{HotkeyManager, string_to_modifiers, string_to_code};
 use crate::platform::SystemTray;
+use crate::platform::theme_detector::SystemThemeCache;
+use crate::ui::command_palette::PaletteAction;
 use crate::ui::search_bar::SearchDebouncer;
 use crate::ui::theme::Theme;
 use crate::utils::path::paths_equal;
 use egui;
 use global_hotkey::hotkey::{Code, Modifiers};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 /// フォーカス領域
@@ -58,11 +69,79 @@ impl PastedFileHighlight {
     }
 
     /// 指定したパスがハイライト対象か
-    pub fn contains(&self, path: &PathBuf) -> bool {
+    pub fn contains(&self, path: &Path) -> bool {
         self.paths.iter().any(|p| paths_equal(p, path))
     }
 }
 
+/// 操作に失敗した項目のエラーバッジ
+///
+/// 一括貼り付け・削除で一部が失敗したとき、失敗した行に一定時間だけ印を付けます。
+/// 記録したフォルダから移動した場合は消去します。
+/// 毎フレーム全行に対して判定するため、パスは正規化せずにそのまま比較します。
+#[derive(Debug, Clone, Default)]
+pub struct FailedPathBadges {
+    /// 失敗したパスと記録した時刻
+    paths: Vec<(PathBuf, Instant)>,
+    /// 記録したときに表示していたフォルダ
+    dir: Option<PathBuf>,
+}
+
+impl FailedPathBadges {
+    /// バッジを表示する時間
+    pub const DURATION: Duration = Duration::from_secs(5);
+
+    /// 失敗したパスを記録
+    ///
+    /// # 引数
+    /// * `dir` - 表示中のフォルダ
+    /// * `paths` - 失敗したパス
+    /// * `now` - 現在時刻
+    pub fn record(&mut self, dir: &Path, paths: impl IntoIterator<Item = PathBuf>, now: Instant) {
+        if self.dir.as_deref().is_some_and(|d| d != dir) {
+            self.paths.clear();
+        }
+        self.dir = Some(dir.to_path_buf());
+        self.paths.extend(paths.into_iter().map(|path| (path, now)));
+    }
+
+    /// 指定したパスにバッジを表示すべきか
+    pub fn should_badge(&self, path: &Path, now: Instant) -> bool {
+        self.paths.iter().any(|(failed, at)| {
+            now.saturating_duration_since(*at) < Self::DURATION && failed == path
+        })
+    }
+
+    /// 期限切れのバッジを取り除き、表示中のフォルダが変わっていればすべて消去
+    ///
+    /// # 引数
+    /// * `current_dir` - 現在表示しているフォルダ
+    /// * `now` - 現在時刻
+    pub fn prune(&mut self, current_dir: Option<&Path>, now: Instant) {
+        let moved = match (self.dir.as_deref(), current_dir) {
+            (Some(dir), Some(current)) => dir != current,
+            (Some(_), None) => true,
+            _ => false,
+        };
+        if moved {
+            self.clear();
+            return;
+        }
+        self.paths.retain(|(_, at)| now.saturating_duration_since(*at) < Self::DURATION);
+    }
+
+    /// すべてのバッジを消去
+    pub fn clear(&mut self) {
+        self.paths.clear();
+        self.dir = None;
+    }
+
+    /// 表示中のバッジがないか
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
 /// ブラウザモード
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BrowseMode {
@@ -70,6 +149,77 @@ pub enum BrowseMode {
     Alias,
     /// ディレクトリブラウザモード
     Directory,
+    /// 最近開いたファイル・フォルダ
+    Recent,
+}
+
+impl BrowseMode {
+    /// 設定画面での表示順の全モード
+    pub const ALL: [BrowseMode; 3] = [BrowseMode::Alias, BrowseMode::Directory, BrowseMode::Recent];
+
+    /// 設定ファイル上の名前からモードを取得
+    ///
+    /// 大文字小文字と前後の空白は無視します。
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "alias" => Some(BrowseMode::Alias),
+            "directory" => Some(BrowseMode::Directory),
+            "recent" => Some(BrowseMode::Recent),
+            _ => None,
+        }
+    }
+
+    /// 設定ファイル上の名前を取得
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            BrowseMode::Alias => "alias",
+            BrowseMode::Directory => "directory",
+            BrowseMode::Recent => "recent",
+        }
+    }
+
+    /// タブのラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            BrowseMode::Alias => "エイリアス",
+            BrowseMode::Directory => "ディレクトリ",
+            BrowseMode::Recent => "最近",
+        }
+    }
+}
+
+/// ディレクトリモードの表示形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirectoryViewMode {
+    /// ツリー表示（名前のみ）
+    #[default]
+    Tree,
+    /// テーブル表示（名前/サイズ/種類/更新日時）
+    Table,
+}
+
+/// ディレクトリ一覧をクリックしたときの選択方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionClick {
+    /// 通常のクリック（複数選択を解除してフォーカスだけ移す）
+    Replace,
+    /// Ctrl+クリック（選択に加える/外す）
+    Toggle,
+    /// Shift+クリック（起点からクリックした項目までを選択）
+    Range,
+}
+
+impl SelectionClick {
+    /// クリック時の修飾キーから選択方法を判定
+    pub fn from_modifiers(modifiers: egui::Modifiers) -> Self {
+        if modifiers.shift {
+            SelectionClick::Range
+        } else if modifiers.command {
+            SelectionClick::Toggle
+        } else {
+            SelectionClick::Replace
+        }
+    }
 }
 
 /// アプリケーション全体の状態
@@ -95,6 +245,9 @@ pub struct AppState {
     /// 検索結果（フィルタリング後のエイリアス）
     pub filtered_items: Vec<FileAlias>,
 
+    /// 検索が時間内に終わらず、途中までの結果を表示しているか
+    pub alias_search_truncated: bool,
+
     /// 選択中のアイテムのインデックス
     pub selected_index: Option<usize>,
 
@@ -104,6 +257,12 @@ pub struct AppState {
     /// 現在のテーマ（Light/Dark）
     pub current_theme: Theme,
 
+    /// 最後に egui へ適用したテーマ（まだ適用していなければ None）
+    pub applied_theme: Option<Theme>,
+
+    /// システムテーマの検出結果のキャッシュ
+    pub system_theme_cache: SystemThemeCache,
+
     /// 検索デバウンサー
     pub search_debouncer: SearchDebouncer,
 
@@ -116,15 +275,44 @@ pub struct AppState {
     /// ディレクトリブラウザ
     pub directory_browser: Option<DirectoryBrowser>,
 
+    /// お気に入り・クイックアクセスのフォルダ一覧の先読みキャッシュ
+    pub directory_cache: SharedDirectoryCache,
+
+    /// 実行中の先読み
+    pub directory_prewarmer: Option<DirectoryPrewarmer>,
+
+    /// キャッシュしたフォルダの変更監視（初期化失敗時はNone）
+    pub directory_cache_watcher: Option<DirectoryCacheWatcher>,
+
+    /// 変更で取り除いたキャッシュを読み込み直すまでの待ち合わせ
+    pub directory_cache_debouncer: ReloadDebouncer,
+
     /// ディレクトリブラウザでの選択インデックス
     pub selected_directory_index: Option<usize>,
 
+    /// ディレクトリブラウザで複数選択しているパス
+    ///
+    /// 矢印キーで動かすフォーカス（`selected_directory_index`）とは独立しています。
+    pub multi_selected_paths: HashSet<PathBuf>,
+
+    /// Shift+クリックで範囲選択するときの起点
+    pub selection_anchor: Option<PathBuf>,
+
     /// 展開されているディレクトリのパスセット
     pub expanded_directories: HashSet<PathBuf>,
 
+    /// ディレクトリモードの表示形式
+    pub directory_view_mode: DirectoryViewMode,
+
     /// グローバルホットキーマネージャ（初期化失敗時はNone）
     pub hotkey_manager: Option<HotkeyManager>,
 
+    /// グローバルホットキーが登録されているか（設定・デフォルトとも失敗した場合は false）
+    pub hotkey_registered: bool,
+
+    /// データフォルダに書き込めない場合の警告（書き込める場合は None）
+    pub data_dir_warning: Option<String>,
+
     /// システムトレイ
     pub system_tray: SystemTray,
 
@@ -134,15 +322,33 @@ pub struct AppState {
     /// 最後にホットキーが押された時刻（重複防止用）
     pub last_hotkey_time: Option<Instant>,
 
+    /// Escキーの2回押し検出
+    pub escape_tracker: DoublePressDetector,
+
+    /// 直前の1回目のEscキーでクリアした選択（2回押しでウィンドウを隠すときに戻す）
+    pub escape_cleared_selection: Option<ClearedSelection>,
+
+    /// エイリアス一覧でのタイプアヘッド（先頭文字によるジャンプ）の入力
+    pub type_ahead: TypeAheadBuffer,
+
     /// 現在のフォーカス領域
     pub current_focus_area: FocusArea,
 
     /// サイドバーの選択インデックス
     pub selected_sidebar_index: Option<usize>,
 
+    /// モード切り替え時に退避したエイリアスモードの選択インデックス
+    pub saved_alias_selection: Option<usize>,
+
+    /// モード切り替え時に退避したディレクトリモードの選択インデックス
+    pub saved_directory_selection: Option<usize>,
+
     /// エイリアス管理
     pub alias_manager: AliasManager,
 
+    /// エイリアスファイルの外部変更ウォッチャー（初期化失敗時はNone）
+    pub alias_watcher: Option<AliasFileWatcher>,
+
     /// エイリアス追加ダイアログを表示するか
     pub show_add_alias_dialog: bool,
 
@@ -150,6 +356,9 @@ pub struct AppState {
     pub new_alias_name: String,
     pub new_alias_path: String,
 
+    /// エイリアス追加ダイアログで表示中の「同じパスのエイリアスがある」警告
+    pub add_alias_path_warning: Option<String>,
+
     /// 検索エンジン
     pub search_engine: SearchEngine,
 
@@ -165,18 +374,27 @@ pub struct AppState {
     /// ペースト直後のハイライト対象パス
     pub pasted_files_highlight: Option<PastedFileHighlight>,
 
-    /// ペースト操作の結果メッセージ
-    pub paste_result_message: Option<PasteResultMessage>,
+    /// 操作に失敗した行のエラーバッジ
+    pub failed_path_badges: FailedPathBadges,
 
-    /// 操作結果メッセージ（汎用）
-    pub operation_result_message: Option<OperationResultMessage>,
+    /// 通知トーストのキュー（ペースト結果・操作結果など）
+    pub toasts: ToastQueue,
 
     /// クイックアクセス追加確認ダイアログの状態
     pub add_quick_access_dialog: Option<AddQuickAccessDialog>,
 
+    /// クイックアクセス名前変更ダイアログの状態
+    pub rename_quick_access_dialog: Option<RenameQuickAccessDialog>,
+
     /// 上書き確認ダイアログの状態
     pub overwrite_confirmation_dialog: Option<OverwriteConfirmationDialog>,
 
+    /// バックグラウンドで実行中の貼り付け
+    pub paste_task: Option<crate::core::paste_task::PasteTask>,
+
+    /// ディレクトリモードの再帰検索（検索クエリが `**` で始まる間だけ保持）
+    pub recursive_search: Option<crate::core::recursive_search::RecursiveSearchTask>,
+
     /// 削除確認ダイアログの状態
     pub delete_confirmation_dialog: Option<DeleteConfirmationDialog>,
 
@@ -186,6 +404,9 @@ pub struct AppState {
     /// プロパティダイアログの状態
     pub properties_dialog: Option<PropertiesDialog>,
 
+    /// 画像プレビュー用のサムネイルキャッシュ
+    pub thumbnail_cache: ThumbnailCache,
+
     /// コンテキストメニューの状態
     pub context_menu_state: Option<ContextMenuState>,
 
@@ -198,6 +419,54 @@ pub struct AppState {
 
     /// 操作履歴マネージャー（Undo/Redo用）
     pub operation_history: OperationHistoryManager,
+
+    /// 最近開いたファイル・フォルダの履歴（「最近」タブ用）
+    pub history_manager: HistoryManager,
+
+    /// コマンドパレットの状態
+    pub command_palette: Option<CommandPaletteDialog>,
+
+    /// ショートカット一覧を表示中か
+    pub show_shortcut_help: bool,
+
+    /// 重複エイリアス解決ダイアログの状態
+    pub duplicate_alias_dialog: Option<DuplicateAliasDialog>,
+
+    /// エイリアス編集ダイアログの状態
+    pub alias_edit_dialog: Option<AliasEditDialog>,
+
+    /// 壊れたエイリアスの修復ダイアログの状態
+    pub alias_repair_dialog: Option<AliasRepairDialog>,
+
+    /// プロファイルのインポート確認ダイアログの状態
+    pub profile_import_dialog: Option<ProfileImportDialog>,
+
+    /// 選択モード（各行にチェックボックスを表示）中か
+    pub selection_mode: bool,
+
+    /// 選択モードでチェックされたパス（チェックした順）
+    pub checked_paths: Vec<PathBuf>,
+
+    /// 一括タグ付けで入力中のタグ
+    pub bulk_tag_input: String,
+
+    /// エイリアス一覧をタグごとにまとめて表示しているか
+    pub group_by_tag: bool,
+}
+
+/// コマンドパレットの状態
+#[derive(Debug, Clone, Default)]
+pub struct CommandPaletteDialog {
+    /// 検索クエリ
+    pub query: String,
+    /// 選択中の候補インデックス
+    pub selected_index: usize,
+}
+
+impl CommandPaletteDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 /// クイックアクセス追加確認ダイアログ
@@ -218,21 +487,348 @@ impl AddQuickAccessDialog {
     }
 }
 
+/// 重複エイリアス解決ダイアログ
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateAliasDialog {
+    /// 名前が重複しているエイリアス
+    pub entries: Vec<DuplicateAliasEntry>,
+}
+
+/// 重複エイリアス解決ダイアログの1行
+#[derive(Debug, Clone)]
+pub struct DuplicateAliasEntry {
+    /// エイリアスID
+    pub id: String,
+    /// 現在の名前
+    pub current_name: String,
+    /// 新しい名前（編集用）
+    pub new_name: String,
+    /// パス
+    pub path: PathBuf,
+}
+
+/// エイリアス編集ダイアログ
+#[derive(Debug, Clone)]
+pub struct AliasEditDialog {
+    /// 対象のエイリアスID
+    pub id: String,
+    /// エイリアス名（編集用）
+    pub name: String,
+    /// パス（編集用）
+    pub path: String,
+    /// タグ（カンマ区切り、編集用）
+    pub tags: String,
+    /// 色（`#RRGGBB` 形式、None は色なし）
+    pub color: Option<String>,
+    /// お気に入りか
+    pub is_favorite: bool,
+    /// 保存できなかった理由（ダイアログ内に表示する）
+    pub error: Option<String>,
+}
+
+impl AliasEditDialog {
+    /// エイリアスの現在の内容を入力済みのダイアログを作成
+    pub fn new(alias: &FileAlias) -> Self {
+        Self {
+            id: alias.id.clone(),
+            name: alias.alias.clone(),
+            path: alias.path.display().to_string(),
+            tags: alias.tags.join(", "),
+            color: alias.color.clone(),
+            is_favorite: alias.is_favorite,
+            error: None,
+        }
+    }
+
+    /// 入力されたタグ（空のものを除く）
+    pub fn parsed_tags(&self) -> Vec<String> {
+        self.tags
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// パスが見つからない場合の警告（保存はできる）
+    pub fn path_warning(&self) -> Option<&'static str> {
+        let path = self.path.trim();
+        if path.is_empty() || crate::utils::path::expand_path(Path::new(path)).exists() {
+            None
+        } else {
+            Some("このパスは見つかりません（このまま保存できます）")
+        }
+    }
+}
+
+/// 壊れたエイリアスの修復ダイアログ
+#[derive(Debug, Clone)]
+pub struct AliasRepairDialog {
+    /// 対象のエイリアスID
+    pub id: String,
+    /// エイリアス名
+    pub alias_name: String,
+    /// 見つからなくなったパス
+    pub old_path: PathBuf,
+    /// 新しいパス（編集用）
+    pub new_path: String,
+    /// 近くで見つかった移動先の候補（まだ探していない場合は None）
+    pub candidates: Option<Vec<PathBuf>>,
+    /// 修復できなかった理由（ダイアログ内に表示する）
+    pub error: Option<String>,
+}
+
+impl AliasRepairDialog {
+    pub fn new(alias: &FileAlias) -> Self {
+        Self {
+            id: alias.id.clone(),
+            alias_name: alias.alias.clone(),
+            old_path: alias.path.clone(),
+            new_path: alias.path.display().to_string(),
+            candidates: None,
+            error: None,
+        }
+    }
+
+    /// 元のパスの近くで移動先の候補を探す
+    ///
+    /// 候補が1件だけの場合は新しいパスに入力します。
+    pub fn search_nearby(&mut self) {
+        let old_path = crate::utils::path::expand_path(&self.old_path);
+        let candidates = crate::core::alias::find_nearby_candidates(&old_path);
+        if let [only] = candidates.as_slice() {
+            self.new_path = only.display().to_string();
+        }
+        self.candidates = Some(candidates);
+    }
+}
+
+/// プロファイルのインポート確認ダイアログ
+///
+/// インポートした場合の変更内容（ドライランの結果）を表示し、確認してから書き込みます。
+#[derive(Debug, Clone)]
+pub struct ProfileImportDialog {
+    /// インポートするバンドル（ZIPファイル）のパス
+    pub path: PathBuf,
+    /// インポートした場合の変更内容
+    pub preview: crate::core::profile::ImportPreview,
+}
+
+/// クイックアクセス名前変更ダイアログ
+#[derive(Debug, Clone)]
+pub struct RenameQuickAccessDialog {
+    /// 対象エントリのID
+    pub id: String,
+    /// 新しい表示名（編集用）
+    pub name: String,
+}
+
+impl RenameQuickAccessDialog {
+    pub fn new(id: String, current_name: String) -> Self {
+        Self {
+            id,
+            name: current_name,
+        }
+    }
+}
+
 /// 上書き確認ダイアログ
 #[derive(Debug, Clone)]
 pub struct OverwriteConfirmationDialog {
-    /// 上書き対象のファイル一覧
-    pub files: Vec<PathBuf>,
+    /// 上書き対象のファイル一覧（貼り付け元と貼り付け先の比較付き）
+    pub files: Vec<OverwriteConflict>,
+    /// 移動しようとしているシステム・隠しファイルの一覧
+    pub sensitive_files: Vec<PathBuf>,
+    /// 作成される項目数がしきい値を超える場合の項目数（上限付きで数えた値）
+    pub large_item_count: Option<usize>,
+    /// `files` の項目ごとに選んだ処理
+    pub resolutions: Vec<ConflictResolution>,
+    /// `files` の項目ごとの「以降すべてに適用」
+    pub apply_to_remaining: Vec<bool>,
     /// ペースト保留中のデータ
     pub pending_paste: PendingPasteOperation,
 }
 
+impl OverwriteConfirmationDialog {
+    /// 確認ダイアログを作成
+    ///
+    /// 各項目の処理は上書きを初期値とし、`OverwriteOnlyIfOlder` の場合は
+    /// 貼り付け先の方が新しい（または更新日時が分からない）項目をスキップにします。
+    pub fn new(
+        files: Vec<OverwriteConflict>,
+        sensitive_files: Vec<PathBuf>,
+        large_item_count: Option<usize>,
+        pending_paste: PendingPasteOperation,
+    ) -> Self {
+        let resolutions = files
+            .iter()
+            .map(|conflict| {
+                let source_is_newer = matches!(conflict.newer_side(), Some(NewerSide::Source | NewerSide::Same));
+                if pending_paste.conflict_policy == ConflictPolicy::OverwriteOnlyIfOlder && !source_is_newer {
+                    ConflictResolution::Skip
+                } else {
+                    ConflictResolution::Overwrite
+                }
+            })
+            .collect();
+        let apply_to_remaining = vec![false; files.len()];
+        Self {
+            files,
+            sensitive_files,
+            large_item_count,
+            resolutions,
+            apply_to_remaining,
+            pending_paste,
+        }
+    }
+
+    /// 項目の処理を選ぶ（「以降すべてに適用」が付いていれば後ろの項目も同じにする）
+    pub fn set_resolution(&mut self, index: usize, resolution: ConflictResolution) {
+        if index >= self.resolutions.len() {
+            return;
+        }
+        self.resolutions[index] = resolution;
+        if self.apply_to_remaining[index] {
+            self.resolutions[index + 1..].fill(resolution);
+        }
+    }
+
+    /// 項目の「以降すべてに適用」を切り替える（付けたときは後ろの項目をこの項目の処理にそろえる）
+    pub fn set_apply_to_remaining(&mut self, index: usize, apply: bool) {
+        if index >= self.apply_to_remaining.len() {
+            return;
+        }
+        self.apply_to_remaining[index] = apply;
+        if apply {
+            let resolution = self.resolutions[index];
+            self.resolutions[index + 1..].fill(resolution);
+        }
+    }
+
+    /// 指定した処理を選んだ項目数
+    pub fn resolution_count(&self, resolution: ConflictResolution) -> usize {
+        self.resolutions.iter().filter(|r| **r == resolution).count()
+    }
+
+    /// 選んだ処理を貼り付け元のパスごとにまとめたもの
+    pub fn resolution_map(&self) -> HashMap<PathBuf, ConflictResolution> {
+        self.files
+            .iter()
+            .zip(&self.resolutions)
+            .map(|(conflict, resolution)| (conflict.source.clone(), *resolution))
+            .collect()
+    }
+
+    /// 貼り付け先の方が新しく（または更新日時が分からず）、そのままではスキップされる項目数
+    pub fn newer_destination_count(&self) -> usize {
+        if self.pending_paste.conflict_policy != ConflictPolicy::OverwriteOnlyIfOlder {
+            return 0;
+        }
+        self.files
+            .iter()
+            .filter(|conflict| !matches!(conflict.newer_side(), Some(NewerSide::Source | NewerSide::Same)))
+            .count()
+    }
+}
+
+/// 上書き確認ダイアログで比較する項目の情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConflictFileInfo {
+    /// ファイルサイズ（フォルダの場合は None）
+    pub size: Option<u64>,
+    /// 最終更新日時
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ConflictFileInfo {
+    /// パスのメタデータから情報を取得（取得できない場合は None）
+    fn from_path(path: &Path) -> Option<Self> {
+        let entry = crate::data::models::DirectoryEntry::from_path(path.to_path_buf()).ok()?;
+        Some(Self {
+            size: entry.size,
+            modified: entry.modified,
+        })
+    }
+
+    /// 表示用の文字列（サイズ・更新日時）
+    pub fn summary(&self) -> String {
+        let size = self.size
+            .map(crate::ui::file_table::format_size)
+            .unwrap_or_else(|| "フォルダ".to_string());
+        let modified = self.modified
+            .map(|m| m.with_timezone(&chrono::Local).format("%Y/%m/%d %H:%M").to_string())
+            .unwrap_or_else(|| "日時不明".to_string());
+        format!("{}・{}", size, modified)
+    }
+}
+
+/// 上書きの際に新しい方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewerSide {
+    /// 貼り付け元の方が新しい
+    Source,
+    /// 貼り付け先の方が新しい
+    Destination,
+    /// 更新日時が同じ
+    Same,
+}
+
+/// 上書き対象の項目（貼り付け元と貼り付け先の組）
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverwriteConflict {
+    /// 貼り付け元のパス
+    pub source: PathBuf,
+    /// 上書きされる貼り付け先のパス
+    pub destination: PathBuf,
+    /// 貼り付け元の情報（取得できない場合は None）
+    pub source_info: Option<ConflictFileInfo>,
+    /// 貼り付け先の情報（取得できない場合は None）
+    pub destination_info: Option<ConflictFileInfo>,
+}
+
+impl OverwriteConflict {
+    /// 貼り付け元と貼り付け先のメタデータを読み取って組を作る
+    pub fn from_paths(source: PathBuf, destination: PathBuf) -> Self {
+        let source_info = ConflictFileInfo::from_path(&source);
+        let destination_info = ConflictFileInfo::from_path(&destination);
+        Self {
+            source,
+            destination,
+            source_info,
+            destination_info,
+        }
+    }
+
+    /// 表示用のファイル名（貼り付け先の名前）
+    pub fn file_name(&self) -> String {
+        self.destination
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 更新日時を比べて新しい方を返す（どちらかの日時が不明な場合は None）
+    pub fn newer_side(&self) -> Option<NewerSide> {
+        let source = self.source_info?.modified?;
+        let destination = self.destination_info?.modified?;
+        Some(match source.cmp(&destination) {
+            std::cmp::Ordering::Greater => NewerSide::Source,
+            std::cmp::Ordering::Less => NewerSide::Destination,
+            std::cmp::Ordering::Equal => NewerSide::Same,
+        })
+    }
+}
+
 /// ペースト保留操作
 #[derive(Debug, Clone)]
 pub struct PendingPasteOperation {
     pub src_paths: Vec<PathBuf>,
     pub dest_dir: PathBuf,
     pub mode: crate::core::clipboard::ClipboardMode,
+    /// 貼り付け先に同名の項目がある場合の扱い
+    pub conflict_policy: ConflictPolicy,
+    /// 上書き確認ダイアログで選んだ、貼り付け元ごとの処理（選んでいない項目は `conflict_policy` に従う）
+    pub resolutions: HashMap<PathBuf, ConflictResolution>,
 }
 
 /// 削除確認ダイアログ
@@ -244,21 +840,37 @@ pub struct DeleteConfirmationDialog {
     pub permanent: bool,
     /// 表示用名前リスト
     pub display_names: Vec<String>,
+    /// 削除対象のうちシステム・隠しファイルの表示名
+    pub sensitive_names: Vec<String>,
+    /// システム・隠しファイルの削除を了承したか
+    pub sensitive_acknowledged: bool,
 }
 
 impl DeleteConfirmationDialog {
     pub fn new(paths: Vec<PathBuf>) -> Self {
-        let display_names = paths.iter()
-            .map(|p| p.file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| p.display().to_string()))
+        let display_name = |p: &PathBuf| p.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| p.display().to_string());
+        let display_names = paths.iter().map(display_name).collect();
+        let sensitive_names = paths.iter()
+            .filter(|p| crate::core::file_manager::is_system_or_hidden(p))
+            .map(display_name)
             .collect();
         Self {
             paths,
             permanent: false,
             display_names,
+            sensitive_names,
+            sensitive_acknowledged: false,
         }
     }
+
+    /// 削除を実行できるか
+    ///
+    /// システム・隠しファイルが含まれる場合は、了承のチェックが必要です。
+    pub fn can_confirm(&self) -> bool {
+        self.sensitive_names.is_empty() || self.sensitive_acknowledged
+    }
 }
 
 /// リネームダイアログ
@@ -270,6 +882,8 @@ pub struct RenameDialog {
     pub new_name: String,
     /// 元の名前
     pub original_name: String,
+    /// 初期選択範囲を適用済みか
+    pub selection_applied: bool,
 }
 
 impl RenameDialog {
@@ -281,12 +895,31 @@ impl RenameDialog {
             path,
             new_name: original_name.clone(),
             original_name,
+            selection_applied: false,
         }
     }
+
+    /// ダイアログを開いたときに選択する範囲（文字単位）
+    ///
+    /// 拡張子を誤って消さないよう、拡張子の前までを選択する。
+    pub fn initial_selection(&self) -> std::ops::Range<usize> {
+        let (stem, _) = split_stem_extension(&self.original_name);
+        0..stem.chars().count()
+    }
+}
+
+/// ファイル名を語幹と拡張子（ドットを含む）に分割
+///
+/// 最後のドットで分割します。`.gitignore` のような先頭ドットのみの名前は拡張子なしとして扱います。
+pub fn split_stem_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(index) if index > 0 => name.split_at(index),
+        _ => (name, ""),
+    }
 }
 
 /// プロパティダイアログ
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct PropertiesDialog {
     pub path: PathBuf,
     pub name: String,
@@ -295,6 +928,10 @@ pub struct PropertiesDialog {
     pub is_readonly: bool,
     pub modified: Option<std::time::SystemTime>,
     pub created: Option<std::time::SystemTime>,
+    /// フォルダの合計サイズの計算（フォルダの場合のみ。ダイアログを閉じると取り消される）
+    pub size_task: Option<DirectorySizeTask>,
+    /// テキストファイルの先頭部分（テキストとして読めるファイルの場合のみ）
+    pub text_preview: Option<DecodedText>,
 }
 
 /// コンテキストメニューの状態
@@ -319,6 +956,171 @@ impl ContextMenuState {
     }
 }
 
+/// キーの2回押し検出
+#[derive(Debug, Clone, Default)]
+pub struct DoublePressDetector {
+    /// 1回目に押された時刻（2回押しの待ち受け中のみ Some）
+    last_press: Option<Instant>,
+}
+
+impl DoublePressDetector {
+    /// キーが押されたことを記録し、2回押しになったかを返す
+    ///
+    /// 前回の押下から `threshold` 以内なら2回押しとして true を返し、状態をリセットします。
+    /// `threshold` が 0 の場合は常に false を返します。
+    pub fn press(&mut self, now: Instant, threshold: Duration) -> bool {
+        if threshold.is_zero() {
+            self.last_press = None;
+            return false;
+        }
+
+        match self.last_press {
+            Some(last) if now.duration_since(last) <= threshold => {
+                self.last_press = None;
+                true
+            }
+            _ => {
+                self.last_press = Some(now);
+                false
+            }
+        }
+    }
+}
+
+/// 1回目のEscキーでクリアした選択と検索クエリ
+///
+/// Escキーの2回押しでウィンドウを隠すときに元に戻し、再表示したときに
+/// 隠す前と同じ選択が残るようにします。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearedSelection {
+    /// クリアしたときの表示モード
+    browse_mode: BrowseMode,
+    /// エイリアス一覧の選択インデックス
+    selected_index: Option<usize>,
+    /// エイリアスの検索クエリ
+    search_query: String,
+    /// ディレクトリブラウザの選択インデックス
+    selected_directory_index: Option<usize>,
+    /// ディレクトリブラウザの複数選択
+    multi_selected_paths: HashSet<PathBuf>,
+    /// 範囲選択の起点
+    selection_anchor: Option<PathBuf>,
+    /// ディレクトリの検索クエリ
+    directory_search_query: String,
+}
+
+/// トップバーの「元に戻す」「やり直す」ボタンの表示状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryButtonState {
+    /// 押せるか（履歴に操作が残っているか）
+    pub enabled: bool,
+    /// ツールチップ（次に取り消す・やり直す操作の説明）
+    pub tooltip: String,
+}
+
+impl HistoryButtonState {
+    /// 履歴の件数と次の操作から作成
+    ///
+    /// # 引数
+    /// * `label` - ボタンの動作（「元に戻す」など）
+    /// * `len` - 履歴に残っている操作の数
+    /// * `next` - 次に取り消す・やり直す操作
+    fn new(label: &str, len: usize, next: Option<&FileOperation>) -> Self {
+        match next {
+            Some(operation) if len > 0 => Self {
+                enabled: true,
+                tooltip: format!("{}: {}（{} 件）", label, operation.description(), len),
+            },
+            _ => Self {
+                enabled: false,
+                tooltip: format!("{}操作はありません", label),
+            },
+        }
+    }
+}
+
+/// タイプアヘッドの入力バッファ
+///
+/// 一定時間入力がないと、次の入力から新しい前方一致の文字列として扱います。
+#[derive(Debug, Clone, Default)]
+pub struct TypeAheadBuffer {
+    /// これまでに入力された文字列
+    buffer: String,
+    /// 最後に入力された時刻
+    last_input: Option<Instant>,
+}
+
+impl TypeAheadBuffer {
+    /// 入力をリセットするまでの無操作時間
+    pub const TIMEOUT: Duration = Duration::from_millis(1000);
+
+    /// 文字を追加し、現在の入力文字列を返す
+    ///
+    /// 前回の入力から `timeout` より長く空いた場合は、それまでの入力を捨ててから追加します。
+    pub fn push(&mut self, ch: char, now: Instant, timeout: Duration) -> &str {
+        if self.last_input.is_some_and(|last| now.duration_since(last) > timeout) {
+            self.buffer.clear();
+        }
+        self.buffer.push(ch);
+        self.last_input = Some(now);
+        &self.buffer
+    }
+
+    /// 現在の入力文字列
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// 入力をリセット
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.last_input = None;
+    }
+}
+
+/// 名前が `prefix` で始まる最初のエイリアスのインデックスを探す（大文字小文字を区別しない）
+pub fn find_alias_by_prefix(items: &[FileAlias], prefix: &str) -> Option<usize> {
+    if prefix.is_empty() {
+        return None;
+    }
+    let prefix = prefix.to_lowercase();
+    items.iter().position(|alias| alias.alias.to_lowercase().starts_with(&prefix))
+}
+
+/// ディレクトリ履歴の移動方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryNavigation {
+    /// 戻る
+    Back,
+    /// 進む
+    Forward,
+}
+
+impl HistoryNavigation {
+    /// マウスのサイドボタンに対応する移動方向を取得
+    ///
+    /// Extra1 が「戻る」、Extra2 が「進む」ボタンに相当します。
+    pub fn from_pointer_button(button: egui::PointerButton) -> Option<Self> {
+        match button {
+            egui::PointerButton::Extra1 => Some(HistoryNavigation::Back),
+            egui::PointerButton::Extra2 => Some(HistoryNavigation::Forward),
+            _ => None,
+        }
+    }
+
+    /// 入力イベントからサイドボタンによる移動方向を取得
+    ///
+    /// ボタンを押した時点のイベントのみを対象とし、最初に見つかったものを返します。
+    pub fn from_events(events: &[egui::Event]) -> Option<Self> {
+        events.iter().find_map(|event| match event {
+            egui::Event::PointerButton { button, pressed: true, .. } => {
+                Self::from_pointer_button(*button)
+            }
+            _ => None,
+        })
+    }
+}
+
 impl PropertiesDialog {
     pub fn new(path: PathBuf) -> Self {
         let name = path.file_name()
@@ -330,55 +1132,39 @@ impl PropertiesDialog {
         let is_readonly = metadata.as_ref().map(|m| m.permissions().readonly()).unwrap_or(false);
         let modified = metadata.as_ref().and_then(|m| m.modified().ok());
         let created = metadata.as_ref().and_then(|m| m.created().ok());
+        let size_task = is_directory.then(|| DirectorySizeTask::spawn(path.clone()));
+        let text_preview = if is_directory {
+            None
+        } else {
+            read_text(&path, TEXT_PREVIEW_MAX_BYTES).unwrap_or_else(|e| {
+                log::warn!("{}", e);
+                None
+            })
+        };
 
-        Self { path, name, size, is_directory, is_readonly, modified, created }
+        Self { path, name, size, is_directory, is_readonly, modified, created, size_task, text_preview }
     }
 }
 
-/// ペースト操作の結果
+/// メッセージの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageType {
+    Success,
+    Error,
+    Warning,
+}
+
+/// 操作結果メッセージ（汎用）
 #[derive(Debug, Clone)]
-pub struct PasteResultMessage {
+pub struct OperationResultMessage {
     /// メッセージテキスト
     pub message: String,
-    /// 成功/失敗のタイプ
+    /// メッセージタイプ
     pub message_type: MessageType,
     /// メッセージが表示された時刻
     pub timestamp: Instant,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum MessageType {
-    Success,
-    Error,
-    Warning,
-}
-
-impl PasteResultMessage {
-    pub fn new(message: String, message_type: MessageType) -> Self {
-        Self {
-            message,
-            message_type,
-            timestamp: Instant::now(),
-        }
-    }
-
-    /// メッセージが期限切れか（5秒経過）
-    pub fn is_expired(&self) -> bool {
-        self.timestamp.elapsed() > Duration::from_secs(5)
-    }
-}
-
-/// 操作結果メッセージ（汎用）
-#[derive(Debug, Clone)]
-pub struct OperationResultMessage {
-    /// メッセージテキスト
-    pub message: String,
-    /// メッセージタイプ
-    pub message_type: MessageType,
-    /// メッセージが表示された時刻
-    pub timestamp: Instant,
-    /// 表示期間
-    pub duration: Duration,
+    /// 表示期間
+    pub duration: Duration,
 }
 
 impl OperationResultMessage {
@@ -412,9 +1198,107 @@ impl OperationResultMessage {
         }
     }
 
+    /// 種類を指定してメッセージを作成（表示期間は種類に応じる）
+    pub fn with_type(message: String, message_type: MessageType) -> Self {
+        match message_type {
+            MessageType::Success => Self::success(message),
+            MessageType::Error => Self::error(message),
+            MessageType::Warning => Self::warning(message),
+        }
+    }
+
     /// メッセージが期限切れかチェック
     pub fn is_expired(&self) -> bool {
-        self.timestamp.elapsed() > self.duration
+        self.is_expired_at(Instant::now())
+    }
+
+    /// 指定時刻の時点で期限切れかチェック
+    pub fn is_expired_at(&self, now: Instant) -> bool {
+        now.duration_since(self.timestamp) > self.duration
+    }
+}
+
+/// 通知トーストのキュー
+///
+/// 複数のメッセージを古い順に保持し、それぞれの表示期間で期限切れにします。
+/// 上限を超えた場合は最も古いメッセージから破棄します。
+#[derive(Debug, Clone)]
+pub struct ToastQueue {
+    /// 表示中のメッセージ（先頭が最も古い）
+    toasts: VecDeque<OperationResultMessage>,
+    /// 同時に保持する最大数
+    max_len: usize,
+}
+
+impl Default for ToastQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToastQueue {
+    /// デフォルトの最大保持数
+    const DEFAULT_MAX_LEN: usize = 5;
+
+    /// 新しい ToastQueue を作成
+    pub fn new() -> Self {
+        Self::with_max_len(Self::DEFAULT_MAX_LEN)
+    }
+
+    /// 最大保持数を指定して ToastQueue を作成
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            max_len: max_len.max(1),
+        }
+    }
+
+    /// メッセージを追加（上限を超えたら最も古いものを破棄）
+    pub fn push(&mut self, toast: OperationResultMessage) {
+        self.toasts.push_back(toast);
+        while self.toasts.len() > self.max_len {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// 期限切れのメッセージを削除
+    pub fn remove_expired(&mut self) {
+        self.remove_expired_at(Instant::now());
+    }
+
+    /// 指定時刻の時点で期限切れのメッセージを削除
+    pub fn remove_expired_at(&mut self, now: Instant) {
+        self.toasts.retain(|toast| !toast.is_expired_at(now));
+    }
+
+    /// 指定インデックスのメッセージを閉じる
+    pub fn dismiss(&mut self, index: usize) {
+        self.toasts.remove(index);
+    }
+
+    /// 全てのメッセージを閉じる
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    /// 表示中のメッセージ（古い順）
+    pub fn iter(&self) -> impl Iterator<Item = &OperationResultMessage> {
+        self.toasts.iter()
+    }
+
+    /// 最も新しいメッセージを取得
+    pub fn latest(&self) -> Option<&OperationResultMessage> {
+        self.toasts.back()
+    }
+
+    /// 表示中のメッセージ数
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// メッセージがないか
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
     }
 }
 
@@ -425,10 +1309,56 @@ impl AppState {
             || self.rename_dialog.is_some()
             || self.properties_dialog.is_some()
             || self.overwrite_confirmation_dialog.is_some()
+            || self.paste_task.is_some()
             || self.add_quick_access_dialog.is_some()
+            || self.rename_quick_access_dialog.is_some()
             || self.show_add_alias_dialog
             || self.context_menu_state.is_some()
+            || self.command_palette.is_some()
+            || self.show_shortcut_help
+            || self.duplicate_alias_dialog.is_some()
+            || self.alias_edit_dialog.is_some()
+            || self.alias_repair_dialog.is_some()
+            || self.profile_import_dialog.is_some()
+    }
+}
+
+/// 編集ダイアログの入力を検証してエイリアスを更新する
+///
+/// パスを変更した場合は、リムーバブルドライブのボリューム情報も記録し直します。
+fn update_alias_from_dialog(manager: &mut AliasManager, dialog: &AliasEditDialog) -> Result<String, String> {
+    let name = dialog.name.trim().to_string();
+    if name.is_empty() {
+        return Err("エイリアス名を入力してください".to_string());
+    }
+    let path_text = dialog.path.trim();
+    if path_text.is_empty() {
+        return Err("パスを入力してください".to_string());
+    }
+
+    let path = PathBuf::from(path_text);
+    let path_changed = manager
+        .get_aliases()
+        .iter()
+        .find(|a| a.id == dialog.id)
+        .is_some_and(|a| a.path != path);
+
+    manager.update_alias(
+        &dialog.id,
+        Some(name.clone()),
+        Some(path.clone()),
+        Some(dialog.parsed_tags()),
+        Some(dialog.color.clone()),
+        Some(dialog.is_favorite),
+    )?;
+
+    if path_changed {
+        let volume = crate::platform::volume_for_path(&path, &crate::platform::SystemDriveEnumerator);
+        if let Err(e) = manager.set_volume(&name, volume) {
+            log::warn!("ボリューム情報の記録に失敗: {}", e);
+        }
     }
+    Ok(name)
 }
 
 impl Default for AppState {
@@ -453,42 +1383,76 @@ impl Default for AppState {
             search_bar_focused: false,
             directory_search_bar_focused: false,
             filtered_items: Vec::new(),
+            alias_search_truncated: false,
             selected_index: None,
             show_settings: false,
             current_theme: Theme::default(),
+            applied_theme: None,
+            system_theme_cache: SystemThemeCache::default(),
             search_debouncer: SearchDebouncer::default(),
             initialized: false,
             browse_mode: BrowseMode::Alias,
             directory_browser: None,
+            directory_cache: SharedDirectoryCache::default(),
+            directory_prewarmer: None,
+            directory_cache_watcher: None,
+            directory_cache_debouncer: ReloadDebouncer::default(),
             selected_directory_index: None,
+            multi_selected_paths: HashSet::new(),
+            selection_anchor: None,
             expanded_directories: HashSet::new(),
+            directory_view_mode: DirectoryViewMode::default(),
             hotkey_manager,
+            hotkey_registered: false,
+            data_dir_warning: None,
             system_tray: SystemTray::new(),
             is_window_visible: true,
             last_hotkey_time: None,
+            escape_tracker: DoublePressDetector::default(),
+            escape_cleared_selection: None,
+            type_ahead: TypeAheadBuffer::default(),
             current_focus_area: FocusArea::default(),
             selected_sidebar_index: None,
+            saved_alias_selection: None,
+            saved_directory_selection: None,
             alias_manager: AliasManager::new(),
+            alias_watcher: None,
             show_add_alias_dialog: false,
             new_alias_name: String::new(),
             new_alias_path: String::new(),
+            add_alias_path_warning: None,
             search_engine: SearchEngine::new(),
             clipboard_state: ClipboardState::new(),
             quick_access_manager: QuickAccessManager::new(),
             quick_access_entries: Vec::new(),
             pasted_files_highlight: None,
-            paste_result_message: None,
-            operation_result_message: None,
+            failed_path_badges: FailedPathBadges::default(),
+            toasts: ToastQueue::new(),
             add_quick_access_dialog: None,
+            rename_quick_access_dialog: None,
             overwrite_confirmation_dialog: None,
+            paste_task: None,
+            recursive_search: None,
             delete_confirmation_dialog: None,
             rename_dialog: None,
             properties_dialog: None,
+            thumbnail_cache: ThumbnailCache::default(),
             context_menu_state: None,
             pending_file_copy: false,
             pending_file_cut: false,
             pending_file_paste: false,
             operation_history: OperationHistoryManager::new(),
+            history_manager: HistoryManager::new(),
+            command_palette: None,
+            show_shortcut_help: false,
+            duplicate_alias_dialog: None,
+            alias_edit_dialog: None,
+            alias_repair_dialog: None,
+            profile_import_dialog: None,
+            selection_mode: false,
+            checked_paths: Vec::new(),
+            bulk_tag_input: String::new(),
+            group_by_tag: false,
         }
     }
 }
@@ -508,7 +1472,8 @@ impl AppState {
 
     /// エイリアスを読み込む
     pub fn load_aliases(&mut self) -> anyhow::Result<()> {
-        let aliases = crate::data::storage::load_aliases()?;
+        let mut aliases = crate::data::storage::load_aliases()?;
+        aliases.sort_by_key(|a| a.order);
         self.file_aliases = aliases;
         self.search_engine.set_aliases(self.file_aliases.clone());
         self.filtered_items = self.file_aliases.clone();
@@ -526,43 +1491,60 @@ impl AppState {
             return Ok(());
         }
 
+        // データフォルダに書き込めるか確認（書き込めない場合は警告を表示し続ける）
+        match crate::data::storage::get_config_dir() {
+            Ok(dir) => {
+                self.check_data_dir_writable(&dir);
+            }
+            Err(e) => {
+                log::error!("データフォルダを用意できません: {:#}", e);
+                self.data_dir_warning = Some(format!("データフォルダを用意できません: {:#}", e));
+            }
+        }
+
         // 設定を読み込み
         if let Err(e) = self.load_config() {
             log::warn!("設定の読み込みに失敗（デフォルト設定を使用）: {}", e);
         }
+        self.apply_search_config();
+        self.apply_history_config();
+
+        // 設定の先頭のタブで起動する
+        self.set_browse_mode(self.default_browse_mode());
+
+        // 「最近」タブの履歴を読み込む
+        if let Err(e) = self.history_manager.load() {
+            log::warn!("履歴の読み込みに失敗: {}", e);
+        }
 
         // エイリアスを読み込む
         if let Err(e) = self.alias_manager.load() {
             log::warn!("エイリアスの読み込みに失敗: {}", e);
         } else {
+            self.check_broken_aliases();
             // 互換性維持のため、file_aliasesにもコピー
-            self.file_aliases = self.alias_manager.get_aliases().to_vec();
-            self.search_engine.set_aliases(self.file_aliases.clone());
-            self.filtered_items = self.file_aliases.clone();
+            self.refresh_after_alias_change();
             log::info!("{} 件のエイリアスを読み込みました", self.file_aliases.len());
-        }
-
-        // 設定からホットキーを登録（フォールバック付き）
-        // hotkey_managerがNoneの場合はスキップ
-        if self.hotkey_manager.is_some() {
-            if let Err(e) = self.register_configured_hotkey() {
-                log::warn!("設定からのホットキー登録に失敗: {}。デフォルト設定を使用します。", e);
+            self.check_duplicate_aliases();
 
-                // デフォルト設定でリトライ
-                let default_modifiers = Modifiers::CONTROL | Modifiers::SHIFT;
-                let default_code = Code::KeyO;
-                if let Some(ref mut manager) = self.hotkey_manager {
-                    if let Err(e) = manager.register(default_modifiers, default_code) {
-                        log::error!("デフォルトホットキーの登録も失敗: {}", e);
-                    } else {
-                        log::info!("デフォルトホットキーを登録しました: Ctrl+Shift+O");
-                    }
-                }
+            // 削除済みのタグの折りたたみ状態を設定から取り除く
+            if self.prune_collapsed_tags() {
+                self.save_config_or_report();
             }
-        } else {
-            log::warn!("HotkeyManagerが利用できないため、ホットキー登録をスキップします");
         }
 
+        // エイリアスファイルの外部変更を監視
+        match crate::data::storage::get_aliases_path() {
+            Ok(path) => match AliasFileWatcher::new(&path) {
+                Ok(watcher) => self.alias_watcher = Some(watcher),
+                Err(e) => log::warn!("エイリアスファイルの監視を開始できません: {}", e),
+            },
+            Err(e) => log::warn!("エイリアスファイルのパス取得に失敗: {}", e),
+        }
+
+        // 設定からホットキーを登録（フォールバック付き）
+        self.register_hotkey_with_fallback();
+
         // システムトレイを構築
         if let Err(e) = self.system_tray.build() {
             log::warn!("システムトレイの構築に失敗しました: {}", e);
@@ -576,6 +1558,16 @@ impl AppState {
             log::warn!("クイックアクセスの読み込みに失敗: {}", e);
         }
 
+        // 前回の検索クエリを復元（設定で有効な場合のみ）
+        self.restore_last_queries();
+
+        // お気に入りとクイックアクセスのフォルダ一覧を先読み
+        match DirectoryCacheWatcher::new() {
+            Ok(watcher) => self.directory_cache_watcher = Some(watcher),
+            Err(e) => log::warn!("フォルダ一覧のキャッシュの監視を開始できません: {}", e),
+        }
+        self.start_directory_prewarm();
+
         self.initialized = true;
         Ok(())
     }
@@ -614,435 +1606,4089 @@ impl AppState {
         Ok(())
     }
 
-    /// 初期化が完了しているか
-    pub fn is_initialized(&self) -> bool {
+    /// 設定のホットキーを登録し、失敗した場合はデフォルト（Ctrl+Shift+O）で再試行
+    ///
+    /// 結果は `hotkey_registered` に記録されます。
+    ///
+    /// # 戻り値
+    /// ホットキーが登録されたか（設定で無効にしている場合は false）
+    pub fn register_hotkey_with_fallback(&mut self) -> bool {
+        // hotkey_managerがNoneの場合はスキップ
+        if self.hotkey_manager.is_none() {
+            log::warn!("HotkeyManagerが利用できないため、ホットキー登録をスキップします");
+            self.hotkey_registered = false;
+            return false;
+        }
+
+        let registered = match self.register_configured_hotkey() {
+            Ok(()) => self.config.as_ref().is_some_and(|c| c.hotkey.enabled),
+            Err(e) => {
+                log::warn!("設定からのホットキー登録に失敗: {}。デフォルト設定を使用します。", e);
+
+                // デフォルト設定でリトライ
+                let default_modifiers = Modifiers::CONTROL | Modifiers::SHIFT;
+                let default_code = Code::KeyO;
+                match self.hotkey_manager.as_mut().map(|m| m.register(default_modifiers, default_code)) {
+                    Some(Ok(())) => {
+                        log::info!("デフォルトホットキーを登録しました: Ctrl+Shift+O");
+                        true
+                    }
+                    Some(Err(e)) => {
+                        log::error!("デフォルトホットキーの登録も失敗: {}", e);
+                        false
+                    }
+                    None => false,
+                }
+            }
+        };
+
+        self.hotkey_registered = registered;
+        registered
+    }
+
+    /// ホットキー登録失敗の警告を表示すべきか
+    ///
+    /// 設定でホットキーを有効にしているのに登録できていない場合に true を返します。
+    pub fn hotkey_warning_visible(&self) -> bool {
         self.initialized
+            && !self.hotkey_registered
+            && self.config.as_ref().is_some_and(|c| c.hotkey.enabled)
     }
 
-    /// ブラウザモードを設定
-    pub fn set_browse_mode(&mut self, mode: BrowseMode) {
-        self.browse_mode = mode;
+    /// データフォルダに書き込めるか確認し、書き込めない場合は警告を設定する
+    ///
+    /// # 戻り値
+    /// 書き込める場合は true
+    pub fn check_data_dir_writable(&mut self, dir: &Path) -> bool {
+        match crate::data::storage::check_dir_writable(dir) {
+            Ok(()) => {
+                self.data_dir_warning = None;
+                true
+            }
+            Err(e) => {
+                log::error!("データフォルダに書き込めません: {:#}", e);
+                self.data_dir_warning = Some(format!(
+                    "データフォルダ {} に書き込めません。読み取り専用のドライブか、アクセス権限がない可能性があります。設定やエイリアスの変更は保存されません。",
+                    dir.display()
+                ));
+                false
+            }
+        }
     }
 
-    /// ディレクトリブラウザを初期化
-    pub fn init_directory_browser(&mut self, path: PathBuf) -> std::io::Result<()> {
-        self.directory_browser = Some(DirectoryBrowser::new(path)?);
-        Ok(())
+    /// 保存の失敗をログに記録し、トーストでも知らせる
+    ///
+    /// # 引数
+    /// * `target` - 保存しようとしたもの（例: "設定"、"エイリアス"）
+    pub fn report_save_error(&mut self, target: &str, error: impl std::fmt::Display) {
+        log::error!("{}の保存に失敗: {:#}", target, error);
+        self.toasts.push(OperationResultMessage::error(
+            format!("{}の保存に失敗しました: {:#}", target, error)
+        ));
     }
 
-    /// 現在表示すべきエントリを取得
-    pub fn get_current_entries(&self) -> Vec<crate::data::models::DirectoryEntry> {
-        if let Some(ref browser) = self.directory_browser {
-            browser.entries().to_vec()
-        } else {
-            Vec::new()
+    /// 設定を保存し、失敗した場合はトーストで知らせる
+    ///
+    /// # 戻り値
+    /// 保存できた場合は true（設定が読み込まれていない場合は false）
+    pub fn save_config_or_report(&mut self) -> bool {
+        let Some(ref config) = self.config else {
+            return false;
+        };
+        match crate::data::storage::save_config(config) {
+            Ok(()) => true,
+            Err(e) => {
+                self.report_save_error("設定", e);
+                false
+            }
         }
     }
 
-    /// 検索クエリに基づいてエイリアスをフィルタリング
-    pub fn filter_aliases(&mut self) {
-        if self.search_query.is_empty() {
-            self.filtered_items = self.file_aliases.clone();
-        } else {
-            // SearchEngineを使用した高度な検索
-            let results = self.search_engine.search(&self.search_query);
+    /// 初期化が完了しているか
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
 
-            // SearchResultからFileAliasに変換
-            // スコア順にソートされているので、その順序を維持
-            self.filtered_items = results
-                .into_iter()
-                .map(|result| result.alias)
-                .collect();
+    /// 現在の検索クエリをモードごとの「最後のクエリ」として設定に記録
+    ///
+    /// 空のクエリは記録しません（以前の値を保持）。
+    /// 設定でクエリの復元が無効な場合は何もしません。
+    ///
+    /// # 戻り値
+    /// 設定の内容が変わった場合は true（呼び出し側で保存する）
+    pub fn capture_last_queries(&mut self) -> bool {
+        let config = match self.config.as_mut() {
+            Some(config) if config.search.remember_last_query => config,
+            _ => return false,
+        };
+
+        let mut changed = false;
+        for (query, slot) in [
+            (&self.search_query, &mut config.search.last_alias_query),
+            (&self.directory_search_query, &mut config.search.last_directory_query),
+        ] {
+            if !query.is_empty() && slot.as_deref() != Some(query.as_str()) {
+                *slot = Some(query.clone());
+                changed = true;
+            }
         }
+        changed
     }
 
-    /// クイックアクセスを読み込む
-    pub fn load_quick_access(&mut self) -> anyhow::Result<()> {
-        self.quick_access_manager.load()?;
-        self.quick_access_entries = self.quick_access_manager.get_entries();
-        Ok(())
+    /// サイドバーの幅を取得（設定がない場合は既定値）
+    pub fn sidebar_width(&self) -> f32 {
+        let width = self.config
+            .as_ref()
+            .map(|c| c.display.sidebar_width)
+            .unwrap_or_else(|| crate::data::models::DisplayConfig::default().sidebar_width);
+        crate::ui::layout::clamp_sidebar_width(width)
     }
 
-    /// クイックアクセスにエントリを追加
-    pub fn add_to_quick_access(&mut self, name: String, path: PathBuf) -> Result<(), String> {
-        self.quick_access_manager.add_entry(name, path)?;
-        self.quick_access_manager.save()
-            .map_err(|e| format!("保存失敗: {}", e))?;
-        self.quick_access_entries = self.quick_access_manager.get_entries();
-        Ok(())
+    /// 貼り付け時にフォルダ構成を再現するか（設定が読み込まれていない場合は無効）
+    pub fn preserve_paste_structure(&self) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(|c| c.file_operations.preserve_structure)
     }
 
-    /// クイックアクセスからエントリを削除
-    pub fn remove_from_quick_access(&mut self, id: &str) -> Result<(), String> {
-        self.quick_access_manager.remove_entry_by_id(id)?;
-        self.quick_access_manager.save()
-            .map_err(|e| format!("保存失敗: {}", e))?;
-        self.quick_access_entries = self.quick_access_manager.get_entries();
-        Ok(())
+    /// 貼り付け（コピー）時にファイル属性を引き継ぐか（設定が読み込まれていない場合は無効）
+    pub fn preserve_paste_attributes(&self) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(|c| c.file_operations.preserve_attributes)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::data::models::*;
+    /// 同じフォルダに貼り付けたときのコピー名の形式（設定が読み込まれていない場合は既定の形式）
+    pub fn copy_name_pattern(&self) -> &str {
+        self.config
+            .as_ref()
+            .map(|c| c.file_operations.copy_name_pattern.as_str())
+            .unwrap_or(crate::core::clipboard::DEFAULT_COPY_NAME_PATTERN)
+    }
 
-    /// テスト用のデフォルト設定を作成
-    fn create_test_config(enabled: bool, modifiers: Vec<String>, key: String) -> Config {
-        Config {
-            version: "1.0.0".to_string(),
-            window: WindowConfig {
-                width: 800.0,
-                height: 600.0,
-                position: WindowPosition { x: 0.0, y: 0.0 },
-                always_on_top: false,
-                decorations: true,
-            },
-            hotkey: HotkeyConfig {
-                enabled,
-                modifiers,
-                key,
-            },
-            edge_trigger: EdgeTriggerConfig {
-                enabled: false,
-                edge: "top".to_string(),
-                delay_ms: 500,
-                trigger_width: 10,
-            },
-            autostart: AutostartConfig { enabled: false },
-            theme: ThemeConfig {
-                mode: "light".to_string(),
-                custom_accent_color: None,
-            },
-            search: SearchConfig {
-                incremental: true,
-                fuzzy_match: false,
-                search_paths: true,
-                search_aliases: true,
-                case_sensitive: false,
-            },
-            file_operations: FileOperationConfig {
-                confirm_delete: true,
-                use_trash: true,
-                default_open_action: "open".to_string(),
-            },
-        }
+    /// 貼り付け先に同名の項目がある場合の扱い（設定が読み込まれていない・不正な値は常に上書き）
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        self.config
+            .as_ref()
+            .and_then(|c| ConflictPolicy::parse(&c.file_operations.conflict_policy))
+            .unwrap_or_default()
     }
 
-    #[test]
-    fn test_register_configured_hotkey_success() {
-        // 正常系: 有効な設定でホットキー登録が成功
-        let mut state = AppState::default();
-        state.config = Some(create_test_config(
-            true,
-            vec!["Ctrl".to_string(), "Shift".to_string()],
-            "O".to_string(),
-        ));
+    /// 貼り付けで作成される項目数がしきい値を超える場合、その項目数を返す
+    ///
+    /// しきい値を少し超えた所で数えるのをやめるため、大きなフォルダでも時間はかかりません。
+    /// 設定が読み込まれていない場合は既定のしきい値（1000）を使います。
+    pub fn large_paste_count(&self, src_paths: &[PathBuf]) -> Option<usize> {
+        let threshold = self.config
+            .as_ref()
+            .map(|c| c.file_operations.large_paste_threshold)
+            .unwrap_or(1000);
+        if threshold == 0 {
+            return None;
+        }
+        let count = crate::core::file_manager::count_items_bounded(src_paths, threshold);
+        crate::core::file_manager::needs_large_paste_confirmation(count, threshold).then_some(count)
+    }
 
-        let result = state.register_configured_hotkey();
-        assert!(result.is_ok(), "ホットキー登録が失敗しました: {:?}", result.err());
+    /// Escキーの2回押しとみなす間隔（0 は無効）
+    pub fn double_escape_threshold(&self) -> Duration {
+        let ms = self.config
+            .as_ref()
+            .map(|c| c.window.double_escape_ms)
+            .unwrap_or(400);
+        Duration::from_millis(ms)
     }
 
-    #[test]
-    fn test_register_configured_hotkey_disabled() {
+    /// 選択と検索クエリをクリア（1回目のEscキー）
+    ///
+    /// クリアした内容は `restore_escape_cleared_selection` で元に戻せます。
+    pub fn clear_selection_and_search(&mut self) {
+        self.escape_cleared_selection = Some(ClearedSelection {
+            browse_mode: self.browse_mode,
+            selected_index: self.selected_index,
+            search_query: self.search_query.clone(),
+            selected_directory_index: self.selected_directory_index,
+            multi_selected_paths: self.multi_selected_paths.clone(),
+            selection_anchor: self.selection_anchor.clone(),
+            directory_search_query: self.directory_search_query.clone(),
+        });
+
+        match self.browse_mode {
+            BrowseMode::Alias => {
+                self.selected_index = None;
+                if !self.search_query.is_empty() {
+                    self.search_query.clear();
+                    self.filter_aliases();
+                }
+            }
+            BrowseMode::Directory => {
+                self.selected_directory_index = None;
+                self.clear_directory_selection();
+                self.directory_search_query.clear();
+            }
+            BrowseMode::Recent => {}
+        }
+    }
+
+    /// 直前の1回目のEscキーでクリアした選択と検索クエリを元に戻す
+    ///
+    /// Escキーの2回押しでウィンドウを隠すときに呼びます。
+    /// 表示モードが切り替わっている場合は何もしません。
+    pub fn restore_escape_cleared_selection(&mut self) {
+        let Some(cleared) = self.escape_cleared_selection.take() else {
+            return;
+        };
+        if cleared.browse_mode != self.browse_mode {
+            return;
+        }
+
+        match cleared.browse_mode {
+            BrowseMode::Alias => {
+                if self.search_query != cleared.search_query {
+                    self.search_query = cleared.search_query;
+                    self.filter_aliases();
+                }
+                self.selected_index = clamp_selection(cleared.selected_index, self.filtered_items.len());
+            }
+            BrowseMode::Directory => {
+                self.selected_directory_index = cleared.selected_directory_index;
+                self.multi_selected_paths = cleared.multi_selected_paths;
+                self.selection_anchor = cleared.selection_anchor;
+                self.directory_search_query = cleared.directory_search_query;
+            }
+            BrowseMode::Recent => {}
+        }
+    }
+
+    /// ウィンドウの表示状態を切り替え、切り替え後に表示中かを返す
+    ///
+    /// 表示フラグを切り替えるだけで、展開中のフォルダや選択などの状態には触れません。
+    /// 再表示したときは隠す前と同じ画面に戻ります。
+    pub fn toggle_window_visible(&mut self) -> bool {
+        self.is_window_visible = !self.is_window_visible;
+        self.is_window_visible
+    }
+
+    /// フォーカスのあるパネルの枠線（設定のアクセントカラーを使う）
+    pub fn focus_stroke(&self) -> egui::Stroke {
+        let accent = self.config
+            .as_ref()
+            .and_then(|c| c.theme.custom_accent_color.as_deref());
+        Theme::focus_stroke(accent)
+    }
+
+    /// 選択モードを切り替える（終了時はチェックを解除する）
+    pub fn toggle_selection_mode(&mut self) {
+        self.selection_mode = !self.selection_mode;
+        if !self.selection_mode {
+            self.checked_paths.clear();
+            self.bulk_tag_input.clear();
+        }
+    }
+
+    /// パスがチェックされているか
+    pub fn is_checked(&self, path: &Path) -> bool {
+        self.checked_paths.iter().any(|p| p == path)
+    }
+
+    /// パスのチェック状態を設定
+    pub fn set_checked(&mut self, path: &Path, checked: bool) {
+        if checked {
+            if !self.is_checked(path) {
+                self.checked_paths.push(path.to_path_buf());
+            }
+        } else {
+            self.checked_paths.retain(|p| p != path);
+        }
+    }
+
+    /// 表示中の項目をすべてチェックする
+    ///
+    /// # 引数
+    /// * `paths` - 表示中の項目のパス
+    pub fn check_all<I: IntoIterator<Item = PathBuf>>(&mut self, paths: I) {
+        for path in paths {
+            self.set_checked(&path, true);
+        }
+    }
+
+    /// すべてのチェックを解除する
+    pub fn uncheck_all(&mut self) {
+        self.checked_paths.clear();
+    }
+
+    /// チェックされたパスを取り出す（チェックは解除される）
+    pub fn take_checked_paths(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.checked_paths)
+    }
+
+    /// チェックされた項目をクリップボードにコピーする
+    ///
+    /// # 戻り値
+    /// コピーした件数（チェックがない場合は 0 で何もしない）
+    pub fn bulk_copy(&mut self) -> usize {
+        let paths = self.take_checked_paths();
+        let count = paths.len();
+        if count > 0 {
+            self.clipboard_state.copy(paths);
+        }
+        count
+    }
+
+    /// チェックされた項目を切り取る
+    ///
+    /// # 戻り値
+    /// 切り取った件数（チェックがない場合は 0 で何もしない）
+    pub fn bulk_cut(&mut self) -> usize {
+        let paths = self.take_checked_paths();
+        let count = paths.len();
+        if count > 0 {
+            self.clipboard_state.cut(paths);
+        }
+        count
+    }
+
+    /// チェックされた項目の削除確認ダイアログを開く
+    ///
+    /// # 戻り値
+    /// ダイアログを開いた場合 true（チェックがない場合は false）
+    pub fn bulk_delete(&mut self) -> bool {
+        let paths = self.take_checked_paths();
+        if paths.is_empty() {
+            return false;
+        }
+        self.delete_confirmation_dialog = Some(DeleteConfirmationDialog::new(paths));
+        true
+    }
+
+    /// ディレクトリ一覧のクリックで選択を更新する
+    ///
+    /// 通常のクリックは複数選択を解除し、Ctrl+クリックは項目を選択に加える/外し、
+    /// Shift+クリックは起点からクリックした項目までを選択し直します。
+    /// フォーカスはいずれの場合もクリックした項目に移します。
+    ///
+    /// # 引数
+    /// * `visible_paths` - 表示中（フィルタ後）の項目のパス
+    /// * `path` - クリックされた項目のパス
+    /// * `click` - 選択方法
+    pub fn click_directory_entry(&mut self, visible_paths: &[PathBuf], path: &Path, click: SelectionClick) {
+        let index = visible_paths.iter().position(|p| paths_equal(p, path));
+
+        match click {
+            SelectionClick::Replace => {
+                self.multi_selected_paths.clear();
+                self.selection_anchor = Some(path.to_path_buf());
+            }
+            SelectionClick::Toggle => {
+                // 単一選択から Ctrl+クリックした場合はフォーカス中の項目も選択に含める
+                if self.multi_selected_paths.is_empty() {
+                    if let Some(focused) = self.selected_directory_index.and_then(|i| visible_paths.get(i)) {
+                        self.multi_selected_paths.insert(focused.clone());
+                    }
+                }
+                if !self.multi_selected_paths.remove(path) {
+                    self.multi_selected_paths.insert(path.to_path_buf());
+                }
+                self.selection_anchor = Some(path.to_path_buf());
+            }
+            SelectionClick::Range => {
+                let anchor = self.selection_anchor
+                    .as_ref()
+                    .and_then(|anchor| visible_paths.iter().position(|p| paths_equal(p, anchor)))
+                    .or(self.selected_directory_index.filter(|&i| i < visible_paths.len()));
+                self.multi_selected_paths.clear();
+                match (anchor, index) {
+                    (Some(anchor), Some(index)) => {
+                        let (start, end) = (anchor.min(index), anchor.max(index));
+                        self.multi_selected_paths.extend(visible_paths[start..=end].iter().cloned());
+                        self.selection_anchor = Some(visible_paths[anchor].clone());
+                    }
+                    _ => {
+                        self.multi_selected_paths.insert(path.to_path_buf());
+                        self.selection_anchor = Some(path.to_path_buf());
+                    }
+                }
+            }
+        }
+
+        self.selected_directory_index = index;
+    }
+
+    /// 表示中の項目をすべて複数選択する（Ctrl+A）
+    pub fn select_all_directory_entries(&mut self, visible_paths: &[PathBuf]) {
+        self.multi_selected_paths = visible_paths.iter().cloned().collect();
+    }
+
+    /// ディレクトリの複数選択を解除する
+    pub fn clear_directory_selection(&mut self) {
+        self.multi_selected_paths.clear();
+        self.selection_anchor = None;
+    }
+
+    /// 表示されなくなった項目を複数選択から外す
+    ///
+    /// 再読み込み・フォルダ移動・検索クエリの変更で一覧が変わった後に呼びます。
+    pub fn retain_visible_selection(&mut self, visible_paths: &[PathBuf]) {
+        if self.multi_selected_paths.is_empty() && self.selection_anchor.is_none() {
+            return;
+        }
+        let visible: HashSet<&PathBuf> = visible_paths.iter().collect();
+        self.multi_selected_paths.retain(|path| visible.contains(path));
+        if self.selection_anchor.as_ref().is_some_and(|anchor| !visible.contains(anchor)) {
+            self.selection_anchor = None;
+        }
+    }
+
+    /// コピー・切り取り・削除の対象となるパスを表示順で取得
+    ///
+    /// 複数選択がある場合はその全体、ない場合はフォーカス中の項目を返します。
+    pub fn directory_selection_paths(&self, visible_paths: &[PathBuf]) -> Vec<PathBuf> {
+        if self.multi_selected_paths.is_empty() {
+            return self.selected_directory_index
+                .and_then(|i| visible_paths.get(i))
+                .cloned()
+                .into_iter()
+                .collect();
+        }
+        visible_paths
+            .iter()
+            .filter(|path| self.multi_selected_paths.contains(*path))
+            .cloned()
+            .collect()
+    }
+
+    /// チェックされたパスを指すエイリアスにタグを付ける（保存は呼び出し側で行う）
+    ///
+    /// # 引数
+    /// * `tag` - 付けるタグ（前後の空白は除く。空の場合は何もしない）
+    ///
+    /// # 戻り値
+    /// タグを追加したエイリアスの件数
+    pub fn bulk_tag(&mut self, tag: &str) -> usize {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return 0;
+        }
+        let paths = self.take_checked_paths();
+        let updated = self.alias_manager.add_tag_to_paths(&paths, tag);
+        if updated > 0 {
+            self.apply_reloaded_aliases();
+        }
+        updated
+    }
+
+    /// 設定に対応するウィンドウの重なり順を設定するコマンドを取得
+    ///
+    /// 設定が読み込まれていない場合は通常の重なり順にします。
+    pub fn window_level_command(&self) -> egui::ViewportCommand {
+        let always_on_top = self.config
+            .as_ref()
+            .is_some_and(|c| c.window.always_on_top);
+        let level = if always_on_top {
+            egui::WindowLevel::AlwaysOnTop
+        } else {
+            egui::WindowLevel::Normal
+        };
+        egui::ViewportCommand::WindowLevel(level)
+    }
+
+    /// サイドバーに最初から表示するエイリアスの件数
+    pub fn sidebar_alias_limit(&self) -> usize {
+        self.config
+            .as_ref()
+            .map(|c| c.display.sidebar_alias_limit)
+            .unwrap_or_else(|| crate::data::models::DisplayConfig::default().sidebar_alias_limit)
+    }
+
+    /// サイドバーのエイリアス一覧を展開しているか
+    pub fn sidebar_aliases_expanded(&self) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(|c| c.display.sidebar_aliases_expanded)
+    }
+
+    /// サイドバーのエイリアス一覧の展開状態を切り替える
+    ///
+    /// # 戻り値
+    /// 設定の内容が変わった場合は true（呼び出し側で保存する）
+    pub fn toggle_sidebar_aliases_expanded(&mut self) -> bool {
+        let Some(config) = self.config.as_mut() else {
+            return false;
+        };
+        config.display.sidebar_aliases_expanded = !config.display.sidebar_aliases_expanded;
+        true
+    }
+
+    /// 設定に従って適用したいテーマを決める
+    ///
+    /// "system" の場合は一定間隔でのみ検出し直したシステムテーマを使います。
+    fn desired_theme_with(&mut self, now: Instant, detect: impl FnOnce() -> Theme) -> Theme {
+        match self.config.as_ref().map(|c| c.theme.mode.as_str()) {
+            Some("system") => self.system_theme_cache.get_with(now, detect),
+            Some("light") => Theme::Light,
+            _ => Theme::Dark, // デフォルトはダーク
+        }
+    }
+
+    /// テーマがシステム設定に従うか
+    pub fn follows_system_theme(&self) -> bool {
+        self.config.as_ref().is_some_and(|c| c.theme.mode == "system")
+    }
+
+    /// テーマを適用し直す必要があれば、適用するテーマを返す
+    ///
+    /// 返したテーマは適用済みとして記録します。テーマが変わらない間は None を返します。
+    ///
+    /// # 引数
+    /// * `visuals_dark_mode` - egui で現在使われている配色がダークか
+    pub fn theme_to_apply(&mut self, now: Instant, visuals_dark_mode: bool) -> Option<Theme> {
+        self.theme_to_apply_with(now, visuals_dark_mode, crate::platform::detect_system_theme)
+    }
+
+    fn theme_to_apply_with(
+        &mut self,
+        now: Instant,
+        visuals_dark_mode: bool,
+        detect: impl FnOnce() -> Theme,
+    ) -> Option<Theme> {
+        let theme = self.desired_theme_with(now, detect);
+        self.current_theme = theme;
+        if !crate::ui::theme::should_reapply_theme(self.applied_theme, theme, visuals_dark_mode) {
+            return None;
+        }
+        self.applied_theme = Some(theme);
+        Some(theme)
+    }
+
+    /// タイプアヘッドで1文字入力し、前方一致する最初のエイリアスを選択する
+    ///
+    /// 一致するエイリアスがない場合は選択を変えません。
+    ///
+    /// # 戻り値
+    /// 選択したエイリアスのインデックス
+    pub fn type_ahead_input(&mut self, ch: char, now: Instant) -> Option<usize> {
+        let prefix = self.type_ahead.push(ch, now, TypeAheadBuffer::TIMEOUT);
+        let index = find_alias_by_prefix(&self.filtered_items, prefix)?;
+        self.selected_index = Some(index);
+        Some(index)
+    }
+
+    /// タグ別表示でタグのグループを折りたたんでいるか
+    pub fn is_tag_collapsed(&self, tag: &str) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(|c| c.display.collapsed_tags.contains(tag))
+    }
+
+    /// タグのグループの折りたたみ状態を切り替える
+    ///
+    /// # 戻り値
+    /// 設定の内容が変わった場合は true（呼び出し側で保存する）
+    pub fn toggle_tag_collapsed(&mut self, tag: &str) -> bool {
+        let Some(config) = self.config.as_mut() else {
+            return false;
+        };
+        let collapsed = &mut config.display.collapsed_tags;
+        if !collapsed.remove(tag) {
+            collapsed.insert(tag.to_string());
+        }
+        true
+    }
+
+    /// どのエイリアスにも付いていないタグを折りたたみ状態から取り除く
+    ///
+    /// # 戻り値
+    /// 設定の内容が変わった場合は true（呼び出し側で保存する）
+    pub fn prune_collapsed_tags(&mut self) -> bool {
+        let Some(config) = self.config.as_mut() else {
+            return false;
+        };
+        let existing: HashSet<&str> = self.file_aliases
+            .iter()
+            .flat_map(|alias| alias.tags.iter().map(String::as_str))
+            .collect();
+        let before = config.display.collapsed_tags.len();
+        config.display.collapsed_tags.retain(|tag| existing.contains(tag.as_str()));
+        config.display.collapsed_tags.len() != before
+    }
+
+    /// サイドバーの幅を設定に記録
+    ///
+    /// 範囲外の幅は有効な範囲に収めてから記録します。
+    ///
+    /// # 戻り値
+    /// 設定の内容が変わった場合は true（呼び出し側で保存する）
+    pub fn record_sidebar_width(&mut self, width: f32) -> bool {
+        let Some(config) = self.config.as_mut() else {
+            return false;
+        };
+
+        let width = crate::ui::layout::clamp_sidebar_width(width);
+        // ピクセル未満の揺れでは保存しない
+        if (config.display.sidebar_width - width).abs() < 1.0 {
+            return false;
+        }
+        config.display.sidebar_width = width;
+        true
+    }
+
+    /// 設定に記録された最後の検索クエリをモードごとに復元
+    ///
+    /// 設定でクエリの復元が有効な場合のみ、起動時に呼び出します。
+    pub fn restore_last_queries(&mut self) {
+        let (alias_query, directory_query) = match self.config.as_ref() {
+            Some(config) if config.search.remember_last_query => (
+                config.search.last_alias_query.clone(),
+                config.search.last_directory_query.clone(),
+            ),
+            _ => return,
+        };
+
+        if let Some(query) = alias_query {
+            self.search_query = query;
+            self.filter_aliases();
+        }
+        if let Some(query) = directory_query {
+            self.directory_search_query = query;
+        }
+        log::info!("前回の検索クエリを復元しました");
+    }
+
+    /// ブラウザモードを設定
+    ///
+    /// 切り替え前のモードの選択インデックスを退避し、
+    /// 切り替え先のモードで以前の選択を復元します（一覧が縮んでいれば範囲内に収める）。
+    pub fn set_browse_mode(&mut self, mode: BrowseMode) {
+        if self.browse_mode == mode {
+            return;
+        }
+
+        // 切り替えでクエリがクリアされる前に記録しておく
+        self.capture_last_queries();
+
+        match self.browse_mode {
+            BrowseMode::Alias => self.saved_alias_selection = self.selected_index,
+            BrowseMode::Directory => self.saved_directory_selection = self.selected_directory_index,
+            BrowseMode::Recent => {}
+        }
+
+        self.browse_mode = mode;
+
+        match mode {
+            BrowseMode::Alias => {
+                self.selected_index = clamp_selection(self.saved_alias_selection, self.filtered_items.len());
+            }
+            BrowseMode::Directory => {
+                let entry_count = self.filtered_directory_entry_count();
+                self.selected_directory_index = clamp_selection(self.saved_directory_selection, entry_count);
+            }
+            BrowseMode::Recent => {}
+        }
+    }
+
+    /// トップバーに表示するタブ（設定が読み込まれていない場合はデフォルトのタブ）
+    pub fn browse_tabs(&self) -> Vec<BrowseMode> {
+        self.config
+            .as_ref()
+            .map(|c| crate::ui::browse_tabs::parse_browse_tabs(&c.display.browse_tabs))
+            .unwrap_or_else(crate::ui::browse_tabs::default_browse_tabs)
+    }
+
+    /// 起動時のモード（先頭のタブ）
+    pub fn default_browse_mode(&self) -> BrowseMode {
+        self.browse_tabs()[0]
+    }
+
+    /// 表示するタブの中で次のモードに切り替える
+    pub fn switch_to_next_browse_mode(&mut self) {
+        let next_mode = crate::ui::browse_tabs::next_browse_mode(&self.browse_tabs(), self.browse_mode);
+        self.set_browse_mode(next_mode);
+    }
+
+    /// 現在のモードのタブが非表示にされた場合は先頭のタブに切り替える
+    pub fn ensure_browse_mode_enabled(&mut self) {
+        if !self.browse_tabs().contains(&self.browse_mode) {
+            self.set_browse_mode(self.default_browse_mode());
+        }
+    }
+
+    /// 「最近」タブに表示する履歴（新しい順）
+    pub fn recent_entries(&self) -> Vec<FileHistory> {
+        self.history_manager.get_recent(RECENT_TAB_LIMIT)
+    }
+
+    /// 開いたファイル・フォルダを「最近」タブの履歴に記録して保存する
+    pub fn record_recent(&mut self, path: &Path) {
+        self.history_manager.add_entry(path);
+        if let Err(e) = self.history_manager.save() {
+            log::warn!("履歴の保存に失敗: {}", e);
+        }
+    }
+
+    /// ディレクトリ検索クエリでフィルタリングしたエントリ数を取得
+    fn filtered_directory_entry_count(&self) -> usize {
+        self.filtered_directory_entries().len()
+    }
+
+    /// ディレクトリブラウザを初期化
+    ///
+    /// フォルダごとのソート設定は前のブラウザから引き継ぎ、
+    /// 既定のソート方法は設定から適用します。
+    pub fn init_directory_browser(&mut self, path: PathBuf) -> std::io::Result<()> {
+        let mut sort_preferences = self.directory_browser
+            .as_ref()
+            .map(|browser| browser.sort_preferences().clone())
+            .unwrap_or_default();
+        let (key, order) = self.default_sort();
+        sort_preferences.set_default(key, order);
+
+        self.directory_browser = Some(DirectoryBrowser::with_cache(
+            path,
+            sort_preferences,
+            Some(std::sync::Arc::clone(&self.directory_cache)),
+        )?);
+        Ok(())
+    }
+
+    /// 結果を待っているバックグラウンドの処理があるか
+    pub fn has_pending_background_work(&self) -> bool {
+        self.directory_prewarmer.is_some()
+            || self.paste_task.is_some()
+            || self.recursive_search.as_ref().is_some_and(|task| task.is_pending())
+            || self.directory_cache_debouncer.is_pending()
+            || self.alias_watcher.as_ref().is_some_and(|watcher| watcher.is_reload_pending())
+    }
+
+    /// 次の再描画までの間隔
+    pub fn repaint_interval(&self) -> Duration {
+        next_repaint_interval(self.is_window_visible, self.has_pending_background_work())
+    }
+
+    /// 先読みするフォルダ（お気に入りのエイリアス、クイックアクセスの順に優先）
+    pub fn prewarm_candidates(&self) -> Vec<PathBuf> {
+        let favorites = self.file_aliases
+            .iter()
+            .filter(|alias| alias.is_favorite)
+            .map(|alias| crate::utils::path::expand_path(&alias.path));
+        let quick_access = self.quick_access_entries.iter().map(|entry| entry.path.clone());
+        prewarm_targets(favorites.chain(quick_access), PREWARM_LIMIT)
+    }
+
+    /// キャッシュにないフォルダの先読みをワーカースレッドで開始する
+    ///
+    /// 先読みが実行中の場合は何もしません。
+    pub fn start_directory_prewarm(&mut self) {
+        if self.directory_prewarmer.is_some() {
+            return;
+        }
+        let targets: Vec<PathBuf> = {
+            let Ok(cache) = self.directory_cache.lock() else {
+                return;
+            };
+            self.prewarm_candidates()
+                .into_iter()
+                .filter(|path| !cache.contains(path))
+                .collect()
+        };
+        if !targets.is_empty() {
+            log::debug!("{} 件のフォルダを先読みします", targets.len());
+            self.directory_prewarmer = Some(DirectoryPrewarmer::spawn(targets));
+        }
+    }
+
+    /// 先読みの結果とフォルダの変更を一覧のキャッシュに反映する
+    ///
+    /// 毎フレーム呼び出すことを想定しています。変更で取り除いたフォルダは、
+    /// 変更が落ち着いてから読み込み直します。
+    pub fn poll_directory_cache(&mut self) {
+        let Ok(mut cache) = self.directory_cache.lock() else {
+            return;
+        };
+
+        if let Some(prewarmer) = self.directory_prewarmer.as_mut() {
+            let (loaded, finished) = prewarmer.poll();
+            for (path, entries) in loaded {
+                if let Some(watcher) = self.directory_cache_watcher.as_mut() {
+                    watcher.watch(&path);
+                }
+                for evicted in cache.insert(path, entries) {
+                    if let Some(watcher) = self.directory_cache_watcher.as_mut() {
+                        watcher.unwatch(&evicted);
+                    }
+                }
+            }
+            if finished {
+                self.directory_prewarmer = None;
+            }
+        }
+
+        if let Some(watcher) = self.directory_cache_watcher.as_mut() {
+            let changed = watcher.poll();
+            let removed = cache.invalidate_changed(&changed);
+            for path in &removed {
+                watcher.unwatch(path);
+            }
+            if !removed.is_empty() {
+                self.directory_cache_debouncer.notify(Instant::now());
+            }
+        }
+        drop(cache);
+
+        if self.directory_cache_debouncer.ready(Instant::now()) {
+            self.start_directory_prewarm();
+        }
+    }
+
+    /// 操作履歴の設定（履歴数・ゴミ箱の使用）を反映する
+    pub fn apply_history_config(&mut self) {
+        let (history_limit, use_trash) = self.config
+            .as_ref()
+            .map(|c| (c.file_operations.history_limit, c.file_operations.use_trash))
+            .unwrap_or((OperationHistoryManager::DEFAULT_MAX_ENTRIES, true));
+        self.operation_history.set_max_entries(history_limit);
+        self.operation_history.set_use_trash(use_trash);
+    }
+
+    /// 検索設定を検索エンジンに反映する
+    pub fn apply_search_config(&mut self) {
+        let stale_penalty = self.config
+            .as_ref()
+            .map(|c| &c.search)
+            .filter(|s| s.stale_penalty_enabled)
+            .map(|s| StalePenalty {
+                threshold_days: s.stale_threshold_days,
+                penalty: s.stale_penalty,
+            });
+        self.search_engine.set_stale_penalty(stale_penalty);
+
+        let match_all_terms = self.config
+            .as_ref()
+            .is_some_and(|c| c.search.match_all_terms);
+        self.search_engine.set_match_all_terms(match_all_terms);
+
+        let (search_notes, search_reading) = self.config
+            .as_ref()
+            .map(|c| (c.search.search_notes, c.search.search_reading))
+            .unwrap_or((false, true));
+        self.search_engine.set_search_notes(search_notes);
+        self.search_engine.set_search_reading(search_reading);
+
+        // 「大文字小文字を区別」は正規化しない raw モードで検索する
+        let raw_mode = self.config
+            .as_ref()
+            .is_some_and(|c| c.search.case_sensitive);
+        self.search_engine.set_raw_mode(raw_mode);
+
+        // キー入力ごとの検索が1フレームを超えないよう、設定があれば走査時間を制限する
+        let time_budget = self.config
+            .as_ref()
+            .and_then(|c| c.search.search_time_budget_ms)
+            .map(std::time::Duration::from_millis);
+        self.search_engine.set_time_budget(time_budget);
+    }
+
+    /// 設定の既定のソート方法を取得（未設定・不正な値は名前の昇順）
+    pub fn default_sort(&self) -> (SortKey, SortOrder) {
+        self.config
+            .as_ref()
+            .map(|config| {
+                (
+                    SortKey::parse(&config.display.default_sort_key).unwrap_or_default(),
+                    SortOrder::parse(&config.display.default_sort_order).unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// ディレクトリ履歴を戻る/進む
+    ///
+    /// 移動した場合は検索バーをクリアします。
+    ///
+    /// # 戻り値
+    /// * `Ok(true)` - 移動した
+    /// * `Ok(false)` - 履歴の端、またはブラウザ未初期化のため移動しなかった
+    pub fn navigate_history(&mut self, navigation: HistoryNavigation) -> std::io::Result<bool> {
+        let Some(ref mut browser) = self.directory_browser else {
+            return Ok(false);
+        };
+
+        match navigation {
+            HistoryNavigation::Back if browser.can_go_back() => browser.go_back()?,
+            HistoryNavigation::Forward if browser.can_go_forward() => browser.go_forward()?,
+            _ => return Ok(false),
+        }
+
+        // 検索バーをクリア（設定によっては絞り込みを続ける）
+        self.update_directory_search_after_navigation();
+        Ok(true)
+    }
+
+    /// フォルダを移動した後に検索クエリを更新する
+    ///
+    /// 既定では検索クエリを消します。設定の `keep_filter_on_navigate` が有効な場合は
+    /// クエリを残して移動先の一覧をそのまま絞り込み、選択を絞り込み後の範囲内に収めます。
+    pub fn update_directory_search_after_navigation(&mut self) {
+        let keep_filter = self.config
+            .as_ref()
+            .is_some_and(|c| c.search.keep_filter_on_navigate);
+        if keep_filter {
+            self.selected_directory_index = clamp_selection(
+                self.selected_directory_index,
+                self.filtered_directory_entries().len(),
+            );
+        } else {
+            self.directory_search_query.clear();
+        }
+    }
+
+    /// 操作に失敗したパスを現在のフォルダのエラーバッジとして記録
+    pub fn record_failed_paths(&mut self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        let Some(dir) = self.directory_browser.as_ref().map(|b| b.current_path().to_path_buf()) else {
+            return;
+        };
+        self.failed_path_badges.record(&dir, paths, Instant::now());
+    }
+
+    /// ディレクトリを再読み込みし、存在しなくなったフォルダの展開状態を取り除く
+    pub fn reload_directory(&mut self) -> std::io::Result<()> {
+        if let Some(ref mut browser) = self.directory_browser {
+            browser.reload()?;
+        }
+        self.prune_expanded_directories();
+        Ok(())
+    }
+
+    /// 存在しなくなったフォルダを展開状態から取り除く
+    ///
+    /// # 戻り値
+    /// 取り除いた件数
+    pub fn prune_expanded_directories(&mut self) -> usize {
+        let before = self.expanded_directories.len();
+        self.expanded_directories.retain(|path| path.is_dir());
+        let pruned = before - self.expanded_directories.len();
+        if pruned > 0 {
+            log::debug!("存在しない展開状態を {} 件削除しました", pruned);
+        }
+        pruned
+    }
+
+    /// 名前変更・移動されたフォルダの展開状態を新しいパスに付け替える
+    ///
+    /// `from` 自身と配下の展開状態を `to` 以下に移します。
+    pub fn remap_expanded_directories(&mut self, from: &Path, to: &Path) {
+        let remapped: Vec<(PathBuf, PathBuf)> = self.expanded_directories
+            .iter()
+            .filter_map(|path| {
+                path.strip_prefix(from).ok().map(|rest| (path.clone(), to.join(rest)))
+            })
+            .collect();
+
+        for (old, new) in remapped {
+            self.expanded_directories.remove(&old);
+            self.expanded_directories.insert(new);
+        }
+    }
+
+    /// リネーム・移動されたパス配下を指すエイリアスとクイックアクセスを付け替える
+    ///
+    /// 保存は行わない。
+    ///
+    /// # 戻り値
+    /// (更新したエイリアスの件数, 更新したクイックアクセスの件数)
+    pub fn remap_alias_targets(&mut self, from: &Path, to: &Path) -> (usize, usize) {
+        let aliases = self.alias_manager.remap_paths(from, to);
+        if aliases > 0 {
+            self.apply_reloaded_aliases();
+        }
+        let quick_access = self.quick_access_manager.remap_paths(from, to);
+        if quick_access > 0 {
+            self.quick_access_entries = self.quick_access_manager.get_entries();
+        }
+        (aliases, quick_access)
+    }
+
+    /// リネーム・移動に合わせてエイリアスとクイックアクセスを付け替えて保存する
+    pub fn follow_renamed_path(&mut self, from: &Path, to: &Path) {
+        let (aliases, quick_access) = self.remap_alias_targets(from, to);
+        if aliases > 0 {
+            log::info!("{} 件のエイリアスのパスを更新: {} -> {}", aliases, from.display(), to.display());
+            if let Err(e) = self.alias_manager.save() {
+                self.report_save_error("エイリアス", e);
+            }
+        }
+        if quick_access > 0 {
+            if let Err(e) = self.quick_access_manager.save() {
+                self.report_save_error("クイックアクセス", e);
+            }
+        }
+    }
+
+    /// 最後の操作を取り消し、ディレクトリを再読み込みする
+    ///
+    /// 一部だけ取り消せた場合も、戻せなかった項目の警告を返す前に再読み込みします。
+    pub fn undo_operation(&mut self) -> Result<String, String> {
+        let redo_len = self.operation_history.redo_len();
+        let result = self.operation_history.undo();
+        if self.operation_history.redo_len() > redo_len {
+            self.refresh_after_history_change(true);
+        }
+        result
+    }
+
+    /// 取り消した操作をやり直し、ディレクトリを再読み込みする
+    pub fn redo_operation(&mut self) -> Result<String, String> {
+        let undo_len = self.operation_history.undo_len();
+        let result = self.operation_history.redo();
+        if self.operation_history.undo_len() > undo_len {
+            self.refresh_after_history_change(false);
+        }
+        result
+    }
+
+    /// トップバーの「元に戻す」ボタンの表示状態
+    pub fn undo_button_state(&self) -> HistoryButtonState {
+        HistoryButtonState::new(
+            "元に戻す",
+            self.operation_history.undo_len(),
+            self.operation_history.last_done(),
+        )
+    }
+
+    /// トップバーの「やり直す」ボタンの表示状態
+    pub fn redo_button_state(&self) -> HistoryButtonState {
+        HistoryButtonState::new(
+            "やり直す",
+            self.operation_history.redo_len(),
+            self.operation_history.last_undone(),
+        )
+    }
+
+    /// Undo/Redo の後にディレクトリを再読み込みし、展開状態を追従させる
+    ///
+    /// # 引数
+    /// * `undone` - Undo の後なら true、Redo の後なら false
+    pub fn refresh_after_history_change(&mut self, undone: bool) {
+        let operation = if undone {
+            self.operation_history.last_undone()
+        } else {
+            self.operation_history.last_done()
+        };
+
+        // 取り消しの場合は操作と逆向きにパスが変わる
+        let moved = match operation {
+            Some(FileOperation::Rename { old_path, new_path }) => vec![(old_path.clone(), new_path.clone())],
+            Some(FileOperation::Move { moved }) => moved.clone(),
+            _ => Vec::new(),
+        };
+        for (before, after) in moved {
+            let (from, to) = if undone { (after, before) } else { (before, after) };
+            self.remap_expanded_directories(&from, &to);
+            self.follow_renamed_path(&from, &to);
+        }
+
+        if let Err(e) = self.reload_directory() {
+            log::error!("ディレクトリリロード失敗: {}", e);
+        }
+    }
+
+    /// 現在表示すべきエントリを取得
+    pub fn get_current_entries(&self) -> Vec<crate::data::models::DirectoryEntry> {
+        if let Some(ref browser) = self.directory_browser {
+            browser.entries().to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// ディレクトリモードで選択中のエントリを取得
+    ///
+    /// ディレクトリ検索クエリでフィルタリングした一覧から選択インデックスのエントリを返す
+    pub fn selected_directory_entry(&self) -> Option<crate::data::models::DirectoryEntry> {
+        let idx = self.selected_directory_index?;
+        self.filtered_directory_entries().into_iter().nth(idx)
+    }
+
+    /// ディレクトリ検索クエリでフィルタリングした一覧のパスを表示順で取得
+    pub fn visible_directory_paths(&self) -> Vec<PathBuf> {
+        self.filtered_directory_entries()
+            .into_iter()
+            .map(|e| e.path)
+            .collect()
+    }
+
+    /// ディレクトリ一覧に表示するエントリを表示順で取得
+    ///
+    /// 再帰検索中は配下から見つかった項目（名前は相対パス）をスコア順に、
+    /// それ以外は現在のフォルダのエントリを検索クエリで絞り込んで返す
+    pub fn filtered_directory_entries(&self) -> Vec<crate::data::models::DirectoryEntry> {
+        if let Some(task) = self.active_recursive_search() {
+            return task.results().iter().map(|m| m.entry.clone()).collect();
+        }
+
+        let entries = self.get_current_entries();
+        if self.directory_search_query.is_empty() {
+            return entries;
+        }
+        let query = self.directory_search_query.to_lowercase();
+        entries.into_iter()
+            .filter(|e| e.name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// 現在のフォルダ・検索クエリに対応する再帰検索
+    fn active_recursive_search(&self) -> Option<&crate::core::recursive_search::RecursiveSearchTask> {
+        let query = crate::core::recursive_search::parse_recursive_query(&self.directory_search_query)?;
+        let browser = self.directory_browser.as_ref()?;
+        self.recursive_search
+            .as_ref()
+            .filter(|task| task.is_for(browser.current_path(), query))
+    }
+
+    /// ディレクトリ一覧が再帰検索の結果を表示しているか
+    pub fn is_recursive_search_active(&self) -> bool {
+        self.active_recursive_search().is_some()
+    }
+
+    /// 再帰検索の条件を設定から作成（設定が読み込まれていない場合は深さ 8・除外なし）
+    fn recursive_search_options(&self) -> crate::core::recursive_search::RecursiveSearchOptions {
+        crate::core::recursive_search::RecursiveSearchOptions {
+            max_depth: self.config.as_ref().map_or(8, |c| c.search.recursive_max_depth).max(1),
+            ignore: self.config.as_ref().map(|c| c.search.recursive_ignore.clone()).unwrap_or_default(),
+            show_hidden: self.directory_browser.as_ref().is_some_and(|b| b.show_hidden()),
+            ..Default::default()
+        }
+    }
+
+    /// 検索クエリに合わせて再帰検索を開始・取り消しし、結果を受け取る
+    ///
+    /// 毎フレーム呼び出すことを想定しています。クエリやフォルダが変わった場合は
+    /// 前の検索を取り消して新しく始めます。
+    ///
+    /// # 戻り値
+    /// この呼び出しで検索結果が届いた場合は true
+    pub fn update_recursive_search(&mut self) -> bool {
+        let query = crate::core::recursive_search::parse_recursive_query(&self.directory_search_query)
+            .map(str::to_string);
+        let root = self.directory_browser.as_ref().map(|b| b.current_path().to_path_buf());
+        let (Some(query), Some(root)) = (query, root) else {
+            self.recursive_search = None;
+            return false;
+        };
+
+        if !self.recursive_search.as_ref().is_some_and(|task| task.is_for(&root, &query)) {
+            log::debug!("再帰検索を開始: {} ({})", query, root.display());
+            let options = self.recursive_search_options();
+            self.recursive_search = Some(crate::core::recursive_search::RecursiveSearchTask::spawn(
+                root, query, options,
+            ));
+        }
+        self.recursive_search.as_mut().is_some_and(|task| task.poll())
+    }
+
+    /// 再帰検索の結果の項目を、それがあるフォルダで表示する
+    ///
+    /// 親フォルダへ移動して検索クエリを消し、項目を選択します。
+    pub fn reveal_directory_entry(&mut self, path: &Path) -> Result<(), String> {
+        let parent = path
+            .parent()
+            .ok_or_else(|| format!("親フォルダがありません: {}", path.display()))?
+            .to_path_buf();
+        let browser = self.directory_browser
+            .as_mut()
+            .ok_or_else(|| "ディレクトリブラウザが初期化されていません".to_string())?;
+        browser.navigate_to(parent)
+            .map_err(|e| format!("フォルダを開けません: {}", e))?;
+
+        self.directory_search_query.clear();
+        self.recursive_search = None;
+        self.clear_directory_selection();
+        self.selected_directory_index = self.visible_directory_paths()
+            .iter()
+            .position(|p| paths_equal(p, path));
+        Ok(())
+    }
+
+    /// 検索クエリに基づいてエイリアスをフィルタリング
+    pub fn filter_aliases(&mut self) {
+        if self.search_query.is_empty() {
+            self.filtered_items = self.file_aliases.clone();
+            self.alias_search_truncated = false;
+        } else {
+            // SearchEngineを使用した高度な検索
+            let results = self.search_engine.search(&self.search_query);
+            self.alias_search_truncated = self.search_engine.truncated_by_budget();
+            if self.alias_search_truncated {
+                log::debug!("検索が時間内に終わらず、途中までの結果を表示: {}", self.search_query);
+            }
+
+            // SearchResultからFileAliasに変換
+            // スコア順にソートされているので、その順序を維持
+            self.filtered_items = results
+                .into_iter()
+                .map(|result| result.alias)
+                .collect();
+        }
+    }
+
+    /// 途中までの結果になった検索を、時間の制限なしで最後まで走査し直す
+    pub fn complete_alias_search(&mut self) {
+        let time_budget = self.search_engine.time_budget();
+        self.search_engine.set_time_budget(None);
+        self.filter_aliases();
+        self.search_engine.set_time_budget(time_budget);
+    }
+
+    /// エイリアスファイルの外部変更を確認し、必要なら再読み込みする
+    ///
+    /// # 戻り値
+    /// エイリアスが再読み込みされ、内容が変化した場合は true
+    pub fn poll_alias_file_changes(&mut self) -> bool {
+        let changed = self.alias_watcher
+            .as_mut()
+            .map(|watcher| watcher.poll())
+            .unwrap_or(false);
+
+        if !changed {
+            return false;
+        }
+
+        if let Err(e) = self.alias_manager.load() {
+            log::error!("エイリアスの再読み込みに失敗: {}", e);
+            return false;
+        }
+
+        self.check_duplicate_aliases();
+        self.check_broken_aliases();
+        self.apply_reloaded_aliases()
+    }
+
+    /// パスが見つからないエイリアスを確認して印を付ける
+    ///
+    /// パスは開くときと同じく環境変数の展開・ボリュームの追従をしたうえで確認します。
+    /// 設定の `network_path_as_unknown` が有効な場合、見つからないネットワークパスは
+    /// 壊れているとせず「確認できない」として印を付けます。
+    /// 表示に反映するには続けて `refresh_after_alias_change` を呼び出してください。
+    ///
+    /// # 戻り値
+    /// パスが見つからないエイリアスの件数（確認できないものは含まない）
+    pub fn check_broken_aliases(&mut self) -> usize {
+        let network_as_unknown = self.config
+            .as_ref()
+            .is_none_or(|c| c.display.network_path_as_unknown);
+        let missing = self.alias_manager.validate_all_with(|alias| {
+            crate::platform::resolve_alias_path(
+                &alias.path,
+                alias.volume.as_ref(),
+                &crate::platform::SystemDriveEnumerator,
+            )
+        });
+        let (unreachable, broken): (Vec<_>, Vec<_>) = missing
+            .into_iter()
+            .partition(|(_, path)| network_as_unknown && crate::utils::path::is_network_path(path));
+        let ids = |pairs: Vec<(String, PathBuf)>| -> Vec<String> {
+            pairs.into_iter().map(|(id, _)| id).collect()
+        };
+
+        let broken_count = self.alias_manager.mark_broken(&ids(broken));
+        let unreachable_count = self.alias_manager.mark_unreachable(&ids(unreachable));
+        if broken_count > 0 {
+            log::warn!("パスが見つからないエイリアスがあります: {} 件", broken_count);
+        } else {
+            log::debug!("すべてのエイリアスのパスを確認しました");
+        }
+        if unreachable_count > 0 {
+            log::info!("接続できないネットワークパスのエイリアスがあります: {} 件", unreachable_count);
+        }
+        broken_count
+    }
+
+    /// 名前が重複しているエイリアスを確認し、あれば解決ダイアログを開く
+    ///
+    /// 重複がなくなった場合はダイアログを閉じます。
+    pub fn check_duplicate_aliases(&mut self) {
+        let duplicates = self.alias_manager.find_duplicate_names();
+        if duplicates.is_empty() {
+            self.duplicate_alias_dialog = None;
+            return;
+        }
+
+        log::warn!("名前が重複しているエイリアスがあります: {} 件", duplicates.len());
+
+        let aliases = self.alias_manager.get_aliases();
+        let entries = duplicates
+            .iter()
+            .flat_map(|(_, ids)| ids.iter())
+            .filter_map(|id| aliases.iter().find(|a| &a.id == id))
+            .map(|alias| DuplicateAliasEntry {
+                id: alias.id.clone(),
+                current_name: alias.alias.clone(),
+                new_name: alias.alias.clone(),
+                path: alias.path.clone(),
+            })
+            .collect();
+
+        self.duplicate_alias_dialog = Some(DuplicateAliasDialog { entries });
+    }
+
+    /// 重複しているエイリアスの名前を変更する
+    ///
+    /// # 引数
+    /// * `id` - 対象のエイリアスID
+    /// * `new_name` - 新しい名前（他のエイリアスと重複しないこと）
+    pub fn rename_duplicate_alias(&mut self, id: &str, new_name: &str) -> Result<(), String> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err("名前を入力してください".to_string());
+        }
+        if self.alias_manager.get_aliases().iter().any(|a| a.alias == new_name && a.id != id) {
+            return Err(format!("エイリアス '{}' は既に存在します", new_name));
+        }
+
+        self.alias_manager.update_alias(id, Some(new_name.to_string()), None, None, None, None)?;
+        self.finish_duplicate_resolution()
+    }
+
+    /// 重複しているエイリアスを削除する
+    pub fn delete_duplicate_alias(&mut self, id: &str) -> Result<(), String> {
+        self.alias_manager.remove_alias_by_id(id)?;
+        self.finish_duplicate_resolution()
+    }
+
+    /// 重複解決の変更を保存して表示に反映する
+    fn finish_duplicate_resolution(&mut self) -> Result<(), String> {
+        self.alias_manager.save()
+            .map_err(|e| format!("エイリアスの保存に失敗: {}", e))?;
+        self.apply_reloaded_aliases();
+        self.check_duplicate_aliases();
+        Ok(())
+    }
+
+    /// 追加しようとしているパスを既に指しているエイリアスがある場合の警告
+    pub fn new_alias_duplicate_warning(&self) -> Option<String> {
+        let path = self.new_alias_path.trim();
+        if path.is_empty() {
+            return None;
+        }
+        self.alias_manager
+            .find_by_path(Path::new(path))
+            .map(|existing| format!("エイリアス「{}」が同じパスを指しています", existing.alias))
+    }
+
+    /// エイリアス編集ダイアログを開く
+    pub fn open_alias_edit_dialog(&mut self, id: &str) -> Result<(), String> {
+        let alias = self.alias_manager
+            .get_aliases()
+            .iter()
+            .find(|a| a.id == id)
+            .ok_or_else(|| format!("エイリアスID '{}' は存在しません", id))?;
+        self.alias_edit_dialog = Some(AliasEditDialog::new(alias));
+        Ok(())
+    }
+
+    /// エイリアス編集ダイアログの内容をエイリアスに反映する
+    ///
+    /// 成功した場合はダイアログを閉じて表示を更新します（保存は呼び出し元で行う）。
+    /// 失敗した場合は理由をダイアログに表示し、ダイアログは開いたままにします。
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - 更新後のエイリアス名
+    /// * `Err(String)` - エラーメッセージ
+    pub fn apply_alias_edit(&mut self) -> Result<String, String> {
+        let Some(dialog) = self.alias_edit_dialog.as_mut() else {
+            return Err("編集中のエイリアスがありません".to_string());
+        };
+
+        let result = update_alias_from_dialog(&mut self.alias_manager, dialog);
+        match &result {
+            Ok(_) => {
+                self.alias_edit_dialog = None;
+                self.check_broken_aliases();
+                self.refresh_after_alias_change();
+            }
+            Err(e) => dialog.error = Some(e.clone()),
+        }
+        result
+    }
+
+    /// 壊れたエイリアスの修復ダイアログを開く
+    pub fn open_alias_repair_dialog(&mut self, id: &str) -> Result<(), String> {
+        let alias = self.alias_manager
+            .get_aliases()
+            .iter()
+            .find(|a| a.id == id)
+            .ok_or_else(|| format!("エイリアスID '{}' は存在しません", id))?;
+        self.alias_repair_dialog = Some(AliasRepairDialog::new(alias));
+        Ok(())
+    }
+
+    /// 修復ダイアログで入力したパスにエイリアスを付け替える
+    ///
+    /// 成功した場合はダイアログを閉じて表示を更新します（保存は呼び出し元で行う）。
+    /// 新しいパスが見つからない場合は付け替えず、理由をダイアログに表示します。
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - エイリアス名
+    /// * `Err(String)` - エラーメッセージ
+    pub fn apply_alias_repair(&mut self) -> Result<String, String> {
+        let Some(dialog) = self.alias_repair_dialog.as_mut() else {
+            return Err("修復中のエイリアスがありません".to_string());
+        };
+
+        let new_path = PathBuf::from(dialog.new_path.trim());
+        let result = if dialog.new_path.trim().is_empty() {
+            Err("パスを入力してください".to_string())
+        } else if !crate::utils::path::expand_path(&new_path).exists() {
+            Err(format!("パスが見つかりません: {}", new_path.display()))
+        } else {
+            self.alias_manager
+                .update_alias(&dialog.id, None, Some(new_path.clone()), None, None, None)
+                .and_then(|()| {
+                    let volume = crate::platform::volume_for_path(&new_path, &crate::platform::SystemDriveEnumerator);
+                    self.alias_manager.set_volume(&dialog.alias_name, volume)
+                })
+                .map(|()| dialog.alias_name.clone())
+        };
+
+        match &result {
+            Ok(_) => {
+                self.alias_repair_dialog = None;
+                self.check_broken_aliases();
+                self.refresh_after_alias_change();
+            }
+            Err(e) => dialog.error = Some(e.clone()),
+        }
+        result
+    }
+
+    /// 修復ダイアログのエイリアスを削除する
+    ///
+    /// ダイアログを閉じて表示を更新します（保存は呼び出し元で行う）。
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - 削除したエイリアス名
+    /// * `Err(String)` - エラーメッセージ
+    pub fn delete_repaired_alias(&mut self) -> Result<String, String> {
+        let dialog = self.alias_repair_dialog
+            .take()
+            .ok_or_else(|| "修復中のエイリアスがありません".to_string())?;
+        self.alias_manager.remove_alias_by_id(&dialog.id)?;
+        self.refresh_after_alias_change();
+        Ok(dialog.alias_name)
+    }
+
+    /// プロファイルのインポート確認ダイアログを開く
+    ///
+    /// インポートした場合の変更内容を確認するだけで、ファイルは書き換えません。
+    pub fn open_profile_import_dialog(&mut self, path: &Path) -> Result<(), String> {
+        let preview = crate::core::profile::preview_bundle(path)?;
+        self.profile_import_dialog = Some(ProfileImportDialog {
+            path: path.to_path_buf(),
+            preview,
+        });
+        Ok(())
+    }
+
+    /// 確認ダイアログのプロファイルをインポートし、設定・エイリアス・クイックアクセスを読み込み直す
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - 結果メッセージ
+    /// * `Err(String)` - エラーメッセージ
+    pub fn apply_profile_import(&mut self) -> Result<String, String> {
+        let dialog = self.profile_import_dialog
+            .take()
+            .ok_or_else(|| "インポートするプロファイルがありません".to_string())?;
+        let changes = crate::core::profile::import_bundle(&dialog.path)?;
+
+        self.load_config()
+            .map_err(|e| format!("設定の読み込みに失敗: {}", e))?;
+        self.alias_manager.load()
+            .map_err(|e| format!("エイリアスの読み込みに失敗: {}", e))?;
+        self.check_broken_aliases();
+        self.refresh_after_alias_change();
+        self.load_quick_access()
+            .map_err(|e| format!("クイックアクセスの読み込みに失敗: {}", e))?;
+
+        let updated = changes
+            .iter()
+            .filter(|(_, change)| *change != crate::core::profile::FileChange::Unchanged)
+            .count();
+        Ok(format!("プロファイルをインポートしました（{} 個のファイルを更新）", updated))
+    }
+
+    /// エイリアスを1つ前（`up` が true の場合）または後ろに移動する
+    ///
+    /// 表示を更新し、移動したエイリアスを選択したままにします。保存は呼び出し元で行います。
+    pub fn move_alias(&mut self, id: &str, up: bool) -> Result<(), String> {
+        if up {
+            self.alias_manager.move_up(id)?;
+        } else {
+            self.alias_manager.move_down(id)?;
+        }
+        self.refresh_after_alias_change();
+        if let Some(index) = self.filtered_items.iter().position(|a| a.id == id) {
+            self.selected_index = Some(index);
+        }
+        Ok(())
+    }
+
+    /// エイリアスを開いたことを記録して保存する
+    ///
+    /// 開いた回数と最終アクセス日時は「よく使う」一覧と検索スコアに使われます。
+    pub fn record_alias_open(&mut self, id: &str) {
+        if let Err(e) = self.alias_manager.record_open(id) {
+            log::warn!("エイリアスの利用記録に失敗: {}", e);
+            return;
+        }
+        if let Err(e) = self.alias_manager.save() {
+            self.report_save_error("エイリアス", e);
+        }
+        self.apply_reloaded_aliases();
+    }
+
+    /// AliasManager の内容を表示用の状態に反映する
+    ///
+    /// `file_aliases`・検索エンジン・`filtered_items` を更新し、
+    /// 現在の検索クエリで再フィルタリングします。
+    ///
+    /// # 戻り値
+    /// 内容が変化した場合は true（自身の保存による通知では false）
+    pub fn apply_reloaded_aliases(&mut self) -> bool {
+        let aliases = self.alias_manager.get_aliases();
+        if aliases == self.file_aliases.as_slice() {
+            return false;
+        }
+
+        self.refresh_after_alias_change();
+        log::info!("エイリアスを再読み込みしました（{} 件）", self.file_aliases.len());
+        true
+    }
+
+    /// エイリアスの追加・削除・変更の後に表示用の状態を更新する
+    ///
+    /// AliasManager の内容を `file_aliases` と検索エンジンに反映し、現在の検索クエリで
+    /// 検索し直したうえで、`selected_index` を新しい一覧の範囲内に収めます
+    /// （一覧が空になった場合は選択を解除します）。
+    pub fn refresh_after_alias_change(&mut self) {
+        self.file_aliases = self.alias_manager.get_aliases().to_vec();
+        self.search_engine.set_aliases(self.file_aliases.clone());
+        self.filter_aliases();
+        self.selected_index = clamp_selection(self.selected_index, self.filtered_items.len());
+    }
+
+    /// クイックアクセスを読み込む
+    pub fn load_quick_access(&mut self) -> anyhow::Result<()> {
+        self.quick_access_manager.load()?;
+        self.quick_access_entries = self.quick_access_manager.get_entries();
+        Ok(())
+    }
+
+    /// クイックアクセスにエントリを追加
+    pub fn add_to_quick_access(&mut self, name: String, path: PathBuf) -> Result<(), String> {
+        self.quick_access_manager.add_entry(name, path)?;
+        self.quick_access_manager.save()
+            .map_err(|e| format!("保存失敗: {}", e))?;
+        self.quick_access_entries = self.quick_access_manager.get_entries();
+        Ok(())
+    }
+
+    /// ドロップされたフォルダをクイックアクセスに追加
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - 追加したエントリ名
+    /// * `Err(String)` - フォルダ以外がドロップされた場合や追加に失敗した場合
+    pub fn add_dropped_to_quick_access(&mut self, path: &Path) -> Result<String, String> {
+        let (name, path) = crate::core::quick_access::entry_for_dropped_path(path)?;
+        self.add_to_quick_access(name.clone(), path)?;
+        Ok(name)
+    }
+
+    /// 選択した項目の親フォルダをクイックアクセスに追加
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - 追加したエントリ名
+    /// * `Err(String)` - 親フォルダがない場合や追加に失敗した場合
+    pub fn add_parent_to_quick_access(&mut self, path: &Path) -> Result<String, String> {
+        let (name, parent) = crate::core::quick_access::entry_for_parent_folder(path)?;
+        self.add_to_quick_access(name.clone(), parent)?;
+        Ok(name)
+    }
+
+    /// クイックアクセスのエントリ名を変更
+    pub fn rename_quick_access(&mut self, id: &str, new_name: &str) -> Result<(), String> {
+        self.quick_access_manager.rename_entry(id, new_name)?;
+        self.quick_access_manager.save()
+            .map_err(|e| format!("保存失敗: {}", e))?;
+        self.quick_access_entries = self.quick_access_manager.get_entries();
+        Ok(())
+    }
+
+    /// クイックアクセスからエントリを削除
+    pub fn remove_from_quick_access(&mut self, id: &str) -> Result<(), String> {
+        self.quick_access_manager.remove_entry_by_id(id)?;
+        self.quick_access_manager.save()
+            .map_err(|e| format!("保存失敗: {}", e))?;
+        self.quick_access_entries = self.quick_access_manager.get_entries();
+        Ok(())
+    }
+}
+
+impl AppState {
+    /// コマンドパレットのアクションを実行
+    ///
+    /// # 引数
+    /// * `action` - 実行するアクション
+    ///
+    /// # 戻り値
+    /// * `Ok(String)` - 結果メッセージ
+    /// * `Err(String)` - エラーメッセージ
+    pub fn dispatch_palette_action(&mut self, action: PaletteAction) -> Result<String, String> {
+        log::info!("コマンドパレット: {:?} を実行", action);
+
+        match action {
+            PaletteAction::NewFolder => {
+                let current_path = self.directory_browser
+                    .as_ref()
+                    .filter(|_| self.browse_mode == BrowseMode::Directory)
+                    .map(|browser| browser.current_path().to_path_buf())
+                    .ok_or_else(|| "ディレクトリモードでのみ実行できます".to_string())?;
+
+                let created = crate::core::file_manager::FileManager::new()
+                    .create_directory(&current_path, "新しいフォルダ")?;
+
+                let _ = self.reload_directory();
+
+                let name = created.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Ok(format!("フォルダ「{}」を作成しました", name))
+            }
+            PaletteAction::ToggleTheme => {
+                let new_theme = match self.current_theme {
+                    Theme::Dark => Theme::Light,
+                    Theme::Light => Theme::Dark,
+                };
+
+                let config = self.config
+                    .as_mut()
+                    .ok_or_else(|| "設定が読み込まれていません".to_string())?;
+                config.theme.mode = new_theme.to_string().to_string();
+                self.current_theme = new_theme;
+
+                Ok(format!("テーマを {} に切り替えました", new_theme.to_string()))
+            }
+            PaletteAction::OpenSettings => {
+                self.show_settings = true;
+                Ok("設定を開きました".to_string())
+            }
+            PaletteAction::Compress => {
+                let entry = self.selected_directory_entry()
+                    .ok_or_else(|| "圧縮する項目が選択されていません".to_string())?;
+
+                let parent = entry.path.parent()
+                    .ok_or_else(|| "親ディレクトリが見つかりません".to_string())?;
+                let dest = parent.join(format!("{}.zip", entry.name));
+
+                crate::core::file_manager::FileManager::new().compress(&entry.path, &dest)?;
+
+                let _ = self.reload_directory();
+
+                Ok(format!("「{}」を圧縮しました", entry.name))
+            }
+            PaletteAction::ExportAliases => {
+                let dest_dir = dirs::document_dir()
+                    .or_else(dirs::home_dir)
+                    .ok_or_else(|| "エクスポート先が見つかりません".to_string())?;
+                let dest = dest_dir.join(format!(
+                    "ofkt_aliases_{}.json",
+                    chrono::Local::now().format("%Y%m%d_%H%M%S")
+                ));
+
+                crate::data::storage::export_aliases(self.alias_manager.get_aliases(), &dest)
+                    .map_err(|e| format!("エクスポート失敗: {}", e))?;
+
+                Ok(format!("エイリアスを {} にエクスポートしました", dest.display()))
+            }
+            PaletteAction::ExportProfile => {
+                let dest_dir = dirs::document_dir()
+                    .or_else(dirs::home_dir)
+                    .ok_or_else(|| "エクスポート先が見つかりません".to_string())?;
+                let dest = dest_dir.join(format!(
+                    "ofkt_profile_{}.zip",
+                    chrono::Local::now().format("%Y%m%d_%H%M%S")
+                ));
+
+                crate::core::profile::export_bundle(&dest)?;
+
+                Ok(format!("プロファイルを {} にエクスポートしました", dest.display()))
+            }
+            PaletteAction::ImportProfile => {
+                let entry = self.selected_directory_entry()
+                    .filter(|entry| entry.is_file() && crate::core::activation::is_archive_file(&entry.path))
+                    .ok_or_else(|| "インポートするプロファイル（ZIPファイル）を選択してください".to_string())?;
+
+                self.open_profile_import_dialog(&entry.path)?;
+
+                Ok("インポートする内容を確認してください".to_string())
+            }
+            PaletteAction::SwitchBrowseMode => {
+                self.switch_to_next_browse_mode();
+                self.current_focus_area = FocusArea::Main;
+
+                Ok(format!("モードを切り替えました: {:?}", self.browse_mode))
+            }
+            PaletteAction::AddAlias => {
+                self.show_add_alias_dialog = true;
+                self.add_alias_path_warning = None;
+                Ok("エイリアス追加ダイアログを開きました".to_string())
+            }
+            PaletteAction::ValidateAliases => {
+                let broken = self.check_broken_aliases();
+                self.refresh_after_alias_change();
+                let unreachable = self.file_aliases.iter().filter(|a| a.is_unreachable).count();
+                let mut message = format!("パスが見つからないエイリアス: {} 件", broken);
+                if unreachable > 0 {
+                    message.push_str(&format!("（確認できないネットワークパス: {} 件）", unreachable));
+                }
+                Ok(message)
+            }
+            PaletteAction::Undo => self.undo_operation(),
+            PaletteAction::Redo => self.redo_operation(),
+            PaletteAction::Reload => {
+                if self.directory_browser.is_none() {
+                    return Err("ディレクトリブラウザが初期化されていません".to_string());
+                }
+                self.reload_directory().map_err(|e| format!("再読み込み失敗: {}", e))?;
+                self.failed_path_badges.clear();
+                Ok("再読み込みしました".to_string())
+            }
+            PaletteAction::ToggleAlwaysOnTop => {
+                let config = self.config
+                    .as_mut()
+                    .ok_or_else(|| "設定が読み込まれていません".to_string())?;
+                config.window.always_on_top = !config.window.always_on_top;
+
+                if config.window.always_on_top {
+                    Ok("常に最前面に表示します".to_string())
+                } else {
+                    Ok("最前面表示を解除しました".to_string())
+                }
+            }
+        }
+    }
+}
+
+/// ウィンドウ表示中の再描画間隔（ホットキーやトレイのイベントを拾うため）
+pub const ACTIVE_REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// ウィンドウ非表示で処理待ちもない場合の再描画間隔
+pub const IDLE_REPAINT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 次の再描画までの間隔を決める
+///
+/// 非表示の間はホットキーとトレイのイベントを拾えれば十分なため間隔を延ばし、
+/// CPU 使用率を抑えます。バックグラウンドの処理を待っている間は表示中と同じ間隔にします。
+///
+/// # 引数
+/// * `visible` - ウィンドウが表示されているか
+/// * `busy` - 結果を待っているバックグラウンドの処理があるか
+pub fn next_repaint_interval(visible: bool, busy: bool) -> Duration {
+    if visible || busy {
+        ACTIVE_REPAINT_INTERVAL
+    } else {
+        IDLE_REPAINT_INTERVAL
+    }
+}
+
+/// 「最近」タブに表示する履歴の件数
+const RECENT_TAB_LIMIT: usize = 10;
+
+/// 選択インデックスを一覧の範囲内に収める
+fn clamp_selection(index: Option<usize>, len: usize) -> Option<usize> {
+    let index = index?;
+    if len == 0 {
+        None
+    } else {
+        Some(index.min(len - 1))
+    }
+}
+
+/// 選択した項目への操作結果のメッセージを作成
+///
+/// 1 件なら「名前」、複数なら件数を示します。
+///
+/// # 引数
+/// * `paths` - 操作した項目のパス
+/// * `done` - 操作を表す語句（「コピーしました」など）
+///
+/// # 戻り値
+/// メッセージ（項目がない場合は None）
+pub fn selection_summary(paths: &[PathBuf], done: &str) -> Option<String> {
+    match paths {
+        [] => None,
+        [path] => {
+            let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy();
+            Some(format!("「{}」を{}", name, done))
+        }
+        _ => Some(format!("{} 件を{}", paths.len(), done)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::*;
+
+    /// テスト用のデフォルト設定を作成
+    fn create_test_config(enabled: bool, modifiers: Vec<String>, key: String) -> Config {
+        Config {
+            version: "1.0.0".to_string(),
+            window: WindowConfig {
+                width: 800.0,
+                height: 600.0,
+                position: WindowPosition { x: 0.0, y: 0.0 },
+                always_on_top: false,
+                decorations: true,
+                double_escape_ms: 400,
+            },
+            hotkey: HotkeyConfig {
+                enabled,
+                modifiers,
+                key,
+            },
+            edge_trigger: EdgeTriggerConfig {
+                enabled: false,
+                edge: "top".to_string(),
+                delay_ms: 500,
+                trigger_width: 10,
+            },
+            autostart: AutostartConfig { enabled: false },
+            theme: ThemeConfig {
+                mode: "light".to_string(),
+                custom_accent_color: None,
+            },
+            search: SearchConfig {
+                incremental: true,
+                fuzzy_match: false,
+                search_paths: true,
+                search_aliases: true,
+                case_sensitive: false,
+                remember_last_query: false,
+                last_alias_query: None,
+                last_directory_query: None,
+                stale_penalty_enabled: false,
+                stale_threshold_days: 180,
+                stale_penalty: 0.2,
+                match_all_terms: false,
+                search_notes: false,
+                search_reading: true,
+                recursive_max_depth: 8,
+                recursive_ignore: vec!["node_modules".to_string(), ".git".to_string()],
+                keep_filter_on_navigate: false,
+                search_time_budget_ms: None,
+            },
+            file_operations: FileOperationConfig {
+                confirm_delete: true,
+                use_trash: true,
+                default_open_action: "open".to_string(),
+                preserve_structure: false,
+                large_paste_threshold: 1000,
+                copy_name_pattern: "{name} (コピー){ext}".to_string(),
+                conflict_policy: "overwrite".to_string(),
+                history_limit: 50,
+                preserve_attributes: false,
+            },
+            display: DisplayConfig::default(),
+            keybindings: KeybindingConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_register_configured_hotkey_success() {
+        // 正常系: 有効な設定でホットキー登録が成功
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(
+            true,
+            vec!["Ctrl".to_string(), "Shift".to_string()],
+            "O".to_string(),
+        ));
+
+        let result = state.register_configured_hotkey();
+        assert!(result.is_ok(), "ホットキー登録が失敗しました: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_register_configured_hotkey_disabled() {
         // ホットキー無効時: enabled=false で何も登録されず Ok が返される
         let mut state = AppState::default();
-        state.config = Some(create_test_config(
-            false,
-            vec!["Ctrl".to_string()],
-            "O".to_string(),
-        ));
+        state.config = Some(create_test_config(
+            false,
+            vec!["Ctrl".to_string()],
+            "O".to_string(),
+        ));
+
+        let result = state.register_configured_hotkey();
+        assert!(result.is_ok(), "ホットキーが無効でもOkを返すべき: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_register_configured_hotkey_invalid_modifier() {
+        // 異常系: 無効な修飾キーでエラーが返される
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(
+            true,
+            vec!["InvalidModifier".to_string()],
+            "O".to_string(),
+        ));
+
+        let result = state.register_configured_hotkey();
+        assert!(result.is_err(), "無効な修飾キーでエラーを返すべき");
+        assert!(
+            result.unwrap_err().contains("修飾キーの変換に失敗"),
+            "エラーメッセージに「修飾キーの変換に失敗」を含むべき"
+        );
+    }
+
+    #[test]
+    fn test_register_configured_hotkey_invalid_key() {
+        // 異常系: 無効なキーコードでエラーが返される
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(
+            true,
+            vec!["Ctrl".to_string()],
+            "InvalidKey".to_string(),
+        ));
+
+        let result = state.register_configured_hotkey();
+        assert!(result.is_err(), "無効なキーでエラーを返すべき");
+        assert!(
+            result.unwrap_err().contains("キーコードの変換に失敗"),
+            "エラーメッセージに「キーコードの変換に失敗」を含むべき"
+        );
+    }
+
+    #[test]
+    fn test_register_configured_hotkey_no_config() {
+        // 異常系: config が None の場合にエラーが返される
+        let mut state = AppState::default();
+        state.config = None;
+
+        let result = state.register_configured_hotkey();
+        assert!(result.is_err(), "設定がない場合はエラーを返すべき");
+        assert!(
+            result.unwrap_err().contains("設定が読み込まれていません"),
+            "エラーメッセージに「設定が読み込まれていません」を含むべき"
+        );
+    }
+
+    #[test]
+    fn test_hotkey_registered_false_when_registration_fails() {
+        // HotkeyManager が使えない状態では設定・デフォルトとも登録できない
+        let mut state = AppState::default();
+        state.hotkey_manager = None;
+        state.config = Some(create_test_config(
+            true,
+            vec!["Ctrl".to_string(), "Shift".to_string()],
+            "O".to_string(),
+        ));
+
+        assert!(!state.register_hotkey_with_fallback());
+        assert!(!state.hotkey_registered);
+
+        state.initialized = true;
+        assert!(state.hotkey_warning_visible());
+    }
+
+    #[test]
+    fn test_hotkey_registered_true_on_success() {
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(
+            true,
+            vec!["Ctrl".to_string(), "Alt".to_string()],
+            "K".to_string(),
+        ));
+
+        assert!(state.register_hotkey_with_fallback());
+        assert!(state.hotkey_registered);
+
+        state.initialized = true;
+        assert!(!state.hotkey_warning_visible());
+    }
+
+    #[test]
+    fn test_read_only_data_dir_sets_warning() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::default();
+
+        // 書き込めるフォルダでは警告を出さない
+        assert!(state.check_data_dir_writable(temp_dir.path()));
+        assert!(state.data_dir_warning.is_none());
+
+        // 書き込めない場所（ディレクトリの代わりにファイルがある）
+        let read_only = temp_dir.path().join("not_a_dir");
+        std::fs::write(&read_only, "").unwrap();
+        assert!(!state.check_data_dir_writable(&read_only));
+        let warning = state.data_dir_warning.clone().unwrap();
+        assert!(warning.contains(&read_only.display().to_string()));
+
+        // 書き込めるようになれば警告を消す
+        assert!(state.check_data_dir_writable(temp_dir.path()));
+        assert!(state.data_dir_warning.is_none());
+    }
+
+    #[test]
+    fn test_report_save_error_shows_toast() {
+        let mut state = AppState::default();
+        // 設定が読み込まれていなければ保存しない
+        assert!(!state.save_config_or_report());
+        assert!(state.toasts.latest().is_none());
+
+        state.report_save_error("エイリアス", "アクセスが拒否されました");
+        let toast = state.toasts.latest().unwrap();
+        assert_eq!(toast.message_type, MessageType::Error);
+        assert_eq!(toast.message, "エイリアスの保存に失敗しました: アクセスが拒否されました");
+    }
+
+    #[test]
+    fn test_hotkey_warning_hidden_when_disabled() {
+        let mut state = AppState::default();
+        state.hotkey_manager = None;
+        state.config = Some(create_test_config(false, vec!["Ctrl".to_string()], "O".to_string()));
+        state.initialized = true;
+
+        state.register_hotkey_with_fallback();
+        assert!(!state.hotkey_registered);
+        // 無効にしている場合は警告しない
+        assert!(!state.hotkey_warning_visible());
+    }
+
+    #[test]
+    fn test_record_sidebar_width_clamps_and_detects_change() {
+        let mut state = AppState::default();
+        // 設定がない場合は記録しない
+        assert!(!state.record_sidebar_width(300.0));
+
+        state.config = Some(create_test_config(true, vec!["Ctrl".to_string()], "O".to_string()));
+        assert!(state.record_sidebar_width(300.0));
+        assert_eq!(state.sidebar_width(), 300.0);
+
+        // ほぼ同じ幅は変更なし
+        assert!(!state.record_sidebar_width(300.4));
+
+        // 範囲外は収めて記録
+        assert!(state.record_sidebar_width(5000.0));
+        assert_eq!(state.sidebar_width(), crate::ui::layout::MAX_SIDEBAR_WIDTH);
+    }
+
+    #[test]
+    fn test_sidebar_width_round_trip() {
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(true, vec!["Ctrl".to_string()], "O".to_string()));
+        state.record_sidebar_width(320.0);
+
+        let json = serde_json::to_string(state.config.as_ref().unwrap()).unwrap();
+        let mut restored = AppState::default();
+        restored.config = Some(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.sidebar_width(), 320.0);
+
+        // 幅を含まない古い設定ファイルは既定値
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["display"].as_object_mut().unwrap().remove("sidebar_width");
+        restored.config = Some(serde_json::from_value(value).unwrap());
+        assert_eq!(restored.sidebar_width(), 200.0);
+    }
+
+    #[test]
+    fn test_failed_badge_expires() {
+        let mut badges = FailedPathBadges::default();
+        let dir = PathBuf::from("/work");
+        let failed = dir.join("locked.txt");
+        let start = Instant::now();
+
+        badges.record(&dir, vec![failed.clone()], start);
+
+        assert!(badges.should_badge(&failed, start));
+        assert!(badges.should_badge(&failed, start + Duration::from_secs(4)));
+        assert!(!badges.should_badge(&failed, start + FailedPathBadges::DURATION));
+        // 失敗していない行には表示しない
+        assert!(!badges.should_badge(&dir.join("ok.txt"), start));
+
+        badges.prune(Some(&dir), start + FailedPathBadges::DURATION);
+        assert!(badges.is_empty());
+    }
+
+    #[test]
+    fn test_failed_badge_cleared_on_navigation() {
+        let mut badges = FailedPathBadges::default();
+        let dir = PathBuf::from("/work");
+        let failed = dir.join("locked.txt");
+        let start = Instant::now();
+        badges.record(&dir, vec![failed.clone()], start);
+
+        // 同じフォルダにいる間は残る
+        badges.prune(Some(&dir), start);
+        assert!(badges.should_badge(&failed, start));
+
+        // 別のフォルダに移動したら消える
+        badges.prune(Some(Path::new("/other")), start);
+        assert!(!badges.should_badge(&failed, start));
+        assert!(badges.is_empty());
+    }
+
+    #[test]
+    fn test_failed_badge_record_in_other_dir_replaces() {
+        let mut badges = FailedPathBadges::default();
+        let start = Instant::now();
+        badges.record(Path::new("/a"), vec![PathBuf::from("/a/1")], start);
+        badges.record(Path::new("/b"), vec![PathBuf::from("/b/2")], start);
+
+        assert!(!badges.should_badge(Path::new("/a/1"), start));
+        assert!(badges.should_badge(Path::new("/b/2"), start));
+    }
+
+    #[test]
+    fn test_app_state_default() {
+        // AppState のデフォルトインスタンス生成をテスト
+        let state = AppState::default();
+        assert!(state.config.is_none(), "デフォルトでは設定がNone");
+        assert_eq!(state.file_aliases.len(), 0, "デフォルトではエイリアスが空");
+        assert_eq!(state.search_query, "", "デフォルトでは検索クエリが空");
+        assert!(!state.initialized, "デフォルトでは初期化されていない");
+    }
+
+    #[test]
+    fn test_filter_aliases_empty_query() {
+        // 検索クエリが空の場合、全てのエイリアスが表示される
+        let mut state = AppState::default();
+        state.file_aliases = vec![
+            FileAlias {
+                id: "1".to_string(),
+                alias: "test1".to_string(),
+                path: PathBuf::from("/path/to/test1"),
+                tags: vec![],
+                color: None,
+                created_at: chrono::Utc::now(),
+                last_accessed: chrono::Utc::now(),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+            FileAlias {
+                id: "2".to_string(),
+                alias: "test2".to_string(),
+                path: PathBuf::from("/path/to/test2"),
+                tags: vec![],
+                color: None,
+                created_at: chrono::Utc::now(),
+                last_accessed: chrono::Utc::now(),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+        ];
+
+        state.search_query = String::new();
+        state.filter_aliases();
+
+        assert_eq!(state.filtered_items.len(), 2, "全エイリアスが表示されるべき");
+    }
+
+    #[test]
+    fn test_filter_aliases_with_query() {
+        // 検索クエリがある場合、マッチするエイリアスのみが表示される
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+        state.file_aliases = vec![
+            FileAlias {
+                id: "1".to_string(),
+                alias: "test1".to_string(),
+                path: PathBuf::from("/path/to/test1"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now,
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+            FileAlias {
+                id: "2".to_string(),
+                alias: "other".to_string(),
+                path: PathBuf::from("/path/to/other"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now,
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+        ];
+
+        // SearchEngineにも設定
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        state.search_query = "test".to_string();
+        state.filter_aliases();
+
+        assert_eq!(state.filtered_items.len(), 1, "マッチするエイリアスのみ表示");
+        assert_eq!(state.filtered_items[0].alias, "test1", "test1がフィルタリングされるべき");
+    }
+
+    #[test]
+    fn test_filter_aliases_with_search_engine_fuzzy() {
+        // SearchEngineのファジーマッチングを使用したテスト
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+
+        // テスト用エイリアスを追加
+        state.file_aliases = vec![
+            FileAlias {
+                id: "1".to_string(),
+                alias: "config".to_string(),
+                path: PathBuf::from("/etc/config"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now - chrono::Duration::days(100),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+            FileAlias {
+                id: "2".to_string(),
+                alias: "configure".to_string(),
+                path: PathBuf::from("/usr/bin/configure"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now - chrono::Duration::days(100),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+        ];
+
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        // 完全一致のテスト
+        state.search_query = "config".to_string();
+        state.filter_aliases();
+
+        // 完全一致で "config" が最初に来るはず
+        assert!(!state.filtered_items.is_empty());
+        assert_eq!(state.filtered_items[0].alias, "config");
+    }
+
+    #[test]
+    fn test_filter_aliases_with_favorite_boost() {
+        // お気に入りブーストのテスト
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+
+        // お気に入りとそうでないエイリアスを作成
+        state.file_aliases = vec![
+            FileAlias {
+                id: "1".to_string(),
+                alias: "config".to_string(),
+                path: PathBuf::from("/etc/config"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now - chrono::Duration::days(100),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+            FileAlias {
+                id: "2".to_string(),
+                alias: "config2".to_string(),
+                path: PathBuf::from("/etc/config2"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now - chrono::Duration::days(100),
+                is_favorite: true,  // お気に入り
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+        ];
+
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        // "config" で検索
+        state.search_query = "config".to_string();
+        state.filter_aliases();
+
+        // お気に入りの "config2" が上位に来るはず
+        // (完全一致のconfigがスコア1.0、前方一致+お気に入りのconfig2がスコア1.0)
+        // ただし、configが完全一致なので最初に来る可能性がある
+        assert!(!state.filtered_items.is_empty());
+
+        // 両方とも結果に含まれているはず
+        let config_found = state.filtered_items.iter().any(|a| a.alias == "config");
+        let config2_found = state.filtered_items.iter().any(|a| a.alias == "config2");
+        assert!(config_found, "configが見つかるはず");
+        assert!(config2_found, "config2が見つかるはず");
+    }
+
+    #[test]
+    fn test_filter_aliases_with_path_search() {
+        // パス検索のテスト
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+
+        state.file_aliases = vec![
+            FileAlias {
+                id: "1".to_string(),
+                alias: "doc".to_string(),
+                path: PathBuf::from("/documents/important/file.txt"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now - chrono::Duration::days(100),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+            FileAlias {
+                id: "2".to_string(),
+                alias: "test".to_string(),
+                path: PathBuf::from("/path/to/test"),
+                tags: vec![],
+                color: None,
+                created_at: now,
+                last_accessed: now - chrono::Duration::days(100),
+                is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
+            },
+        ];
+
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        // パスに対する検索
+        state.search_query = "documents".to_string();
+        state.filter_aliases();
+
+        // パスにマッチしたエイリアスが見つかるはず
+        assert!(!state.filtered_items.is_empty());
+        assert_eq!(state.filtered_items[0].alias, "doc");
+    }
+
+    #[test]
+    fn test_filter_aliases_with_tag_search() {
+        // タグ検索のテスト
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+
+        let mut alias_with_tags = FileAlias {
+            id: "1".to_string(),
+            alias: "document".to_string(),
+            path: PathBuf::from("/path/to/doc"),
+            tags: vec!["important".to_string(), "work".to_string()],
+            color: None,
+            created_at: now,
+            last_accessed: now - chrono::Duration::days(100),
+            is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        };
+
+        state.file_aliases = vec![alias_with_tags];
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        // タグに対する検索
+        state.search_query = "important".to_string();
+        state.filter_aliases();
+
+        // タグにマッチしたエイリアスが見つかるはず
+        assert!(!state.filtered_items.is_empty());
+        assert_eq!(state.filtered_items[0].alias, "document");
+    }
+
+    #[test]
+    fn test_dispatch_open_settings() {
+        let mut state = AppState::default();
+        assert!(!state.show_settings);
+
+        let result = state.dispatch_palette_action(PaletteAction::OpenSettings);
+        assert!(result.is_ok());
+        assert!(state.show_settings, "設定画面が開かれるべき");
+    }
+
+    #[test]
+    fn test_dispatch_toggle_theme() {
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(true, vec!["Ctrl".to_string()], "O".to_string()));
+        state.current_theme = Theme::Dark;
+
+        state.dispatch_palette_action(PaletteAction::ToggleTheme).unwrap();
+        assert_eq!(state.current_theme, Theme::Light);
+        assert_eq!(state.config.as_ref().unwrap().theme.mode, "light");
+
+        state.dispatch_palette_action(PaletteAction::ToggleTheme).unwrap();
+        assert_eq!(state.current_theme, Theme::Dark);
+        assert_eq!(state.config.as_ref().unwrap().theme.mode, "dark");
+    }
+
+    #[test]
+    fn test_window_level_command_follows_config() {
+        let mut state = AppState::default();
+        assert_eq!(state.window_level_command(), egui::ViewportCommand::WindowLevel(egui::WindowLevel::Normal));
+
+        let mut config = create_test_config(true, vec!["Ctrl".to_string()], "O".to_string());
+        config.window.always_on_top = true;
+        state.config = Some(config);
+        assert_eq!(state.window_level_command(), egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+
+        state.config.as_mut().unwrap().window.always_on_top = false;
+        assert_eq!(state.window_level_command(), egui::ViewportCommand::WindowLevel(egui::WindowLevel::Normal));
+    }
+
+    #[test]
+    fn test_dispatch_toggle_always_on_top_updates_config() {
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(true, vec!["Ctrl".to_string()], "O".to_string()));
+        state.config.as_mut().unwrap().window.always_on_top = false;
+
+        state.dispatch_palette_action(PaletteAction::ToggleAlwaysOnTop).unwrap();
+        assert!(state.config.as_ref().unwrap().window.always_on_top);
+        assert_eq!(state.window_level_command(), egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+
+        state.dispatch_palette_action(PaletteAction::ToggleAlwaysOnTop).unwrap();
+        assert!(!state.config.as_ref().unwrap().window.always_on_top);
+
+        let mut state = AppState::default();
+        assert!(state.dispatch_palette_action(PaletteAction::ToggleAlwaysOnTop).is_err());
+    }
+
+    #[test]
+    fn test_dispatch_toggle_theme_no_config() {
+        let mut state = AppState::default();
+        let result = state.dispatch_palette_action(PaletteAction::ToggleTheme);
+        assert!(result.is_err(), "設定がない場合はエラーになるべき");
+    }
+
+    #[test]
+    fn test_dispatch_switch_browse_mode() {
+        let mut state = AppState::default();
+        state.current_focus_area = FocusArea::Search;
+
+        state.dispatch_palette_action(PaletteAction::SwitchBrowseMode).unwrap();
+        assert_eq!(state.browse_mode, BrowseMode::Directory);
+        assert_eq!(state.current_focus_area, FocusArea::Main);
+
+        state.dispatch_palette_action(PaletteAction::SwitchBrowseMode).unwrap();
+        assert_eq!(state.browse_mode, BrowseMode::Alias);
+    }
+
+    #[test]
+    fn test_dispatch_add_alias() {
+        let mut state = AppState::default();
+        state.dispatch_palette_action(PaletteAction::AddAlias).unwrap();
+        assert!(state.show_add_alias_dialog);
+    }
+
+    #[test]
+    fn test_dispatch_import_profile_previews_selected_zip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let profile_dir = temp_dir.path().join("profile");
+        std::fs::create_dir(&profile_dir).unwrap();
+        std::fs::write(profile_dir.join("aliases.json"), "[]").unwrap();
+        crate::core::profile::export_bundle_from(&profile_dir, &temp_dir.path().join("profile.zip")).unwrap();
+
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.browse_mode = BrowseMode::Directory;
+
+        // ZIPファイルを選択していない場合は開かない
+        state.selected_directory_index = state.filtered_directory_entries()
+            .iter()
+            .position(|entry| entry.name == "profile");
+        assert!(state.dispatch_palette_action(PaletteAction::ImportProfile).is_err());
+        assert!(state.profile_import_dialog.is_none());
+
+        // 選択したZIPファイルの内容を確認するダイアログを開く（まだ書き込まない）
+        state.selected_directory_index = state.filtered_directory_entries()
+            .iter()
+            .position(|entry| entry.name == "profile.zip");
+        state.dispatch_palette_action(PaletteAction::ImportProfile).unwrap();
+        let dialog = state.profile_import_dialog.as_ref().unwrap();
+        assert_eq!(dialog.path, temp_dir.path().join("profile.zip"));
+        assert_eq!(dialog.preview.alias_count, Some(0));
+        assert!(state.is_any_dialog_open());
+    }
+
+    #[test]
+    fn test_dispatch_new_folder() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.browse_mode = BrowseMode::Directory;
+
+        let result = state.dispatch_palette_action(PaletteAction::NewFolder);
+        assert!(result.is_ok());
+        assert!(temp_dir.path().join("新しいフォルダ").is_dir());
+        // ブラウザが再読み込みされている
+        assert_eq!(state.get_current_entries().len(), 1);
+    }
+
+    #[test]
+    fn test_dispatch_new_folder_requires_directory_mode() {
+        let mut state = AppState::default();
+        let result = state.dispatch_palette_action(PaletteAction::NewFolder);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_dispatch_compress_without_selection() {
+        let mut state = AppState::default();
+        let result = state.dispatch_palette_action(PaletteAction::Compress);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_dispatch_undo_empty_history() {
+        let mut state = AppState::default();
+        let result = state.dispatch_palette_action(PaletteAction::Undo);
+        assert!(result.is_err(), "履歴が空の場合はエラーになるべき");
+    }
+
+    #[test]
+    fn test_command_palette_opens_dialog() {
+        let mut state = AppState::default();
+        assert!(!state.is_any_dialog_open());
 
-        let result = state.register_configured_hotkey();
-        assert!(result.is_ok(), "ホットキーが無効でもOkを返すべき: {:?}", result.err());
+        state.command_palette = Some(CommandPaletteDialog::new());
+        assert!(state.is_any_dialog_open());
     }
 
     #[test]
-    fn test_register_configured_hotkey_invalid_modifier() {
-        // 異常系: 無効な修飾キーでエラーが返される
+    fn test_apply_reloaded_aliases_refreshes_state() {
         let mut state = AppState::default();
-        state.config = Some(create_test_config(
-            true,
-            vec!["InvalidModifier".to_string()],
-            "O".to_string(),
-        ));
+        state.alias_manager
+            .add_alias("project".to_string(), PathBuf::from("/path/to/project"), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("docs".to_string(), PathBuf::from("/path/to/docs"), vec![], None, false)
+            .unwrap();
+
+        // 変更イベント後の反映
+        assert!(state.apply_reloaded_aliases(), "内容が変化したので true");
+        assert_eq!(state.file_aliases.len(), 2);
+        assert_eq!(state.filtered_items.len(), 2);
+        assert_eq!(state.search_engine.aliases().len(), 2);
+
+        // 同じ内容なら変化なし
+        assert!(!state.apply_reloaded_aliases(), "内容が同じなので false");
+    }
 
-        let result = state.register_configured_hotkey();
-        assert!(result.is_err(), "無効な修飾キーでエラーを返すべき");
-        assert!(
-            result.unwrap_err().contains("修飾キーの変換に失敗"),
-            "エラーメッセージに「修飾キーの変換に失敗」を含むべき"
+    #[test]
+    fn test_new_alias_duplicate_warning() {
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("docs".to_string(), PathBuf::from(r"C:\Users\taro\Documents"), vec![], None, false)
+            .unwrap();
+
+        assert_eq!(state.new_alias_duplicate_warning(), None);
+        state.new_alias_path = "c:/users/taro/documents/".to_string();
+        assert_eq!(
+            state.new_alias_duplicate_warning().as_deref(),
+            Some("エイリアス「docs」が同じパスを指しています")
         );
+        state.new_alias_path = "c:/users/taro/downloads".to_string();
+        assert_eq!(state.new_alias_duplicate_warning(), None);
     }
 
     #[test]
-    fn test_register_configured_hotkey_invalid_key() {
-        // 異常系: 無効なキーコードでエラーが返される
+    fn test_alias_edit_dialog_applies_changes() {
+        let temp_dir = tempfile::tempdir().unwrap();
         let mut state = AppState::default();
-        state.config = Some(create_test_config(
-            true,
-            vec!["Ctrl".to_string()],
-            "InvalidKey".to_string(),
-        ));
+        state.alias_manager
+            .add_alias("docs".to_string(), PathBuf::from("/path/to/docs"), vec!["old".to_string()], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("work".to_string(), PathBuf::from("/path/to/work"), vec![], None, false)
+            .unwrap();
+        state.refresh_after_alias_change();
+        let docs_id = state.file_aliases[0].id.clone();
+
+        state.open_alias_edit_dialog(&docs_id).unwrap();
+        assert!(state.is_any_dialog_open());
+        let dialog = state.alias_edit_dialog.as_mut().unwrap();
+        assert_eq!(dialog.name, "docs");
+        assert_eq!(dialog.tags, "old");
+        // 見つからないパスは警告のみ
+        assert!(dialog.path_warning().is_some());
+
+        // 重複する名前はダイアログ内にエラーを表示し、開いたまま
+        dialog.name = "work".to_string();
+        assert!(state.apply_alias_edit().is_err());
+        let dialog = state.alias_edit_dialog.as_mut().unwrap();
+        assert_eq!(dialog.error.as_deref(), Some("エイリアス 'work' は既に存在します"));
+
+        dialog.name = " 資料 ".to_string();
+        dialog.path = temp_dir.path().display().to_string();
+        dialog.tags = "project, , 2025".to_string();
+        dialog.color = Some("#10B981".to_string());
+        dialog.is_favorite = true;
+        assert!(dialog.path_warning().is_none());
+        assert_eq!(state.apply_alias_edit().unwrap(), "資料");
+
+        assert!(state.alias_edit_dialog.is_none());
+        let edited = state.file_aliases.iter().find(|a| a.id == docs_id).unwrap();
+        assert_eq!(edited.alias, "資料");
+        assert_eq!(edited.path, temp_dir.path());
+        assert_eq!(edited.tags, vec!["project".to_string(), "2025".to_string()]);
+        assert_eq!(edited.color.as_deref(), Some("#10B981"));
+        assert!(edited.is_favorite);
+        assert!(!edited.is_broken);
+    }
 
-        let result = state.register_configured_hotkey();
-        assert!(result.is_err(), "無効なキーでエラーを返すべき");
-        assert!(
-            result.unwrap_err().contains("キーコードの変換に失敗"),
-            "エラーメッセージに「キーコードの変換に失敗」を含むべき"
+    #[test]
+    fn test_check_broken_aliases_treats_network_paths_as_unknown() {
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("share".to_string(), PathBuf::from("//fileserver/share/docs"), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("local".to_string(), PathBuf::from("/nonexistent/ofkt/local"), vec![], None, false)
+            .unwrap();
+
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        state.config = Some(config.clone());
+        assert_eq!(state.check_broken_aliases(), 1);
+        let share = &state.alias_manager.get_aliases()[0];
+        assert!(share.is_unreachable && !share.is_broken);
+
+        // 設定を無効にするとネットワークパスも壊れているとする
+        config.display.network_path_as_unknown = false;
+        state.config = Some(config);
+        assert_eq!(state.check_broken_aliases(), 2);
+        assert!(state.alias_manager.get_aliases().iter().all(|a| a.is_broken && !a.is_unreachable));
+
+        let message = state.dispatch_palette_action(PaletteAction::ValidateAliases).unwrap();
+        assert_eq!(message, "パスが見つからないエイリアス: 2 件");
+        assert_eq!(state.file_aliases.iter().filter(|a| a.is_broken).count(), 2);
+    }
+
+    #[test]
+    fn test_alias_repair_repoints_or_deletes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let new_home = temp_dir.path().join("2025").join("report");
+        std::fs::create_dir_all(&new_home).unwrap();
+
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("report".to_string(), temp_dir.path().join("2024").join("report"), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("gone".to_string(), PathBuf::from("/nonexistent/ofkt/gone"), vec![], None, false)
+            .unwrap();
+        state.check_broken_aliases();
+        state.refresh_after_alias_change();
+        let report_id = state.file_aliases[0].id.clone();
+        let gone_id = state.file_aliases[1].id.clone();
+
+        // 近くを探すと、親フォルダの兄弟から同じ名前のフォルダが見つかる
+        state.open_alias_repair_dialog(&report_id).unwrap();
+        assert!(state.is_any_dialog_open());
+        let dialog = state.alias_repair_dialog.as_mut().unwrap();
+        dialog.new_path = "/nonexistent/ofkt/elsewhere".to_string();
+        assert!(state.apply_alias_repair().is_err(), "見つからないパスには付け替えない");
+        let dialog = state.alias_repair_dialog.as_mut().unwrap();
+        assert!(dialog.error.as_deref().unwrap().starts_with("パスが見つかりません"));
+
+        dialog.search_nearby();
+        assert_eq!(dialog.candidates, Some(vec![new_home.clone()]));
+        assert_eq!(dialog.new_path, new_home.display().to_string());
+        assert_eq!(state.apply_alias_repair().unwrap(), "report");
+        assert!(state.alias_repair_dialog.is_none());
+        let repaired = state.file_aliases.iter().find(|a| a.id == report_id).unwrap();
+        assert_eq!(repaired.path, new_home);
+        assert!(!repaired.is_broken);
+
+        // 修復せずに削除する
+        state.open_alias_repair_dialog(&gone_id).unwrap();
+        assert_eq!(state.delete_repaired_alias().unwrap(), "gone");
+        assert!(state.alias_repair_dialog.is_none());
+        assert_eq!(state.file_aliases.len(), 1);
+    }
+
+    #[test]
+    fn test_check_broken_aliases_marks_missing_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("project".to_string(), temp_dir.path().to_path_buf(), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("moved".to_string(), temp_dir.path().join("moved"), vec![], None, false)
+            .unwrap();
+
+        assert_eq!(state.check_broken_aliases(), 1);
+        // 印の変化も再読み込みの差分として表示に反映される
+        assert!(state.apply_reloaded_aliases());
+        let broken: Vec<&str> = state.file_aliases
+            .iter()
+            .filter(|a| a.is_broken)
+            .map(|a| a.alias.as_str())
+            .collect();
+        assert_eq!(broken, vec!["moved"]);
+    }
+
+    #[test]
+    fn test_apply_reloaded_aliases_keeps_query_and_clamps_selection() {
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("project".to_string(), PathBuf::from("/path/to/project"), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("docs".to_string(), PathBuf::from("/path/to/docs"), vec![], None, false)
+            .unwrap();
+        state.apply_reloaded_aliases();
+
+        state.selected_index = Some(1);
+        state.search_query = "project".to_string();
+
+        // 外部編集で "docs" が削除された
+        let docs_id = state.alias_manager.get_aliases()[1].id.clone();
+        state.alias_manager.remove_alias_by_id(&docs_id).unwrap();
+
+        assert!(state.apply_reloaded_aliases());
+        assert_eq!(state.file_aliases.len(), 1);
+        assert_eq!(state.filtered_items.len(), 1);
+        assert_eq!(state.filtered_items[0].alias, "project");
+        assert_eq!(state.selected_index, Some(0), "選択は範囲内に収まるべき");
+    }
+
+    #[test]
+    fn test_refresh_after_alias_change_clamps_selection_with_active_query() {
+        let mut state = AppState::default();
+        for name in ["report_a", "report_b", "report_c", "notes"] {
+            state.alias_manager
+                .add_alias(name.to_string(), PathBuf::from(format!("/work/{}", name)), vec![], None, false)
+                .unwrap();
+        }
+        state.refresh_after_alias_change();
+        state.search_query = "report".to_string();
+        state.filter_aliases();
+        assert_eq!(state.filtered_items.len(), 3);
+        state.selected_index = Some(2);
+
+        // 検索結果の末尾を選択中に、一致するエイリアスが減った
+        for name in ["report_b", "report_c"] {
+            state.alias_manager.remove_alias_by_name(name).unwrap();
+        }
+        state.refresh_after_alias_change();
+
+        assert_eq!(state.search_query, "report");
+        assert_eq!(state.filtered_items.len(), 1);
+        assert_eq!(state.filtered_items[0].alias, "report_a");
+        assert_eq!(state.selected_index, Some(0));
+        // 検索エンジンも新しい一覧で検索している
+        assert_eq!(state.search_engine.search("report").len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_after_alias_change_clears_selection_when_no_match() {
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("report".to_string(), PathBuf::from("/work/report"), vec![], None, false)
+            .unwrap();
+        state.refresh_after_alias_change();
+        state.search_query = "report".to_string();
+        state.filter_aliases();
+        state.selected_index = Some(0);
+
+        state.alias_manager.remove_alias_by_name("report").unwrap();
+        state.refresh_after_alias_change();
+
+        assert!(state.filtered_items.is_empty());
+        assert_eq!(state.selected_index, None);
+    }
+
+    /// テスト用のエイリアスを作成
+    fn create_named_alias(id: &str, alias: &str) -> FileAlias {
+        FileAlias {
+            id: id.to_string(),
+            alias: alias.to_string(),
+            path: PathBuf::from(format!("/path/to/{}", alias)),
+            tags: vec![],
+            color: None,
+            created_at: chrono::Utc::now(),
+            last_accessed: chrono::Utc::now(),
+            is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        }
+    }
+
+    #[test]
+    fn test_browse_tabs_from_config_drive_default_mode() {
+        let mut state = AppState::default();
+        assert_eq!(state.browse_tabs(), vec![BrowseMode::Alias, BrowseMode::Directory]);
+        assert_eq!(state.default_browse_mode(), BrowseMode::Alias);
+
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.display.browse_tabs = vec!["recent".to_string(), "directory".to_string()];
+        state.config = Some(config);
+        assert_eq!(state.browse_tabs(), vec![BrowseMode::Recent, BrowseMode::Directory]);
+        assert_eq!(state.default_browse_mode(), BrowseMode::Recent);
+
+        // 非表示にしたエイリアスタブからは先頭のタブに切り替える
+        state.ensure_browse_mode_enabled();
+        assert_eq!(state.browse_mode, BrowseMode::Recent);
+
+        // Ctrl+Tab は表示中のタブだけを順に切り替える
+        state.switch_to_next_browse_mode();
+        assert_eq!(state.browse_mode, BrowseMode::Directory);
+        state.switch_to_next_browse_mode();
+        assert_eq!(state.browse_mode, BrowseMode::Recent);
+    }
+
+    #[test]
+    fn test_set_browse_mode_restores_selection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+
+        let mut state = AppState::default();
+        state.filtered_items = vec![
+            create_named_alias("1", "one"),
+            create_named_alias("2", "two"),
+            create_named_alias("3", "three"),
+        ];
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+
+        state.selected_index = Some(2);
+        state.set_browse_mode(BrowseMode::Directory);
+        state.selected_directory_index = Some(1);
+
+        // エイリアスモードに戻ると以前の選択が復元される
+        state.set_browse_mode(BrowseMode::Alias);
+        assert_eq!(state.selected_index, Some(2));
+
+        // ディレクトリモードに戻ると以前の選択が復元される
+        state.selected_index = Some(0);
+        state.set_browse_mode(BrowseMode::Directory);
+        assert_eq!(state.selected_directory_index, Some(1));
+    }
+
+    #[test]
+    fn test_set_browse_mode_clamps_when_list_shrank() {
+        let mut state = AppState::default();
+        state.filtered_items = vec![
+            create_named_alias("1", "one"),
+            create_named_alias("2", "two"),
+            create_named_alias("3", "three"),
+        ];
+
+        state.selected_index = Some(2);
+        state.set_browse_mode(BrowseMode::Directory);
+
+        // ディレクトリモード中にエイリアス一覧が縮んだ
+        state.filtered_items.truncate(1);
+        state.selected_index = None;
+
+        state.set_browse_mode(BrowseMode::Alias);
+        assert_eq!(state.selected_index, Some(0), "範囲内に収められるべき");
+    }
+
+    #[test]
+    fn test_set_browse_mode_clears_when_list_empty() {
+        let mut state = AppState::default();
+        state.filtered_items = vec![create_named_alias("1", "one")];
+
+        state.selected_index = Some(0);
+        state.set_browse_mode(BrowseMode::Directory);
+        state.filtered_items.clear();
+
+        state.set_browse_mode(BrowseMode::Alias);
+        assert_eq!(state.selected_index, None, "一覧が空なら選択なし");
+    }
+
+    #[test]
+    fn test_next_repaint_interval() {
+        assert_eq!(next_repaint_interval(true, false), ACTIVE_REPAINT_INTERVAL);
+        assert_eq!(next_repaint_interval(true, true), ACTIVE_REPAINT_INTERVAL);
+        // 非表示でも処理待ちがあれば短い間隔のまま
+        assert_eq!(next_repaint_interval(false, true), ACTIVE_REPAINT_INTERVAL);
+        assert_eq!(next_repaint_interval(false, false), IDLE_REPAINT_INTERVAL);
+    }
+
+    #[test]
+    fn test_repaint_interval_follows_visibility_and_pending_work() {
+        let mut state = AppState::default();
+        assert_eq!(state.repaint_interval(), ACTIVE_REPAINT_INTERVAL);
+
+        state.is_window_visible = false;
+        assert_eq!(state.repaint_interval(), IDLE_REPAINT_INTERVAL);
+
+        state.directory_cache_debouncer.notify(Instant::now());
+        assert_eq!(state.repaint_interval(), ACTIVE_REPAINT_INTERVAL);
+
+        state.is_window_visible = true;
+        assert_eq!(state.repaint_interval(), ACTIVE_REPAINT_INTERVAL);
+    }
+
+    #[test]
+    fn test_clamp_selection() {
+        assert_eq!(clamp_selection(None, 5), None);
+        assert_eq!(clamp_selection(Some(3), 5), Some(3));
+        assert_eq!(clamp_selection(Some(10), 5), Some(4));
+        assert_eq!(clamp_selection(Some(0), 0), None);
+    }
+
+    /// 指定した経過時間だけ過去に作成されたメッセージを作成
+    fn create_toast_at(message: &str, timestamp: Instant, duration: Duration) -> OperationResultMessage {
+        OperationResultMessage {
+            message: message.to_string(),
+            message_type: MessageType::Success,
+            timestamp,
+            duration,
+        }
+    }
+
+    #[test]
+    fn test_toast_queue_keeps_enqueue_order() {
+        let mut queue = ToastQueue::new();
+        queue.push(OperationResultMessage::success("1件目".to_string()));
+        queue.push(OperationResultMessage::error("2件目".to_string()));
+        queue.push(OperationResultMessage::warning("3件目".to_string()));
+
+        let messages: Vec<&str> = queue.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["1件目", "2件目", "3件目"]);
+        assert_eq!(queue.latest().unwrap().message, "3件目");
+    }
+
+    #[test]
+    fn test_toast_queue_expires_individually() {
+        let mut queue = ToastQueue::new();
+        let base = Instant::now();
+        queue.push(create_toast_at("短い", base, Duration::from_secs(1)));
+        queue.push(create_toast_at("長い", base, Duration::from_secs(5)));
+        queue.push(create_toast_at("中間", base, Duration::from_secs(3)));
+
+        // 2秒後: 「短い」だけが期限切れ
+        queue.remove_expired_at(base + Duration::from_secs(2));
+        let messages: Vec<&str> = queue.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["長い", "中間"]);
+
+        // 4秒後: 「中間」も期限切れ
+        queue.remove_expired_at(base + Duration::from_secs(4));
+        let messages: Vec<&str> = queue.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["長い"]);
+
+        // 6秒後: 全て期限切れ
+        queue.remove_expired_at(base + Duration::from_secs(6));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_toast_queue_cap_drops_oldest() {
+        let mut queue = ToastQueue::with_max_len(3);
+        for i in 0..5 {
+            queue.push(OperationResultMessage::success(format!("メッセージ{}", i)));
+        }
+
+        assert_eq!(queue.len(), 3);
+        let messages: Vec<&str> = queue.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["メッセージ2", "メッセージ3", "メッセージ4"]);
+    }
+
+    #[test]
+    fn test_toast_queue_dismiss() {
+        let mut queue = ToastQueue::new();
+        queue.push(OperationResultMessage::success("a".to_string()));
+        queue.push(OperationResultMessage::success("b".to_string()));
+
+        queue.dismiss(0);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.latest().unwrap().message, "b");
+    }
+
+    #[test]
+    fn test_operation_result_message_with_type() {
+        let msg = OperationResultMessage::with_type("エラー".to_string(), MessageType::Error);
+        assert_eq!(msg.message_type, MessageType::Error);
+        assert_eq!(msg.duration, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_restore_last_queries_on_startup() {
+        let mut state = AppState::default();
+        state.file_aliases = vec![create_named_alias("1", "project_alpha"), create_named_alias("2", "notes")];
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.search.remember_last_query = true;
+        config.search.last_alias_query = Some("project".to_string());
+        config.search.last_directory_query = Some("src".to_string());
+        state.config = Some(config);
+
+        state.restore_last_queries();
+
+        // モードごとのクエリが復元され、エイリアスの絞り込みも反映される
+        assert_eq!(state.search_query, "project");
+        assert_eq!(state.directory_search_query, "src");
+        assert_eq!(state.filtered_items.len(), 1);
+        assert_eq!(state.filtered_items[0].alias, "project_alpha");
+    }
+
+    #[test]
+    fn test_search_time_budget_from_config_and_complete_search() {
+        let mut state = AppState::default();
+        state.file_aliases = (0..1000)
+            .map(|i| create_named_alias(&i.to_string(), &format!("project_{}", i)))
+            .collect();
+        state.search_engine.set_aliases(state.file_aliases.clone());
+
+        // 既定では走査時間を制限しない
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        state.config = Some(config.clone());
+        state.apply_search_config();
+        assert_eq!(state.search_engine.time_budget(), None);
+
+        // 制限を設定すると途中までの結果になったことが分かる
+        config.search.search_time_budget_ms = Some(0);
+        state.config = Some(config);
+        state.apply_search_config();
+        state.search_query = "project".to_string();
+        state.filter_aliases();
+        assert!(state.alias_search_truncated);
+        let partial = state.filtered_items.len();
+
+        // 最後まで走査し直すと全件の結果になり、制限は元に戻る
+        state.complete_alias_search();
+        assert!(!state.alias_search_truncated);
+        assert!(state.filtered_items.len() > partial);
+        assert_eq!(state.search_engine.time_budget(), Some(std::time::Duration::ZERO));
+    }
+
+    #[test]
+    fn test_restore_last_queries_disabled() {
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.search.remember_last_query = false;
+        config.search.last_alias_query = Some("project".to_string());
+        state.config = Some(config);
+
+        state.restore_last_queries();
+
+        // 設定が無効なら復元しない
+        assert_eq!(state.search_query, "");
+    }
+
+    #[test]
+    fn test_capture_last_queries_keeps_non_empty() {
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.search.remember_last_query = true;
+        state.config = Some(config);
+
+        state.search_query = "report".to_string();
+        assert!(state.capture_last_queries());
+        // 変更がなければ false
+        assert!(!state.capture_last_queries());
+
+        // 空のクエリでは上書きしない
+        state.search_query.clear();
+        state.directory_search_query = "docs".to_string();
+        assert!(state.capture_last_queries());
+
+        let search = &state.config.as_ref().unwrap().search;
+        assert_eq!(search.last_alias_query.as_deref(), Some("report"));
+        assert_eq!(search.last_directory_query.as_deref(), Some("docs"));
+    }
+
+    #[test]
+    fn test_set_browse_mode_captures_query() {
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.search.remember_last_query = true;
+        state.config = Some(config);
+
+        state.search_query = "会計".to_string();
+        state.set_browse_mode(BrowseMode::Directory);
+
+        let search = &state.config.as_ref().unwrap().search;
+        assert_eq!(search.last_alias_query.as_deref(), Some("会計"));
+    }
+
+    fn pointer_event(button: egui::PointerButton, pressed: bool) -> egui::Event {
+        egui::Event::PointerButton {
+            pos: egui::Pos2::ZERO,
+            button,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_pointer_button_navigation_mapping() {
+        assert_eq!(
+            HistoryNavigation::from_events(&[pointer_event(egui::PointerButton::Extra1, true)]),
+            Some(HistoryNavigation::Back)
+        );
+        assert_eq!(
+            HistoryNavigation::from_events(&[pointer_event(egui::PointerButton::Extra2, true)]),
+            Some(HistoryNavigation::Forward)
+        );
+        // 離したとき・その他のボタンは無視
+        assert_eq!(
+            HistoryNavigation::from_events(&[pointer_event(egui::PointerButton::Extra1, false)]),
+            None
         );
+        assert_eq!(
+            HistoryNavigation::from_events(&[
+                pointer_event(egui::PointerButton::Primary, true),
+                pointer_event(egui::PointerButton::Secondary, true),
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_navigate_history_with_side_buttons() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let child = temp_dir.path().join("child");
+        std::fs::create_dir(&child).unwrap();
+
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.directory_browser.as_mut().unwrap().navigate_to(child.clone()).unwrap();
+
+        // 戻る
+        state.directory_search_query = "abc".to_string();
+        let back = HistoryNavigation::from_events(&[pointer_event(egui::PointerButton::Extra1, true)]).unwrap();
+        assert!(state.navigate_history(back).unwrap());
+        assert_eq!(state.directory_browser.as_ref().unwrap().current_path(), temp_dir.path());
+        assert!(state.directory_search_query.is_empty());
+
+        // 履歴の先頭ではそれ以上戻らず、検索バーもそのまま
+        state.directory_search_query = "abc".to_string();
+        assert!(!state.navigate_history(HistoryNavigation::Back).unwrap());
+        assert_eq!(state.directory_search_query, "abc");
+
+        // 進む
+        let forward = HistoryNavigation::from_events(&[pointer_event(egui::PointerButton::Extra2, true)]).unwrap();
+        assert!(state.navigate_history(forward).unwrap());
+        assert_eq!(state.directory_browser.as_ref().unwrap().current_path(), child.as_path());
+        assert!(state.directory_search_query.is_empty());
+    }
+
+    #[test]
+    fn test_navigation_clears_filter_by_default() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let child = temp_dir.path().join("child");
+        std::fs::create_dir(&child).unwrap();
+
+        let mut state = AppState::default();
+        state.config = Some(create_test_config(false, vec![], "O".to_string()));
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.directory_browser.as_mut().unwrap().navigate_to(child).unwrap();
+
+        state.directory_search_query = "report".to_string();
+        assert!(state.navigate_history(HistoryNavigation::Back).unwrap());
+        assert!(state.directory_search_query.is_empty());
+    }
+
+    #[test]
+    fn test_navigation_keeps_filter_when_enabled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let child = temp_dir.path().join("child");
+        std::fs::create_dir(&child).unwrap();
+        std::fs::write(temp_dir.path().join("report.txt"), "").unwrap();
+        std::fs::write(temp_dir.path().join("memo.txt"), "").unwrap();
+        std::fs::write(child.join("report_q1.txt"), "").unwrap();
+        std::fs::write(child.join("report_q2.txt"), "").unwrap();
+        std::fs::write(child.join("invoice.txt"), "").unwrap();
+
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.search.keep_filter_on_navigate = true;
+        let mut state = AppState::default();
+        state.config = Some(config);
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.directory_browser.as_mut().unwrap().navigate_to(child).unwrap();
+
+        // 子フォルダで2件目を選択した状態から戻る
+        state.directory_search_query = "report".to_string();
+        state.selected_directory_index = Some(1);
+        assert!(state.navigate_history(HistoryNavigation::Back).unwrap());
+
+        // クエリは残り、移動先の一覧に適用される
+        assert_eq!(state.directory_search_query, "report");
+        let names: Vec<String> = state.filtered_directory_entries().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["report.txt".to_string()]);
+        assert_eq!(state.selected_directory_index, Some(0));
+    }
+
+    #[test]
+    fn test_reload_prunes_removed_expanded_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let kept = temp_dir.path().join("kept");
+        let removed = temp_dir.path().join("removed");
+        std::fs::create_dir(&kept).unwrap();
+        std::fs::create_dir(&removed).unwrap();
+
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.expanded_directories.insert(kept.clone());
+        state.expanded_directories.insert(removed.clone());
+
+        // 展開中のフォルダを削除してから再読み込み
+        std::fs::remove_dir(&removed).unwrap();
+        state.reload_directory().unwrap();
+
+        assert!(state.expanded_directories.contains(&kept));
+        assert!(!state.expanded_directories.contains(&removed));
+    }
+
+    #[test]
+    fn test_recursive_search_results_and_reveal() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let nested = temp_dir.path().join("docs").join("2025");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("aaa.txt"), "").unwrap();
+        std::fs::write(nested.join("report.txt"), "").unwrap();
+        std::fs::write(temp_dir.path().join("other.txt"), "").unwrap();
+
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.directory_search_query = "**report".to_string();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !state.update_recursive_search() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(state.is_recursive_search_active());
+        let target = nested.join("report.txt");
+        assert_eq!(state.visible_directory_paths(), vec![target.clone()]);
+
+        // 結果を選ぶと親フォルダへ移動して項目を選択する
+        state.reveal_directory_entry(&target).unwrap();
+        assert_eq!(state.directory_browser.as_ref().unwrap().current_path(), nested.as_path());
+        assert!(state.directory_search_query.is_empty());
+        assert!(!state.is_recursive_search_active());
+        assert_eq!(state.selected_directory_entry().map(|e| e.path), Some(target));
+
+        // `**` で始まらないクエリでは検索を保持しない
+        state.directory_search_query = "aaa".to_string();
+        assert!(!state.update_recursive_search());
+        assert!(state.recursive_search.is_none());
+    }
+
+    #[test]
+    fn test_large_paste_count_threshold() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path().join("many");
+        std::fs::create_dir(&folder).unwrap();
+        for i in 0..5 {
+            std::fs::write(folder.join(format!("{}.txt", i)), "x").unwrap();
+        }
+        let srcs = vec![folder];
+
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], String::new());
+        config.file_operations.large_paste_threshold = 3;
+        state.config = Some(config);
+        // フォルダ自身 + 5 ファイルだが、しきい値 + 1 で数えるのをやめる
+        assert_eq!(state.large_paste_count(&srcs), Some(4));
+
+        state.config.as_mut().unwrap().file_operations.large_paste_threshold = 0;
+        assert_eq!(state.large_paste_count(&srcs), None);
+    }
+
+    #[test]
+    fn test_small_paste_skips_large_warning() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("a.txt");
+        std::fs::write(&file, "x").unwrap();
+
+        // 設定未読み込み時は既定のしきい値（1000）
+        let state = AppState::default();
+        assert_eq!(state.large_paste_count(&[file]), None);
+    }
+
+    #[test]
+    fn test_check_all_and_uncheck_all() {
+        let mut state = AppState::default();
+        state.toggle_selection_mode();
+        assert!(state.selection_mode);
+
+        state.set_checked(Path::new("/work/a"), true);
+        state.check_all(vec![PathBuf::from("/work/a"), PathBuf::from("/work/b")]);
+        // 既にチェック済みのパスは重複しない
+        assert_eq!(state.checked_paths, vec![PathBuf::from("/work/a"), PathBuf::from("/work/b")]);
+        assert!(state.is_checked(Path::new("/work/b")));
+
+        state.set_checked(Path::new("/work/a"), false);
+        assert_eq!(state.checked_paths, vec![PathBuf::from("/work/b")]);
+
+        state.uncheck_all();
+        assert!(state.checked_paths.is_empty());
+
+        // 選択モードを終了するとチェックも解除される
+        state.set_checked(Path::new("/work/c"), true);
+        state.toggle_selection_mode();
+        assert!(!state.selection_mode);
+        assert!(state.checked_paths.is_empty());
+    }
+
+    #[test]
+    fn test_bulk_copy_and_cut_consume_checked_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| temp_dir.path().join(name))
+            .collect();
+        for file in &files {
+            std::fs::write(file, "x").unwrap();
+        }
+
+        let mut state = AppState::default();
+        state.check_all(files[..2].to_vec());
+
+        assert_eq!(state.bulk_copy(), 2);
+        assert!(state.checked_paths.is_empty());
+        assert_eq!(state.clipboard_state.paths.len(), 2);
+
+        // チェックがなければクリップボードは変更しない
+        assert_eq!(state.bulk_cut(), 0);
+        assert_eq!(state.clipboard_state.paths.len(), 2);
+        assert!(!state.clipboard_state.is_pending_cut(&files[0]));
+
+        state.set_checked(&files[2], true);
+        assert_eq!(state.bulk_cut(), 1);
+        assert!(state.clipboard_state.is_pending_cut(&files[2]));
+        assert!(state.checked_paths.is_empty());
+    }
+
+    #[test]
+    fn test_bulk_delete_opens_dialog_with_checked_paths() {
+        let mut state = AppState::default();
+        assert!(!state.bulk_delete());
+        assert!(state.delete_confirmation_dialog.is_none());
+
+        state.check_all(vec![PathBuf::from("/work/a.txt"), PathBuf::from("/work/b.txt")]);
+        assert!(state.bulk_delete());
+        assert!(state.checked_paths.is_empty());
+        let dialog = state.delete_confirmation_dialog.as_ref().unwrap();
+        assert_eq!(dialog.paths, vec![PathBuf::from("/work/a.txt"), PathBuf::from("/work/b.txt")]);
+    }
+
+    #[test]
+    fn test_bulk_tag_updates_checked_aliases() {
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("a".to_string(), PathBuf::from("/work/a"), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("b".to_string(), PathBuf::from("/work/b"), vec![], None, false)
+            .unwrap();
+        state.apply_reloaded_aliases();
+
+        // 空のタグでは何もせず、チェックも残す
+        state.set_checked(Path::new("/work/a"), true);
+        assert_eq!(state.bulk_tag("  "), 0);
+        assert_eq!(state.checked_paths.len(), 1);
+
+        assert_eq!(state.bulk_tag(" 重要 "), 1);
+        assert!(state.checked_paths.is_empty());
+        assert_eq!(state.file_aliases[0].tags, vec!["重要".to_string()]);
+        assert!(state.file_aliases[1].tags.is_empty());
+    }
+
+    #[test]
+    fn test_click_directory_entry_toggle_and_range() {
+        let paths: Vec<PathBuf> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| PathBuf::from("/work").join(name))
+            .collect();
+        let mut state = AppState::default();
+
+        // 通常のクリックはフォーカスのみ
+        state.click_directory_entry(&paths, &paths[1], SelectionClick::Replace);
+        assert_eq!(state.selected_directory_index, Some(1));
+        assert!(state.multi_selected_paths.is_empty());
+        assert_eq!(state.directory_selection_paths(&paths), vec![paths[1].clone()]);
+
+        // Ctrl+クリックはフォーカス中の項目と合わせて選択する
+        state.click_directory_entry(&paths, &paths[3], SelectionClick::Toggle);
+        assert_eq!(state.directory_selection_paths(&paths), vec![paths[1].clone(), paths[3].clone()]);
+        state.click_directory_entry(&paths, &paths[1], SelectionClick::Toggle);
+        assert_eq!(state.directory_selection_paths(&paths), vec![paths[3].clone()]);
+
+        // Shift+クリックは最後に Ctrl+クリックした項目を起点に範囲を選択し直す
+        state.click_directory_entry(&paths, &paths[4], SelectionClick::Range);
+        state.click_directory_entry(&paths, &paths[0], SelectionClick::Range);
+        assert_eq!(state.directory_selection_paths(&paths), paths[..=1].to_vec());
+        assert_eq!(state.selected_directory_index, Some(0));
+
+        // 矢印キーでフォーカスを動かしても選択は変わらない
+        state.selected_directory_index = Some(4);
+        assert_eq!(state.directory_selection_paths(&paths), paths[..=1].to_vec());
+
+        state.click_directory_entry(&paths, &paths[2], SelectionClick::Replace);
+        assert!(state.multi_selected_paths.is_empty());
+
+        assert_eq!(selection_summary(&paths[..1], "コピーしました"), Some("「a」をコピーしました".to_string()));
+        assert_eq!(selection_summary(&paths, "コピーしました"), Some("5 件をコピーしました".to_string()));
+        assert_eq!(selection_summary(&[], "コピーしました"), None);
+    }
+
+    #[test]
+    fn test_retain_visible_selection_after_filter_change() {
+        let paths: Vec<PathBuf> = ["a.txt", "b.txt", "c.md"]
+            .iter()
+            .map(|name| PathBuf::from("/work").join(name))
+            .collect();
+        let mut state = AppState::default();
+        state.select_all_directory_entries(&paths);
+        state.selection_anchor = Some(paths[2].clone());
+
+        // 検索で c.md が表示されなくなった
+        let visible = paths[..2].to_vec();
+        state.retain_visible_selection(&visible);
+        assert_eq!(state.directory_selection_paths(&visible), visible);
+        assert_eq!(state.selection_anchor, None);
+
+        state.clear_directory_selection();
+        assert!(state.multi_selected_paths.is_empty());
+    }
+
+    #[test]
+    fn test_remap_expanded_directories_moves_descendants() {
+        let mut state = AppState::default();
+        state.expanded_directories.insert(PathBuf::from("/root/old"));
+        state.expanded_directories.insert(PathBuf::from("/root/old/child"));
+        state.expanded_directories.insert(PathBuf::from("/root/other"));
+
+        state.remap_expanded_directories(Path::new("/root/old"), Path::new("/root/new"));
+
+        assert!(state.expanded_directories.contains(&PathBuf::from("/root/new")));
+        assert!(state.expanded_directories.contains(&PathBuf::from("/root/new/child")));
+        assert!(state.expanded_directories.contains(&PathBuf::from("/root/other")));
+        assert!(!state.expanded_directories.contains(&PathBuf::from("/root/old")));
+        // 文字列として前方一致するだけのパスは対象外
+        state.expanded_directories.insert(PathBuf::from("/root/newer"));
+        state.remap_expanded_directories(Path::new("/root/new"), Path::new("/root/x"));
+        assert!(state.expanded_directories.contains(&PathBuf::from("/root/newer")));
     }
 
     #[test]
-    fn test_register_configured_hotkey_no_config() {
-        // 異常系: config が None の場合にエラーが返される
-        let mut state = AppState::default();
-        state.config = None;
+    fn test_undo_rename_follows_expanded_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let old_path = temp_dir.path().join("before");
+        let new_path = temp_dir.path().join("after");
+        std::fs::create_dir(&old_path).unwrap();
+        std::fs::rename(&old_path, &new_path).unwrap();
 
-        let result = state.register_configured_hotkey();
-        assert!(result.is_err(), "設定がない場合はエラーを返すべき");
-        assert!(
-            result.unwrap_err().contains("設定が読み込まれていません"),
-            "エラーメッセージに「設定が読み込まれていません」を含むべき"
-        );
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.operation_history.push(FileOperation::Rename {
+            old_path: old_path.clone(),
+            new_path: new_path.clone(),
+        });
+        state.expanded_directories.insert(new_path.clone());
+
+        // Undo で元の名前に戻ると展開状態も追従する
+        state.operation_history.undo().unwrap();
+        state.refresh_after_history_change(true);
+        assert!(state.expanded_directories.contains(&old_path));
+        assert!(!state.expanded_directories.contains(&new_path));
+
+        // Redo で再び新しい名前に追従する
+        state.operation_history.redo().unwrap();
+        state.refresh_after_history_change(false);
+        assert!(state.expanded_directories.contains(&new_path));
+        assert!(!state.expanded_directories.contains(&old_path));
     }
 
     #[test]
-    fn test_app_state_default() {
-        // AppState のデフォルトインスタンス生成をテスト
-        let state = AppState::default();
-        assert!(state.config.is_none(), "デフォルトでは設定がNone");
-        assert_eq!(state.file_aliases.len(), 0, "デフォルトではエイリアスが空");
-        assert_eq!(state.search_query, "", "デフォルトでは検索クエリが空");
-        assert!(!state.initialized, "デフォルトでは初期化されていない");
+    fn test_history_button_state_follows_history_lengths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+
+        let undo = state.undo_button_state();
+        assert!(!undo.enabled);
+        assert_eq!(undo.tooltip, "元に戻す操作はありません");
+        assert!(!state.redo_button_state().enabled);
+
+        for name in ["a", "b"] {
+            let old_path = temp_dir.path().join(format!("{}.txt", name));
+            let new_path = temp_dir.path().join(format!("{}2.txt", name));
+            std::fs::write(&new_path, "").unwrap();
+            state.operation_history.push(FileOperation::Rename { old_path, new_path });
+        }
+        let undo = state.undo_button_state();
+        assert_eq!(undo.enabled, state.operation_history.undo_len() > 0);
+        assert_eq!(undo.tooltip, "元に戻す: 名前変更: b.txt -> b2.txt（2 件）");
+        assert!(!state.redo_button_state().enabled);
+
+        // 取り消すと「やり直す」が押せるようになる
+        state.undo_operation().unwrap();
+        assert_eq!(state.operation_history.undo_len(), 1);
+        assert_eq!(state.operation_history.redo_len(), 1);
+        assert!(state.undo_button_state().enabled);
+        let redo = state.redo_button_state();
+        assert!(redo.enabled);
+        assert_eq!(redo.tooltip, "やり直す: 名前変更: b.txt -> b2.txt（1 件）");
+
+        state.undo_operation().unwrap();
+        assert!(!state.undo_button_state().enabled);
+        assert_eq!(state.redo_button_state().tooltip, "やり直す: 名前変更: a.txt -> a2.txt（2 件）");
     }
 
     #[test]
-    fn test_filter_aliases_empty_query() {
-        // 検索クエリが空の場合、全てのエイリアスが表示される
+    fn test_rename_remaps_alias_and_quick_access_targets() {
         let mut state = AppState::default();
-        state.file_aliases = vec![
-            FileAlias {
-                id: "1".to_string(),
-                alias: "test1".to_string(),
-                path: PathBuf::from("/path/to/test1"),
-                tags: vec![],
-                color: None,
-                created_at: chrono::Utc::now(),
-                last_accessed: chrono::Utc::now(),
-                is_favorite: false,
-            },
-            FileAlias {
-                id: "2".to_string(),
-                alias: "test2".to_string(),
-                path: PathBuf::from("/path/to/test2"),
-                tags: vec![],
-                color: None,
-                created_at: chrono::Utc::now(),
-                last_accessed: chrono::Utc::now(),
-                is_favorite: false,
-            },
-        ];
+        state.alias_manager
+            .add_alias("report".to_string(), PathBuf::from("/work/old/report.xlsx"), vec![], None, false)
+            .unwrap();
+        state.alias_manager
+            .add_alias("other".to_string(), PathBuf::from("/work/other"), vec![], None, false)
+            .unwrap();
+        state.apply_reloaded_aliases();
+        state.quick_access_manager = QuickAccessManager::from_entries(vec![QuickAccessEntry {
+            id: "old".to_string(),
+            name: "old".to_string(),
+            path: PathBuf::from("/work/old"),
+            added_at: chrono::Utc::now(),
+            order: 0,
+            is_system: false,
+        }]);
+
+        // 親フォルダのリネームで配下のエイリアスが追従する
+        let updated = state.remap_alias_targets(Path::new("/work/old"), Path::new("/work/new"));
+        assert_eq!(updated, (1, 1));
+        assert_eq!(state.file_aliases[0].path, PathBuf::from("/work/new/report.xlsx"));
+        assert_eq!(state.file_aliases[1].path, PathBuf::from("/work/other"));
+        assert_eq!(state.quick_access_entries[0].path, PathBuf::from("/work/new"));
+
+        // Undo（逆向きの付け替え）で元に戻る
+        let updated = state.remap_alias_targets(Path::new("/work/new"), Path::new("/work/old"));
+        assert_eq!(updated, (1, 1));
+        assert_eq!(state.file_aliases[0].path, PathBuf::from("/work/old/report.xlsx"));
+        assert_eq!(state.quick_access_entries[0].path, PathBuf::from("/work/old"));
+    }
 
-        state.search_query = String::new();
-        state.filter_aliases();
+    #[test]
+    fn test_new_directory_uses_global_default_sort() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        std::fs::create_dir(&first).unwrap();
+        std::fs::create_dir(&second).unwrap();
 
-        assert_eq!(state.filtered_items.len(), 2, "全エイリアスが表示されるべき");
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.display.default_sort_key = "size".to_string();
+        config.display.default_sort_order = "descending".to_string();
+        state.config = Some(config);
+
+        state.init_directory_browser(first.clone()).unwrap();
+        let browser = state.directory_browser.as_mut().unwrap();
+        assert_eq!(browser.sort_key(), SortKey::Size);
+        assert_eq!(browser.sort_order(), SortOrder::Descending);
+
+        // first だけ並び順を変更しても、上書きのないフォルダは既定値のまま
+        browser.set_sort(SortKey::Name, SortOrder::Ascending);
+        browser.navigate_to(second.clone()).unwrap();
+        assert_eq!(browser.sort_key(), SortKey::Size);
+        assert_eq!(browser.sort_order(), SortOrder::Descending);
+
+        // 上書きしたフォルダに戻ると上書きが適用される（ブラウザを作り直しても維持）
+        state.init_directory_browser(first).unwrap();
+        let browser = state.directory_browser.as_ref().unwrap();
+        assert_eq!(browser.sort_key(), SortKey::Name);
+        assert_eq!(browser.sort_order(), SortOrder::Ascending);
     }
 
     #[test]
-    fn test_filter_aliases_with_query() {
-        // 検索クエリがある場合、マッチするエイリアスのみが表示される
+    fn test_default_sort_falls_back_for_invalid_config() {
         let mut state = AppState::default();
-        let now = chrono::Utc::now();
-        state.file_aliases = vec![
-            FileAlias {
-                id: "1".to_string(),
-                alias: "test1".to_string(),
-                path: PathBuf::from("/path/to/test1"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now,
-                is_favorite: false,
-            },
-            FileAlias {
-                id: "2".to_string(),
-                alias: "other".to_string(),
-                path: PathBuf::from("/path/to/other"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now,
-                is_favorite: false,
-            },
-        ];
+        state.config = None;
+        assert_eq!(state.default_sort(), (SortKey::Name, SortOrder::Ascending));
 
-        // SearchEngineにも設定
-        state.search_engine.set_aliases(state.file_aliases.clone());
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.display.default_sort_key = "unknown".to_string();
+        config.display.default_sort_order = "descending".to_string();
+        state.config = Some(config);
+        assert_eq!(state.default_sort(), (SortKey::Name, SortOrder::Descending));
+    }
 
-        state.search_query = "test".to_string();
-        state.filter_aliases();
+    #[test]
+    fn test_split_stem_extension() {
+        assert_eq!(split_stem_extension("report.xlsx"), ("report", ".xlsx"));
+        assert_eq!(split_stem_extension("archive.tar.gz"), ("archive.tar", ".gz"));
+        assert_eq!(split_stem_extension("README"), ("README", ""));
+        assert_eq!(split_stem_extension(".gitignore"), (".gitignore", ""));
+        assert_eq!(split_stem_extension("資料.docx"), ("資料", ".docx"));
+    }
 
-        assert_eq!(state.filtered_items.len(), 1, "マッチするエイリアスのみ表示");
-        assert_eq!(state.filtered_items[0].alias, "test1", "test1がフィルタリングされるべき");
+    #[test]
+    fn test_rename_dialog_initial_selection_excludes_extension() {
+        let dialog = RenameDialog::new(PathBuf::from("/tmp/資料.docx"));
+        assert_eq!(dialog.initial_selection(), 0..2);
+
+        // 拡張子がない場合は全体を選択
+        let dialog = RenameDialog::new(PathBuf::from("/tmp/Makefile"));
+        assert_eq!(dialog.initial_selection(), 0..8);
+        let dialog = RenameDialog::new(PathBuf::from("/tmp/.env"));
+        assert_eq!(dialog.initial_selection(), 0..4);
     }
 
     #[test]
-    fn test_filter_aliases_with_search_engine_fuzzy() {
-        // SearchEngineのファジーマッチングを使用したテスト
+    fn test_sidebar_alias_limit_and_expansion() {
         let mut state = AppState::default();
-        let now = chrono::Utc::now();
+        assert_eq!(state.sidebar_alias_limit(), 10);
+        assert!(!state.sidebar_aliases_expanded());
+        // 設定がない場合は切り替えない
+        assert!(!state.toggle_sidebar_aliases_expanded());
+
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.display.sidebar_alias_limit = 5;
+        state.config = Some(config);
+        assert_eq!(state.sidebar_alias_limit(), 5);
+
+        assert!(state.toggle_sidebar_aliases_expanded());
+        assert!(state.sidebar_aliases_expanded());
+        assert!(state.toggle_sidebar_aliases_expanded());
+        assert!(!state.sidebar_aliases_expanded());
+    }
 
-        // テスト用エイリアスを追加
-        state.file_aliases = vec![
-            FileAlias {
-                id: "1".to_string(),
-                alias: "config".to_string(),
-                path: PathBuf::from("/etc/config"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now - chrono::Duration::days(100),
-                is_favorite: false,
-            },
-            FileAlias {
-                id: "2".to_string(),
-                alias: "configure".to_string(),
-                path: PathBuf::from("/usr/bin/configure"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now - chrono::Duration::days(100),
-                is_favorite: false,
-            },
-        ];
+    #[test]
+    fn test_toggle_tag_collapsed_persists_in_config() {
+        let mut state = AppState::default();
+        // 設定がない場合は切り替えない
+        assert!(!state.toggle_tag_collapsed("work"));
+        assert!(!state.is_tag_collapsed("work"));
+
+        state.config = Some(create_test_config(false, vec![], "O".to_string()));
+        assert!(state.toggle_tag_collapsed("work"));
+        assert!(state.is_tag_collapsed("work"));
+        assert!(!state.is_tag_collapsed("private"));
+
+        // 保存した設定を読み直しても折りたたみ状態が残る
+        let json = serde_json::to_string(state.config.as_ref().unwrap()).unwrap();
+        let reloaded: Config = serde_json::from_str(&json).unwrap();
+        assert!(reloaded.display.collapsed_tags.contains("work"));
+
+        assert!(state.toggle_tag_collapsed("work"));
+        assert!(!state.is_tag_collapsed("work"));
+    }
 
-        state.search_engine.set_aliases(state.file_aliases.clone());
+    #[test]
+    fn test_prune_collapsed_tags_removes_missing_tags() {
+        let mut state = AppState::default();
+        state.alias_manager
+            .add_alias("report".to_string(), PathBuf::from("/work/report"), vec!["work".to_string()], None, false)
+            .unwrap();
+        state.refresh_after_alias_change();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.display.collapsed_tags = ["work", "deleted"].iter().map(|t| t.to_string()).collect();
+        state.config = Some(config);
+
+        assert!(state.prune_collapsed_tags());
+        assert!(state.is_tag_collapsed("work"));
+        assert!(!state.is_tag_collapsed("deleted"));
+
+        // 取り除くものがなければ変更なし
+        assert!(!state.prune_collapsed_tags());
+    }
 
-        // 完全一致のテスト
-        state.search_query = "config".to_string();
-        state.filter_aliases();
+    #[test]
+    fn test_theme_to_apply_follows_system_theme_changes() {
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.theme.mode = "system".to_string();
+        state.config = Some(config);
+        let start = Instant::now();
+        let interval = state.system_theme_cache.interval();
+
+        // 初回は適用する
+        assert_eq!(state.theme_to_apply_with(start, true, || Theme::Dark), Some(Theme::Dark));
+        // 変化がなければ毎フレーム適用し直さない
+        assert_eq!(state.theme_to_apply_with(start + interval, true, || Theme::Dark), None);
+
+        // OSのテーマが変わると、再起動せずに適用し直す
+        let later = start + interval * 2;
+        assert_eq!(state.theme_to_apply_with(later, true, || Theme::Light), Some(Theme::Light));
+        assert_eq!(state.current_theme, Theme::Light);
+        assert_eq!(state.theme_to_apply_with(later, false, || Theme::Light), None);
+    }
 
-        // 完全一致で "config" が最初に来るはず
-        assert!(!state.filtered_items.is_empty());
-        assert_eq!(state.filtered_items[0].alias, "config");
+    #[test]
+    fn test_theme_to_apply_on_config_change() {
+        let mut state = AppState::default();
+        let mut config = create_test_config(false, vec![], "O".to_string());
+        config.theme.mode = "dark".to_string();
+        state.config = Some(config);
+        let now = Instant::now();
+
+        assert_eq!(state.theme_to_apply_with(now, true, || Theme::Light), Some(Theme::Dark));
+        assert_eq!(state.theme_to_apply_with(now, true, || Theme::Light), None);
+
+        // 設定でライトに切り替えた
+        state.config.as_mut().unwrap().theme.mode = "light".to_string();
+        assert_eq!(state.theme_to_apply_with(now, true, || Theme::Dark), Some(Theme::Light));
+        assert!(!state.follows_system_theme());
     }
 
     #[test]
-    fn test_filter_aliases_with_favorite_boost() {
-        // お気に入りブーストのテスト
+    fn test_type_ahead_buffer_builds_and_resets_after_idle() {
+        let mut buffer = TypeAheadBuffer::default();
+        let timeout = Duration::from_millis(1000);
+        let start = Instant::now();
+
+        assert_eq!(buffer.push('r', start, timeout), "r");
+        assert_eq!(buffer.push('e', start + Duration::from_millis(300), timeout), "re");
+        assert_eq!(buffer.push('p', start + Duration::from_millis(900), timeout), "rep");
+        // 間隔が空くと新しい入力として扱う
+        assert_eq!(buffer.push('n', start + Duration::from_millis(2000), timeout), "n");
+
+        buffer.reset();
+        assert_eq!(buffer.as_str(), "");
+    }
+
+    #[test]
+    fn test_type_ahead_input_selects_prefix_match() {
         let mut state = AppState::default();
-        let now = chrono::Utc::now();
+        for name in ["notes", "Report", "repository", "photos"] {
+            state.alias_manager
+                .add_alias(name.to_string(), PathBuf::from(format!("/work/{}", name)), vec![], None, false)
+                .unwrap();
+        }
+        state.refresh_after_alias_change();
+        let index_of = |state: &AppState, name: &str| {
+            state.filtered_items.iter().position(|a| a.alias == name)
+        };
+        let start = Instant::now();
+
+        // 大文字小文字を区別せず、入力するたびに絞り込む
+        let report = index_of(&state, "Report");
+        assert_eq!(state.type_ahead_input('r', start), report);
+        assert_eq!(state.type_ahead_input('e', start + Duration::from_millis(100)), report);
+        assert_eq!(state.type_ahead_input('p', start + Duration::from_millis(200)), report);
+        // "repo" は先に並ぶ "Report" に一致し、"repos" で "repository" に移る
+        assert_eq!(state.type_ahead_input('o', start + Duration::from_millis(300)), report);
+        let repository = index_of(&state, "repository");
+        assert_eq!(state.type_ahead_input('s', start + Duration::from_millis(400)), repository);
+        assert_eq!(state.selected_index, repository);
+
+        // 一致しない入力では選択を変えない
+        assert_eq!(state.type_ahead_input('z', start + Duration::from_millis(500)), None);
+        assert_eq!(state.selected_index, repository);
+
+        // しばらく入力がなければ先頭から探し直す
+        let photos = index_of(&state, "photos");
+        assert_eq!(state.type_ahead_input('p', start + Duration::from_secs(3)), photos);
+        assert_eq!(state.selected_index, photos);
+    }
 
-        // お気に入りとそうでないエイリアスを作成
-        state.file_aliases = vec![
-            FileAlias {
-                id: "1".to_string(),
-                alias: "config".to_string(),
-                path: PathBuf::from("/etc/config"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now - chrono::Duration::days(100),
-                is_favorite: false,
-            },
-            FileAlias {
-                id: "2".to_string(),
-                alias: "config2".to_string(),
-                path: PathBuf::from("/etc/config2"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now - chrono::Duration::days(100),
-                is_favorite: true,  // お気に入り
-            },
-        ];
+    #[test]
+    fn test_double_press_within_threshold() {
+        let mut detector = DoublePressDetector::default();
+        let threshold = Duration::from_millis(400);
+        let start = Instant::now();
+
+        assert!(!detector.press(start, threshold));
+        assert!(detector.press(start + Duration::from_millis(300), threshold));
+        // 2回押しの後はリセットされ、次の押下は1回目として扱う
+        assert!(!detector.press(start + Duration::from_millis(350), threshold));
+    }
 
-        state.search_engine.set_aliases(state.file_aliases.clone());
+    #[test]
+    fn test_double_press_too_slow_restarts() {
+        let mut detector = DoublePressDetector::default();
+        let threshold = Duration::from_millis(400);
+        let start = Instant::now();
+
+        assert!(!detector.press(start, threshold));
+        // 間隔が空きすぎた押下は新しい1回目になる
+        assert!(!detector.press(start + Duration::from_millis(500), threshold));
+        assert!(detector.press(start + Duration::from_millis(800), threshold));
+    }
 
-        // "config" で検索
-        state.search_query = "config".to_string();
-        state.filter_aliases();
+    #[test]
+    fn test_double_press_disabled_with_zero_threshold() {
+        let mut detector = DoublePressDetector::default();
+        let start = Instant::now();
 
-        // お気に入りの "config2" が上位に来るはず
-        // (完全一致のconfigがスコア1.0、前方一致+お気に入りのconfig2がスコア1.0)
-        // ただし、configが完全一致なので最初に来る可能性がある
-        assert!(!state.filtered_items.is_empty());
+        assert!(!detector.press(start, Duration::ZERO));
+        assert!(!detector.press(start + Duration::from_millis(10), Duration::ZERO));
+    }
 
-        // 両方とも結果に含まれているはず
-        let config_found = state.filtered_items.iter().any(|a| a.alias == "config");
-        let config2_found = state.filtered_items.iter().any(|a| a.alias == "config2");
-        assert!(config_found, "configが見つかるはず");
-        assert!(config2_found, "config2が見つかるはず");
+    #[test]
+    fn test_toggle_window_visible_preserves_directory_state() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let child = temp_dir.path().join("child");
+        std::fs::create_dir(&child).unwrap();
+
+        let mut state = AppState::default();
+        state.init_directory_browser(temp_dir.path().to_path_buf()).unwrap();
+        state.browse_mode = BrowseMode::Directory;
+        state.expanded_directories.insert(child.clone());
+        state.selected_directory_index = Some(0);
+        state.multi_selected_paths.insert(child.clone());
+
+        assert!(!state.toggle_window_visible());
+        assert!(state.toggle_window_visible());
+
+        // 隠して再表示しても展開状態と選択はそのまま
+        assert!(state.expanded_directories.contains(&child));
+        assert_eq!(state.selected_directory_index, Some(0));
+        assert!(state.multi_selected_paths.contains(&child));
     }
 
     #[test]
-    fn test_filter_aliases_with_path_search() {
-        // パス検索のテスト
+    fn test_double_escape_restores_cleared_selection() {
         let mut state = AppState::default();
-        let now = chrono::Utc::now();
+        state.browse_mode = BrowseMode::Directory;
+        let expanded = PathBuf::from("/root/expanded");
+        let selected = PathBuf::from("/root/selected");
+        state.expanded_directories.insert(expanded.clone());
+        state.selected_directory_index = Some(2);
+        state.multi_selected_paths.insert(selected.clone());
+        state.directory_search_query = "sel".to_string();
+
+        // 1回目のEscでクリアし、2回目で隠すときに元に戻す
+        state.clear_selection_and_search();
+        assert_eq!(state.selected_directory_index, None);
+        assert!(state.multi_selected_paths.is_empty());
+
+        state.restore_escape_cleared_selection();
+        assert!(!state.toggle_window_visible());
+        assert!(state.toggle_window_visible());
+        assert_eq!(state.selected_directory_index, Some(2));
+        assert!(state.multi_selected_paths.contains(&selected));
+        assert_eq!(state.directory_search_query, "sel");
+        assert!(state.expanded_directories.contains(&expanded));
+
+        // 戻すのは直前のクリア1回分だけ
+        state.selected_directory_index = None;
+        state.restore_escape_cleared_selection();
+        assert_eq!(state.selected_directory_index, None);
+    }
 
-        state.file_aliases = vec![
-            FileAlias {
-                id: "1".to_string(),
-                alias: "doc".to_string(),
-                path: PathBuf::from("/documents/important/file.txt"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now - chrono::Duration::days(100),
-                is_favorite: false,
-            },
-            FileAlias {
-                id: "2".to_string(),
-                alias: "test".to_string(),
-                path: PathBuf::from("/path/to/test"),
-                tags: vec![],
-                color: None,
-                created_at: now,
-                last_accessed: now - chrono::Duration::days(100),
-                is_favorite: false,
-            },
-        ];
+    #[test]
+    fn test_delete_dialog_requires_ack_for_hidden_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let normal = temp_dir.path().join("normal.txt");
+        std::fs::write(&normal, "").unwrap();
+
+        let dialog = DeleteConfirmationDialog::new(vec![normal.clone()]);
+        assert!(dialog.sensitive_names.is_empty());
+        assert!(dialog.can_confirm());
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let hidden = temp_dir.path().join(".config");
+            std::fs::write(&hidden, "").unwrap();
+
+            let mut dialog = DeleteConfirmationDialog::new(vec![normal, hidden]);
+            assert_eq!(dialog.sensitive_names, vec![".config".to_string()]);
+            assert!(!dialog.can_confirm());
+            dialog.sensitive_acknowledged = true;
+            assert!(dialog.can_confirm());
+        }
+    }
 
-        state.search_engine.set_aliases(state.file_aliases.clone());
+    #[test]
+    fn test_overwrite_conflict_pairs_source_and_destination_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("src").join("report.txt");
+        let destination = temp_dir.path().join("report.txt");
+        std::fs::create_dir(source.parent().unwrap()).unwrap();
+        std::fs::write(&source, "new content").unwrap();
+        std::fs::write(&destination, "old").unwrap();
+
+        // 貼り付け先を1時間前、貼り付け元を現在の更新日時にする
+        let now = std::time::SystemTime::now();
+        let hour_ago = now - Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&destination).unwrap().set_modified(hour_ago).unwrap();
+        std::fs::File::options().write(true).open(&source).unwrap().set_modified(now).unwrap();
+
+        let conflict = OverwriteConflict::from_paths(source.clone(), destination.clone());
+        assert_eq!(conflict.file_name(), "report.txt");
+        assert_eq!(conflict.source, source);
+        assert_eq!(conflict.destination, destination);
+        assert_eq!(conflict.source_info.unwrap().size, Some(11));
+        assert_eq!(conflict.destination_info.unwrap().size, Some(3));
+        assert_eq!(conflict.newer_side(), Some(NewerSide::Source));
+        assert!(conflict.source_info.unwrap().summary().starts_with("11 B・"));
+    }
 
-        // パスに対する検索
-        state.search_query = "documents".to_string();
-        state.filter_aliases();
+    #[test]
+    fn test_overwrite_conflict_newer_side() {
+        let earlier = chrono::Utc::now() - chrono::Duration::days(1);
+        let later = chrono::Utc::now();
+        let info = |modified| Some(ConflictFileInfo { size: Some(1), modified });
+        let conflict = |source, destination| OverwriteConflict {
+            source: PathBuf::from("/a/file.txt"),
+            destination: PathBuf::from("/b/file.txt"),
+            source_info: source,
+            destination_info: destination,
+        };
 
-        // パスにマッチしたエイリアスが見つかるはず
-        assert!(!state.filtered_items.is_empty());
-        assert_eq!(state.filtered_items[0].alias, "doc");
+        assert_eq!(conflict(info(Some(earlier)), info(Some(later))).newer_side(), Some(NewerSide::Destination));
+        assert_eq!(conflict(info(Some(later)), info(Some(later))).newer_side(), Some(NewerSide::Same));
+        // どちらかの日時が不明なら判定しない
+        assert_eq!(conflict(info(None), info(Some(later))).newer_side(), None);
+        assert_eq!(conflict(info(Some(later)), None).newer_side(), None);
+
+        // フォルダはサイズの代わりに「フォルダ」と表示する
+        let folder = ConflictFileInfo { size: None, modified: None };
+        assert_eq!(folder.summary(), "フォルダ・日時不明");
     }
 
     #[test]
-    fn test_filter_aliases_with_tag_search() {
-        // タグ検索のテスト
-        let mut state = AppState::default();
-        let now = chrono::Utc::now();
-
-        let mut alias_with_tags = FileAlias {
-            id: "1".to_string(),
-            alias: "document".to_string(),
-            path: PathBuf::from("/path/to/doc"),
-            tags: vec!["important".to_string(), "work".to_string()],
-            color: None,
-            created_at: now,
-            last_accessed: now - chrono::Duration::days(100),
-            is_favorite: false,
+    fn test_newer_destination_count_depends_on_conflict_policy() {
+        let earlier = chrono::Utc::now() - chrono::Duration::days(1);
+        let later = chrono::Utc::now();
+        let conflict = |source, destination| OverwriteConflict {
+            source: PathBuf::from("/a/file.txt"),
+            destination: PathBuf::from("/b/file.txt"),
+            source_info: Some(ConflictFileInfo { size: Some(1), modified: Some(source) }),
+            destination_info: Some(ConflictFileInfo { size: Some(1), modified: Some(destination) }),
         };
+        let mut dialog = OverwriteConfirmationDialog::new(
+            vec![conflict(earlier, later), conflict(later, earlier), conflict(later, later)],
+            vec![],
+            None,
+            PendingPasteOperation {
+                src_paths: vec![],
+                dest_dir: PathBuf::from("/b"),
+                mode: crate::core::clipboard::ClipboardMode::Copy,
+                conflict_policy: ConflictPolicy::Overwrite,
+                resolutions: HashMap::new(),
+            },
+        );
 
-        state.file_aliases = vec![alias_with_tags];
-        state.search_engine.set_aliases(state.file_aliases.clone());
+        // 常に上書きする設定ではスキップしない
+        assert_eq!(dialog.newer_destination_count(), 0);
 
-        // タグに対する検索
-        state.search_query = "important".to_string();
-        state.filter_aliases();
+        dialog.pending_paste.conflict_policy = ConflictPolicy::OverwriteOnlyIfOlder;
+        assert_eq!(dialog.newer_destination_count(), 1);
+    }
 
-        // タグにマッチしたエイリアスが見つかるはず
-        assert!(!state.filtered_items.is_empty());
-        assert_eq!(state.filtered_items[0].alias, "document");
+    #[test]
+    fn test_overwrite_dialog_per_file_resolutions() {
+        let earlier = chrono::Utc::now() - chrono::Duration::days(1);
+        let later = chrono::Utc::now();
+        let conflict = |name: &str, source, destination| OverwriteConflict {
+            source: PathBuf::from("/a").join(name),
+            destination: PathBuf::from("/b").join(name),
+            source_info: Some(ConflictFileInfo { size: Some(1), modified: Some(source) }),
+            destination_info: Some(ConflictFileInfo { size: Some(1), modified: Some(destination) }),
+        };
+        let mut dialog = OverwriteConfirmationDialog::new(
+            vec![conflict("1.txt", later, earlier), conflict("2.txt", earlier, later), conflict("3.txt", later, earlier)],
+            vec![],
+            None,
+            PendingPasteOperation {
+                src_paths: vec![],
+                dest_dir: PathBuf::from("/b"),
+                mode: crate::core::clipboard::ClipboardMode::Copy,
+                conflict_policy: ConflictPolicy::OverwriteOnlyIfOlder,
+                resolutions: HashMap::new(),
+            },
+        );
+
+        // 貼り付け先の方が新しい項目だけ、初期値がスキップになる
+        use ConflictResolution::*;
+        assert_eq!(dialog.resolutions, vec![Overwrite, Skip, Overwrite]);
+
+        // 「以降すべてに適用」を付けた項目の選択は後ろの項目にも反映される
+        dialog.set_resolution(0, KeepBoth);
+        assert_eq!(dialog.resolutions, vec![KeepBoth, Skip, Overwrite]);
+        dialog.set_apply_to_remaining(1, true);
+        assert_eq!(dialog.resolutions, vec![KeepBoth, Skip, Skip]);
+        dialog.set_resolution(1, Overwrite);
+        assert_eq!(dialog.resolutions, vec![KeepBoth, Overwrite, Overwrite]);
+        assert_eq!(dialog.resolution_count(Overwrite), 2);
+
+        let map = dialog.resolution_map();
+        assert_eq!(map[&PathBuf::from("/a/1.txt")], KeepBoth);
+        assert_eq!(map[&PathBuf::from("/a/3.txt")], Overwrite);
     }
 }
@@ -0,0 +1,215 @@
+//! 項目を開く（Enter・ダブルクリック）ときの動作の判定
+//!
+//! エイリアス一覧・ディレクトリ一覧・「最近」タブで共通の入口です。
+//! フォルダは移動先として返し、ファイルは `OpenAction` に従って開きます。
+//! ショートカット（.lnk）はリンク先に置き換えてから同じ規則で扱います。
+
+use crate::core::file_manager::{is_shortcut_file, open_with_action, resolve_lnk, FileOpener};
+use crate::data::models::OpenAction;
+use std::path::{Path, PathBuf};
+
+/// 項目を開いた結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivationResult {
+    /// フォルダへ移動する（移動は呼び出し元で行う）
+    Navigate(PathBuf),
+    /// ファイルを開いた（ショートカットの場合はリンク先のパス）
+    Opened(PathBuf),
+    /// アーカイブの中をフォルダとして表示する（表示は呼び出し元で行う）
+    EnterArchive(PathBuf),
+}
+
+impl ActivationResult {
+    /// 移動先・開いた項目のパス
+    pub fn path(&self) -> &Path {
+        match self {
+            ActivationResult::Navigate(path)
+            | ActivationResult::Opened(path)
+            | ActivationResult::EnterArchive(path) => path,
+        }
+    }
+}
+
+/// 項目を開くときの条件
+pub struct ActivationContext<'a, O: FileOpener> {
+    /// 開く操作の実装（通常は `FileManager`）
+    pub opener: &'a O,
+    /// 開くときの動作（エイリアス以外は `OpenAction::Open`）
+    pub open_action: &'a OpenAction,
+    /// アーカイブの中をフォルダとして表示するか
+    pub browse_archives: bool,
+}
+
+impl<'a, O: FileOpener> ActivationContext<'a, O> {
+    /// アーカイブを既定のアプリで開く条件を作成
+    pub fn new(opener: &'a O, open_action: &'a OpenAction) -> Self {
+        Self {
+            opener,
+            open_action,
+            browse_archives: false,
+        }
+    }
+}
+
+/// 中をフォルダとして表示できるアーカイブか（現在は ZIP のみ）
+pub fn is_archive_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+/// 項目を開く
+///
+/// * フォルダ（動作が `Open` の場合）→ `Navigate`
+/// * ショートカット → リンク先を解決して同じ規則で開く（解決できなければショートカット自体を開く）
+/// * アーカイブ（`browse_archives` が有効で動作が `Open` の場合）→ `EnterArchive`
+/// * それ以外 → `OpenAction` に従って開き `Opened`
+///
+/// # 引数
+/// * `path` - 開く項目のパス
+/// * `context` - 開くときの条件
+///
+/// # 戻り値
+/// * `Ok(ActivationResult)` - 呼び出し元で行う後処理
+/// * `Err(String)` - エラーメッセージ
+pub fn activate<O: FileOpener>(path: &Path, context: &ActivationContext<'_, O>) -> Result<ActivationResult, String> {
+    let target = if is_shortcut_file(path) {
+        match resolve_lnk(path) {
+            Ok(target) => {
+                log::debug!("ショートカットを解決: {} -> {}", path.display(), target.display());
+                target
+            }
+            Err(e) => {
+                log::warn!("ショートカットを解決できないため、そのまま開きます: {}", e);
+                path.to_path_buf()
+            }
+        }
+    } else {
+        path.to_path_buf()
+    };
+
+    let default_action = *context.open_action == OpenAction::Open;
+    if default_action && target.is_dir() {
+        return Ok(ActivationResult::Navigate(target));
+    }
+    if default_action && context.browse_archives && is_archive_file(&target) && target.is_file() {
+        return Ok(ActivationResult::EnterArchive(target));
+    }
+
+    open_with_action(context.opener, &target, context.open_action)?;
+    Ok(ActivationResult::Opened(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct RecordingOpener {
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl FileOpener for RecordingOpener {
+        fn open(&self, path: &Path) -> Result<(), String> {
+            self.calls.borrow_mut().push(format!("open:{}", path.display()));
+            Ok(())
+        }
+
+        fn reveal_in_folder(&self, path: &Path) -> Result<(), String> {
+            self.calls.borrow_mut().push(format!("reveal:{}", path.display()));
+            Ok(())
+        }
+
+        fn open_with(&self, path: &Path, program: &str) -> Result<(), String> {
+            self.calls.borrow_mut().push(format!("open_with:{}:{}", program, path.display()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_activate_directory_and_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("memo.txt");
+        std::fs::write(&file, "").unwrap();
+        let opener = RecordingOpener::default();
+        let context = ActivationContext::new(&opener, &OpenAction::Open);
+
+        // フォルダは開かずに移動先として返す
+        assert_eq!(
+            activate(temp_dir.path(), &context).unwrap(),
+            ActivationResult::Navigate(temp_dir.path().to_path_buf())
+        );
+        assert!(opener.calls.borrow().is_empty());
+
+        assert_eq!(activate(&file, &context).unwrap(), ActivationResult::Opened(file.clone()));
+        assert_eq!(*opener.calls.borrow(), vec![format!("open:{}", file.display())]);
+    }
+
+    #[test]
+    fn test_activate_follows_open_action() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let opener = RecordingOpener::default();
+        let action = OpenAction::RevealInFolder;
+        let context = ActivationContext::new(&opener, &action);
+
+        // 既定以外の動作ではフォルダも移動せずに動作どおり開く
+        assert_eq!(
+            activate(temp_dir.path(), &context).unwrap(),
+            ActivationResult::Opened(temp_dir.path().to_path_buf())
+        );
+        assert_eq!(*opener.calls.borrow(), vec![format!("reveal:{}", temp_dir.path().display())]);
+    }
+
+    #[test]
+    fn test_activate_archive_only_when_enabled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = temp_dir.path().join("photos.ZIP");
+        std::fs::write(&archive, "").unwrap();
+        let opener = RecordingOpener::default();
+        let mut context = ActivationContext::new(&opener, &OpenAction::Open);
+
+        assert_eq!(activate(&archive, &context).unwrap(), ActivationResult::Opened(archive.clone()));
+
+        context.browse_archives = true;
+        assert_eq!(activate(&archive, &context).unwrap(), ActivationResult::EnterArchive(archive.clone()));
+        assert_eq!(opener.calls.borrow().len(), 1);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_activate_unresolved_shortcut_opens_itself() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let shortcut = temp_dir.path().join("link.lnk");
+        std::fs::write(&shortcut, "").unwrap();
+        let opener = RecordingOpener::default();
+        let context = ActivationContext::new(&opener, &OpenAction::Open);
+
+        assert_eq!(activate(&shortcut, &context).unwrap(), ActivationResult::Opened(shortcut.clone()));
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_activate_shortcut_to_directory_navigates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = temp_dir.path().join("target");
+        std::fs::create_dir(&target).unwrap();
+        let shortcut = temp_dir.path().join("target.lnk");
+
+        // WScript.Shell でショートカットを作成
+        let script = format!(
+            "$s = (New-Object -ComObject WScript.Shell).CreateShortcut('{}'); $s.TargetPath = '{}'; $s.Save()",
+            shortcut.display(),
+            target.display()
+        );
+        let status = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let opener = RecordingOpener::default();
+        let context = ActivationContext::new(&opener, &OpenAction::Open);
+        let result = activate(&shortcut, &context).unwrap();
+        assert!(matches!(result, ActivationResult::Navigate(_)));
+        assert_eq!(result.path().canonicalize().unwrap(), target.canonicalize().unwrap());
+    }
+}
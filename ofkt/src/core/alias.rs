@@ -1,8 +1,10 @@
-use crate::data::models::FileAlias;
+use crate::core::alias_csv::{self, CsvImportSummary};
+use crate::data::models::{FileAlias, OpenAction, VolumeId};
 use crate::data::storage;
+use crate::utils::path::{path_key, paths_equal, remap_prefix};
 use anyhow::Result;
 use chrono::Utc;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// エイリアス管理
@@ -36,6 +38,9 @@ impl AliasManager {
         // UUID生成
         let id = Uuid::new_v4().to_string();
 
+        // 末尾に並べる
+        let order = self.aliases.last().map_or(0, |a| a.order + 1);
+
         // タイムスタンプ生成
         let now = Utc::now();
 
@@ -49,6 +54,14 @@ impl AliasManager {
             created_at: now,
             last_accessed: now,
             is_favorite,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order,
+            is_broken: false,
+            is_unreachable: false,
         };
 
         // リストに追加
@@ -57,11 +70,76 @@ impl AliasManager {
         Ok(())
     }
 
-    /// エイリアス一覧を取得
+    /// エイリアス一覧を取得（`order` 順）
     pub fn get_aliases(&self) -> &[FileAlias] {
         &self.aliases
     }
 
+    /// エイリアスを1つ前に移動
+    ///
+    /// 先頭のエイリアスの場合は何もしません。
+    pub fn move_up(&mut self, id: &str) -> Result<(), String> {
+        let index = self.position_by_id(id)?;
+        if index > 0 {
+            self.aliases.swap(index - 1, index);
+            self.renumber();
+        }
+        Ok(())
+    }
+
+    /// エイリアスを1つ後ろに移動
+    ///
+    /// 末尾のエイリアスの場合は何もしません。
+    pub fn move_down(&mut self, id: &str) -> Result<(), String> {
+        let index = self.position_by_id(id)?;
+        if index + 1 < self.aliases.len() {
+            self.aliases.swap(index, index + 1);
+            self.renumber();
+        }
+        Ok(())
+    }
+
+    /// エイリアスを指定したIDの順に並べ替え
+    ///
+    /// `ids` に含まれないエイリアスは、元の順のまま `ids` のエイリアスの後ろに並べます。
+    ///
+    /// # 引数
+    /// * `ids` - 先頭から並べるエイリアスのID
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 並べ替えに成功
+    /// * `Err(String)` - 存在しないIDが含まれている場合（並びは変更しません）
+    pub fn reorder(&mut self, ids: &[String]) -> Result<(), String> {
+        if let Some(missing) = ids.iter().find(|id| !self.aliases.iter().any(|a| &a.id == *id)) {
+            return Err(format!("エイリアスID '{}' は存在しません", missing));
+        }
+
+        let mut rest = std::mem::take(&mut self.aliases);
+        for id in ids {
+            if let Some(index) = rest.iter().position(|a| &a.id == id) {
+                self.aliases.push(rest.remove(index));
+            }
+        }
+        self.aliases.append(&mut rest);
+        self.renumber();
+        Ok(())
+    }
+
+    /// IDからエイリアスの位置を取得
+    fn position_by_id(&self, id: &str) -> Result<usize, String> {
+        self.aliases
+            .iter()
+            .position(|a| a.id == id)
+            .ok_or_else(|| format!("エイリアスID '{}' は存在しません", id))
+    }
+
+    /// 現在の並びで `order` を 0 からの連番に振り直す
+    fn renumber(&mut self) {
+        for (order, alias) in (0..).zip(self.aliases.iter_mut()) {
+            alias.order = order;
+        }
+    }
+
     /// IDでエイリアスを削除
     pub fn remove_alias_by_id(&mut self, id: &str) -> Result<(), String> {
         let index = self
@@ -87,6 +165,8 @@ impl AliasManager {
     }
 
     /// エイリアスを更新
+    ///
+    /// 名前を変更する場合、空の名前や他のエイリアスと重複する名前は受け付けません。
     pub fn update_alias(
         &mut self,
         id: &str,
@@ -96,12 +176,24 @@ impl AliasManager {
         color: Option<Option<String>>,
         is_favorite: Option<bool>,
     ) -> Result<(), String> {
-        let file_alias = self
+        let index = self
             .aliases
-            .iter_mut()
-            .find(|a| a.id == id)
+            .iter()
+            .position(|a| a.id == id)
             .ok_or_else(|| format!("エイリアスID '{}' は存在しません", id))?;
 
+        // 名前の検証（更新前に行い、失敗した場合は何も変更しない）
+        if let Some(alias_val) = &alias {
+            if alias_val.trim().is_empty() {
+                return Err("エイリアス名を入力してください".to_string());
+            }
+            if self.aliases.iter().any(|a| a.alias == *alias_val && a.id != id) {
+                return Err(format!("エイリアス '{}' は既に存在します", alias_val));
+            }
+        }
+
+        let file_alias = &mut self.aliases[index];
+
         // Option値の更新
         if let Some(alias_val) = alias {
             file_alias.alias = alias_val;
@@ -122,14 +214,162 @@ impl AliasManager {
         Ok(())
     }
 
+    /// フォルダ直下のサブフォルダをまとめてエイリアスとして登録
+    ///
+    /// サブフォルダごとにフォルダ名のエイリアスを作成し、指定したタグを付けます。
+    /// 同じパスのエイリアスが既にある場合はスキップし、
+    /// 名前が重複する場合は " (2)" のような連番を付けます。
+    ///
+    /// # 引数
+    /// * `root` - スキャンするフォルダ
+    /// * `tag` - 作成したエイリアスに付けるタグ（空文字の場合は付けない）
+    ///
+    /// # 戻り値
+    /// * `Ok(usize)` - 作成したエイリアスの数
+    /// * `Err(String)` - エラーメッセージ
+    pub fn import_subdirs(&mut self, root: &Path, tag: &str) -> Result<usize, String> {
+        if !root.is_dir() {
+            return Err(format!("フォルダ '{}' は存在しません", root.display()));
+        }
+
+        let mut subdirs: Vec<PathBuf> = std::fs::read_dir(root)
+            .map_err(|e| format!("フォルダの読み込みに失敗: {}", e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        // 作成順を安定させるため名前順に並べる
+        subdirs.sort();
+
+        let tags = if tag.trim().is_empty() {
+            Vec::new()
+        } else {
+            vec![tag.trim().to_string()]
+        };
+
+        let mut created = 0;
+        for path in subdirs {
+            if self.aliases.iter().any(|a| paths_equal(&a.path, &path)) {
+                log::debug!("既に登録済みのためスキップ: {}", path.display());
+                continue;
+            }
+
+            let base_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string());
+            let mut name = base_name.clone();
+            let mut counter = 2;
+            while self.aliases.iter().any(|a| a.alias == name) {
+                name = format!("{} ({})", base_name, counter);
+                counter += 1;
+            }
+
+            self.add_alias(name, path, tags.clone(), None, false)?;
+            created += 1;
+        }
+
+        log::info!("サブフォルダから {} 件のエイリアスを作成: {}", created, root.display());
+        Ok(created)
+    }
+
+    /// エイリアスをCSVファイルにエクスポート
+    ///
+    /// カラムは `alias,path,tags,color,is_favorite` です（タグはセミコロン区切り）。
+    ///
+    /// # 引数
+    /// * `path` - 作成するCSVファイルのパス
+    pub fn export_csv(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, alias_csv::to_csv(&self.aliases))
+            .map_err(|e| format!("CSVファイルの書き込みに失敗: {}", e))?;
+        log::info!("{} 件のエイリアスをCSVにエクスポート: {}", self.aliases.len(), path.display());
+        Ok(())
+    }
+
+    /// CSVファイルからエイリアスをインポート
+    ///
+    /// 既存のエイリアス（またはCSV内の先の行）と同じ名前のエイリアスはスキップします。
+    /// CSVに不正な行がある場合は何も追加しません。
+    ///
+    /// # 引数
+    /// * `path` - 読み込むCSVファイルのパス
+    ///
+    /// # 戻り値
+    /// * `Ok(CsvImportSummary)` - 追加した件数とスキップした件数
+    /// * `Err(String)` - エラーメッセージ
+    pub fn import_csv(&mut self, path: &Path) -> Result<CsvImportSummary, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("CSVファイルの読み込みに失敗: {}", e))?;
+        let rows = alias_csv::parse_csv(&text)?;
+
+        let mut summary = CsvImportSummary::default();
+        for row in rows {
+            if self.aliases.iter().any(|a| a.alias == row.alias) {
+                log::debug!("同名のエイリアスがあるためスキップ: {}", row.alias);
+                summary.skipped += 1;
+                continue;
+            }
+            self.add_alias(row.alias, row.path, row.tags, row.color, row.is_favorite)?;
+            summary.added += 1;
+        }
+
+        log::info!(
+            "CSVからエイリアスをインポート: 追加 {} 件、スキップ {} 件 ({})",
+            summary.added,
+            summary.skipped,
+            path.display()
+        );
+        Ok(summary)
+    }
+
     /// エイリアスリストをファイルに保存
-    pub fn save(&self) -> Result<()> {
+    ///
+    /// 保存前に `order` を連番に振り直します。
+    pub fn save(&mut self) -> Result<()> {
+        self.renumber();
         storage::save_aliases(&self.aliases)
     }
 
     /// ファイルからエイリアスリストを読み込み
+    ///
+    /// `order` 順に並べます。`order` のない古いファイル（すべて 0）はファイルの順のままです。
     pub fn load(&mut self) -> Result<()> {
         self.aliases = storage::load_aliases()?;
+        self.aliases.sort_by_key(|a| a.order);
+        Ok(())
+    }
+
+    /// 名前が重複しているエイリアスを検出
+    ///
+    /// `add_alias` では重複を拒否しますが、インポートや手動編集されたファイルを
+    /// `load` した場合には重複が含まれることがあります。
+    ///
+    /// # 戻り値
+    /// (エイリアス名, 同じ名前のエイリアスIDリスト) のリスト（最初に出現した順）
+    pub fn find_duplicate_names(&self) -> Vec<(String, Vec<String>)> {
+        let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+        for alias in &self.aliases {
+            match groups.iter_mut().find(|(name, _)| *name == alias.alias) {
+                Some((_, ids)) => ids.push(alias.id.clone()),
+                None => groups.push((alias.alias.clone(), vec![alias.id.clone()])),
+            }
+        }
+
+        groups.retain(|(_, ids)| ids.len() > 1);
+        groups
+    }
+
+    /// エイリアスにボリューム情報を設定
+    ///
+    /// リムーバブルドライブ上のエイリアスで、ドライブレターが変わっても
+    /// パスを解決できるようにするために使用します。
+    pub fn set_volume(&mut self, alias: &str, volume: Option<VolumeId>) -> Result<(), String> {
+        let file_alias = self.aliases
+            .iter_mut()
+            .find(|a| a.alias == alias)
+            .ok_or_else(|| format!("エイリアス '{}' は存在しません", alias))?;
+
+        file_alias.volume = volume;
         Ok(())
     }
 
@@ -144,6 +384,85 @@ impl AliasManager {
         Ok(())
     }
 
+    /// エイリアスを開いたことを記録
+    ///
+    /// 開いた回数を1増やし、最終アクセス日時を現在時刻に更新します。
+    pub fn record_open(&mut self, id: &str) -> Result<(), String> {
+        let alias = self.aliases
+            .iter_mut()
+            .find(|a| a.id == id)
+            .ok_or_else(|| format!("エイリアスID '{}' は存在しません", id))?;
+
+        alias.open_count = alias.open_count.saturating_add(1);
+        alias.last_accessed = Utc::now();
+        Ok(())
+    }
+
+    /// リネーム・移動されたパス配下を指すエイリアスのパスを付け替える
+    ///
+    /// # 引数
+    /// * `from` - 変更前のパス
+    /// * `to` - 変更後のパス
+    ///
+    /// # 戻り値
+    /// パスを更新したエイリアスの件数
+    pub fn remap_paths(&mut self, from: &Path, to: &Path) -> usize {
+        let mut updated = 0;
+        for alias in &mut self.aliases {
+            if let Some(new_path) = remap_prefix(&alias.path, from, to) {
+                alias.path = new_path;
+                updated += 1;
+            }
+        }
+        updated
+    }
+
+    /// 指定したパスを指すエイリアスにタグを追加
+    ///
+    /// 同じタグ（大文字小文字を区別しない）が既に付いているエイリアスは変更しません。
+    ///
+    /// # 引数
+    /// * `paths` - 対象のパス
+    /// * `tag` - 追加するタグ
+    ///
+    /// # 戻り値
+    /// タグを追加したエイリアスの件数
+    pub fn add_tag_to_paths(&mut self, paths: &[PathBuf], tag: &str) -> usize {
+        let mut updated = 0;
+        for alias in &mut self.aliases {
+            if !paths.contains(&alias.path) {
+                continue;
+            }
+            if alias.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                continue;
+            }
+            alias.tags.push(tag.to_string());
+            updated += 1;
+        }
+        updated
+    }
+
+    /// よく使うエイリアスを取得
+    ///
+    /// 開いた回数の多い順に並べ、同じ回数の場合は最近アクセスしたものを優先します。
+    /// 一度も開いていないエイリアスは含みません。
+    ///
+    /// # 引数
+    /// * `n` - 取得する最大件数
+    pub fn most_used(&self, n: usize) -> Vec<&FileAlias> {
+        let mut used: Vec<&FileAlias> = self.aliases
+            .iter()
+            .filter(|a| a.open_count > 0)
+            .collect();
+        used.sort_by(|a, b| {
+            b.open_count
+                .cmp(&a.open_count)
+                .then_with(|| b.last_accessed.cmp(&a.last_accessed))
+        });
+        used.truncate(n);
+        used
+    }
+
     /// お気に入り一覧を取得
     pub fn get_favorites(&self) -> Vec<&FileAlias> {
         self.aliases
@@ -151,6 +470,130 @@ impl AliasManager {
             .filter(|a| a.is_favorite)
             .collect()
     }
+
+    /// サイドバー表示順（お気に入り優先、その後名前順）のエイリアス一覧を取得
+    pub fn sorted_for_sidebar(&self) -> Vec<&FileAlias> {
+        sorted_for_sidebar(&self.aliases)
+    }
+
+    /// 指定したパスと同じ場所を指すエイリアスを探す
+    ///
+    /// 環境変数の展開・大文字小文字・区切り文字の違いを無視して比べます（`path_key` を参照）。
+    pub fn find_by_path(&self, path: &Path) -> Option<&FileAlias> {
+        let key = path_key(path);
+        self.aliases.iter().find(|alias| path_key(&alias.path) == key)
+    }
+
+    /// パスが存在しないエイリアスのIDを取得
+    pub fn validate_paths(&self) -> Vec<String> {
+        self.validate_all().into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// パスが存在しないエイリアスの (ID, パス) を取得
+    pub fn validate_all(&self) -> Vec<(String, PathBuf)> {
+        self.validate_all_with(|alias| alias.path.clone())
+    }
+
+    /// パスが存在しないエイリアスの (ID, 確認したパス) を取得（実際のパスの解決方法を指定）
+    ///
+    /// # 引数
+    /// * `resolve` - エイリアスから確認するパスを求める（環境変数の展開・ボリュームの追従など）
+    pub fn validate_all_with(&self, resolve: impl Fn(&FileAlias) -> PathBuf) -> Vec<(String, PathBuf)> {
+        self.aliases
+            .iter()
+            .map(|alias| (alias.id.clone(), resolve(alias)))
+            .filter(|(_, path)| !path.exists())
+            .collect()
+    }
+
+    /// 指定したIDのエイリアスを壊れている（パスが見つからない）として印を付ける
+    ///
+    /// 指定していないエイリアスの印は外します。
+    ///
+    /// # 戻り値
+    /// 印を付けたエイリアスの件数
+    pub fn mark_broken(&mut self, ids: &[String]) -> usize {
+        let mut marked = 0;
+        for alias in &mut self.aliases {
+            alias.is_broken = ids.contains(&alias.id);
+            if alias.is_broken {
+                marked += 1;
+            }
+        }
+        marked
+    }
+
+    /// 指定したIDのエイリアスを確認できない（ネットワークパスに接続できない）として印を付ける
+    ///
+    /// 指定していないエイリアスの印は外します。
+    ///
+    /// # 戻り値
+    /// 印を付けたエイリアスの件数
+    pub fn mark_unreachable(&mut self, ids: &[String]) -> usize {
+        let mut marked = 0;
+        for alias in &mut self.aliases {
+            alias.is_unreachable = ids.contains(&alias.id);
+            if alias.is_unreachable {
+                marked += 1;
+            }
+        }
+        marked
+    }
+}
+
+/// 見つからなくなったフォルダの移動先の候補を探す
+///
+/// 元の親フォルダと同じ階層にあるフォルダ（親の兄弟）の中に、同じ名前の項目があるものを返します。
+/// 例えば `/work/2024/report` が見つからない場合、`/work/2025/report` などが候補になります。
+///
+/// # 引数
+/// * `old_path` - 見つからなくなったパス
+///
+/// # 戻り値
+/// 名前順の候補（元のパスは含まない）
+pub fn find_nearby_candidates(old_path: &Path) -> Vec<PathBuf> {
+    let (Some(name), Some(parent)) = (old_path.file_name(), old_path.parent()) else {
+        return Vec::new();
+    };
+    let Some(grandparent) = parent.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(grandparent) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|sibling| sibling.is_dir())
+        .map(|sibling| sibling.join(name))
+        .filter(|candidate| candidate.exists() && !paths_equal(candidate, old_path))
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+/// エイリアスをサイドバー表示順に並べる
+///
+/// お気に入りを先頭にし、同じグループ内はエイリアス名の順（文字コード順）に並べます。
+/// 安定ソートのため、同名のエイリアスは元の順序を維持します。
+///
+/// # 引数
+/// * `aliases` - 並べ替えるエイリアス
+///
+/// # 戻り値
+/// 並べ替えたエイリアスへの参照リスト
+pub fn sorted_for_sidebar(aliases: &[FileAlias]) -> Vec<&FileAlias> {
+    let mut sorted: Vec<&FileAlias> = aliases.iter().collect();
+    sorted.sort_by(|a, b| {
+        // お気に入りを優先、その後名前順
+        match (b.is_favorite, a.is_favorite) {
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            _ => a.alias.cmp(&b.alias),
+        }
+    });
+    sorted
 }
 
 impl Default for AliasManager {
@@ -561,6 +1004,46 @@ mod tests {
         assert_eq!(alias.alias, "test");
     }
 
+    #[test]
+    fn test_update_alias_rejects_duplicate_name() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("docs".to_string(), PathBuf::from("/docs"), vec![], None, false).unwrap();
+        manager.add_alias("work".to_string(), PathBuf::from("/work"), vec![], None, false).unwrap();
+        let work_id = manager.get_aliases()[1].id.clone();
+
+        // 他のエイリアスと同じ名前には変更できず、他の項目も更新されない
+        let result = manager.update_alias(
+            &work_id,
+            Some("docs".to_string()),
+            Some(PathBuf::from("/new/work")),
+            None,
+            None,
+            Some(true),
+        );
+        assert_eq!(result.unwrap_err(), "エイリアス 'docs' は既に存在します");
+        let work = &manager.get_aliases()[1];
+        assert_eq!(work.alias, "work");
+        assert_eq!(work.path, PathBuf::from("/work"));
+        assert!(!work.is_favorite);
+
+        // 自分自身の名前のままなら更新できる
+        manager
+            .update_alias(&work_id, Some("work".to_string()), Some(PathBuf::from("/new/work")), None, None, None)
+            .unwrap();
+        assert_eq!(manager.get_aliases()[1].path, PathBuf::from("/new/work"));
+    }
+
+    #[test]
+    fn test_update_alias_rejects_empty_name() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("docs".to_string(), PathBuf::from("/docs"), vec![], None, false).unwrap();
+        let id = manager.get_aliases()[0].id.clone();
+
+        let result = manager.update_alias(&id, Some("  ".to_string()), None, None, None, None);
+        assert_eq!(result.unwrap_err(), "エイリアス名を入力してください");
+        assert_eq!(manager.get_aliases()[0].alias, "docs");
+    }
+
     #[test]
     fn test_update_alias_clear_color() {
         let mut manager = AliasManager::new();
@@ -720,6 +1203,72 @@ mod tests {
         assert_eq!(alias2.is_favorite, false);
     }
 
+    #[test]
+    fn test_save_renumbers_and_load_sorts_by_order() {
+        use std::env;
+        use std::fs;
+
+        let _lock = TEST_ENV_LOCK.lock().unwrap();
+
+        let temp_dir = env::temp_dir().join(format!("ofkt_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&temp_dir).unwrap();
+        let original_config_home = env::var("XDG_CONFIG_HOME").ok();
+
+        struct EnvGuard {
+            original: Option<String>,
+            temp_dir: PathBuf,
+        }
+
+        impl Drop for EnvGuard {
+            fn drop(&mut self) {
+                if let Some(original) = &self.original {
+                    env::set_var("XDG_CONFIG_HOME", original);
+                } else {
+                    env::remove_var("XDG_CONFIG_HOME");
+                }
+                fs::remove_dir_all(&self.temp_dir).ok();
+            }
+        }
+
+        let _guard = EnvGuard {
+            original: original_config_home,
+            temp_dir: temp_dir.clone(),
+        };
+        env::set_var("XDG_CONFIG_HOME", &temp_dir);
+
+        let mut manager = AliasManager::new();
+        manager.add_alias("a".to_string(), PathBuf::from("/a"), vec![], None, false).unwrap();
+        manager.add_alias("b".to_string(), PathBuf::from("/b"), vec![], None, false).unwrap();
+        manager.add_alias("c".to_string(), PathBuf::from("/c"), vec![], None, false).unwrap();
+        manager.remove_alias_by_name("b").unwrap();
+        let orders: Vec<u32> = manager.get_aliases().iter().map(|a| a.order).collect();
+        assert_eq!(orders, vec![0, 2]);
+
+        // 保存時に連番に振り直す
+        manager.save().unwrap();
+        let orders: Vec<u32> = manager.get_aliases().iter().map(|a| a.order).collect();
+        assert_eq!(orders, vec![0, 1]);
+
+        // ファイルの順ではなく order 順に読み込む
+        let aliases_path = storage::get_aliases_path().unwrap();
+        let mut json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&aliases_path).unwrap()).unwrap();
+        json[0]["order"] = serde_json::json!(5);
+        fs::write(&aliases_path, json.to_string()).unwrap();
+        let mut loaded = AliasManager::new();
+        loaded.load().unwrap();
+        let names: Vec<&str> = loaded.get_aliases().iter().map(|a| a.alias.as_str()).collect();
+        assert_eq!(names, vec!["c", "a"]);
+
+        // order のない古いファイルはファイルの順のまま
+        for alias in json.as_array_mut().unwrap() {
+            alias.as_object_mut().unwrap().remove("order");
+        }
+        fs::write(&aliases_path, json.to_string()).unwrap();
+        loaded.load().unwrap();
+        let names: Vec<&str> = loaded.get_aliases().iter().map(|a| a.alias.as_str()).collect();
+        assert_eq!(names, vec!["a", "c"]);
+    }
+
     #[test]
     fn test_load_empty_file() {
         use std::env;
@@ -879,6 +1428,87 @@ mod tests {
         assert_eq!(manager.get_aliases()[0].is_favorite, true);
     }
 
+    #[test]
+    fn test_set_volume() {
+        let mut manager = AliasManager::new();
+        manager
+            .add_alias(
+                "usb".to_string(),
+                PathBuf::from("/mnt/e/work"),
+                vec![],
+                None,
+                false,
+            )
+            .unwrap();
+
+        let volume = VolumeId {
+            root: PathBuf::from("/mnt/e"),
+            label: Some("USB_WORK".to_string()),
+            serial: Some(1234),
+        };
+        assert!(manager.set_volume("usb", Some(volume.clone())).is_ok());
+        assert_eq!(manager.get_aliases()[0].volume, Some(volume));
+
+        // 存在しないエイリアス
+        assert!(manager.set_volume("missing", None).is_err());
+    }
+
+    #[test]
+    fn test_record_open_increments_count() {
+        let mut manager = AliasManager::new();
+        manager
+            .add_alias("test".to_string(), PathBuf::from("/path/to/file"), vec![], None, false)
+            .unwrap();
+        let id = manager.get_aliases()[0].id.clone();
+        let before = manager.get_aliases()[0].last_accessed;
+
+        manager.record_open(&id).unwrap();
+        manager.record_open(&id).unwrap();
+
+        let alias = &manager.get_aliases()[0];
+        assert_eq!(alias.open_count, 2);
+        assert!(alias.last_accessed >= before);
+        assert!(manager.record_open("nonexistent").is_err());
+    }
+
+    #[test]
+    fn test_most_used_ranks_by_count() {
+        let mut manager = AliasManager::new();
+        for name in ["rare", "often", "never", "sometimes"] {
+            manager
+                .add_alias(name.to_string(), PathBuf::from(format!("/{}", name)), vec![], None, false)
+                .unwrap();
+        }
+        manager.aliases[0].open_count = 1;
+        manager.aliases[1].open_count = 10;
+        manager.aliases[3].open_count = 4;
+
+        let names: Vec<&str> = manager.most_used(10).iter().map(|a| a.alias.as_str()).collect();
+        // 一度も開いていないものは含まない
+        assert_eq!(names, vec!["often", "sometimes", "rare"]);
+
+        let top: Vec<&str> = manager.most_used(2).iter().map(|a| a.alias.as_str()).collect();
+        assert_eq!(top, vec!["often", "sometimes"]);
+    }
+
+    #[test]
+    fn test_most_used_tie_break_by_recency() {
+        let mut manager = AliasManager::new();
+        for name in ["old", "recent"] {
+            manager
+                .add_alias(name.to_string(), PathBuf::from(format!("/{}", name)), vec![], None, false)
+                .unwrap();
+        }
+        let now = Utc::now();
+        manager.aliases[0].open_count = 3;
+        manager.aliases[0].last_accessed = now - chrono::Duration::days(5);
+        manager.aliases[1].open_count = 3;
+        manager.aliases[1].last_accessed = now;
+
+        let names: Vec<&str> = manager.most_used(10).iter().map(|a| a.alias.as_str()).collect();
+        assert_eq!(names, vec!["recent", "old"]);
+    }
+
     #[test]
     fn test_toggle_favorite_nonexistent() {
         let mut manager = AliasManager::new();
@@ -1011,4 +1641,418 @@ mod tests {
         assert_eq!(favorites.len(), 1);
         assert_eq!(favorites[0].alias, "test2");
     }
+
+    #[test]
+    fn test_validate_paths_and_mark_broken() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let existing = temp_dir.path().join("existing");
+        std::fs::create_dir(&existing).unwrap();
+
+        let mut manager = AliasManager::new();
+        manager.add_alias("ok".to_string(), existing, vec![], None, false).unwrap();
+        manager
+            .add_alias("moved".to_string(), temp_dir.path().join("moved"), vec![], None, false)
+            .unwrap();
+        let moved_id = manager.get_aliases()[1].id.clone();
+
+        let broken = manager.validate_paths();
+        assert_eq!(broken, vec![moved_id.clone()]);
+
+        assert_eq!(manager.mark_broken(&broken), 1);
+        assert!(!manager.get_aliases()[0].is_broken);
+        assert!(manager.get_aliases()[1].is_broken);
+
+        // 再検証で見つかった場合は印を外す
+        std::fs::create_dir(temp_dir.path().join("moved")).unwrap();
+        let broken = manager.validate_paths();
+        assert!(broken.is_empty());
+        assert_eq!(manager.mark_broken(&broken), 0);
+        assert!(manager.get_aliases().iter().all(|a| !a.is_broken));
+    }
+
+    #[test]
+    fn test_find_by_path_ignores_case_and_separators() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("docs".to_string(), PathBuf::from(r"C:\Work\Docs"), vec![], None, false).unwrap();
+        manager.add_alias("plain".to_string(), PathBuf::from("/home/taro/plain"), vec![], None, false).unwrap();
+
+        assert_eq!(manager.find_by_path(Path::new("c:/work/docs/")).unwrap().alias, "docs");
+        assert_eq!(manager.find_by_path(Path::new(r"C:\WORK\DOCS\")).unwrap().alias, "docs");
+        assert_eq!(manager.find_by_path(Path::new("/home/taro/plain")).unwrap().alias, "plain");
+        assert!(manager.find_by_path(Path::new("c:/work/docs2")).is_none());
+        assert!(manager.find_by_path(Path::new("/home/taro")).is_none());
+    }
+
+    #[test]
+    fn test_find_by_path_resolves_existing_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+
+        let mut manager = AliasManager::new();
+        manager.add_alias("project".to_string(), project.clone(), vec![], None, false).unwrap();
+
+        let roundabout = project.join("..").join("project");
+        assert_eq!(manager.find_by_path(&roundabout).unwrap().alias, "project");
+    }
+
+    #[test]
+    fn test_validate_all_returns_ids_and_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let missing = temp_dir.path().join("missing");
+
+        let mut manager = AliasManager::new();
+        manager.add_alias("ok".to_string(), temp_dir.path().to_path_buf(), vec![], None, false).unwrap();
+        manager.add_alias("missing".to_string(), missing.clone(), vec![], None, false).unwrap();
+        let missing_id = manager.get_aliases()[1].id.clone();
+
+        assert_eq!(manager.validate_all(), vec![(missing_id.clone(), missing)]);
+        assert_eq!(manager.mark_unreachable(&[missing_id]), 1);
+        assert!(manager.get_aliases()[1].is_unreachable);
+        assert!(!manager.get_aliases()[1].is_broken);
+    }
+
+    #[test]
+    fn test_find_nearby_candidates_checks_parent_siblings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("2025/report")).unwrap();
+        std::fs::create_dir_all(root.join("archive/report")).unwrap();
+        std::fs::create_dir_all(root.join("other/memo")).unwrap();
+        std::fs::write(root.join("notes.txt"), "").unwrap();
+
+        // 元の親フォルダ（2024）は削除済み
+        let old_path = root.join("2024").join("report");
+        assert_eq!(
+            find_nearby_candidates(&old_path),
+            vec![root.join("2025/report"), root.join("archive/report")]
+        );
+
+        assert!(find_nearby_candidates(&root.join("2024").join("none")).is_empty());
+        assert!(find_nearby_candidates(Path::new("/")).is_empty());
+    }
+
+    #[test]
+    fn test_is_broken_is_not_serialized() {
+        let mut manager = AliasManager::new();
+        manager
+            .add_alias("gone".to_string(), PathBuf::from("/nonexistent/ofkt"), vec![], None, false)
+            .unwrap();
+        let ids = manager.validate_paths();
+        manager.mark_broken(&ids);
+
+        let json = serde_json::to_string(manager.get_aliases()).unwrap();
+        assert!(!json.contains("is_broken"));
+        let restored: Vec<FileAlias> = serde_json::from_str(&json).unwrap();
+        assert!(!restored[0].is_broken);
+    }
+
+    #[test]
+    fn test_csv_round_trip_preserves_aliases() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let csv_path = temp_dir.path().join("aliases.csv");
+
+        let mut source = AliasManager::new();
+        source
+            .add_alias(
+                "資料, 2025".to_string(),
+                PathBuf::from("C:\\Users\\me\\Documents\\\"資料\""),
+                vec!["work".to_string(), "経理".to_string()],
+                Some("#3B82F6".to_string()),
+                true,
+            )
+            .unwrap();
+        source
+            .add_alias("plain".to_string(), PathBuf::from("/home/me/plain"), vec![], None, false)
+            .unwrap();
+        source.export_csv(&csv_path).unwrap();
+
+        let mut target = AliasManager::new();
+        let summary = target.import_csv(&csv_path).unwrap();
+        assert_eq!(summary, CsvImportSummary { added: 2, skipped: 0 });
+
+        for (original, imported) in source.get_aliases().iter().zip(target.get_aliases()) {
+            assert_eq!(imported.alias, original.alias);
+            assert_eq!(imported.path, original.path);
+            assert_eq!(imported.tags, original.tags);
+            assert_eq!(imported.color, original.color);
+            assert_eq!(imported.is_favorite, original.is_favorite);
+        }
+    }
+
+    #[test]
+    fn test_import_csv_skips_existing_names() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let csv_path = temp_dir.path().join("aliases.csv");
+        std::fs::write(
+            &csv_path,
+            "alias,path,tags,color,is_favorite\ndocs,/other/docs,,,false\nwork,/work,a;b,,true\nwork,/work2,,,false\n",
+        )
+        .unwrap();
+
+        let mut manager = AliasManager::new();
+        manager.add_alias("docs".to_string(), PathBuf::from("/docs"), vec![], None, false).unwrap();
+
+        let summary = manager.import_csv(&csv_path).unwrap();
+        assert_eq!(summary, CsvImportSummary { added: 1, skipped: 2 });
+        let aliases = manager.get_aliases();
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases[0].path, PathBuf::from("/docs"), "既存のエイリアスは変更しない");
+        assert_eq!(aliases[1].path, PathBuf::from("/work"));
+        assert_eq!(aliases[1].tags, vec!["a".to_string(), "b".to_string()]);
+
+        // 不正な行があれば何も追加しない
+        std::fs::write(&csv_path, "new,/new,,,false\nbroken,/b\n").unwrap();
+        assert!(manager.import_csv(&csv_path).is_err());
+        assert_eq!(manager.get_aliases().len(), 2);
+    }
+
+    #[test]
+    fn test_import_subdirs_creates_alias_per_subdir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("alpha")).unwrap();
+        std::fs::create_dir(root.join("beta")).unwrap();
+        std::fs::create_dir_all(root.join("gamma/nested")).unwrap();
+        // ファイルは対象外
+        std::fs::write(root.join("readme.txt"), "readme").unwrap();
+
+        let mut manager = AliasManager::new();
+        let created = manager.import_subdirs(root, "project").unwrap();
+
+        // 直下のサブフォルダのみ（nested は含まない）
+        assert_eq!(created, 3);
+        let aliases = manager.get_aliases();
+        assert_eq!(aliases.len(), 3);
+        assert_eq!(aliases[0].alias, "alpha");
+        assert_eq!(aliases[0].path, root.join("alpha"));
+        assert_eq!(aliases[1].alias, "beta");
+        assert_eq!(aliases[2].alias, "gamma");
+        assert!(aliases.iter().all(|a| a.tags == vec!["project".to_string()]));
+    }
+
+    #[test]
+    fn test_import_subdirs_skips_duplicate_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("alpha")).unwrap();
+        std::fs::create_dir(root.join("beta")).unwrap();
+
+        let mut manager = AliasManager::new();
+        // alpha は別名で登録済み
+        manager.add_alias("既存".to_string(), root.join("alpha"), vec![], None, false).unwrap();
+
+        let created = manager.import_subdirs(root, "").unwrap();
+        assert_eq!(created, 1);
+        assert_eq!(manager.get_aliases().len(), 2);
+        assert_eq!(manager.get_aliases()[1].alias, "beta");
+        assert!(manager.get_aliases()[1].tags.is_empty());
+
+        // 2回目は全てスキップされる
+        let created = manager.import_subdirs(root, "").unwrap();
+        assert_eq!(created, 0);
+        assert_eq!(manager.get_aliases().len(), 2);
+    }
+
+    #[test]
+    fn test_import_subdirs_renames_conflicting_names() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("docs")).unwrap();
+
+        let mut manager = AliasManager::new();
+        // 同名だが別パスのエイリアス
+        manager.add_alias("docs".to_string(), PathBuf::from("/other/docs"), vec![], None, false).unwrap();
+
+        let created = manager.import_subdirs(root, "").unwrap();
+        assert_eq!(created, 1);
+        assert_eq!(manager.get_aliases()[1].alias, "docs (2)");
+    }
+
+    #[test]
+    fn test_import_subdirs_nonexistent_root() {
+        let mut manager = AliasManager::new();
+        let result = manager.import_subdirs(Path::new("/nonexistent/root"), "tag");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_find_duplicate_names_from_loaded_list() {
+        // 手動編集されたファイルを読み込んだ想定
+        let json = r#"[
+            {"id": "1", "alias": "docs", "path": "/a", "color": null,
+             "created_at": "2024-01-01T00:00:00Z", "last_accessed": "2024-01-01T00:00:00Z"},
+            {"id": "2", "alias": "music", "path": "/b", "color": null,
+             "created_at": "2024-01-01T00:00:00Z", "last_accessed": "2024-01-01T00:00:00Z"},
+            {"id": "3", "alias": "docs", "path": "/c", "color": null,
+             "created_at": "2024-01-01T00:00:00Z", "last_accessed": "2024-01-01T00:00:00Z"},
+            {"id": "4", "alias": "docs", "path": "/d", "color": null,
+             "created_at": "2024-01-01T00:00:00Z", "last_accessed": "2024-01-01T00:00:00Z"}
+        ]"#;
+        let mut manager = AliasManager::new();
+        manager.aliases = serde_json::from_str(json).unwrap();
+
+        let duplicates = manager.find_duplicate_names();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].0, "docs");
+        assert_eq!(duplicates[0].1, vec!["1".to_string(), "3".to_string(), "4".to_string()]);
+    }
+
+    #[test]
+    fn test_find_duplicate_names_none_for_unique_list() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("docs".to_string(), PathBuf::from("/a"), vec![], None, false).unwrap();
+        manager.add_alias("Docs".to_string(), PathBuf::from("/b"), vec![], None, false).unwrap();
+
+        // 大文字小文字が異なる名前は重複として扱わない
+        assert!(manager.find_duplicate_names().is_empty());
+        assert!(AliasManager::new().find_duplicate_names().is_empty());
+    }
+
+    fn alias_names(manager: &AliasManager) -> Vec<&str> {
+        manager.get_aliases().iter().map(|a| a.alias.as_str()).collect()
+    }
+
+    #[test]
+    fn test_move_up_and_down() {
+        let mut manager = AliasManager::new();
+        for name in ["a", "b", "c"] {
+            manager.add_alias(name.to_string(), PathBuf::from(format!("/{}", name)), vec![], None, false).unwrap();
+        }
+        let id_of = |manager: &AliasManager, name: &str| {
+            manager.get_aliases().iter().find(|a| a.alias == name).unwrap().id.clone()
+        };
+
+        let c = id_of(&manager, "c");
+        manager.move_up(&c).unwrap();
+        assert_eq!(alias_names(&manager), vec!["a", "c", "b"]);
+        let orders: Vec<u32> = manager.get_aliases().iter().map(|a| a.order).collect();
+        assert_eq!(orders, vec![0, 1, 2]);
+
+        // 先頭・末尾ではそれ以上移動しない
+        let a = id_of(&manager, "a");
+        manager.move_up(&a).unwrap();
+        let b = id_of(&manager, "b");
+        manager.move_down(&b).unwrap();
+        assert_eq!(alias_names(&manager), vec!["a", "c", "b"]);
+
+        manager.move_down(&a).unwrap();
+        assert_eq!(alias_names(&manager), vec!["c", "a", "b"]);
+
+        assert!(manager.move_up("nonexistent").is_err());
+        assert!(manager.move_down("nonexistent").is_err());
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut manager = AliasManager::new();
+        for name in ["a", "b", "c", "d"] {
+            manager.add_alias(name.to_string(), PathBuf::from(format!("/{}", name)), vec![], None, false).unwrap();
+        }
+        let ids: Vec<String> = manager.get_aliases().iter().map(|a| a.id.clone()).collect();
+
+        // 指定しなかったエイリアスは元の順で後ろに並ぶ
+        manager.reorder(&[ids[3].clone(), ids[1].clone()]).unwrap();
+        assert_eq!(alias_names(&manager), vec!["d", "b", "a", "c"]);
+        let orders: Vec<u32> = manager.get_aliases().iter().map(|a| a.order).collect();
+        assert_eq!(orders, vec![0, 1, 2, 3]);
+
+        // 存在しないIDを含む場合は並びを変えない
+        assert!(manager.reorder(&[ids[0].clone(), "nonexistent".to_string()]).is_err());
+        assert_eq!(alias_names(&manager), vec!["d", "b", "a", "c"]);
+
+        // 新しいエイリアスは末尾に並ぶ
+        manager.add_alias("e".to_string(), PathBuf::from("/e"), vec![], None, false).unwrap();
+        assert_eq!(manager.get_aliases()[4].order, 4);
+    }
+
+    #[test]
+    fn test_sorted_for_sidebar_favorites_first() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("alpha".to_string(), PathBuf::from("/a"), vec![], None, false).unwrap();
+        manager.add_alias("zulu".to_string(), PathBuf::from("/z"), vec![], None, true).unwrap();
+        manager.add_alias("bravo".to_string(), PathBuf::from("/b"), vec![], None, false).unwrap();
+        manager.add_alias("mike".to_string(), PathBuf::from("/m"), vec![], None, true).unwrap();
+
+        let names: Vec<&str> = manager.sorted_for_sidebar().iter().map(|a| a.alias.as_str()).collect();
+
+        // お気に入りが先頭、各グループ内は名前順
+        assert_eq!(names, vec!["mike", "zulu", "alpha", "bravo"]);
+    }
+
+    #[test]
+    fn test_sorted_for_sidebar_alphabetical_tiebreak() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("beta".to_string(), PathBuf::from("/b"), vec![], None, false).unwrap();
+        manager.add_alias("Alpha".to_string(), PathBuf::from("/a"), vec![], None, false).unwrap();
+        manager.add_alias("alpha2".to_string(), PathBuf::from("/a2"), vec![], None, false).unwrap();
+
+        let names: Vec<&str> = manager.sorted_for_sidebar().iter().map(|a| a.alias.as_str()).collect();
+
+        // 文字コード順のため大文字が先
+        assert_eq!(names, vec!["Alpha", "alpha2", "beta"]);
+    }
+
+    #[test]
+    fn test_sorted_for_sidebar_japanese() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("試算表".to_string(), PathBuf::from("/s"), vec![], None, false).unwrap();
+        manager.add_alias("あいさつ".to_string(), PathBuf::from("/a"), vec![], None, false).unwrap();
+        manager.add_alias("カタログ".to_string(), PathBuf::from("/k"), vec![], None, false).unwrap();
+        manager.add_alias("会計".to_string(), PathBuf::from("/k2"), vec![], None, true).unwrap();
+        manager.add_alias("report".to_string(), PathBuf::from("/r"), vec![], None, false).unwrap();
+
+        let names: Vec<&str> = manager.sorted_for_sidebar().iter().map(|a| a.alias.as_str()).collect();
+
+        // お気に入り → ASCII → ひらがな → カタカナ → 漢字（文字コード順）
+        assert_eq!(names, vec!["会計", "report", "あいさつ", "カタログ", "試算表"]);
+    }
+
+    #[test]
+    fn test_sorted_for_sidebar_does_not_modify_source() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("b".to_string(), PathBuf::from("/b"), vec![], None, false).unwrap();
+        manager.add_alias("a".to_string(), PathBuf::from("/a"), vec![], None, false).unwrap();
+
+        let _ = manager.sorted_for_sidebar();
+        assert_eq!(manager.get_aliases()[0].alias, "b");
+        assert_eq!(manager.get_aliases()[1].alias, "a");
+    }
+
+    #[test]
+    fn test_remap_paths_updates_descendants() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("root".to_string(), PathBuf::from("/work/old"), vec![], None, false).unwrap();
+        manager.add_alias("child".to_string(), PathBuf::from("/work/old/src/main.rs"), vec![], None, false).unwrap();
+        manager.add_alias("sibling".to_string(), PathBuf::from("/work/older"), vec![], None, false).unwrap();
+
+        let updated = manager.remap_paths(Path::new("/work/old"), Path::new("/work/new"));
+
+        assert_eq!(updated, 2);
+        let aliases = manager.get_aliases();
+        assert_eq!(aliases[0].path, PathBuf::from("/work/new"));
+        assert_eq!(aliases[1].path, PathBuf::from("/work/new/src/main.rs"));
+        // 文字列として前方一致するだけのパスは対象外
+        assert_eq!(aliases[2].path, PathBuf::from("/work/older"));
+
+        // 逆向きに付け替えると元に戻る（Undo）
+        assert_eq!(manager.remap_paths(Path::new("/work/new"), Path::new("/work/old")), 2);
+        assert_eq!(manager.get_aliases()[1].path, PathBuf::from("/work/old/src/main.rs"));
+    }
+
+    #[test]
+    fn test_add_tag_to_paths_skips_existing_tag() {
+        let mut manager = AliasManager::new();
+        manager.add_alias("a".to_string(), PathBuf::from("/work/a"), vec![], None, false).unwrap();
+        manager.add_alias("b".to_string(), PathBuf::from("/work/b"), vec!["Urgent".to_string()], None, false).unwrap();
+        manager.add_alias("c".to_string(), PathBuf::from("/work/c"), vec![], None, false).unwrap();
+
+        let paths = vec![PathBuf::from("/work/a"), PathBuf::from("/work/b")];
+        assert_eq!(manager.add_tag_to_paths(&paths, "urgent"), 1);
+
+        let aliases = manager.get_aliases();
+        assert_eq!(aliases[0].tags, vec!["urgent".to_string()]);
+        assert_eq!(aliases[1].tags, vec!["Urgent".to_string()]);
+        assert!(aliases[2].tags.is_empty());
+    }
 }
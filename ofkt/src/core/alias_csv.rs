@@ -0,0 +1,221 @@
+//! エイリアスのCSV形式への変換
+//!
+//! 複数のPCでエイリアスを共有するためのCSV形式です。
+//! カラムは `alias,path,tags,color,is_favorite` で、タグはセミコロン区切りで1つのカラムに入れます。
+//! カンマ・改行・ダブルクォートを含む値はダブルクォートで囲みます（RFC 4180）。
+
+use crate::data::models::FileAlias;
+use std::path::PathBuf;
+
+/// CSVのヘッダー行のカラム
+pub const CSV_COLUMNS: [&str; 5] = ["alias", "path", "tags", "color", "is_favorite"];
+
+/// タグの区切り文字
+const TAG_SEPARATOR: &str = ";";
+
+/// CSVの1行分のエイリアス
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvAliasRow {
+    pub alias: String,
+    pub path: PathBuf,
+    pub tags: Vec<String>,
+    pub color: Option<String>,
+    pub is_favorite: bool,
+}
+
+/// CSVインポートの結果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CsvImportSummary {
+    /// 追加したエイリアスの件数
+    pub added: usize,
+    /// 同名のエイリアスがあるためスキップした件数
+    pub skipped: usize,
+}
+
+/// エイリアスをCSV形式の文字列に変換（ヘッダー行付き）
+pub fn to_csv(aliases: &[FileAlias]) -> String {
+    let mut csv = CSV_COLUMNS.join(",");
+    csv.push_str("\r\n");
+    for alias in aliases {
+        let tags = alias.tags.join(TAG_SEPARATOR);
+        let fields = [
+            alias.alias.as_str(),
+            &alias.path.display().to_string(),
+            &tags,
+            alias.color.as_deref().unwrap_or(""),
+            if alias.is_favorite { "true" } else { "false" },
+        ]
+        .map(escape_field);
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// CSV形式の文字列をエイリアスの行に変換
+///
+/// 先頭行がヘッダーの場合は読み飛ばします。空行は無視します。
+///
+/// # 戻り値
+/// * `Ok(Vec<CsvAliasRow>)` - ファイルの順の行
+/// * `Err(String)` - エラーメッセージ（行番号付き）
+pub fn parse_csv(text: &str) -> Result<Vec<CsvAliasRow>, String> {
+    // Excel などが付ける BOM を取り除く
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let mut rows = Vec::new();
+    for (index, record) in split_records(text)?.into_iter().enumerate() {
+        let line = index + 1;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        if index == 0 && is_header(&record) {
+            continue;
+        }
+        if record.len() != CSV_COLUMNS.len() {
+            return Err(format!(
+                "{} 行目: カラム数が {} 個ではありません（{} 個）",
+                line,
+                CSV_COLUMNS.len(),
+                record.len()
+            ));
+        }
+
+        let alias = record[0].trim().to_string();
+        let path = record[1].trim();
+        if alias.is_empty() || path.is_empty() {
+            return Err(format!("{} 行目: エイリアス名とパスは必須です", line));
+        }
+        let tags = record[2]
+            .split(TAG_SEPARATOR)
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        let color = Some(record[3].trim())
+            .filter(|color| !color.is_empty())
+            .map(str::to_string);
+        let is_favorite = parse_bool(&record[4])
+            .ok_or_else(|| format!("{} 行目: is_favorite の値 '{}' が不正です", line, record[4]))?;
+
+        rows.push(CsvAliasRow {
+            alias,
+            path: PathBuf::from(path),
+            tags,
+            color,
+            is_favorite,
+        });
+    }
+    Ok(rows)
+}
+
+/// 必要ならダブルクォートで囲む
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// ヘッダー行か
+fn is_header(record: &[String]) -> bool {
+    record.len() == CSV_COLUMNS.len()
+        && record
+            .iter()
+            .zip(CSV_COLUMNS)
+            .all(|(field, column)| field.trim().eq_ignore_ascii_case(column))
+}
+
+/// 真偽値を解析（空欄は false）
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" => Some(true),
+        "false" | "0" | "no" | "" => Some(false),
+        _ => None,
+    }
+}
+
+/// CSVを行（フィールドのリスト）に分割
+///
+/// ダブルクォートで囲まれたフィールドの中のカンマ・改行はフィールドの一部として扱います。
+fn split_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' | '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("{} 行目: ダブルクォートが閉じられていません", records.len() + 1));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_records_handles_quotes_and_newlines() {
+        let records = split_records("a,\"b,c\",\"say \"\"hi\"\"\"\r\n\"multi\nline\",,x\n").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["a".to_string(), "b,c".to_string(), "say \"hi\"".to_string()],
+                vec!["multi\nline".to_string(), String::new(), "x".to_string()],
+            ]
+        );
+        assert!(split_records("\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_skips_header_and_reports_errors() {
+        let rows = parse_csv("\u{feff}alias,path,tags,color,is_favorite\n資料,C:\\docs, work ; 2025 ,,TRUE\n\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![CsvAliasRow {
+                alias: "資料".to_string(),
+                path: PathBuf::from("C:\\docs"),
+                tags: vec!["work".to_string(), "2025".to_string()],
+                color: None,
+                is_favorite: true,
+            }]
+        );
+
+        assert_eq!(
+            parse_csv("a,/a,,\n").unwrap_err(),
+            "1 行目: カラム数が 5 個ではありません（4 個）"
+        );
+        assert_eq!(parse_csv("a,,,,false\n").unwrap_err(), "1 行目: エイリアス名とパスは必須です");
+        assert!(parse_csv("a,/a,,,maybe\n").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! エイリアスファイル監視モジュール
+//!
+//! `aliases.json` が外部で編集されたことを検知し、
+//! 短時間の連続書き込みをまとめて1回の再読み込みに変換します。
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// 再読み込みのデフォルトデバウンス時間
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 再読み込みデバウンサー
+///
+/// 変更通知を受けてから一定時間新しい通知がなければ再読み込みを許可します。
+#[derive(Debug, Clone)]
+pub struct ReloadDebouncer {
+    /// 最後に変更を受け取った時刻（未処理の変更がない場合はNone）
+    pending_since: Option<Instant>,
+    /// デバウンス時間
+    debounce: Duration,
+}
+
+impl Default for ReloadDebouncer {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEBOUNCE)
+    }
+}
+
+impl ReloadDebouncer {
+    /// 新しい ReloadDebouncer を作成
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            pending_since: None,
+            debounce,
+        }
+    }
+
+    /// 変更を通知（デバウンス期間をリセット）
+    pub fn notify(&mut self, now: Instant) {
+        self.pending_since = Some(now);
+    }
+
+    /// 未処理の変更があるか
+    pub fn is_pending(&self) -> bool {
+        self.pending_since.is_some()
+    }
+
+    /// 再読み込みを実行すべきかチェック
+    ///
+    /// デバウンス期間が経過していれば true を返し、未処理状態をクリアします。
+    pub fn ready(&mut self, now: Instant) -> bool {
+        match self.pending_since {
+            Some(since) if now.duration_since(since) >= self.debounce => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// イベントが監視対象ファイルの内容変更に関するものか判定
+///
+/// # 引数
+/// * `event` - ファイルシステムイベント
+/// * `file_name` - 監視対象のファイル名
+pub fn is_target_file_event(event: &Event, file_name: &OsStr) -> bool {
+    let relevant_kind = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) | EventKind::Any
+    );
+
+    relevant_kind
+        && event.paths.iter().any(|p| p.file_name() == Some(file_name))
+}
+
+/// エイリアスファイルウォッチャー
+///
+/// アトミック書き込み（一時ファイル→リネーム）にも対応するため、
+/// ファイル自体ではなく親ディレクトリを監視します。
+pub struct AliasFileWatcher {
+    /// ウォッチャー本体（ドロップすると監視が止まるため保持する）
+    _watcher: RecommendedWatcher,
+    /// イベント受信チャネル
+    receiver: Receiver<notify::Result<Event>>,
+    /// 監視対象のファイル名
+    file_name: OsString,
+    /// デバウンサー
+    debouncer: ReloadDebouncer,
+}
+
+impl AliasFileWatcher {
+    /// 指定ファイルの監視を開始
+    ///
+    /// # 引数
+    /// * `path` - 監視するファイルのパス
+    ///
+    /// # 戻り値
+    /// * `Ok(AliasFileWatcher)` - 成功
+    /// * `Err(String)` - エラーメッセージ
+    pub fn new(path: &Path) -> Result<Self, String> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| format!("ファイル名を取得できません: {}", path.display()))?
+            .to_os_string();
+        let parent = path
+            .parent()
+            .ok_or_else(|| "親ディレクトリが見つかりません".to_string())?;
+
+        let (sender, receiver) = channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| format!("ファイル監視の初期化に失敗: {}", e))?;
+        watcher
+            .watch(parent, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("ファイル監視の開始に失敗: {}", e))?;
+
+        log::info!("エイリアスファイルの監視を開始: {}", path.display());
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+            file_name,
+            debouncer: ReloadDebouncer::default(),
+        })
+    }
+
+    /// 受信済みのイベントを処理し、再読み込みすべきか返す
+    ///
+    /// 毎フレーム呼び出すことを想定しています。
+    pub fn poll(&mut self) -> bool {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(Ok(event)) => {
+                    if is_target_file_event(&event, &self.file_name) {
+                        log::debug!("エイリアスファイルの変更を検知: {:?}", event.kind);
+                        self.debouncer.notify(Instant::now());
+                    }
+                }
+                Ok(Err(e)) => {
+                    log::warn!("ファイル監視エラー: {}", e);
+                }
+                Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        self.debouncer.ready(Instant::now())
+    }
+
+    /// デバウンス待ちの変更があるか
+    pub fn is_reload_pending(&self) -> bool {
+        self.debouncer.is_pending()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_debouncer_initial_state() {
+        let mut debouncer = ReloadDebouncer::new(Duration::from_millis(100));
+        assert!(!debouncer.is_pending());
+        assert!(!debouncer.ready(Instant::now()));
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let mut debouncer = ReloadDebouncer::new(Duration::from_millis(100));
+        let start = Instant::now();
+
+        debouncer.notify(start);
+        assert!(debouncer.is_pending());
+        // デバウンス期間内は再読み込みしない
+        assert!(!debouncer.ready(start + Duration::from_millis(50)));
+        // 期間経過後は再読み込みする
+        assert!(debouncer.ready(start + Duration::from_millis(100)));
+        // 一度処理したらクリアされる
+        assert!(!debouncer.is_pending());
+        assert!(!debouncer.ready(start + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_debouncer_coalesces_rapid_writes() {
+        let mut debouncer = ReloadDebouncer::new(Duration::from_millis(100));
+        let start = Instant::now();
+
+        // 連続した書き込みはデバウンス期間をリセットする
+        debouncer.notify(start);
+        debouncer.notify(start + Duration::from_millis(80));
+        assert!(!debouncer.ready(start + Duration::from_millis(120)));
+        assert!(debouncer.ready(start + Duration::from_millis(180)));
+    }
+
+    #[test]
+    fn test_is_target_file_event() {
+        let file_name = OsStr::new("aliases.json");
+
+        let modify = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/config/ofkt/aliases.json"));
+        assert!(is_target_file_event(&modify, file_name));
+
+        let create = Event::new(EventKind::Create(CreateKind::File))
+            .add_path(PathBuf::from("/config/ofkt/aliases.json"));
+        assert!(is_target_file_event(&create, file_name));
+
+        // 別ファイルの変更は無視
+        let other = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/config/ofkt/config.json"));
+        assert!(!is_target_file_event(&other, file_name));
+
+        // アクセスイベントは無視
+        let access = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(PathBuf::from("/config/ofkt/aliases.json"));
+        assert!(!is_target_file_event(&access, file_name));
+    }
+}
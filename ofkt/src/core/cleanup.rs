@@ -0,0 +1,172 @@
+//! 空フォルダの整理
+//!
+//! 配下にファイルを含まないフォルダを探し、ゴミ箱へまとめて移動します。
+
+use crate::core::file_manager::{BatchResult, FileManager};
+use std::path::{Path, PathBuf};
+
+/// 空のサブフォルダを探す
+///
+/// 深い階層から順に調べ、空のフォルダだけを含むフォルダも空とみなします。
+/// `root` 自身は結果に含めません。読み取れないフォルダやシンボリックリンクは
+/// 中身があるものとして扱い、削除対象にしません。
+///
+/// # 引数
+/// * `root` - 探索を開始するフォルダ
+///
+/// # 戻り値
+/// 空のフォルダのパス（子が親より先に並ぶ）
+pub fn find_empty_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut empty_dirs = Vec::new();
+    collect_empty_dirs(root, &mut empty_dirs);
+    empty_dirs
+}
+
+/// `dir` 配下の空フォルダを集め、`dir` 自身が空かどうかを返す
+fn collect_empty_dirs(dir: &Path, empty_dirs: &mut Vec<PathBuf>) -> bool {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("フォルダを読み取れません: {} ({})", dir.display(), e);
+            return false;
+        }
+    };
+
+    let mut is_empty = true;
+    for entry in entries {
+        let Ok(entry) = entry else {
+            is_empty = false;
+            continue;
+        };
+        let is_real_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if is_real_dir {
+            let path = entry.path();
+            if collect_empty_dirs(&path, empty_dirs) {
+                empty_dirs.push(path);
+            } else {
+                is_empty = false;
+            }
+        } else {
+            is_empty = false;
+        }
+    }
+    is_empty
+}
+
+/// フォルダの配下にファイルが1つもないか確認
+fn is_empty_tree(dir: &Path) -> bool {
+    dir.is_dir() && collect_empty_dirs(dir, &mut Vec::new())
+}
+
+/// 最上位のフォルダだけを残す
+///
+/// 親フォルダを削除すれば配下も削除されるため、他のフォルダの配下にあるものは除きます。
+pub fn topmost_dirs(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    dirs.iter()
+        .filter(|dir| !dirs.iter().any(|other| other != *dir && dir.starts_with(other)))
+        .cloned()
+        .collect()
+}
+
+/// 空フォルダをゴミ箱へ移動
+///
+/// 探索から削除までの間にファイルが追加されたフォルダを消さないよう、
+/// 削除の直前にもう一度空であることを確認します。
+///
+/// # 引数
+/// * `file_manager` - ゴミ箱への移動に使う FileManager
+/// * `dirs` - `find_empty_dirs` で見つけた空フォルダ
+///
+/// # 戻り値
+/// フォルダごとの成否をまとめた BatchResult
+pub fn trash_empty_dirs(file_manager: &FileManager, dirs: &[PathBuf]) -> BatchResult {
+    let (still_empty, changed): (Vec<PathBuf>, Vec<PathBuf>) = topmost_dirs(dirs)
+        .into_iter()
+        .partition(|dir| is_empty_tree(dir));
+
+    let mut result = file_manager.delete_many_to_trash(&still_empty);
+    for dir in changed {
+        result.failed.push((dir.clone(), format!("'{}' は空ではなくなったため削除しません", dir.display())));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_empty_dirs_nested() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+
+        // empty/            ← 空
+        // nested/a/b/       ← nested, a, b すべて空
+        // mixed/empty/      ← mixed/empty のみ空
+        // mixed/file.txt
+        // full/sub/file.txt ← 空ではない
+        fs::create_dir(root.join("empty")).unwrap();
+        fs::create_dir_all(root.join("nested/a/b")).unwrap();
+        fs::create_dir_all(root.join("mixed/empty")).unwrap();
+        fs::write(root.join("mixed/file.txt"), "x").unwrap();
+        fs::create_dir_all(root.join("full/sub")).unwrap();
+        fs::write(root.join("full/sub/file.txt"), "x").unwrap();
+
+        let mut found = find_empty_dirs(root);
+        found.sort();
+
+        let mut expected = vec![
+            root.join("empty"),
+            root.join("nested"),
+            root.join("nested/a"),
+            root.join("nested/a/b"),
+            root.join("mixed/empty"),
+        ];
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_find_empty_dirs_lists_children_before_parents() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+
+        assert_eq!(find_empty_dirs(root), vec![root.join("a/b"), root.join("a")]);
+    }
+
+    #[test]
+    fn test_find_empty_dirs_excludes_root() {
+        let temp_dir = tempdir().unwrap();
+        assert!(find_empty_dirs(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_topmost_dirs() {
+        let dirs = vec![
+            PathBuf::from("/r/a/b"),
+            PathBuf::from("/r/a"),
+            PathBuf::from("/r/ab"),
+        ];
+        assert_eq!(topmost_dirs(&dirs), vec![PathBuf::from("/r/a"), PathBuf::from("/r/ab")]);
+    }
+
+    #[test]
+    fn test_trash_skips_dirs_that_are_no_longer_empty() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+
+        let found = find_empty_dirs(root);
+        // 探索後にファイルが追加された
+        fs::write(root.join("a/b/new.txt"), "x").unwrap();
+
+        let result = trash_empty_dirs(&FileManager::new(), &found);
+        assert_eq!(result.succeeded, 0);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, root.join("a"));
+        assert!(root.join("a/b/new.txt").exists());
+    }
+}
@@ -1,5 +1,5 @@
-use std::path::PathBuf;
-use crate::utils::path::normalize_paths;
+use std::path::{Path, PathBuf};
+use crate::utils::path::{normalize_paths, paths_equal};
 
 /// クリップボードの操作モード
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -120,6 +120,21 @@ impl ClipboardState {
     pub fn is_empty(&self) -> bool {
         !self.is_active || self.paths.is_empty()
     }
+
+    /// 指定パスが切り取り済み（ペースト待ち）かどうか
+    ///
+    /// 切り取りモードでクリップボードが有効な間だけ true を返します。
+    /// ペーストやクリアでクリップボードが消費されると false になります。
+    pub fn is_pending_cut(&self, path: &Path) -> bool {
+        if self.is_empty() || self.mode != ClipboardMode::Cut {
+            return false;
+        }
+
+        // 保持しているパスは正規化済みのため、ファイル名が一致した場合のみ正規化して比較する
+        self.paths.iter().any(|cut| {
+            cut == path || (cut.file_name() == path.file_name() && paths_equal(cut, path))
+        })
+    }
 }
 
 impl Default for ClipboardState {
@@ -128,34 +143,324 @@ impl Default for ClipboardState {
     }
 }
 
+/// テキストクリップボードから読み取ったパスの解析結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedClipboardPaths {
+    /// 存在が確認できたパス
+    pub paths: Vec<PathBuf>,
+    /// パスとして扱えなかった行
+    pub invalid_lines: Vec<String>,
+}
+
+/// テキストクリップボードの内容をファイルパスのリストとして解析
+///
+/// 1行に1つのパスを想定し、空行は無視します。
+/// エクスプローラーの「パスのコピー」のように前後が `"` で囲まれていても受け付けます。
+/// 存在しないパスの行は `invalid_lines` に入れて返します。
+///
+/// # 引数
+/// * `text` - クリップボードのテキスト
+///
+/// # 戻り値
+/// 解析結果
+pub fn parse_clipboard_paths(text: &str) -> ParsedClipboardPaths {
+    let mut result = ParsedClipboardPaths::default();
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let unquoted = trimmed
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .unwrap_or(trimmed);
+        let path = PathBuf::from(unquoted);
+
+        if !unquoted.is_empty() && path.exists() {
+            if !result.paths.contains(&path) {
+                result.paths.push(path);
+            }
+        } else {
+            result.invalid_lines.push(trimmed.to_string());
+        }
+    }
+
+    result
+}
+
+/// 既定のコピー名の形式
+pub const DEFAULT_COPY_NAME_PATTERN: &str = "{name} (コピー){ext}";
+
+/// コピー名の形式として使えるか
+///
+/// `{name}` を含み、パスの区切り文字を含まない必要があります。
+pub fn is_valid_copy_name_pattern(pattern: &str) -> bool {
+    pattern.contains("{name}") && !pattern.contains(['/', '\\'])
+}
+
+/// 名前が重複したときに使う番号付きの形式
+///
+/// `{n}` を含む形式はそのまま使います。含まない場合は、`{ext}` の直前が `)` なら
+/// 括弧の中に " {n}" を、そうでなければ `{ext}` の直前に `{n}` を入れます。
+/// （例: "{name} (コピー){ext}" → "{name} (コピー {n}){ext}"、"{name}_copy{ext}" → "{name}_copy{n}{ext}"）
+fn numbered_copy_name_pattern(pattern: &str) -> String {
+    if pattern.contains("{n}") {
+        return pattern.to_string();
+    }
+    let position = pattern.find("{ext}").unwrap_or(pattern.len());
+    let (head, tail) = pattern.split_at(position);
+    match head.strip_suffix(')') {
+        Some(inner) => format!("{} {{n}}){}", inner, tail),
+        None => format!("{}{{n}}{}", head, tail),
+    }
+}
+
+/// 形式の `{name}`・`{ext}`・`{n}` を置き換える
+fn render_copy_name(pattern: &str, name: &str, ext: &str, counter: u32) -> String {
+    pattern
+        .replace("{name}", name)
+        .replace("{ext}", ext)
+        .replace("{n}", &counter.to_string())
+}
+
 /// コピー時のファイル名を生成（同一ディレクトリの場合）
 pub fn generate_copy_name(original_path: &std::path::Path, dest_dir: &std::path::Path) -> PathBuf {
-    let file_name = original_path.file_stem().unwrap_or_default();
-    let extension = original_path.extension();
+    generate_copy_name_with_pattern(original_path, dest_dir, DEFAULT_COPY_NAME_PATTERN)
+}
 
-    let mut counter = 0;
-    loop {
-        let new_name = if counter == 0 {
-            format!("{} (コピー)", file_name.to_string_lossy())
-        } else {
-            format!("{} (コピー {})", file_name.to_string_lossy(), counter + 1)
-        };
+/// 形式を指定してコピー時のファイル名を生成（同一ディレクトリの場合）
+///
+/// 形式では `{name}`（拡張子を除いた名前）、`{ext}`（"." を含む拡張子、なければ空）、
+/// `{n}`（重複を避けるための番号、2から始まる）が使えます。
+/// `{n}` を含まない形式は、まず番号なしの名前を試します。
+/// 使えない形式の場合は既定の形式を使います。
+///
+/// # 引数
+/// * `original_path` - コピー元のパス
+/// * `dest_dir` - コピー先のフォルダ
+/// * `pattern` - コピー名の形式（例: "{name} ({n}){ext}"、"{name}_copy{ext}"）
+pub fn generate_copy_name_with_pattern(
+    original_path: &std::path::Path,
+    dest_dir: &std::path::Path,
+    pattern: &str,
+) -> PathBuf {
+    let pattern = if is_valid_copy_name_pattern(pattern) {
+        pattern
+    } else {
+        log::warn!("コピー名の形式が正しくないため既定の形式を使います: {}", pattern);
+        DEFAULT_COPY_NAME_PATTERN
+    };
+    let file_name = original_path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = original_path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
 
-        let new_path = if let Some(ext) = extension {
-            dest_dir.join(format!("{}.{}", new_name, ext.to_string_lossy()))
-        } else {
-            dest_dir.join(new_name)
-        };
+    if !pattern.contains("{n}") {
+        let new_path = dest_dir.join(render_copy_name(pattern, &file_name, &extension, 1));
+        if !new_path.exists() {
+            return new_path;
+        }
+    }
 
+    let numbered = numbered_copy_name_pattern(pattern);
+    // 無限ループ防止
+    for counter in 2..=10000 {
+        let new_path = dest_dir.join(render_copy_name(&numbered, &file_name, &extension, counter));
         if !new_path.exists() {
             return new_path;
         }
+    }
+    dest_dir.join(format!("{}_{}", file_name, uuid::Uuid::new_v4()))
+}
 
-        counter += 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
 
-        // 無限ループ防止
-        if counter > 9999 {
-            return dest_dir.join(format!("{}_{}", file_name.to_string_lossy(), uuid::Uuid::new_v4()));
-        }
+    #[test]
+    fn test_is_pending_cut_after_cut() {
+        let temp_dir = tempdir().unwrap();
+        let cut_file = temp_dir.path().join("cut.txt");
+        let other_file = temp_dir.path().join("other.txt");
+        std::fs::write(&cut_file, "cut").unwrap();
+        std::fs::write(&other_file, "other").unwrap();
+
+        let mut clipboard = ClipboardState::new();
+        clipboard.cut(vec![cut_file.clone()]);
+
+        // 正規化前のパスでも判定できる
+        assert!(clipboard.is_pending_cut(&cut_file));
+        assert!(!clipboard.is_pending_cut(&other_file));
+    }
+
+    #[test]
+    fn test_is_pending_cut_ignores_copy_mode() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("copy.txt");
+        std::fs::write(&file, "copy").unwrap();
+
+        let mut clipboard = ClipboardState::new();
+        clipboard.copy(vec![file.clone()]);
+
+        // コピーモードでは薄く表示しない
+        assert!(!clipboard.is_pending_cut(&file));
+    }
+
+    #[test]
+    fn test_is_pending_cut_cleared_after_consume() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("moved.txt");
+        std::fs::write(&file, "moved").unwrap();
+
+        let mut clipboard = ClipboardState::new();
+        clipboard.cut(vec![file.clone()]);
+        assert!(clipboard.is_pending_cut(&file));
+
+        // ペースト完了でクリアされたら通常表示に戻る
+        clipboard.clear();
+        assert!(!clipboard.is_pending_cut(&file));
+    }
+
+    #[test]
+    fn test_is_pending_cut_empty_clipboard() {
+        let clipboard = ClipboardState::new();
+        assert!(!clipboard.is_pending_cut(Path::new("/any/path")));
+    }
+
+    #[test]
+    fn test_parse_clipboard_paths_multi_line() {
+        let temp_dir = tempdir().unwrap();
+        let file_a = temp_dir.path().join("a.txt");
+        let dir_b = temp_dir.path().join("b");
+        std::fs::write(&file_a, "a").unwrap();
+        std::fs::create_dir(&dir_b).unwrap();
+
+        // 改行区切り・前後の空白・空行を含むテキスト
+        let text = format!("{}\n\n  {}  \n", file_a.display(), dir_b.display());
+        let parsed = parse_clipboard_paths(&text);
+
+        assert_eq!(parsed.paths, vec![file_a, dir_b]);
+        assert!(parsed.invalid_lines.is_empty());
+    }
+
+    #[test]
+    fn test_parse_clipboard_paths_quoted_and_crlf() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("quoted file.txt");
+        std::fs::write(&file, "q").unwrap();
+
+        // エクスプローラーの「パスのコピー」形式（ダブルクォート + CRLF）
+        let text = format!("\"{}\"\r\n", file.display());
+        let parsed = parse_clipboard_paths(&text);
+
+        assert_eq!(parsed.paths, vec![file]);
+    }
+
+    #[test]
+    fn test_parse_clipboard_paths_ignores_non_paths() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("exists.txt");
+        std::fs::write(&file, "e").unwrap();
+        let missing = temp_dir.path().join("missing.txt");
+
+        let text = format!("hello world\n{}\n{}\n\"\"", file.display(), missing.display());
+        let parsed = parse_clipboard_paths(&text);
+
+        assert_eq!(parsed.paths, vec![file.clone()]);
+        assert_eq!(
+            parsed.invalid_lines,
+            vec!["hello world".to_string(), missing.display().to_string(), "\"\"".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_clipboard_paths_deduplicates() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("dup.txt");
+        std::fs::write(&file, "d").unwrap();
+
+        let text = format!("{}\n{}", file.display(), file.display());
+        let parsed = parse_clipboard_paths(&text);
+
+        assert_eq!(parsed.paths.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_clipboard_paths_empty_text() {
+        let parsed = parse_clipboard_paths("");
+        assert!(parsed.paths.is_empty());
+        assert!(parsed.invalid_lines.is_empty());
+    }
+
+    /// コピー元と同名のファイルを作成し、指定した形式でコピー名を3回生成する
+    fn generate_three_copies(pattern: &str, original: &str) -> Vec<String> {
+        let temp_dir = tempdir().unwrap();
+        let src = temp_dir.path().join(original);
+        std::fs::write(&src, "data").unwrap();
+
+        (0..3)
+            .map(|_| {
+                let path = generate_copy_name_with_pattern(&src, temp_dir.path(), pattern);
+                assert!(!path.exists());
+                std::fs::write(&path, "copy").unwrap();
+                path.file_name().unwrap().to_string_lossy().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_copy_name_default_pattern() {
+        assert_eq!(
+            generate_three_copies(DEFAULT_COPY_NAME_PATTERN, "report.txt"),
+            vec!["report (コピー).txt", "report (コピー 2).txt", "report (コピー 3).txt"]
+        );
+        // 拡張子のない項目
+        assert_eq!(
+            generate_three_copies(DEFAULT_COPY_NAME_PATTERN, "README"),
+            vec!["README (コピー)", "README (コピー 2)", "README (コピー 3)"]
+        );
+    }
+
+    #[test]
+    fn test_generate_copy_name_numbered_pattern() {
+        assert_eq!(
+            generate_three_copies("{name} ({n}){ext}", "report.txt"),
+            vec!["report (2).txt", "report (3).txt", "report (4).txt"]
+        );
+    }
+
+    #[test]
+    fn test_generate_copy_name_suffix_pattern() {
+        assert_eq!(
+            generate_three_copies("{name}_copy{ext}", "report.txt"),
+            vec!["report_copy.txt", "report_copy2.txt", "report_copy3.txt"]
+        );
+    }
+
+    #[test]
+    fn test_generate_copy_name_skips_existing_numbers() {
+        let temp_dir = tempdir().unwrap();
+        let src = temp_dir.path().join("photo.jpg");
+        std::fs::write(&src, "data").unwrap();
+        std::fs::write(temp_dir.path().join("photo (2).jpg"), "data").unwrap();
+
+        let path = generate_copy_name_with_pattern(&src, temp_dir.path(), "{name} ({n}){ext}");
+        assert_eq!(path, temp_dir.path().join("photo (3).jpg"));
+    }
+
+    #[test]
+    fn test_invalid_copy_name_pattern_falls_back_to_default() {
+        assert!(is_valid_copy_name_pattern("{name}_copy{ext}"));
+        assert!(!is_valid_copy_name_pattern("copy{ext}"));
+        assert!(!is_valid_copy_name_pattern("sub/{name}{ext}"));
+
+        let temp_dir = tempdir().unwrap();
+        let src = temp_dir.path().join("memo.txt");
+        let path = generate_copy_name_with_pattern(&src, temp_dir.path(), "copy{ext}");
+        assert_eq!(path, temp_dir.path().join("memo (コピー).txt"));
     }
 }
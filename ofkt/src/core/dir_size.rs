@@ -0,0 +1,179 @@
+//! フォルダサイズの計算
+//!
+//! 大きなフォルダでは時間がかかるため、ワーカースレッドで計算し、
+//! 途中で取り消せるようにします。
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::sync::Arc;
+
+/// フォルダ配下のファイルサイズの合計を計算
+///
+/// 読み取れない項目は無視します。シンボリックリンクはたどりません。
+///
+/// # 引数
+/// * `root` - 対象のフォルダ
+/// * `cancelled` - true になった時点で計算を打ち切る
+///
+/// # 戻り値
+/// 合計バイト数（取り消された場合は None）
+pub fn directory_size(root: &Path, cancelled: &AtomicBool) -> Option<u64> {
+    let mut total = 0u64;
+    for entry in walkdir::WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        if entry.file_type().is_file() {
+            total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+    Some(total)
+}
+
+/// サイズ計算の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeTaskState {
+    /// 計算中
+    Pending,
+    /// 計算完了（バイト数）
+    Done(u64),
+    /// 取り消し済み
+    Cancelled,
+}
+
+/// バックグラウンドで実行するフォルダサイズ計算
+///
+/// ドロップすると計算を取り消します（ダイアログを閉じた場合など）。
+#[derive(Debug)]
+pub struct DirectorySizeTask {
+    /// 現在の状態
+    state: SizeTaskState,
+    /// 計算結果の受信チャネル
+    receiver: Receiver<u64>,
+    /// 取り消しフラグ（ワーカースレッドと共有）
+    cancelled: Arc<AtomicBool>,
+}
+
+impl DirectorySizeTask {
+    /// ワーカースレッドで計算を開始
+    pub fn spawn(root: PathBuf) -> Self {
+        let (sender, receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_cancelled = Arc::clone(&cancelled);
+
+        std::thread::spawn(move || {
+            if let Some(size) = directory_size(&root, &worker_cancelled) {
+                // 受信側が破棄済みなら結果は捨てる
+                let _ = sender.send(size);
+            } else {
+                log::debug!("フォルダサイズの計算を取り消しました: {}", root.display());
+            }
+        });
+
+        Self::from_parts(receiver, cancelled)
+    }
+
+    fn from_parts(receiver: Receiver<u64>, cancelled: Arc<AtomicBool>) -> Self {
+        Self {
+            state: SizeTaskState::Pending,
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// 計算結果を確認して現在の状態を返す
+    ///
+    /// 毎フレーム呼び出すことを想定しています。
+    pub fn poll(&mut self) -> SizeTaskState {
+        if self.state == SizeTaskState::Pending {
+            match self.receiver.try_recv() {
+                Ok(size) => self.state = SizeTaskState::Done(size),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.state = SizeTaskState::Cancelled,
+            }
+        }
+        self.state
+    }
+
+    /// 計算を取り消す（完了済みの場合は何もしない）
+    pub fn cancel(&mut self) {
+        if self.state == SizeTaskState::Pending {
+            self.cancelled.store(true, Ordering::Relaxed);
+            self.state = SizeTaskState::Cancelled;
+        }
+    }
+}
+
+impl Drop for DirectorySizeTask {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_directory_size_sums_nested_files() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("top.txt"), vec![0u8; 10]).unwrap();
+        fs::write(root.join("a/b/deep.bin"), vec![0u8; 32]).unwrap();
+
+        assert_eq!(directory_size(root, &AtomicBool::new(false)), Some(42));
+        assert_eq!(directory_size(root, &AtomicBool::new(true)), None);
+    }
+
+    #[test]
+    fn test_task_pending_then_done() {
+        let (sender, receiver) = channel();
+        let mut task = DirectorySizeTask::from_parts(receiver, Arc::new(AtomicBool::new(false)));
+        assert_eq!(task.poll(), SizeTaskState::Pending);
+
+        sender.send(1024).unwrap();
+        assert_eq!(task.poll(), SizeTaskState::Done(1024));
+        // 完了後の取り消しは無視する
+        task.cancel();
+        assert_eq!(task.poll(), SizeTaskState::Done(1024));
+    }
+
+    #[test]
+    fn test_task_cancel_discards_late_result() {
+        let (sender, receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut task = DirectorySizeTask::from_parts(receiver, Arc::clone(&cancelled));
+
+        task.cancel();
+        assert!(cancelled.load(Ordering::Relaxed));
+        // 取り消し後に届いた結果は反映しない
+        let _ = sender.send(1024);
+        assert_eq!(task.poll(), SizeTaskState::Cancelled);
+    }
+
+    #[test]
+    fn test_task_drop_signals_cancel() {
+        let (_sender, receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        drop(DirectorySizeTask::from_parts(receiver, Arc::clone(&cancelled)));
+        assert!(cancelled.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_spawned_task_completes() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("file.txt"), vec![0u8; 5]).unwrap();
+
+        let mut task = DirectorySizeTask::spawn(temp_dir.path().to_path_buf());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while task.poll() == SizeTaskState::Pending && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(task.poll(), SizeTaskState::Done(5));
+    }
+}
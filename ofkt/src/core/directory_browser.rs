@@ -2,10 +2,144 @@
 //!
 //! ファイルシステムの動的閲覧機能を提供します。
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::io;
+use crate::core::directory_cache::{read_directory, SharedDirectoryCache};
 use crate::data::models::DirectoryEntry;
 
+/// エントリのソートキー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    /// 名前順（大文字小文字を区別しない）
+    #[default]
+    Name,
+    /// サイズ順
+    Size,
+    /// 種類（拡張子）順
+    Type,
+    /// 更新日時順
+    Modified,
+}
+
+/// ソート順
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// 昇順
+    #[default]
+    Ascending,
+    /// 降順
+    Descending,
+}
+
+impl SortKey {
+    /// 設定画面での表示順の全キー
+    pub const ALL: [SortKey; 4] = [SortKey::Name, SortKey::Size, SortKey::Type, SortKey::Modified];
+
+    /// 設定ファイル上の名前からソートキーを取得
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "name" => Some(SortKey::Name),
+            "size" => Some(SortKey::Size),
+            "type" => Some(SortKey::Type),
+            "modified" => Some(SortKey::Modified),
+            _ => None,
+        }
+    }
+
+    /// 設定ファイル上の名前を取得
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Size => "size",
+            SortKey::Type => "type",
+            SortKey::Modified => "modified",
+        }
+    }
+
+    /// 設定画面のラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Name => "名前",
+            SortKey::Size => "サイズ",
+            SortKey::Type => "種類",
+            SortKey::Modified => "更新日時",
+        }
+    }
+}
+
+impl SortOrder {
+    /// 設定ファイル上の名前からソート順を取得
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "ascending" => Some(SortOrder::Ascending),
+            "descending" => Some(SortOrder::Descending),
+            _ => None,
+        }
+    }
+
+    /// 設定ファイル上の名前を取得
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "ascending",
+            SortOrder::Descending => "descending",
+        }
+    }
+
+    /// 反対のソート順を取得
+    pub fn reversed(self) -> Self {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+}
+
+/// ソート設定
+///
+/// 全体の既定値と、列見出しで変更したフォルダごとの上書きを保持します。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SortPreferences {
+    /// 既定のソートキー
+    default_key: SortKey,
+    /// 既定のソート順
+    default_order: SortOrder,
+    /// フォルダごとの上書き
+    overrides: HashMap<PathBuf, (SortKey, SortOrder)>,
+}
+
+impl SortPreferences {
+    /// 既定のソート方法を指定して作成
+    pub fn new(key: SortKey, order: SortOrder) -> Self {
+        Self {
+            default_key: key,
+            default_order: order,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// 既定のソート方法を変更（フォルダごとの上書きは維持）
+    pub fn set_default(&mut self, key: SortKey, order: SortOrder) {
+        self.default_key = key;
+        self.default_order = order;
+    }
+
+    /// フォルダのソート方法を上書き
+    pub fn set_override(&mut self, path: PathBuf, key: SortKey, order: SortOrder) {
+        self.overrides.insert(path, (key, order));
+    }
+
+    /// フォルダに適用するソート方法を取得
+    ///
+    /// 上書きがなければ既定のソート方法を返します。
+    pub fn for_path(&self, path: &Path) -> (SortKey, SortOrder) {
+        self.overrides
+            .get(path)
+            .copied()
+            .unwrap_or((self.default_key, self.default_order))
+    }
+}
+
 /// ディレクトリブラウザ
 ///
 /// ファイルシステムを閲覧し、ナビゲーション履歴を管理します。
@@ -25,6 +159,18 @@ pub struct DirectoryBrowser {
 
     /// 隠しファイル/フォルダを表示するか
     show_hidden: bool,
+
+    /// ソートキー
+    sort_key: SortKey,
+
+    /// ソート順
+    sort_order: SortOrder,
+
+    /// ソート設定（既定値とフォルダごとの上書き）
+    sort_preferences: SortPreferences,
+
+    /// 先読みしたフォルダ一覧のキャッシュ
+    cache: Option<SharedDirectoryCache>,
 }
 
 impl DirectoryBrowser {
@@ -48,6 +194,43 @@ impl DirectoryBrowser {
     /// let browser = DirectoryBrowser::new(PathBuf::from("C:\\Users")).unwrap();
     /// ```
     pub fn new(path: PathBuf) -> io::Result<Self> {
+        Self::with_sort_preferences(path, SortPreferences::default())
+    }
+
+    /// ソート設定を指定してDirectoryBrowserを作成
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - 開くディレクトリのパス
+    /// * `sort_preferences` - ソートの既定値とフォルダごとの上書き
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DirectoryBrowser)` - 成功時
+    /// * `Err(io::Error)` - パスが存在しない、またはディレクトリでない場合
+    pub fn with_sort_preferences(path: PathBuf, sort_preferences: SortPreferences) -> io::Result<Self> {
+        Self::with_cache(path, sort_preferences, None)
+    }
+
+    /// ソート設定と先読みキャッシュを指定してDirectoryBrowserを作成
+    ///
+    /// キャッシュにあるフォルダへ移動した場合は、ディスクを読まずにキャッシュの一覧を表示します。
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - 開くディレクトリのパス
+    /// * `sort_preferences` - ソートの既定値とフォルダごとの上書き
+    /// * `cache` - 先読みしたフォルダ一覧のキャッシュ
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DirectoryBrowser)` - 成功時
+    /// * `Err(io::Error)` - パスが存在しない、またはディレクトリでない場合
+    pub fn with_cache(
+        path: PathBuf,
+        sort_preferences: SortPreferences,
+        cache: Option<SharedDirectoryCache>,
+    ) -> io::Result<Self> {
         // パスが存在し、ディレクトリであることを確認
         if !path.exists() {
             return Err(io::Error::new(
@@ -69,6 +252,10 @@ impl DirectoryBrowser {
             history: vec![path],
             history_index: 0,
             show_hidden: false,
+            sort_key: SortKey::default(),
+            sort_order: SortOrder::default(),
+            sort_preferences,
+            cache,
         };
 
         // 初期エントリを読み込み
@@ -218,12 +405,23 @@ impl DirectoryBrowser {
 
     /// 現在のディレクトリを再読み込み
     ///
+    /// キャッシュは使わずにディスクから読み込み、キャッシュ済みのフォルダならキャッシュも更新します。
+    ///
     /// # Returns
     ///
     /// * `Ok(())` - 成功時
     /// * `Err(io::Error)` - ディレクトリの読み込みに失敗した場合
     pub fn reload(&mut self) -> io::Result<()> {
-        self.load_entries()
+        let entries = self.read_current_directory()?;
+        if let Some(cache) = &self.cache {
+            if let Ok(mut cache) = cache.lock() {
+                if cache.contains(&self.current_path) {
+                    cache.insert(self.current_path.clone(), entries.clone());
+                }
+            }
+        }
+        self.apply_entries(entries);
+        Ok(())
     }
 
     /// 隠しファイル/フォルダの表示設定を変更
@@ -235,6 +433,53 @@ impl DirectoryBrowser {
         self.show_hidden = show;
     }
 
+    /// 隠しファイル/フォルダを表示しているか
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// ソート方法を変更し、現在のエントリを並べ替える
+    ///
+    /// ディレクトリは常にファイルより前に表示されます。
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - ソートキー
+    /// * `order` - ソート順
+    pub fn set_sort(&mut self, key: SortKey, order: SortOrder) {
+        self.sort_key = key;
+        self.sort_order = order;
+        // 同じフォルダを再度開いたときも同じ並びにする
+        self.sort_preferences.set_override(self.current_path.clone(), key, order);
+        sort_entries(&mut self.entries, key, order);
+    }
+
+    /// 既定のソート方法を変更
+    ///
+    /// 現在のフォルダに上書きがなければ、現在のエントリも並べ替えます。
+    pub fn set_default_sort(&mut self, key: SortKey, order: SortOrder) {
+        self.sort_preferences.set_default(key, order);
+        let (key, order) = self.sort_preferences.for_path(&self.current_path);
+        self.sort_key = key;
+        self.sort_order = order;
+        sort_entries(&mut self.entries, key, order);
+    }
+
+    /// ソート設定を取得
+    pub fn sort_preferences(&self) -> &SortPreferences {
+        &self.sort_preferences
+    }
+
+    /// 現在のソートキーを取得
+    pub fn sort_key(&self) -> SortKey {
+        self.sort_key
+    }
+
+    /// 現在のソート順を取得
+    pub fn sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
+
     /// 内部メソッド: エントリを読み込む
     ///
     /// 現在のパスからディレクトリエントリを読み込み、ソートします。
@@ -245,54 +490,86 @@ impl DirectoryBrowser {
     /// * `Ok(())` - 成功時
     /// * `Err(io::Error)` - ディレクトリの読み込みに失敗した場合
     fn load_entries(&mut self) -> io::Result<()> {
+        // 先読み済みのフォルダはキャッシュから表示
+        let cached = self.cache
+            .as_ref()
+            .and_then(|cache| cache.lock().ok()?.get(&self.current_path));
+
+        let entries = match cached {
+            Some(entries) => {
+                log::debug!("キャッシュから一覧を表示: {}", self.current_path.display());
+                entries
+            }
+            None => self.read_current_directory()?,
+        };
+        self.apply_entries(entries);
+
+        Ok(())
+    }
+
+    /// 内部メソッド: 現在のディレクトリをディスクから読み込む
+    fn read_current_directory(&self) -> io::Result<Vec<DirectoryEntry>> {
         // WSLパスの場合の特別処理
         if is_wsl_path(&self.current_path) {
             log::info!("WSLパスを読み込み: {}", self.current_path.display());
         }
 
-        let mut entries = Vec::new();
-
-        // ディレクトリを読み込む
-        let dir_result = std::fs::read_dir(&self.current_path);
-        if let Err(e) = &dir_result {
+        read_directory(&self.current_path).inspect_err(|e| {
             if is_wsl_path(&self.current_path) {
                 log::error!("WSLパスの読み込みエラー: {} - {}", self.current_path.display(), e);
             }
-        }
+        })
+    }
 
-        for entry in dir_result? {
-            let entry = entry?;
-            let path = entry.path();
-
-            // DirectoryEntryを作成
-            match DirectoryEntry::from_path(path) {
-                Ok(dir_entry) => {
-                    // 隠しファイルのフィルタリング
-                    if !self.show_hidden && dir_entry.is_hidden {
-                        continue;
-                    }
-                    entries.push(dir_entry);
-                }
-                Err(e) => {
-                    // アクセス権限エラーなどは無視して続行
-                    eprintln!("Warning: Failed to read entry: {}", e);
-                }
-            }
+    /// 内部メソッド: 読み込んだエントリを隠しファイルの設定で絞り込み、ソートして表示する
+    fn apply_entries(&mut self, mut entries: Vec<DirectoryEntry>) {
+        // 隠しファイルのフィルタリング
+        if !self.show_hidden {
+            entries.retain(|entry| !entry.is_hidden);
         }
 
-        // エントリをソート（ディレクトリ優先、その後名前順）
-        entries.sort_by(|a, b| {
-            match (a.is_directory, b.is_directory) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            }
-        });
+        // エントリをソート（ディレクトリ優先、その後ソートキー順）
+        let (sort_key, sort_order) = self.sort_preferences.for_path(&self.current_path);
+        self.sort_key = sort_key;
+        self.sort_order = sort_order;
+        sort_entries(&mut entries, sort_key, sort_order);
 
         self.entries = entries;
+    }
+}
 
-        Ok(())
+/// エントリをソート（ディレクトリ優先、その後ソートキー順）
+fn sort_entries(entries: &mut [DirectoryEntry], key: SortKey, order: SortOrder) {
+    entries.sort_by(|a, b| {
+        match (a.is_directory, b.is_directory) {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => {
+                let name_order = a.name.to_lowercase().cmp(&b.name.to_lowercase());
+                let ordering = match key {
+                    SortKey::Name => name_order,
+                    SortKey::Size => a.size.cmp(&b.size).then(name_order),
+                    SortKey::Type => extension_of(a).cmp(&extension_of(b)).then(name_order),
+                    SortKey::Modified => a.modified.cmp(&b.modified).then(name_order),
+                };
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            }
+        }
+    });
+}
+
+/// エントリの拡張子を小文字で取得（ディレクトリや拡張子なしは空文字）
+fn extension_of(entry: &DirectoryEntry) -> String {
+    if entry.is_directory {
+        return String::new();
     }
+    entry.path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
 }
 
 /// WSLパスかどうかを判定
@@ -607,4 +884,135 @@ mod tests {
 
         cleanup_test_dir(&test_dir);
     }
+
+    #[test]
+    fn test_set_sort_by_size() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a_large.txt"), "x".repeat(300)).unwrap();
+        fs::write(temp_dir.path().join("b_small.txt"), "x").unwrap();
+        fs::write(temp_dir.path().join("c_medium.txt"), "x".repeat(50)).unwrap();
+        fs::create_dir(temp_dir.path().join("z_dir")).unwrap();
+
+        let mut browser = DirectoryBrowser::new(temp_dir.path().to_path_buf()).unwrap();
+
+        browser.set_sort(SortKey::Size, SortOrder::Ascending);
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        // ディレクトリは常に先頭
+        assert_eq!(names, vec!["z_dir", "b_small.txt", "c_medium.txt", "a_large.txt"]);
+
+        browser.set_sort(SortKey::Size, SortOrder::Descending);
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["z_dir", "a_large.txt", "c_medium.txt", "b_small.txt"]);
+    }
+
+    #[test]
+    fn test_set_sort_by_type() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("b.csv"), "").unwrap();
+        fs::write(temp_dir.path().join("c.md"), "").unwrap();
+
+        let mut browser = DirectoryBrowser::new(temp_dir.path().to_path_buf()).unwrap();
+        browser.set_sort(SortKey::Type, SortOrder::Ascending);
+
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["b.csv", "c.md", "a.txt"]);
+        assert_eq!(browser.sort_key(), SortKey::Type);
+        assert_eq!(browser.sort_order(), SortOrder::Ascending);
+    }
+
+    #[test]
+    fn test_sort_persists_across_reload() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "").unwrap();
+
+        let mut browser = DirectoryBrowser::new(temp_dir.path().to_path_buf()).unwrap();
+        browser.set_sort(SortKey::Name, SortOrder::Descending);
+        browser.reload().unwrap();
+
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["b.txt", "a.txt"]);
+    }
+
+    #[test]
+    fn test_sort_order_reversed() {
+        assert_eq!(SortOrder::Ascending.reversed(), SortOrder::Descending);
+        assert_eq!(SortOrder::Descending.reversed(), SortOrder::Ascending);
+    }
+
+    #[test]
+    fn test_sort_config_name_round_trip() {
+        for key in SortKey::ALL {
+            assert_eq!(SortKey::parse(key.config_name()), Some(key));
+        }
+        assert_eq!(SortOrder::parse("Descending"), Some(SortOrder::Descending));
+        assert_eq!(SortOrder::parse("ascending"), Some(SortOrder::Ascending));
+        assert_eq!(SortKey::parse("owner"), None);
+    }
+
+    #[test]
+    fn test_new_browser_uses_default_sort_preferences() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "").unwrap();
+
+        let preferences = SortPreferences::new(SortKey::Name, SortOrder::Descending);
+        let browser = DirectoryBrowser::with_sort_preferences(temp_dir.path().to_path_buf(), preferences).unwrap();
+
+        assert_eq!(browser.sort_order(), SortOrder::Descending);
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["b.txt", "a.txt"]);
+    }
+
+    #[test]
+    fn test_sort_override_applies_only_to_its_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sub = temp_dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+
+        let mut browser = DirectoryBrowser::new(temp_dir.path().to_path_buf()).unwrap();
+        browser.set_sort(SortKey::Size, SortOrder::Descending);
+
+        // 上書きのないフォルダは既定値
+        browser.navigate_to(sub).unwrap();
+        assert_eq!(browser.sort_key(), SortKey::Name);
+        assert_eq!(browser.sort_order(), SortOrder::Ascending);
+
+        // 戻ると上書きが適用される
+        browser.go_back().unwrap();
+        assert_eq!(browser.sort_key(), SortKey::Size);
+        assert_eq!(browser.sort_order(), SortOrder::Descending);
+    }
+
+    #[test]
+    fn test_navigate_to_serves_prewarmed_listing_from_cache() {
+        use crate::core::directory_cache::{read_directory, DirectoryCache, SharedDirectoryCache};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let favorite = temp_dir.path().join("favorite");
+        fs::create_dir(&favorite).unwrap();
+        fs::write(favorite.join("cached.txt"), "").unwrap();
+
+        let cache = SharedDirectoryCache::new(std::sync::Mutex::new(DirectoryCache::new(4)));
+        cache.lock().unwrap().insert(favorite.clone(), read_directory(&favorite).unwrap());
+        // 先読みの後に追加したファイルは、再読み込みするまで表示されない
+        fs::write(favorite.join("added.txt"), "").unwrap();
+
+        let mut browser = DirectoryBrowser::with_cache(
+            temp_dir.path().to_path_buf(),
+            SortPreferences::default(),
+            Some(cache.clone()),
+        )
+        .unwrap();
+        browser.navigate_to(favorite.clone()).unwrap();
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["cached.txt"]);
+
+        // 再読み込みはディスクから読み、キャッシュも更新する
+        browser.reload().unwrap();
+        let names: Vec<&str> = browser.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["added.txt", "cached.txt"]);
+        assert_eq!(cache.lock().unwrap().get(&favorite).unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,336 @@
+//! よく開くフォルダの一覧の先読みキャッシュ
+//!
+//! お気に入りやクイックアクセスのフォルダの一覧をワーカースレッドで先に読み込んでおき、
+//! `DirectoryBrowser` で開いたときにすぐ表示できるようにします。
+//! キャッシュしたフォルダはファイル監視で変更を検知し、変更があればキャッシュから取り除きます。
+
+use crate::data::models::DirectoryEntry;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+
+/// キャッシュに保持するフォルダ数の上限
+pub const DEFAULT_CACHE_CAPACITY: usize = 8;
+
+/// 先読みするフォルダ数の上限
+pub const PREWARM_LIMIT: usize = 5;
+
+/// ブラウザとアプリで共有するキャッシュ
+pub type SharedDirectoryCache = Arc<Mutex<DirectoryCache>>;
+
+/// フォルダの一覧のキャッシュ
+///
+/// 上限を超えた場合は最も長く使われていないフォルダから取り除きます。
+/// 一覧は隠しファイルを含めたまま、並べ替えずに保持します。
+#[derive(Debug, Clone)]
+pub struct DirectoryCache {
+    /// フォルダごとの一覧
+    listings: HashMap<PathBuf, Vec<DirectoryEntry>>,
+    /// 使用順（先頭が最も古い）
+    order: VecDeque<PathBuf>,
+    /// 保持するフォルダ数の上限
+    capacity: usize,
+}
+
+impl Default for DirectoryCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+impl DirectoryCache {
+    /// 上限を指定してキャッシュを作成
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            listings: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// キャッシュ済みの一覧を取得（使用順を更新する）
+    pub fn get(&mut self, path: &Path) -> Option<Vec<DirectoryEntry>> {
+        let entries = self.listings.get(path)?.clone();
+        self.touch(path);
+        Some(entries)
+    }
+
+    /// キャッシュ済みか
+    pub fn contains(&self, path: &Path) -> bool {
+        self.listings.contains_key(path)
+    }
+
+    /// キャッシュしているフォルダ数
+    pub fn len(&self) -> usize {
+        self.listings.len()
+    }
+
+    /// キャッシュが空か
+    pub fn is_empty(&self) -> bool {
+        self.listings.is_empty()
+    }
+
+    /// 一覧をキャッシュする
+    ///
+    /// # 戻り値
+    /// 上限を超えたために取り除いたフォルダ
+    pub fn insert(&mut self, path: PathBuf, entries: Vec<DirectoryEntry>) -> Vec<PathBuf> {
+        self.touch(&path);
+        self.listings.insert(path, entries);
+
+        let mut evicted = Vec::new();
+        while self.listings.len() > self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.listings.remove(&oldest);
+            evicted.push(oldest);
+        }
+        evicted
+    }
+
+    /// フォルダのキャッシュを取り除く
+    ///
+    /// # 戻り値
+    /// キャッシュされていた場合は true
+    pub fn invalidate(&mut self, path: &Path) -> bool {
+        self.order.retain(|p| p != path);
+        self.listings.remove(path).is_some()
+    }
+
+    /// 変更されたパスに関係するキャッシュを取り除く
+    ///
+    /// 変更されたパスそのもの（フォルダの削除・名前変更）と、
+    /// その親フォルダ（中の項目の追加・削除・変更）のキャッシュを取り除きます。
+    ///
+    /// # 戻り値
+    /// 取り除いたフォルダ
+    pub fn invalidate_changed(&mut self, changed: &[PathBuf]) -> Vec<PathBuf> {
+        let mut removed = Vec::new();
+        for path in changed {
+            for target in std::iter::once(path.as_path()).chain(path.parent()) {
+                if self.invalidate(target) {
+                    removed.push(target.to_path_buf());
+                }
+            }
+        }
+        removed
+    }
+
+    fn touch(&mut self, path: &Path) {
+        self.order.retain(|p| p != path);
+        self.order.push_back(path.to_path_buf());
+    }
+}
+
+/// フォルダ直下の項目をすべて読み込む（隠しファイルを含む、並べ替えない）
+///
+/// 読み取れない項目は無視します。
+pub fn read_directory(path: &Path) -> io::Result<Vec<DirectoryEntry>> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        match DirectoryEntry::from_path(entry.path()) {
+            Ok(dir_entry) => entries.push(dir_entry),
+            Err(e) => {
+                // アクセス権限エラーなどは無視して続行
+                eprintln!("Warning: Failed to read entry: {}", e);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// 先読みするフォルダを決める
+///
+/// 候補の順番を優先度とみなし、重複を除いて `limit` 件までを返します。
+pub fn prewarm_targets(candidates: impl IntoIterator<Item = PathBuf>, limit: usize) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|path| seen.insert(path.clone()))
+        .take(limit)
+        .collect()
+}
+
+/// ワーカースレッドでのフォルダ一覧の先読み
+#[derive(Debug)]
+pub struct DirectoryPrewarmer {
+    /// 読み込んだ一覧の受信チャネル
+    receiver: Receiver<(PathBuf, Vec<DirectoryEntry>)>,
+}
+
+impl DirectoryPrewarmer {
+    /// ワーカースレッドで読み込みを開始
+    pub fn spawn(paths: Vec<PathBuf>) -> Self {
+        let (sender, receiver) = channel();
+
+        std::thread::spawn(move || {
+            for path in paths {
+                if !path.is_dir() {
+                    continue;
+                }
+                match read_directory(&path) {
+                    Ok(entries) => {
+                        // 受信側が破棄済みなら残りは読み込まない
+                        if sender.send((path, entries)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => log::debug!("フォルダの先読みに失敗: {} ({})", path.display(), e),
+                }
+            }
+        });
+
+        Self { receiver }
+    }
+
+    /// 読み込み済みの一覧を受け取る
+    ///
+    /// # 戻り値
+    /// (受け取った一覧, すべて読み込み終わったか)
+    pub fn poll(&mut self) -> (Vec<(PathBuf, Vec<DirectoryEntry>)>, bool) {
+        let mut loaded = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(listing) => loaded.push(listing),
+                Err(TryRecvError::Empty) => return (loaded, false),
+                Err(TryRecvError::Disconnected) => return (loaded, true),
+            }
+        }
+    }
+}
+
+/// キャッシュしたフォルダの変更監視
+pub struct DirectoryCacheWatcher {
+    /// ウォッチャー本体
+    watcher: RecommendedWatcher,
+    /// イベント受信チャネル
+    receiver: Receiver<notify::Result<Event>>,
+    /// 監視中のフォルダ
+    watched: HashSet<PathBuf>,
+}
+
+impl DirectoryCacheWatcher {
+    /// ウォッチャーを作成
+    pub fn new() -> Result<Self, String> {
+        let (sender, receiver) = channel();
+        let watcher = notify::recommended_watcher(sender)
+            .map_err(|e| format!("ファイル監視の初期化に失敗: {}", e))?;
+        Ok(Self {
+            watcher,
+            receiver,
+            watched: HashSet::new(),
+        })
+    }
+
+    /// フォルダの監視を開始
+    pub fn watch(&mut self, path: &Path) {
+        if self.watched.contains(path) {
+            return;
+        }
+        match self.watcher.watch(path, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                self.watched.insert(path.to_path_buf());
+            }
+            Err(e) => log::debug!("フォルダの監視を開始できません: {} ({})", path.display(), e),
+        }
+    }
+
+    /// フォルダの監視を止める
+    pub fn unwatch(&mut self, path: &Path) {
+        if self.watched.remove(path) {
+            let _ = self.watcher.unwatch(path);
+        }
+    }
+
+    /// 受信済みのイベントから変更されたパスを取り出す
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(Ok(event)) => {
+                    if !matches!(event.kind, EventKind::Access(_)) {
+                        changed.extend(event.paths);
+                    }
+                }
+                Ok(Err(e)) => log::warn!("ファイル監視エラー: {}", e),
+                Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn listing(dir: &TempDir) -> Vec<DirectoryEntry> {
+        read_directory(dir.path()).unwrap()
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+
+        let mut cache = DirectoryCache::new(4);
+        assert!(cache.get(dir.path()).is_none());
+
+        cache.insert(dir.path().to_path_buf(), listing(&dir));
+        let entries = cache.get(dir.path()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "a.txt");
+        assert!(cache.get(Path::new("/not/cached")).is_none());
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let mut cache = DirectoryCache::new(2);
+        cache.insert(PathBuf::from("/a"), Vec::new());
+        cache.insert(PathBuf::from("/b"), Vec::new());
+        // /a を使うと、次に追い出されるのは /b
+        assert!(cache.get(Path::new("/a")).is_some());
+
+        let evicted = cache.insert(PathBuf::from("/c"), Vec::new());
+        assert_eq!(evicted, vec![PathBuf::from("/b")]);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(Path::new("/a")));
+        assert!(cache.contains(Path::new("/c")));
+    }
+
+    #[test]
+    fn test_invalidate_changed_removes_parent_and_self() {
+        let mut cache = DirectoryCache::new(4);
+        cache.insert(PathBuf::from("/work"), Vec::new());
+        cache.insert(PathBuf::from("/work/docs"), Vec::new());
+        cache.insert(PathBuf::from("/photos"), Vec::new());
+
+        // /work/docs/new.txt の作成は /work/docs の一覧を変える
+        let removed = cache.invalidate_changed(&[PathBuf::from("/work/docs/new.txt")]);
+        assert_eq!(removed, vec![PathBuf::from("/work/docs")]);
+        assert!(cache.contains(Path::new("/work")));
+
+        // /work/docs 自体の削除は /work と /work/docs の両方に影響する
+        cache.insert(PathBuf::from("/work/docs"), Vec::new());
+        let removed = cache.invalidate_changed(&[PathBuf::from("/work/docs")]);
+        assert_eq!(removed, vec![PathBuf::from("/work/docs"), PathBuf::from("/work")]);
+        assert!(cache.contains(Path::new("/photos")));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_prewarm_targets_dedupes_and_limits() {
+        let candidates = ["/a", "/b", "/a", "/c", "/d"].map(PathBuf::from);
+        assert_eq!(
+            prewarm_targets(candidates, 3),
+            ["/a", "/b", "/c"].map(PathBuf::from).to_vec()
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use crate::data::models::OpenAction;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(target_os = "windows")]
 use std::process::Command;
@@ -13,14 +14,25 @@ use std::process::Command;
 /// - ファイルを削除
 /// - ファイル名を変更
 pub struct FileManager {
-    // 将来の拡張のためのフィールド
-    // 例: 操作履歴、設定など
+    /// コピー時に読み取り専用・隠し属性を引き継ぐか（Windowsのみ）
+    preserve_attributes: bool,
 }
 
 impl FileManager {
     /// 新しい FileManager を作成
     pub fn new() -> Self {
-        Self {}
+        Self {
+            preserve_attributes: false,
+        }
+    }
+
+    /// コピー時にファイル属性（読み取り専用・隠し）を引き継ぐかを設定
+    ///
+    /// # 引数
+    /// * `enabled` - true の場合、コピー後にコピー元の属性をコピー先へ適用する
+    pub fn with_preserve_attributes(mut self, enabled: bool) -> Self {
+        self.preserve_attributes = enabled;
+        self
     }
 
     /// ファイル/フォルダをデフォルトアプリケーションで開く
@@ -60,6 +72,88 @@ impl FileManager {
         }
     }
 
+    /// エクスプローラーで親フォルダを開き、項目を選択状態で表示
+    ///
+    /// # 引数
+    /// * `path` - 表示するファイルまたはフォルダのパス
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - エラーメッセージ
+    pub fn reveal_in_folder(&self, path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            return Err(format!("パス '{}' は存在しません", path.display()));
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            Command::new("explorer")
+                .arg(format!("/select,{}", path.display()))
+                .spawn()
+                .map_err(|e| format!("フォルダを開けません: {}", e))?;
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err("Windows環境でのみサポートされています".to_string())
+        }
+    }
+
+    /// 指定したプログラムでファイルを開く
+    ///
+    /// # 引数
+    /// * `path` - 開くファイルまたはフォルダのパス
+    /// * `program` - 使用するプログラム（実行ファイルのパスまたはコマンド名）
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - エラーメッセージ
+    pub fn open_with(&self, path: &Path, program: &str) -> Result<(), String> {
+        if !path.exists() {
+            return Err(format!("パス '{}' は存在しません", path.display()));
+        }
+        if program.trim().is_empty() {
+            return Err("プログラムが指定されていません".to_string());
+        }
+
+        std::process::Command::new(program.trim())
+            .arg(path)
+            .spawn()
+            .map_err(|e| format!("'{}' で開けません: {}", program, e))?;
+        Ok(())
+    }
+
+    /// フォルダをカレントディレクトリにしてターミナルを開く
+    ///
+    /// プラットフォームごとの候補（`terminal_commands`）を順に試し、
+    /// 最初に起動できたものを使用します。
+    ///
+    /// # 引数
+    /// * `dir` - ターミナルのカレントディレクトリにするフォルダ
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - フォルダが存在しない、またはターミナルが見つからない場合
+    pub fn open_terminal(&self, dir: &Path) -> Result<(), String> {
+        if !dir.is_dir() {
+            return Err(format!("フォルダ '{}' は存在しません", dir.display()));
+        }
+
+        for command in terminal_commands(TerminalPlatform::current(), dir) {
+            match std::process::Command::new(&command.program)
+                .args(&command.args)
+                .current_dir(dir)
+                .spawn()
+            {
+                Ok(_) => return Ok(()),
+                Err(e) => log::debug!("ターミナル '{}' を起動できません: {}", command.program, e),
+            }
+        }
+
+        Err("利用できるターミナルが見つかりません".to_string())
+    }
+
     /// ファイルをコピー
     ///
     /// # 引数
@@ -99,6 +193,50 @@ impl FileManager {
         fs::copy(src, dest)
             .map_err(|e| format!("コピー失敗: {}", e))?;
 
+        if self.preserve_attributes {
+            Self::apply_file_attributes(src, dest)?;
+        }
+
+        Ok(())
+    }
+
+    /// コピー元の読み取り専用・隠し属性をコピー先に適用
+    #[cfg(target_os = "windows")]
+    fn apply_file_attributes(src: &Path, dest: &Path) -> Result<(), String> {
+        use std::os::windows::fs::MetadataExt;
+        use windows::core::HSTRING;
+        use windows::Win32::Storage::FileSystem::{
+            SetFileAttributesW, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY,
+            FILE_FLAGS_AND_ATTRIBUTES,
+        };
+
+        let src_attributes = fs::metadata(src)
+            .map_err(|e| format!("属性の取得に失敗: {}", e))?
+            .file_attributes();
+        let dest_attributes = fs::metadata(dest)
+            .map_err(|e| format!("属性の取得に失敗: {}", e))?
+            .file_attributes();
+
+        let mask = FILE_ATTRIBUTE_READONLY.0 | FILE_ATTRIBUTE_HIDDEN.0;
+        let new_attributes = (dest_attributes & !mask) | (src_attributes & mask);
+
+        if new_attributes != dest_attributes {
+            log::debug!("属性を適用: {} (0x{:x})", dest.display(), new_attributes);
+            unsafe {
+                SetFileAttributesW(
+                    &HSTRING::from(dest.as_os_str()),
+                    FILE_FLAGS_AND_ATTRIBUTES(new_attributes),
+                )
+            }
+            .map_err(|e| format!("属性の設定に失敗: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// コピー元の読み取り専用・隠し属性をコピー先に適用（Windows以外では何もしない）
+    #[cfg(not(target_os = "windows"))]
+    fn apply_file_attributes(_src: &Path, _dest: &Path) -> Result<(), String> {
         Ok(())
     }
 
@@ -228,6 +366,76 @@ impl FileManager {
         Ok(())
     }
 
+    /// 複数の項目をまとめてゴミ箱へ移動
+    ///
+    /// 存在する項目を `trash::delete_all` で一括移動し、一括移動が失敗した場合は
+    /// 残っている項目を1件ずつ移動して、どの項目が失敗したかを記録します。
+    ///
+    /// # 引数
+    /// * `paths` - ゴミ箱へ移動するファイルまたはフォルダのパス
+    ///
+    /// # 戻り値
+    /// 項目ごとの成否をまとめた BatchResult
+    pub fn delete_many_to_trash(&self, paths: &[PathBuf]) -> BatchResult {
+        let mut result = BatchResult::default();
+
+        let (existing, missing): (Vec<&PathBuf>, Vec<&PathBuf>) =
+            paths.iter().partition(|path| path.exists());
+        for path in missing {
+            result.failed.push((path.clone(), format!("削除対象 '{}' は存在しません", path.display())));
+        }
+
+        if existing.is_empty() {
+            return result;
+        }
+
+        match trash::delete_all(&existing) {
+            Ok(()) => result.succeeded += existing.len(),
+            Err(e) => {
+                log::warn!("一括でのゴミ箱への移動に失敗したため1件ずつ移動します: {}", e);
+                for path in existing {
+                    // 一括移動の途中で移動済みになった項目は成功として扱う
+                    if !path.exists() {
+                        result.succeeded += 1;
+                        continue;
+                    }
+                    match trash::delete(path) {
+                        Ok(()) => result.succeeded += 1,
+                        Err(e) => result.failed.push((path.clone(), format!("ゴミ箱への移動失敗: {}", e))),
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// フォルダを進捗通知付きで完全に削除
+    ///
+    /// 配下を深い階層から順に1件ずつ削除し、削除できない項目があっても中断せずに
+    /// 残りの削除を続けます。削除できなかった項目の親フォルダは削除を試みず、
+    /// 失敗として記録します。
+    ///
+    /// # 引数
+    /// * `path` - 削除するファイルまたはフォルダのパス
+    /// * `progress` - 1件処理するごとに呼ばれるコールバック（処理済み件数, 全件数, 処理したパス）
+    ///
+    /// # 戻り値
+    /// * `Ok(BatchResult)` - 削除結果（一部失敗を含む）
+    /// * `Err(String)` - 削除対象が存在しない場合などのエラーメッセージ
+    pub fn delete_recursive_with_progress<F>(&self, path: &Path, progress: F) -> Result<BatchResult, String>
+    where
+        F: FnMut(usize, usize, &Path),
+    {
+        delete_tree_with(path, progress, |entry, is_dir| {
+            if is_dir {
+                fs::remove_dir(entry)
+            } else {
+                fs::remove_file(entry)
+            }
+        })
+    }
+
     /// ファイル名を変更
     ///
     /// # 引数
@@ -288,6 +496,226 @@ impl FileManager {
         self.copy_recursive_internal(src, dest, 0)
     }
 
+    /// 新しいフォルダを作成
+    ///
+    /// 同名のフォルダが既に存在する場合は "名前 (2)" のように番号を付けて作成します。
+    ///
+    /// # 引数
+    /// * `parent` - 作成先の親ディレクトリ
+    /// * `name` - フォルダ名
+    ///
+    /// # 戻り値
+    /// * `Ok(PathBuf)` - 作成したフォルダのパス
+    /// * `Err(String)` - エラーメッセージ
+    pub fn create_directory(&self, parent: &Path, name: &str) -> Result<PathBuf, String> {
+        if !parent.is_dir() {
+            return Err(format!("親ディレクトリ '{}' は存在しません", parent.display()));
+        }
+
+        let mut candidate = parent.join(name);
+        let mut counter = 2;
+        while candidate.exists() {
+            candidate = parent.join(format!("{} ({})", name, counter));
+            counter += 1;
+        }
+
+        fs::create_dir(&candidate)
+            .map_err(|e| format!("フォルダ作成失敗: {}", e))?;
+
+        Ok(candidate)
+    }
+
+    /// フォルダへのジャンクションを作成
+    ///
+    /// Windows では `mklink /J` と同じジャンクションを作成します（管理者権限は不要）。
+    /// その他の環境ではシンボリックリンクを作成します。
+    ///
+    /// # 引数
+    /// * `link` - 作成するリンクのパス（存在しないこと）
+    /// * `target` - リンク先のフォルダ
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - エラーメッセージ（権限不足の場合はその旨を含む）
+    pub fn create_junction(&self, link: &Path, target: &Path) -> Result<(), String> {
+        if !target.is_dir() {
+            return Err(format!("リンク先のフォルダ '{}' は存在しません", target.display()));
+        }
+        if link.symlink_metadata().is_ok() {
+            return Err(format!("'{}' は既に存在します", link.display()));
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let output = Command::new("cmd")
+                .args(["/C", "mklink", "/J"])
+                .arg(link)
+                .arg(target)
+                .output()
+                .map_err(|e| link_error("ジャンクション", e))?;
+            if !output.status.success() {
+                let message = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("ジャンクションを作成できません: {}", message.trim()));
+            }
+            Ok(())
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(target, link).map_err(|e| link_error("シンボリックリンク", e))
+        }
+
+        #[cfg(not(any(target_os = "windows", unix)))]
+        {
+            Err("この環境ではサポートされていません".to_string())
+        }
+    }
+
+    /// ファイルへのハードリンクを作成
+    ///
+    /// # 引数
+    /// * `link` - 作成するリンクのパス（存在しないこと）
+    /// * `target` - リンク先のファイル（同じドライブ上にあること）
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - エラーメッセージ（権限不足の場合はその旨を含む）
+    pub fn create_hardlink(&self, link: &Path, target: &Path) -> Result<(), String> {
+        if !target.is_file() {
+            return Err(format!("リンク先のファイル '{}' は存在しません", target.display()));
+        }
+        if link.symlink_metadata().is_ok() {
+            return Err(format!("'{}' は既に存在します", link.display()));
+        }
+
+        fs::hard_link(target, link).map_err(|e| link_error("ハードリンク", e))
+    }
+
+    /// ファイル/フォルダをZIPに圧縮
+    ///
+    /// Windows では PowerShell の `Compress-Archive` を使用します。
+    ///
+    /// # 引数
+    /// * `src` - 圧縮するファイルまたはフォルダのパス
+    /// * `dest` - 作成するZIPファイルのパス
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - エラーメッセージ
+    pub fn compress(&self, src: &Path, dest: &Path) -> Result<(), String> {
+        if !src.exists() {
+            return Err(format!("圧縮元 '{}' は存在しません", src.display()));
+        }
+
+        if dest.exists() {
+            return Err(format!("圧縮先 '{}' は既に存在します", dest.display()));
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let status = Command::new("powershell")
+                .args(&[
+                    "-NoProfile",
+                    "-Command",
+                    "Compress-Archive",
+                    "-LiteralPath",
+                    &format!("'{}'", src.display().to_string().replace('\'', "''")),
+                    "-DestinationPath",
+                    &format!("'{}'", dest.display().to_string().replace('\'', "''")),
+                ])
+                .status()
+                .map_err(|e| format!("圧縮に失敗しました: {}", e))?;
+
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("圧縮に失敗しました: 終了コード {:?}", status.code()))
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err("Windows環境でのみサポートされています".to_string())
+        }
+    }
+
+    /// 除外パターンを指定してファイルまたはディレクトリを再帰的にコピー
+    ///
+    /// パターンは gitignore 風に解釈します。
+    /// - `node_modules` のような名前はファイル/フォルダ名に一致
+    /// - `target/` のように末尾が `/` のものはフォルダのみに一致
+    /// - `*.log` のようなワイルドカード（`*`, `?`, `[...]`）に対応
+    /// - `docs/*.tmp` のように `/` を含むものはコピー元からの相対パスに一致
+    ///
+    /// 一致したフォルダは配下ごとスキップします。
+    ///
+    /// # 引数
+    /// * `src` - コピー元のパス
+    /// * `dest` - コピー先のパス
+    /// * `excludes` - 除外パターン
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 成功
+    /// * `Err(String)` - エラーメッセージ
+    ///
+    /// # 例
+    /// ```no_run
+    /// use ofkt::core::FileManager;
+    /// use std::path::Path;
+    ///
+    /// let manager = FileManager::new();
+    /// manager.copy_recursive_filtered(
+    ///     Path::new("C:\\Users\\project"),
+    ///     Path::new("D:\\backup\\project"),
+    ///     &["target/".to_string(), "node_modules/".to_string(), ".git/".to_string()]
+    /// ).unwrap();
+    /// ```
+    pub fn copy_recursive_filtered(&self, src: &Path, dest: &Path, excludes: &[String]) -> Result<(), String> {
+        log::debug!("copy_recursive_filtered開始: {} -> {} (除外: {:?})", src.display(), dest.display(), excludes);
+
+        if !src.exists() {
+            return Err(format!("コピー元 '{}' は存在しません", src.display()));
+        }
+
+        let rules = excludes
+            .iter()
+            .map(|pattern| ExcludeRule::parse(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.copy_filtered_internal(src, dest, Path::new(""), &rules)
+    }
+
+    fn copy_filtered_internal(
+        &self,
+        src: &Path,
+        dest: &Path,
+        relative: &Path,
+        rules: &[ExcludeRule],
+    ) -> Result<(), String> {
+        if !src.is_dir() {
+            return self.copy(src, dest);
+        }
+
+        fs::create_dir_all(dest)
+            .map_err(|e| format!("ディレクトリ作成失敗: {}", e))?;
+
+        for entry in fs::read_dir(src).map_err(|e| format!("ディレクトリ読み込み失敗: {}", e))? {
+            let entry = entry.map_err(|e| format!("エントリ読み込み失敗: {}", e))?;
+            let src_path = entry.path();
+            let child_relative = relative.join(entry.file_name());
+
+            if rules.iter().any(|rule| rule.matches(&child_relative, src_path.is_dir())) {
+                log::debug!("除外パターンに一致したためスキップ: {}", src_path.display());
+                continue;
+            }
+
+            let dest_path = dest.join(entry.file_name());
+            self.copy_filtered_internal(&src_path, &dest_path, &child_relative, rules)?;
+        }
+
+        Ok(())
+    }
+
     fn copy_recursive_internal(&self, src: &Path, dest: &Path, depth: usize) -> Result<(), String> {
         if src.is_dir() {
             // ディレクトリの場合
@@ -311,21 +739,605 @@ impl FileManager {
                 let src_path = entry.path();
                 let dest_path = dest.join(entry.file_name());
 
-                self.copy_recursive_internal(&src_path, &dest_path, depth + 1)?;
-            }
+                self.copy_recursive_internal(&src_path, &dest_path, depth + 1)?;
+            }
+
+            log::debug!("[深度:{}] ディレクトリコピー完了: {}", depth, src.display());
+            Ok(())
+        } else {
+            // ファイルの場合
+            log::debug!("[深度:{}] ファイルコピー: {} -> {}", depth, src.display(), dest.display());
+            let result = self.copy(src, dest);
+            if let Err(ref e) = result {
+                log::error!("[深度:{}] ファイルコピー失敗: {} - エラー: {}", depth, src.display(), e);
+            } else {
+                log::debug!("[深度:{}] ファイルコピー完了: {}", depth, src.display());
+            }
+            result
+        }
+    }
+}
+
+/// ターミナルを起動するプラットフォーム
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalPlatform {
+    Windows,
+    MacOs,
+    Linux,
+}
+
+impl TerminalPlatform {
+    /// 実行中のプラットフォームを取得
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            TerminalPlatform::Windows
+        } else if cfg!(target_os = "macos") {
+            TerminalPlatform::MacOs
+        } else {
+            TerminalPlatform::Linux
+        }
+    }
+}
+
+/// ターミナルを起動するコマンド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalCommand {
+    /// 実行するプログラム
+    pub program: String,
+    /// 引数
+    pub args: Vec<String>,
+}
+
+impl TerminalCommand {
+    fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+/// フォルダでターミナルを開くコマンドの候補を優先順に取得
+///
+/// 起動時のカレントディレクトリも `dir` に設定されるため、
+/// カレントディレクトリを引き継ぐターミナルには引数でフォルダを渡さない。
+///
+/// # 引数
+/// * `platform` - 対象のプラットフォーム
+/// * `dir` - ターミナルで開くフォルダ
+pub fn terminal_commands(platform: TerminalPlatform, dir: &Path) -> Vec<TerminalCommand> {
+    let dir = dir.to_string_lossy();
+    match platform {
+        TerminalPlatform::Windows => vec![
+            // Windows Terminal
+            TerminalCommand::new("wt", &["-d", &dir]),
+            // 新しいコンソールウィンドウで cmd を起動
+            TerminalCommand::new("cmd", &["/C", "start", "", "/D", &dir, "cmd"]),
+        ],
+        TerminalPlatform::MacOs => vec![
+            TerminalCommand::new("open", &["-a", "Terminal", &dir]),
+        ],
+        TerminalPlatform::Linux => vec![
+            TerminalCommand::new("x-terminal-emulator", &[]),
+            TerminalCommand::new("gnome-terminal", &["--working-directory", &dir]),
+            TerminalCommand::new("konsole", &["--workdir", &dir]),
+            TerminalCommand::new("xterm", &[]),
+        ],
+    }
+}
+
+/// OSの動作に関わるため、操作前に追加の確認が必要なフォルダ（Windows以外）
+#[cfg(not(target_os = "windows"))]
+const UNIX_SYSTEM_DIRS: [&str; 10] = [
+    "/bin", "/boot", "/dev", "/etc", "/lib", "/lib64", "/proc", "/sbin", "/sys", "/usr",
+];
+
+/// システムファイルまたは隠しファイルかどうかを判定
+///
+/// 移動・削除すると OS が正しく動かなくなるおそれがあるため、
+/// 呼び出し側で追加の確認を求める判定に使います。
+/// - Windows: 隠し属性またはシステム属性が付いている
+/// - それ以外: 名前が `.` で始まる、またはシステムフォルダ配下にある
+///
+/// 存在しないパスは属性を取得できないため、名前と場所のみで判定します。
+pub fn is_system_or_hidden(path: &Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+        fs::symlink_metadata(path)
+            .map(|m| m.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0)
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        hidden || UNIX_SYSTEM_DIRS.iter().any(|dir| path.starts_with(dir))
+    }
+}
+
+/// 複数のパスに共通する最も深い親フォルダを求める
+///
+/// 各パスの親フォルダを比較します。共通部分がない場合（別ドライブなど）は None を返します。
+///
+/// # 引数
+/// * `paths` - 対象のパス
+pub fn common_ancestor(paths: &[PathBuf]) -> Option<PathBuf> {
+    let mut parents = paths.iter().map(|p| p.parent().unwrap_or(p));
+    let mut ancestor = parents.next()?.to_path_buf();
+
+    for parent in parents {
+        while !parent.starts_with(&ancestor) {
+            if !ancestor.pop() {
+                return None;
+            }
+        }
+    }
+
+    if ancestor.as_os_str().is_empty() {
+        None
+    } else {
+        Some(ancestor)
+    }
+}
+
+/// 貼り付け先のパスを決める
+///
+/// `preserve_structure` が false の場合、または共通の親フォルダがない場合は
+/// すべての項目を `dest_dir` 直下に置きます。true の場合は共通の親フォルダからの
+/// 相対パスを `dest_dir` の下に再現します（同じフォルダの項目だけなら結果は同じ）。
+///
+/// # 引数
+/// * `src_paths` - 貼り付ける項目
+/// * `dest_dir` - 貼り付け先フォルダ
+/// * `preserve_structure` - フォルダ構成を再現するか
+///
+/// # 戻り値
+/// `src_paths` と同じ順序の貼り付け先パス
+pub fn paste_destinations(src_paths: &[PathBuf], dest_dir: &Path, preserve_structure: bool) -> Vec<PathBuf> {
+    let ancestor = if preserve_structure {
+        common_ancestor(src_paths)
+    } else {
+        None
+    };
+
+    src_paths
+        .iter()
+        .map(|src| {
+            let relative = ancestor
+                .as_ref()
+                .and_then(|a| src.strip_prefix(a).ok())
+                .filter(|rest| !rest.as_os_str().is_empty());
+            match relative {
+                Some(rest) => dest_dir.join(rest),
+                None => dest_dir.join(src.file_name().unwrap_or(src.as_os_str())),
+            }
+        })
+        .collect()
+}
+
+/// リンク作成時のエラーメッセージを作成
+///
+/// 権限不足（Windows の ERROR_PRIVILEGE_NOT_HELD を含む）の場合は対処方法を案内します。
+fn link_error(kind: &str, error: std::io::Error) -> String {
+    const ERROR_PRIVILEGE_NOT_HELD: i32 = 1314;
+    if error.kind() == std::io::ErrorKind::PermissionDenied
+        || error.raw_os_error() == Some(ERROR_PRIVILEGE_NOT_HELD)
+    {
+        format!("{}を作成する権限がありません（管理者として実行するか、開発者モードを有効にしてください）", kind)
+    } else {
+        format!("{}を作成できません: {}", kind, error)
+    }
+}
+
+/// 項目と同じフォルダに作成するリンクのパスを決める
+///
+/// "名前 - リンク" とし、既に存在する場合は "名前 - リンク (2)" のように番号を付けます。
+/// ファイルの場合は拡張子を残します。
+pub fn link_path_for(target: &Path) -> Option<PathBuf> {
+    let parent = target.parent()?;
+    let (stem, extension) = if target.is_file() {
+        (
+            target.file_stem()?.to_string_lossy().to_string(),
+            target.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default(),
+        )
+    } else {
+        (target.file_name()?.to_string_lossy().to_string(), String::new())
+    };
+
+    let mut candidate = parent.join(format!("{} - リンク{}", stem, extension));
+    let mut counter = 2;
+    while candidate.symlink_metadata().is_ok() {
+        candidate = parent.join(format!("{} - リンク ({}){}", stem, counter, extension));
+        counter += 1;
+    }
+    Some(candidate)
+}
+
+/// Windows のショートカットファイル（.lnk）か
+pub fn is_shortcut_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+}
+
+/// ショートカットファイル（.lnk）のリンク先を取得
+///
+/// シェルリンクの COM インターフェース（IShellLinkW）でショートカットを読み込み、
+/// リンク先のパスを返します。コントロールパネルなどファイルシステム上にない項目を
+/// 指すショートカットはエラーになります。
+///
+/// # 引数
+/// * `path` - ショートカットファイルのパス
+///
+/// # 戻り値
+/// * `Ok(PathBuf)` - リンク先のパス
+/// * `Err(String)` - エラーメッセージ
+pub fn resolve_lnk(path: &Path) -> Result<PathBuf, String> {
+    if !path.is_file() {
+        return Err(format!("ショートカット '{}' は存在しません", path.display()));
+    }
+    if !is_shortcut_file(path) {
+        return Err(format!("'{}' はショートカットファイルではありません", path.display()));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        read_shell_link_target(path)
+            .map_err(|e| format!("ショートカットを読み込めません: {}", e))?
+            .ok_or_else(|| "ショートカットのリンク先がファイルシステム上にありません".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("この環境ではサポートされていません".to_string())
+    }
+}
+
+/// IShellLinkW でショートカットのリンク先を読み込む（リンク先が空の場合は None）
+#[cfg(target_os = "windows")]
+fn read_shell_link_target(path: &Path) -> windows::core::Result<Option<PathBuf>> {
+    use std::os::windows::ffi::OsStringExt;
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::Foundation::MAX_PATH;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, IPersistFile, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED, STGM_READ,
+    };
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+    unsafe {
+        // 既に別のモードで初期化済みのスレッドでもそのまま使う
+        let initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+
+        let result = (|| -> windows::core::Result<Option<PathBuf>> {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            let file: IPersistFile = link.cast()?;
+            file.Load(&HSTRING::from(path.as_os_str()), STGM_READ)?;
+
+            let mut buffer = [0u16; MAX_PATH as usize];
+            link.GetPath(&mut buffer, std::ptr::null_mut(), 0)?;
+            let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+            Ok((len > 0).then(|| PathBuf::from(std::ffi::OsString::from_wide(&buffer[..len]))))
+        })();
+
+        if initialized {
+            CoUninitialize();
+        }
+        result
+    }
+}
+
+/// 貼り付けで作成される項目数（フォルダ配下を含む）を上限付きで数える
+///
+/// 巨大なフォルダで時間がかからないよう、`limit` を超えた時点で数えるのをやめます。
+///
+/// # 引数
+/// * `src_paths` - 貼り付け元のパス
+/// * `limit` - 数える上限
+///
+/// # 戻り値
+/// 項目数（`limit` を超える場合は `limit + 1`）
+pub fn count_items_bounded(src_paths: &[PathBuf], limit: usize) -> usize {
+    let mut count = 0;
+    for src in src_paths {
+        for _ in walkdir::WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+            count += 1;
+            if count > limit {
+                return count;
+            }
+        }
+    }
+    count
+}
+
+/// 貼り付け前に項目数の確認が必要か
+///
+/// # 引数
+/// * `count` - 貼り付けで作成される項目数
+/// * `threshold` - 確認する項目数のしきい値（0 は確認しない）
+pub fn needs_large_paste_confirmation(count: usize, threshold: usize) -> bool {
+    threshold > 0 && count > threshold
+}
+
+/// 貼り付け先に同名の項目がある場合の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// 確認のうえ常に上書きする
+    #[default]
+    Overwrite,
+    /// 貼り付け先の方が新しい場合は上書きしない（明示的に許可した場合のみ上書き）
+    OverwriteOnlyIfOlder,
+}
+
+impl ConflictPolicy {
+    /// 設定画面での表示順の全ポリシー
+    pub const ALL: [ConflictPolicy; 2] = [ConflictPolicy::Overwrite, ConflictPolicy::OverwriteOnlyIfOlder];
+
+    /// 設定ファイル上の名前からポリシーを取得
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "overwrite" => Some(ConflictPolicy::Overwrite),
+            "overwrite_only_if_older" => Some(ConflictPolicy::OverwriteOnlyIfOlder),
+            _ => None,
+        }
+    }
+
+    /// 設定ファイル上の名前を取得
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::OverwriteOnlyIfOlder => "overwrite_only_if_older",
+        }
+    }
+
+    /// 設定画面のラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Overwrite => "上書きする",
+            ConflictPolicy::OverwriteOnlyIfOlder => "貼り付け先の方が新しければスキップ",
+        }
+    }
+}
+
+/// 同名の項目がある場合の判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictDecision {
+    /// 上書きする
+    Overwrite,
+    /// 上書きせずにスキップする
+    Skip,
+}
+
+/// 上書き確認ダイアログで項目ごとに選ぶ、同名の項目がある場合の処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// 貼り付け先を上書きする
+    Overwrite,
+    /// 貼り付けずにスキップする
+    Skip,
+    /// 両方残す（貼り付ける側の名前をコピー名に変える）
+    KeepBoth,
+}
+
+impl ConflictResolution {
+    /// ダイアログでの表示順の全選択肢
+    pub const ALL: [ConflictResolution; 3] = [
+        ConflictResolution::Overwrite,
+        ConflictResolution::Skip,
+        ConflictResolution::KeepBoth,
+    ];
+
+    /// ダイアログのラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictResolution::Overwrite => "上書き",
+            ConflictResolution::Skip => "スキップ",
+            ConflictResolution::KeepBoth => "両方残す",
+        }
+    }
+}
+
+/// 更新日時を比べて上書きするか判定
+///
+/// `OverwriteOnlyIfOlder` では貼り付け先の方が新しい場合に加え、
+/// どちらかの更新日時が分からない場合も安全のためスキップします。
+///
+/// # 引数
+/// * `policy` - 同名の項目がある場合の扱い
+/// * `src_modified` - 貼り付け元の更新日時
+/// * `dest_modified` - 貼り付け先の更新日時
+pub fn decide_conflict(
+    policy: ConflictPolicy,
+    src_modified: Option<std::time::SystemTime>,
+    dest_modified: Option<std::time::SystemTime>,
+) -> ConflictDecision {
+    match policy {
+        ConflictPolicy::Overwrite => ConflictDecision::Overwrite,
+        ConflictPolicy::OverwriteOnlyIfOlder => match (src_modified, dest_modified) {
+            (Some(src), Some(dest)) if dest <= src => ConflictDecision::Overwrite,
+            _ => ConflictDecision::Skip,
+        },
+    }
+}
+
+/// 貼り付け先の項目を上書きするか、実際の更新日時から判定
+///
+/// 貼り付け先が存在しない場合は常に上書き（作成）します。
+pub fn resolve_conflict(policy: ConflictPolicy, src: &Path, dest: &Path) -> ConflictDecision {
+    if !dest.exists() {
+        return ConflictDecision::Overwrite;
+    }
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    decide_conflict(policy, modified(src), modified(dest))
+}
+
+/// 複数項目を処理した結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchResult {
+    /// 成功した件数
+    pub succeeded: usize,
+    /// 失敗した項目とエラーメッセージ
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl BatchResult {
+    /// すべて成功したか
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// 処理した総件数
+    pub fn total(&self) -> usize {
+        self.succeeded + self.failed.len()
+    }
+}
+
+/// 削除処理の本体（削除方法を差し替え可能）
+fn delete_tree_with<F, R>(root: &Path, mut progress: F, mut remove: R) -> Result<BatchResult, String>
+where
+    F: FnMut(usize, usize, &Path),
+    R: FnMut(&Path, bool) -> std::io::Result<()>,
+{
+    if !root.exists() {
+        return Err(format!("削除対象 '{}' は存在しません", root.display()));
+    }
+
+    // 深い階層から順に並べる（フォルダは中身の後）
+    let entries: Vec<(PathBuf, bool)> = walkdir::WalkDir::new(root)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some((entry.path().to_path_buf(), entry.file_type().is_dir())),
+            Err(e) => {
+                log::warn!("削除対象の列挙に失敗: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let total = entries.len();
+    let mut result = BatchResult::default();
+    // 配下の削除に失敗したフォルダ（削除を試みない）
+    let mut blocked_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
+    for (index, (entry, is_dir)) in entries.iter().enumerate() {
+        if *is_dir && blocked_dirs.contains(entry) {
+            result.failed.push((entry.clone(), "配下に削除できない項目があります".to_string()));
+        } else {
+            match remove(entry, *is_dir) {
+                Ok(()) => result.succeeded += 1,
+                Err(e) => {
+                    log::warn!("削除失敗: {} ({})", entry.display(), e);
+                    result.failed.push((entry.clone(), format!("削除失敗: {}", e)));
+                }
+            }
+        }
+
+        // 失敗した項目の親フォルダはすべて削除できないため記録しておく
+        if result.failed.last().map(|(p, _)| p == entry).unwrap_or(false) && entry != root {
+            for ancestor in entry.ancestors().skip(1) {
+                blocked_dirs.insert(ancestor.to_path_buf());
+                if ancestor == root {
+                    break;
+                }
+            }
+        }
+
+        progress(index + 1, total, entry);
+    }
+
+    Ok(result)
+}
+
+/// エイリアスを開く操作
+///
+/// `OpenAction` に応じた呼び出し先を差し替えられるよう、
+/// `FileManager` の開く系メソッドを抽象化しています。
+pub trait FileOpener {
+    /// 既定のアプリケーションで開く
+    fn open(&self, path: &Path) -> Result<(), String>;
+    /// 親フォルダで項目を表示
+    fn reveal_in_folder(&self, path: &Path) -> Result<(), String>;
+    /// 指定したプログラムで開く
+    fn open_with(&self, path: &Path, program: &str) -> Result<(), String>;
+}
+
+impl FileOpener for FileManager {
+    fn open(&self, path: &Path) -> Result<(), String> {
+        FileManager::open(self, path)
+    }
+
+    fn reveal_in_folder(&self, path: &Path) -> Result<(), String> {
+        FileManager::reveal_in_folder(self, path)
+    }
+
+    fn open_with(&self, path: &Path, program: &str) -> Result<(), String> {
+        FileManager::open_with(self, path, program)
+    }
+}
+
+/// `OpenAction` に従ってパスを開く
+///
+/// # 引数
+/// * `opener` - 開く操作の実装（通常は `FileManager`）
+/// * `path` - 開くパス
+/// * `action` - エイリアスに設定された動作
+///
+/// # 戻り値
+/// * `Ok(())` - 成功
+/// * `Err(String)` - エラーメッセージ
+pub fn open_with_action<O: FileOpener>(opener: &O, path: &Path, action: &OpenAction) -> Result<(), String> {
+    match action {
+        OpenAction::Open => opener.open(path),
+        OpenAction::RevealInFolder => opener.reveal_in_folder(path),
+        OpenAction::OpenWith(program) => opener.open_with(path, program),
+    }
+}
+
+/// コピー時の除外ルール（gitignore 風）
+struct ExcludeRule {
+    pattern: glob::Pattern,
+    /// フォルダのみに一致するか（末尾が `/` のパターン）
+    dir_only: bool,
+    /// 相対パス全体に一致させるか（`/` を含むパターン）
+    match_path: bool,
+}
+
+impl ExcludeRule {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let trimmed = raw.trim();
+        let dir_only = trimmed.ends_with('/');
+        let body = trimmed.trim_end_matches('/').trim_start_matches('/');
+        if body.is_empty() {
+            return Err(format!("除外パターン '{}' が不正です", raw));
+        }
+
+        let pattern = glob::Pattern::new(body)
+            .map_err(|e| format!("除外パターン '{}' が不正です: {}", raw, e))?;
+
+        Ok(Self {
+            pattern,
+            dir_only,
+            match_path: body.contains('/'),
+        })
+    }
 
-            log::debug!("[深度:{}] ディレクトリコピー完了: {}", depth, src.display());
-            Ok(())
+    fn matches(&self, relative: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        if self.match_path {
+            let normalized = relative.to_string_lossy().replace('\\', "/");
+            self.pattern.matches(&normalized)
         } else {
-            // ファイルの場合
-            log::debug!("[深度:{}] ファイルコピー: {} -> {}", depth, src.display(), dest.display());
-            let result = self.copy(src, dest);
-            if let Err(ref e) = result {
-                log::error!("[深度:{}] ファイルコピー失敗: {} - エラー: {}", depth, src.display(), e);
-            } else {
-                log::debug!("[深度:{}] ファイルコピー完了: {}", depth, src.display());
-            }
-            result
+            relative
+                .file_name()
+                .map(|name| self.pattern.matches(&name.to_string_lossy()))
+                .unwrap_or(false)
         }
     }
 }
@@ -383,6 +1395,88 @@ mod tests {
         assert_eq!(src_content, dest_content);
     }
 
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_copy_preserves_readonly_attribute() {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_READONLY: u32 = 0x1;
+
+        let manager = FileManager::new().with_preserve_attributes(true);
+        let temp_dir = tempdir().unwrap();
+
+        // 読み取り専用のテストファイルを作成
+        let src_path = temp_dir.path().join("readonly.txt");
+        let mut file = File::create(&src_path).unwrap();
+        writeln!(file, "読み取り専用").unwrap();
+        drop(file);
+
+        let mut permissions = fs::metadata(&src_path).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&src_path, permissions).unwrap();
+
+        let dest_path = temp_dir.path().join("readonly_copy.txt");
+        let result = manager.copy(&src_path, &dest_path);
+        assert!(result.is_ok());
+
+        // コピー先も読み取り専用であることを確認
+        let attributes = fs::metadata(&dest_path).unwrap().file_attributes();
+        assert_ne!(attributes & FILE_ATTRIBUTE_READONLY, 0);
+
+        // 一時ディレクトリを削除できるよう読み取り専用を解除
+        for path in [&src_path, &dest_path] {
+            let mut permissions = fs::metadata(path).unwrap().permissions();
+            #[allow(clippy::permissions_set_readonly_false)]
+            permissions.set_readonly(false);
+            fs::set_permissions(path, permissions).unwrap();
+        }
+    }
+
+    /// 隠し属性を付ける（テスト用）
+    #[cfg(target_os = "windows")]
+    fn set_hidden(path: &Path) {
+        use windows::core::HSTRING;
+        use windows::Win32::Storage::FileSystem::{SetFileAttributesW, FILE_ATTRIBUTE_HIDDEN};
+
+        unsafe { SetFileAttributesW(&HSTRING::from(path.as_os_str()), FILE_ATTRIBUTE_HIDDEN) }.unwrap();
+    }
+
+    /// 隠し属性が付いているか（テスト用）
+    #[cfg(target_os = "windows")]
+    fn is_hidden(path: &Path) -> bool {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        fs::metadata(path).unwrap().file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_copy_preserves_hidden_attribute() {
+        let temp_dir = tempdir().unwrap();
+        let src_path = temp_dir.path().join("hidden.txt");
+        fs::write(&src_path, "隠しファイル").unwrap();
+        set_hidden(&src_path);
+
+        let dest_path = temp_dir.path().join("hidden_copy.txt");
+        FileManager::new().with_preserve_attributes(true).copy(&src_path, &dest_path).unwrap();
+        assert!(is_hidden(&dest_path));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_copy_recursive_preserves_hidden_attribute() {
+        let temp_dir = tempdir().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        let hidden_file = src_dir.join("hidden.txt");
+        fs::write(&hidden_file, "隠しファイル").unwrap();
+        set_hidden(&hidden_file);
+
+        // フォルダ内のファイルも属性を引き継ぐ
+        let dest_dir = temp_dir.path().join("dest");
+        FileManager::new().with_preserve_attributes(true).copy_recursive(&src_dir, &dest_dir).unwrap();
+        assert!(is_hidden(&dest_dir.join("hidden.txt")));
+    }
+
     #[test]
     fn test_copy_nonexistent_source() {
         let manager = FileManager::new();
@@ -529,6 +1623,76 @@ mod tests {
         assert!(!file_path.exists());
     }
 
+    #[test]
+    fn test_delete_many_to_trash_partitions_results() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let existing1 = temp_dir.path().join("a.txt");
+        let existing2 = temp_dir.path().join("b.txt");
+        let missing = temp_dir.path().join("missing.txt");
+        File::create(&existing1).unwrap();
+        File::create(&existing2).unwrap();
+
+        let result = manager.delete_many_to_trash(&[existing1.clone(), missing.clone(), existing2.clone()]);
+
+        assert_eq!(result.succeeded, 2);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, missing);
+        assert!(result.failed[0].1.contains("存在しません"));
+        assert_eq!(result.total(), 3);
+        assert!(!existing1.exists());
+        assert!(!existing2.exists());
+    }
+
+    #[test]
+    fn test_delete_many_to_trash_all_missing() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let paths = vec![temp_dir.path().join("x"), temp_dir.path().join("y")];
+        let result = manager.delete_many_to_trash(&paths);
+
+        assert_eq!(result.succeeded, 0);
+        assert_eq!(result.failed.len(), 2);
+        assert!(!result.is_complete());
+    }
+
+    #[test]
+    fn test_terminal_commands_windows() {
+        let commands = terminal_commands(TerminalPlatform::Windows, Path::new(r"C:\work"));
+
+        assert_eq!(commands[0], TerminalCommand::new("wt", &["-d", r"C:\work"]));
+        assert_eq!(commands[1], TerminalCommand::new("cmd", &["/C", "start", "", "/D", r"C:\work", "cmd"]));
+    }
+
+    #[test]
+    fn test_terminal_commands_macos() {
+        let commands = terminal_commands(TerminalPlatform::MacOs, Path::new("/Users/me/work"));
+
+        assert_eq!(commands, vec![TerminalCommand::new("open", &["-a", "Terminal", "/Users/me/work"])]);
+    }
+
+    #[test]
+    fn test_terminal_commands_linux() {
+        let commands = terminal_commands(TerminalPlatform::Linux, Path::new("/home/me/work"));
+        let programs: Vec<&str> = commands.iter().map(|c| c.program.as_str()).collect();
+
+        // x-terminal-emulator を優先し、見つからない場合に備えて代替を用意
+        assert_eq!(programs, vec!["x-terminal-emulator", "gnome-terminal", "konsole", "xterm"]);
+        assert_eq!(commands[1].args, vec!["--working-directory".to_string(), "/home/me/work".to_string()]);
+    }
+
+    #[test]
+    fn test_open_terminal_nonexistent_dir() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let result = manager.open_terminal(&temp_dir.path().join("missing"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("存在しません"));
+    }
+
     #[test]
     fn test_delete_nonexistent() {
         let manager = FileManager::new();
@@ -664,4 +1828,542 @@ mod tests {
         let err_msg = open_err.unwrap_err();
         assert!(err_msg.contains("存在しません"));
     }
+
+    #[test]
+    fn test_create_directory() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let created = manager.create_directory(temp_dir.path(), "新しいフォルダ").unwrap();
+        assert!(created.is_dir());
+        assert_eq!(created, temp_dir.path().join("新しいフォルダ"));
+
+        // 同名フォルダがある場合は番号付きで作成
+        let second = manager.create_directory(temp_dir.path(), "新しいフォルダ").unwrap();
+        assert!(second.is_dir());
+        assert_eq!(second, temp_dir.path().join("新しいフォルダ (2)"));
+    }
+
+    #[test]
+    fn test_create_hardlink_shares_content() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("data.txt");
+        fs::write(&target, "before").unwrap();
+
+        let link = link_path_for(&target).unwrap();
+        assert_eq!(link, temp_dir.path().join("data - リンク.txt"));
+        manager.create_hardlink(&link, &target).unwrap();
+
+        fs::write(&target, "after").unwrap();
+        assert_eq!(fs::read_to_string(&link).unwrap(), "after");
+        // 既に存在するパスには作成しない
+        assert!(manager.create_hardlink(&link, &target).is_err());
+        assert_eq!(link_path_for(&target).unwrap(), temp_dir.path().join("data - リンク (2).txt"));
+    }
+
+    #[test]
+    fn test_create_links_require_existing_target() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let missing = temp_dir.path().join("missing");
+        let link = temp_dir.path().join("link");
+
+        assert!(manager.create_junction(&link, &missing).is_err());
+        assert!(manager.create_hardlink(&link, &missing).is_err());
+        // フォルダにはハードリンクを作成できない
+        assert!(manager.create_hardlink(&link, temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_link_error_explains_privilege() {
+        let denied = link_error("ジャンクション", std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        assert!(denied.contains("権限がありません"));
+        let privilege = link_error("ジャンクション", std::io::Error::from_raw_os_error(1314));
+        assert!(privilege.contains("権限がありません"));
+        let other = link_error("ジャンクション", std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert!(other.starts_with("ジャンクションを作成できません"));
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_create_junction_resolves_to_target() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("shared");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("file.txt"), "content").unwrap();
+
+        let link = link_path_for(&target).unwrap();
+        manager.create_junction(&link, &target).unwrap();
+
+        assert_eq!(fs::read_to_string(link.join("file.txt")).unwrap(), "content");
+        assert_eq!(fs::canonicalize(&link).unwrap(), fs::canonicalize(&target).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_junction_falls_back_to_symlink() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("shared");
+        fs::create_dir(&target).unwrap();
+
+        let link = temp_dir.path().join("shared - リンク");
+        manager.create_junction(&link, &target).unwrap();
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::canonicalize(&link).unwrap(), fs::canonicalize(&target).unwrap());
+    }
+
+    #[test]
+    fn test_create_directory_nonexistent_parent() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let result = manager.create_directory(&temp_dir.path().join("nonexistent"), "dir");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("存在しません"));
+    }
+
+    #[test]
+    fn test_compress_nonexistent_source() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let result = manager.compress(
+            &temp_dir.path().join("nonexistent"),
+            &temp_dir.path().join("archive.zip"),
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("存在しません"));
+    }
+
+    #[test]
+    fn test_copy_recursive_filtered_skips_excluded_dirs() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        // プロジェクト風のツリーを作成
+        let src = temp_dir.path().join("project");
+        fs::create_dir_all(src.join("src")).unwrap();
+        fs::create_dir_all(src.join("target/debug")).unwrap();
+        fs::create_dir_all(src.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(src.join(".git")).unwrap();
+        fs::write(src.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(src.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(src.join("target/debug/app"), "binary").unwrap();
+        fs::write(src.join("node_modules/pkg/index.js"), "js").unwrap();
+        fs::write(src.join(".git/HEAD"), "ref").unwrap();
+
+        let dest = temp_dir.path().join("copy");
+        let excludes = vec![
+            "target/".to_string(),
+            "node_modules/".to_string(),
+            ".git".to_string(),
+        ];
+        let result = manager.copy_recursive_filtered(&src, &dest, &excludes);
+        assert!(result.is_ok());
+
+        // 通常のファイルはコピーされる
+        assert!(dest.join("Cargo.toml").exists());
+        assert!(dest.join("src/main.rs").exists());
+
+        // 除外したフォルダは配下ごとコピーされない
+        assert!(!dest.join("target").exists());
+        assert!(!dest.join("node_modules").exists());
+        assert!(!dest.join(".git").exists());
+    }
+
+    #[test]
+    fn test_copy_recursive_filtered_glob_patterns() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+
+        let src = temp_dir.path().join("src_dir");
+        fs::create_dir_all(src.join("docs")).unwrap();
+        fs::write(src.join("app.log"), "log").unwrap();
+        fs::write(src.join("readme.md"), "readme").unwrap();
+        fs::write(src.join("docs/draft.tmp"), "tmp").unwrap();
+        fs::write(src.join("docs/guide.md"), "guide").unwrap();
+        // 末尾 "/" のパターンはファイルには一致しない
+        fs::write(src.join("build"), "file named build").unwrap();
+
+        let dest = temp_dir.path().join("dest_dir");
+        let excludes = vec![
+            "*.log".to_string(),
+            "docs/*.tmp".to_string(),
+            "build/".to_string(),
+        ];
+        manager.copy_recursive_filtered(&src, &dest, &excludes).unwrap();
+
+        assert!(!dest.join("app.log").exists());
+        assert!(dest.join("readme.md").exists());
+        assert!(!dest.join("docs/draft.tmp").exists());
+        assert!(dest.join("docs/guide.md").exists());
+        assert!(dest.join("build").exists());
+    }
+
+    #[test]
+    fn test_copy_recursive_filtered_invalid_pattern() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let src = temp_dir.path().join("src_dir");
+        fs::create_dir_all(&src).unwrap();
+
+        let result = manager.copy_recursive_filtered(
+            &src,
+            &temp_dir.path().join("dest_dir"),
+            &["[".to_string()],
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("除外パターン"));
+    }
+
+    /// 呼び出されたメソッドを記録するテスト用の FileOpener
+    #[derive(Default)]
+    struct RecordingOpener {
+        calls: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl FileOpener for RecordingOpener {
+        fn open(&self, path: &Path) -> Result<(), String> {
+            self.calls.borrow_mut().push(format!("open:{}", path.display()));
+            Ok(())
+        }
+
+        fn reveal_in_folder(&self, path: &Path) -> Result<(), String> {
+            self.calls.borrow_mut().push(format!("reveal:{}", path.display()));
+            Ok(())
+        }
+
+        fn open_with(&self, path: &Path, program: &str) -> Result<(), String> {
+            self.calls.borrow_mut().push(format!("open_with:{}:{}", program, path.display()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_open_with_action_dispatch() {
+        let opener = RecordingOpener::default();
+        let path = Path::new("/tmp/report.pdf");
+
+        open_with_action(&opener, path, &OpenAction::Open).unwrap();
+        open_with_action(&opener, path, &OpenAction::RevealInFolder).unwrap();
+        open_with_action(&opener, path, &OpenAction::OpenWith("viewer".to_string())).unwrap();
+
+        let calls = opener.calls.borrow();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], "open:/tmp/report.pdf");
+        assert_eq!(calls[1], "reveal:/tmp/report.pdf");
+        assert_eq!(calls[2], "open_with:viewer:/tmp/report.pdf");
+    }
+
+    #[test]
+    fn test_reveal_in_folder_nonexistent() {
+        let manager = FileManager::new();
+        let result = manager.reveal_in_folder(Path::new("/nonexistent/path/file.txt"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_open_with_empty_program() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, "test").unwrap();
+
+        let result = manager.open_with(&file_path, "  ");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("プログラム"));
+    }
+
+    /// 削除テスト用のツリーを作成
+    fn create_delete_tree(root: &Path) {
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::create_dir_all(root.join("c")).unwrap();
+        fs::write(root.join("top.txt"), "top").unwrap();
+        fs::write(root.join("a/one.txt"), "1").unwrap();
+        fs::write(root.join("a/b/locked.txt"), "locked").unwrap();
+        fs::write(root.join("c/two.txt"), "2").unwrap();
+    }
+
+    #[test]
+    fn test_delete_recursive_with_progress() {
+        let manager = FileManager::new();
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("big");
+        create_delete_tree(&root);
+
+        let mut calls = Vec::new();
+        let result = manager
+            .delete_recursive_with_progress(&root, |done, total, _| calls.push((done, total)))
+            .unwrap();
+
+        // ファイル4件 + フォルダ4件（root, a, a/b, c）
+        assert!(result.is_complete());
+        assert_eq!(result.succeeded, 8);
+        assert!(!root.exists());
+
+        // 進捗は1件ずつ全件数まで通知される
+        assert_eq!(calls.len(), 8);
+        assert_eq!(calls.first(), Some(&(1, 8)));
+        assert_eq!(calls.last(), Some(&(8, 8)));
+    }
+
+    #[test]
+    fn test_delete_tree_continues_after_error() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("big");
+        create_delete_tree(&root);
+        let locked = root.join("a/b/locked.txt");
+
+        // locked.txt だけ削除できない状況を再現
+        let mut progress_count = 0;
+        let result = delete_tree_with(
+            &root,
+            |_, _, _| progress_count += 1,
+            |entry, is_dir| {
+                if entry == locked {
+                    return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "locked"));
+                }
+                if is_dir { fs::remove_dir(entry) } else { fs::remove_file(entry) }
+            },
+        )
+        .unwrap();
+
+        // 中断せずに全件処理される
+        assert_eq!(progress_count, 8);
+        assert!(!result.is_complete());
+        assert_eq!(result.total(), 8);
+
+        // locked.txt と、その親フォルダ（a/b, a, root）が失敗として記録される
+        let failed: Vec<&PathBuf> = result.failed.iter().map(|(p, _)| p).collect();
+        assert_eq!(failed.len(), 4);
+        assert!(failed.contains(&&locked));
+        assert!(failed.contains(&&root.join("a/b")));
+        assert!(failed.contains(&&root.join("a")));
+        assert!(failed.contains(&&root));
+
+        // 他の項目は削除されている
+        assert_eq!(result.succeeded, 4);
+        assert!(!root.join("top.txt").exists());
+        assert!(!root.join("a/one.txt").exists());
+        assert!(!root.join("c").exists());
+        assert!(locked.exists());
+    }
+
+    #[test]
+    fn test_delete_recursive_with_progress_nonexistent() {
+        let manager = FileManager::new();
+        let result = manager.delete_recursive_with_progress(Path::new("/nonexistent/tree"), |_, _, _| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_common_ancestor() {
+        let paths = vec![
+            PathBuf::from("/work/project/src/main.rs"),
+            PathBuf::from("/work/project/docs/guide.md"),
+            PathBuf::from("/work/project/src/ui/view.rs"),
+        ];
+        assert_eq!(common_ancestor(&paths), Some(PathBuf::from("/work/project")));
+
+        // 同じフォルダの項目だけならそのフォルダ
+        let same = vec![PathBuf::from("/work/a.txt"), PathBuf::from("/work/b.txt")];
+        assert_eq!(common_ancestor(&same), Some(PathBuf::from("/work")));
+
+        assert_eq!(common_ancestor(&[]), None);
+        assert_eq!(common_ancestor(&[PathBuf::from("a.txt"), PathBuf::from("b.txt")]), None);
+    }
+
+    #[test]
+    fn test_paste_destinations_preserves_structure() {
+        let srcs = vec![
+            PathBuf::from("/work/project/src/main.rs"),
+            PathBuf::from("/work/project/docs/main.rs"),
+            PathBuf::from("/work/project/README.md"),
+        ];
+        let dest = Path::new("/backup");
+
+        let structured = paste_destinations(&srcs, dest, true);
+        assert_eq!(structured, vec![
+            PathBuf::from("/backup/src/main.rs"),
+            PathBuf::from("/backup/docs/main.rs"),
+            PathBuf::from("/backup/README.md"),
+        ]);
+
+        // 既定（無効）ではすべて直下に置かれ、同名ファイルが衝突する
+        let flat = paste_destinations(&srcs, dest, false);
+        assert_eq!(flat[0], PathBuf::from("/backup/main.rs"));
+        assert_eq!(flat[1], PathBuf::from("/backup/main.rs"));
+    }
+
+    #[test]
+    fn test_paste_destinations_same_folder_is_flat() {
+        let srcs = vec![PathBuf::from("/work/a.txt"), PathBuf::from("/work/sub")];
+        let dest = Path::new("/backup");
+        assert_eq!(paste_destinations(&srcs, dest, true), paste_destinations(&srcs, dest, false));
+    }
+
+    #[test]
+    fn test_count_items_bounded() {
+        let temp_dir = tempdir().unwrap();
+        let folder = temp_dir.path().join("folder");
+        fs::create_dir_all(folder.join("sub")).unwrap();
+        for name in ["a.txt", "b.txt", "sub/c.txt"] {
+            File::create(folder.join(name)).unwrap();
+        }
+        let single = temp_dir.path().join("single.txt");
+        File::create(&single).unwrap();
+        let srcs = vec![folder, single];
+
+        // folder, sub, a, b, c + single
+        assert_eq!(count_items_bounded(&srcs, 100), 6);
+        // 上限を超えたら数えるのをやめる
+        assert_eq!(count_items_bounded(&srcs, 3), 4);
+    }
+
+    #[test]
+    fn test_is_shortcut_file() {
+        assert!(is_shortcut_file(Path::new("C:\\Users\\taro\\Desktop\\メモ.lnk")));
+        assert!(is_shortcut_file(Path::new("/tmp/Report.LNK")));
+        assert!(!is_shortcut_file(Path::new("/tmp/report.txt")));
+        assert!(!is_shortcut_file(Path::new("/tmp/lnk")));
+    }
+
+    #[test]
+    fn test_resolve_lnk_rejects_missing_or_non_shortcut() {
+        let temp_dir = tempdir().unwrap();
+        assert!(resolve_lnk(&temp_dir.path().join("missing.lnk")).unwrap_err().contains("存在しません"));
+
+        let text = temp_dir.path().join("memo.txt");
+        File::create(&text).unwrap();
+        assert!(resolve_lnk(&text).unwrap_err().contains("ショートカットファイルではありません"));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_resolve_lnk_returns_target() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("target.txt");
+        File::create(&target).unwrap();
+        let shortcut = temp_dir.path().join("target.lnk");
+
+        // WScript.Shell でショートカットを作成
+        let script = format!(
+            "$s = (New-Object -ComObject WScript.Shell).CreateShortcut('{}'); $s.TargetPath = '{}'; $s.Save()",
+            shortcut.display(),
+            target.display()
+        );
+        let status = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let resolved = resolve_lnk(&shortcut).unwrap();
+        assert_eq!(resolved.canonicalize().unwrap(), target.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_needs_large_paste_confirmation() {
+        assert!(needs_large_paste_confirmation(1001, 1000));
+        assert!(!needs_large_paste_confirmation(1000, 1000));
+        assert!(!needs_large_paste_confirmation(3, 1000));
+        // 0 は確認しない
+        assert!(!needs_large_paste_confirmation(1_000_000, 0));
+    }
+
+    #[test]
+    fn test_decide_conflict_by_modified_time() {
+        let older = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000);
+        let newer = older + std::time::Duration::from_secs(60);
+
+        // 通常は常に上書き
+        assert_eq!(decide_conflict(ConflictPolicy::Overwrite, Some(older), Some(newer)), ConflictDecision::Overwrite);
+
+        let policy = ConflictPolicy::OverwriteOnlyIfOlder;
+        assert_eq!(decide_conflict(policy, Some(newer), Some(older)), ConflictDecision::Overwrite);
+        assert_eq!(decide_conflict(policy, Some(older), Some(older)), ConflictDecision::Overwrite);
+        assert_eq!(decide_conflict(policy, Some(older), Some(newer)), ConflictDecision::Skip);
+        // 更新日時が分からない場合はスキップ
+        assert_eq!(decide_conflict(policy, None, Some(older)), ConflictDecision::Skip);
+        assert_eq!(decide_conflict(policy, Some(newer), None), ConflictDecision::Skip);
+    }
+
+    #[test]
+    fn test_resolve_conflict_skips_newer_destination() {
+        let temp_dir = tempdir().unwrap();
+        let src = temp_dir.path().join("src.txt");
+        let dest = temp_dir.path().join("dest.txt");
+        fs::write(&src, "source").unwrap();
+        fs::write(&dest, "destination").unwrap();
+
+        let now = std::time::SystemTime::now();
+        let hour_ago = now - std::time::Duration::from_secs(3600);
+        let set_modified = |path: &Path, time| {
+            File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+        };
+
+        // 貼り付け先の方が新しい
+        set_modified(&src, hour_ago);
+        set_modified(&dest, now);
+        assert_eq!(resolve_conflict(ConflictPolicy::OverwriteOnlyIfOlder, &src, &dest), ConflictDecision::Skip);
+        assert_eq!(resolve_conflict(ConflictPolicy::Overwrite, &src, &dest), ConflictDecision::Overwrite);
+
+        // 貼り付け元の方が新しい
+        set_modified(&src, now);
+        set_modified(&dest, hour_ago);
+        assert_eq!(resolve_conflict(ConflictPolicy::OverwriteOnlyIfOlder, &src, &dest), ConflictDecision::Overwrite);
+
+        // 貼り付け先がなければ作成する
+        let missing = temp_dir.path().join("missing.txt");
+        assert_eq!(resolve_conflict(ConflictPolicy::OverwriteOnlyIfOlder, &src, &missing), ConflictDecision::Overwrite);
+    }
+
+    #[test]
+    fn test_conflict_policy_config_names() {
+        for policy in ConflictPolicy::ALL {
+            assert_eq!(ConflictPolicy::parse(policy.config_name()), Some(policy));
+        }
+        assert_eq!(ConflictPolicy::parse(" Overwrite_Only_If_Older "), Some(ConflictPolicy::OverwriteOnlyIfOlder));
+        assert_eq!(ConflictPolicy::parse("unknown"), None);
+    }
+
+    #[test]
+    fn test_is_system_or_hidden_normal_file() {
+        let temp_dir = tempdir().unwrap();
+        let normal = temp_dir.path().join("normal.txt");
+        File::create(&normal).unwrap();
+        assert!(!is_system_or_hidden(&normal));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_is_system_or_hidden_unix_conventions() {
+        let temp_dir = tempdir().unwrap();
+        let hidden = temp_dir.path().join(".hidden");
+        File::create(&hidden).unwrap();
+        assert!(is_system_or_hidden(&hidden));
+
+        assert!(is_system_or_hidden(Path::new("/etc/hosts")));
+        assert!(is_system_or_hidden(Path::new("/usr")));
+        // 文字列として前方一致するだけのパスは対象外
+        assert!(!is_system_or_hidden(Path::new("/etcetera/file.txt")));
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_is_system_or_hidden_windows_attribute() {
+        use windows::core::HSTRING;
+        use windows::Win32::Storage::FileSystem::{SetFileAttributesW, FILE_ATTRIBUTE_HIDDEN};
+
+        let temp_dir = tempdir().unwrap();
+        let hidden = temp_dir.path().join("hidden.txt");
+        File::create(&hidden).unwrap();
+        assert!(!is_system_or_hidden(&hidden));
+
+        unsafe { SetFileAttributesW(&HSTRING::from(hidden.as_os_str()), FILE_ATTRIBUTE_HIDDEN) }.unwrap();
+        assert!(is_system_or_hidden(&hidden));
+    }
 }
@@ -1,8 +1,20 @@
+pub mod activation;
 pub mod alias;
+pub mod alias_csv;
+pub mod alias_watcher;
 pub mod directory_browser;
+pub mod directory_cache;
 pub mod file_manager;
 pub mod search;
 pub mod history;
 pub mod clipboard;
 pub mod quick_access;
+pub mod recursive_search;
 pub mod operation_history;
+pub mod cleanup;
+pub mod dir_size;
+pub mod paste_task;
+pub mod profile;
+pub mod thumbnail;
+pub mod text_preview;
+pub mod unseen_changes;
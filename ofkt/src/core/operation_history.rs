@@ -2,8 +2,13 @@
 //!
 //! ファイル操作（削除、移動、コピー、リネーム）の履歴を管理し、
 //! Undo/Redo機能を提供します。
+//!
+//! 貼り付け（コピー・移動）は複数の項目を1件の操作として記録し、まとめて取り消します。
+//! 一部の項目だけ元に戻せなかった場合は、戻せた部分をやり直し用に、戻せなかった部分を
+//! 履歴に残して、戻せなかった項目を警告として返します。
 
-use std::path::PathBuf;
+use crate::core::file_manager::FileManager;
+use std::path::{Path, PathBuf};
 
 /// ファイル操作の種類
 #[derive(Debug, Clone)]
@@ -13,19 +18,17 @@ pub enum FileOperation {
         /// 削除されたファイルの元のパス
         original_path: PathBuf,
     },
-    /// ファイル/フォルダの移動
+    /// ファイル/フォルダの移動（1回の貼り付けでまとめて移動した項目）
     Move {
-        /// 移動元のパス
-        source: PathBuf,
-        /// 移動先のパス
-        destination: PathBuf,
+        /// (移動元のパス, 移動先のパス)
+        moved: Vec<(PathBuf, PathBuf)>,
     },
-    /// ファイル/フォルダのコピー
+    /// ファイル/フォルダのコピー（1回の貼り付けでまとめてコピーした項目）
     Copy {
         /// コピー元のパス
-        source: PathBuf,
-        /// コピー先のパス
-        destination: PathBuf,
+        sources: Vec<PathBuf>,
+        /// 作成したコピーのパス（`sources` と同じ順）
+        created: Vec<PathBuf>,
     },
     /// ファイル/フォルダの名前変更
     Rename {
@@ -45,16 +48,18 @@ impl FileOperation {
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_else(|| original_path.display().to_string()))
             }
-            FileOperation::Move { source, destination } => {
-                format!("移動: {} -> {}",
+            FileOperation::Move { moved } => match moved.as_slice() {
+                [(source, destination)] => format!("移動: {} -> {}",
                     source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
-                    destination.display())
-            }
-            FileOperation::Copy { destination, .. } => {
-                format!("コピー: {}", destination.file_name()
+                    destination.display()),
+                _ => format!("移動: {} 項目", moved.len()),
+            },
+            FileOperation::Copy { created, .. } => match created.as_slice() {
+                [destination] => format!("コピー: {}", destination.file_name()
                     .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default())
-            }
+                    .unwrap_or_default()),
+                _ => format!("コピー: {} 項目", created.len()),
+            },
             FileOperation::Rename { old_path, new_path } => {
                 format!("名前変更: {} -> {}",
                     old_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
@@ -64,6 +69,28 @@ impl FileOperation {
     }
 }
 
+/// 取り消し・やり直しを実行した結果
+#[derive(Debug)]
+struct StepOutcome {
+    /// 反映できた部分（もう一方のスタックに積む）
+    applied: Option<FileOperation>,
+    /// 反映できなかった部分（元のスタックに戻す）
+    remaining: Option<FileOperation>,
+    /// 結果のメッセージ（反映できなかった項目がある場合は警告）
+    message: String,
+}
+
+impl StepOutcome {
+    /// 操作全体を反映できた結果
+    fn complete(operation: FileOperation, message: String) -> Self {
+        Self {
+            applied: Some(operation),
+            remaining: None,
+            message,
+        }
+    }
+}
+
 /// 操作履歴マネージャー
 #[derive(Debug)]
 pub struct OperationHistoryManager {
@@ -73,6 +100,8 @@ pub struct OperationHistoryManager {
     redo_stack: Vec<FileOperation>,
     /// 最大履歴数
     max_entries: usize,
+    /// コピーの取り消しで作成した項目をゴミ箱へ移動するか（false の場合は完全に削除）
+    use_trash: bool,
 }
 
 impl Default for OperationHistoryManager {
@@ -82,34 +111,66 @@ impl Default for OperationHistoryManager {
 }
 
 impl OperationHistoryManager {
+    /// 既定の最大履歴数
+    pub const DEFAULT_MAX_ENTRIES: usize = 50;
+
     /// 新しい OperationHistoryManager を作成
     pub fn new() -> Self {
         Self {
             history: Vec::new(),
             redo_stack: Vec::new(),
-            max_entries: 50,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            use_trash: true,
         }
     }
 
+    /// 最大履歴数を変更（1 未満は 1 とみなす）
+    ///
+    /// 超えている古い操作は取り消せなくなります。
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries.max(1);
+        Self::trim(&mut self.history, self.max_entries);
+        Self::trim(&mut self.redo_stack, self.max_entries);
+    }
+
+    /// 最大履歴数
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// コピーの取り消しで作成した項目をゴミ箱へ移動するかを設定
+    pub fn set_use_trash(&mut self, use_trash: bool) {
+        self.use_trash = use_trash;
+    }
+
+    /// 古い操作から削除して件数を上限に収める
+    fn trim(stack: &mut Vec<FileOperation>, max_entries: usize) {
+        let excess = stack.len().saturating_sub(max_entries);
+        stack.drain(..excess);
+    }
+
     /// 操作を履歴に追加
     pub fn push(&mut self, operation: FileOperation) {
         self.history.push(operation);
         // 最大履歴数を超えた場合、古いエントリを削除
-        while self.history.len() > self.max_entries {
-            self.history.remove(0);
-        }
+        Self::trim(&mut self.history, self.max_entries);
         // 新しい操作を追加したらRedoスタックをクリア
         self.redo_stack.clear();
     }
 
     /// Undo: 最後の操作を取り消す
+    ///
+    /// 貼り付けの一部だけ元に戻せなかった場合は、戻せなかった項目を列挙した警告を
+    /// `Err` で返します（戻せた項目はやり直せます）。
     pub fn undo(&mut self) -> Result<String, String> {
         let operation = self.history.pop()
             .ok_or_else(|| "取り消す操作がありません".to_string())?;
 
-        let result = self.execute_undo(&operation)?;
-        self.redo_stack.push(operation);
-        Ok(result)
+        let outcome = self.execute_undo(operation)?;
+        if let Some(applied) = outcome.applied {
+            self.redo_stack.push(applied);
+        }
+        Self::settle(&mut self.history, outcome.remaining, outcome.message)
     }
 
     /// Redo: 取り消した操作をやり直す
@@ -117,9 +178,22 @@ impl OperationHistoryManager {
         let operation = self.redo_stack.pop()
             .ok_or_else(|| "やり直す操作がありません".to_string())?;
 
-        let result = self.execute_redo(&operation)?;
-        self.history.push(operation);
-        Ok(result)
+        let outcome = self.execute_redo(operation)?;
+        if let Some(applied) = outcome.applied {
+            self.history.push(applied);
+        }
+        Self::settle(&mut self.redo_stack, outcome.remaining, outcome.message)
+    }
+
+    /// 反映できなかった部分を元のスタックに戻し、結果を返す
+    fn settle(stack: &mut Vec<FileOperation>, remaining: Option<FileOperation>, message: String) -> Result<String, String> {
+        match remaining {
+            Some(remaining) => {
+                stack.push(remaining);
+                Err(message)
+            }
+            None => Ok(message),
+        }
     }
 
     /// Undoが可能かどうか
@@ -132,40 +206,51 @@ impl OperationHistoryManager {
         !self.redo_stack.is_empty()
     }
 
+    /// 取り消せる操作の数
+    pub fn undo_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// やり直せる操作の数
+    pub fn redo_len(&self) -> usize {
+        self.redo_stack.len()
+    }
+
+    /// 最後に実行（またはやり直し）した操作を取得
+    pub fn last_done(&self) -> Option<&FileOperation> {
+        self.history.last()
+    }
+
+    /// 最後に取り消した操作を取得
+    pub fn last_undone(&self) -> Option<&FileOperation> {
+        self.redo_stack.last()
+    }
+
     /// Undo操作の実行
-    fn execute_undo(&self, operation: &FileOperation) -> Result<String, String> {
-        match operation {
+    fn execute_undo(&self, operation: FileOperation) -> Result<StepOutcome, String> {
+        let message = match &operation {
             FileOperation::Delete { original_path, .. } => {
                 // ゴミ箱からの復元は難しいので、メッセージのみ
                 Err(format!("「{}」の削除は取り消せません（ゴミ箱から手動で復元してください）",
                     original_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()))
             }
-            FileOperation::Move { source, destination } => {
-                // 移動の逆: destination から source に戻す
-                if destination.exists() {
-                    std::fs::rename(destination, source)
-                        .map_err(|e| format!("移動の取り消しに失敗: {}", e))?;
-                    Ok(format!("移動を取り消しました: {} に戻しました",
-                        source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()))
-                } else {
-                    Err("移動先のファイルが見つかりません".to_string())
-                }
-            }
-            FileOperation::Copy { destination, .. } => {
-                // コピーの逆: destination を削除
-                if destination.exists() {
-                    if destination.is_dir() {
-                        std::fs::remove_dir_all(destination)
-                            .map_err(|e| format!("コピーの取り消しに失敗: {}", e))?;
-                    } else {
-                        std::fs::remove_file(destination)
-                            .map_err(|e| format!("コピーの取り消しに失敗: {}", e))?;
+            FileOperation::Move { moved } => {
+                // 移動の逆: 移動先から移動元に戻す
+                let file_manager = FileManager::new();
+                return Ok(run_batch(moved, "移動の取り消し", |from, to| {
+                    if from.exists() {
+                        return Err("元の場所に同名の項目があります".to_string());
                     }
-                    Ok(format!("コピーを取り消しました: {} を削除しました",
-                        destination.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()))
-                } else {
-                    Err("コピー先のファイルが見つかりません".to_string())
-                }
+                    file_manager.move_file(to, from)
+                }, |moved| FileOperation::Move { moved }));
+            }
+            FileOperation::Copy { sources, created } => {
+                // コピーの逆: 作成したコピーを削除（設定に従いゴミ箱へ）
+                let file_manager = FileManager::new();
+                let permanent = !self.use_trash;
+                return Ok(run_batch(&copy_pairs(sources, created), "コピーの取り消し", |_, created| {
+                    file_manager.delete(created, permanent)
+                }, into_copy));
             }
             FileOperation::Rename { old_path, new_path } => {
                 // リネームの逆: new_path から old_path に戻す
@@ -178,48 +263,49 @@ impl OperationHistoryManager {
                     Err("変更後のファイルが見つかりません".to_string())
                 }
             }
-        }
+        }?;
+        Ok(StepOutcome::complete(operation, message))
     }
 
     /// Redo操作の実行
-    fn execute_redo(&self, operation: &FileOperation) -> Result<String, String> {
-        match operation {
+    fn execute_redo(&self, operation: FileOperation) -> Result<StepOutcome, String> {
+        let message = match &operation {
             FileOperation::Delete { .. } => {
                 Err("削除のやり直しはサポートされていません".to_string())
             }
-            FileOperation::Move { source, destination } => {
-                if source.exists() {
-                    std::fs::rename(source, destination)
-                        .map_err(|e| format!("移動のやり直しに失敗: {}", e))?;
-                    Ok(format!("移動をやり直しました"))
-                } else {
-                    Err("移動元のファイルが見つかりません".to_string())
-                }
+            FileOperation::Move { moved } => {
+                let file_manager = FileManager::new();
+                return Ok(run_batch(moved, "移動のやり直し", |from, to| {
+                    if to.exists() {
+                        return Err("移動先に同名の項目があります".to_string());
+                    }
+                    file_manager.move_file(from, to)
+                }, |moved| FileOperation::Move { moved }));
             }
-            FileOperation::Copy { source, destination } => {
-                if source.exists() {
+            FileOperation::Copy { sources, created } => {
+                let file_manager = FileManager::new();
+                return Ok(run_batch(&copy_pairs(sources, created), "コピーのやり直し", |source, created| {
+                    if created.exists() {
+                        return Err("コピー先に同名の項目があります".to_string());
+                    }
                     if source.is_dir() {
-                        // ディレクトリのコピーは複雑なので簡略化
-                        Err("ディレクトリのコピーやり直しはサポートされていません".to_string())
+                        file_manager.copy_recursive(source, created)
                     } else {
-                        std::fs::copy(source, destination)
-                            .map_err(|e| format!("コピーのやり直しに失敗: {}", e))?;
-                        Ok(format!("コピーをやり直しました"))
+                        file_manager.copy(source, created)
                     }
-                } else {
-                    Err("コピー元のファイルが見つかりません".to_string())
-                }
+                }, into_copy));
             }
             FileOperation::Rename { old_path, new_path } => {
                 if old_path.exists() {
                     std::fs::rename(old_path, new_path)
                         .map_err(|e| format!("名前変更のやり直しに失敗: {}", e))?;
-                    Ok(format!("名前変更をやり直しました"))
+                    Ok("名前変更をやり直しました".to_string())
                 } else {
                     Err("ファイルが見つかりません".to_string())
                 }
             }
-        }
+        }?;
+        Ok(StepOutcome::complete(operation, message))
     }
 
     /// 履歴をクリア
@@ -228,3 +314,188 @@ impl OperationHistoryManager {
         self.redo_stack.clear();
     }
 }
+
+/// コピーの記録を (コピー元, 作成したコピー) の組にする
+fn copy_pairs(sources: &[PathBuf], created: &[PathBuf]) -> Vec<(PathBuf, PathBuf)> {
+    sources.iter().cloned().zip(created.iter().cloned()).collect()
+}
+
+/// (コピー元, 作成したコピー) の組からコピーの操作を作る
+fn into_copy(pairs: Vec<(PathBuf, PathBuf)>) -> FileOperation {
+    let (sources, created) = pairs.into_iter().unzip();
+    FileOperation::Copy { sources, created }
+}
+
+/// 貼り付けの各項目を取り消し・やり直しし、反映できた部分とできなかった部分に分ける
+///
+/// # 引数
+/// * `pairs` - (元のパス, 貼り付け先のパス) の組
+/// * `label` - メッセージに使う処理名（「コピーの取り消し」など）
+/// * `step` - 1 項目を処理する
+/// * `rebuild` - 組から操作を作り直す
+fn run_batch(
+    pairs: &[(PathBuf, PathBuf)],
+    label: &str,
+    step: impl Fn(&Path, &Path) -> Result<(), String>,
+    rebuild: impl Fn(Vec<(PathBuf, PathBuf)>) -> FileOperation,
+) -> StepOutcome {
+    let mut applied = Vec::new();
+    let mut remaining = Vec::new();
+    let mut errors = Vec::new();
+    for (original, pasted) in pairs {
+        match step(original, pasted) {
+            Ok(()) => applied.push((original.clone(), pasted.clone())),
+            Err(e) => {
+                log::warn!("{}に失敗: {} ({})", label, pasted.display(), e);
+                errors.push(format!("{}: {}", pasted.display(), e));
+                remaining.push((original.clone(), pasted.clone()));
+            }
+        }
+    }
+
+    let message = if errors.is_empty() {
+        format!("{}が完了しました（{} 項目）", label, applied.len())
+    } else {
+        format!("{}で {} 項目中 {} 項目を処理できませんでした:\n{}",
+            label, pairs.len(), errors.len(), errors.join("\n"))
+    };
+    StepOutcome {
+        applied: (!applied.is_empty()).then(|| rebuild(applied)),
+        remaining: (!remaining.is_empty()).then(|| rebuild(remaining)),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn manager_without_trash() -> OperationHistoryManager {
+        let mut manager = OperationHistoryManager::new();
+        manager.set_use_trash(false);
+        manager
+    }
+
+    #[test]
+    fn test_undo_and_redo_copy_of_nested_directory() {
+        let temp_dir = tempdir().unwrap();
+        let source = temp_dir.path().join("src").join("project");
+        fs::create_dir_all(source.join("docs/2025")).unwrap();
+        fs::write(source.join("docs/2025/report.txt"), "report").unwrap();
+        let source_file = temp_dir.path().join("src").join("memo.txt");
+        fs::write(&source_file, "memo").unwrap();
+
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        let file_manager = FileManager::new();
+        let created = vec![dest_dir.join("project"), dest_dir.join("memo.txt")];
+        file_manager.copy_recursive(&source, &created[0]).unwrap();
+        file_manager.copy(&source_file, &created[1]).unwrap();
+
+        let mut manager = manager_without_trash();
+        manager.push(FileOperation::Copy {
+            sources: vec![source.clone(), source_file.clone()],
+            created: created.clone(),
+        });
+
+        // 作成したコピーだけを削除し、コピー元は残す
+        let message = manager.undo().unwrap();
+        assert!(message.contains("2 項目"), "{}", message);
+        assert!(!created[0].exists());
+        assert!(!created[1].exists());
+        assert!(source.join("docs/2025/report.txt").exists());
+        assert!(source_file.exists());
+        assert_eq!((manager.undo_len(), manager.redo_len()), (0, 1));
+
+        // やり直すと階層ごとコピーし直す
+        manager.redo().unwrap();
+        assert_eq!(fs::read_to_string(created[0].join("docs/2025/report.txt")).unwrap(), "report");
+        assert_eq!(fs::read_to_string(&created[1]).unwrap(), "memo");
+        assert_eq!((manager.undo_len(), manager.redo_len()), (1, 0));
+    }
+
+    #[test]
+    fn test_undo_and_redo_cross_directory_move() {
+        let temp_dir = tempdir().unwrap();
+        let from_dir = temp_dir.path().join("a");
+        let to_dir = temp_dir.path().join("b").join("nested");
+        fs::create_dir_all(from_dir.join("folder")).unwrap();
+        fs::create_dir_all(&to_dir).unwrap();
+        fs::write(from_dir.join("folder/inner.txt"), "inner").unwrap();
+        fs::write(from_dir.join("file.txt"), "file").unwrap();
+
+        let moved = vec![
+            (from_dir.join("folder"), to_dir.join("folder")),
+            (from_dir.join("file.txt"), to_dir.join("file.txt")),
+        ];
+        for (from, to) in &moved {
+            fs::rename(from, to).unwrap();
+        }
+
+        let mut manager = manager_without_trash();
+        manager.push(FileOperation::Move { moved: moved.clone() });
+        assert_eq!(manager.last_done().unwrap().description(), "移動: 2 項目");
+
+        manager.undo().unwrap();
+        assert_eq!(fs::read_to_string(from_dir.join("folder/inner.txt")).unwrap(), "inner");
+        assert_eq!(fs::read_to_string(from_dir.join("file.txt")).unwrap(), "file");
+        assert!(!to_dir.join("folder").exists());
+        assert!(!to_dir.join("file.txt").exists());
+
+        manager.redo().unwrap();
+        assert!(to_dir.join("folder/inner.txt").exists());
+        assert!(to_dir.join("file.txt").exists());
+        assert!(!from_dir.join("file.txt").exists());
+    }
+
+    #[test]
+    fn test_partial_undo_reports_and_keeps_remaining_items() {
+        let temp_dir = tempdir().unwrap();
+        let from_dir = temp_dir.path().join("a");
+        let to_dir = temp_dir.path().join("b");
+        fs::create_dir_all(&from_dir).unwrap();
+        fs::create_dir_all(&to_dir).unwrap();
+        fs::write(to_dir.join("ok.txt"), "").unwrap();
+        fs::write(to_dir.join("blocked.txt"), "").unwrap();
+        // 元の場所に同名の項目ができていると戻せない
+        fs::write(from_dir.join("blocked.txt"), "new").unwrap();
+
+        let mut manager = manager_without_trash();
+        manager.push(FileOperation::Move {
+            moved: vec![
+                (from_dir.join("ok.txt"), to_dir.join("ok.txt")),
+                (from_dir.join("blocked.txt"), to_dir.join("blocked.txt")),
+            ],
+        });
+
+        let warning = manager.undo().unwrap_err();
+        assert!(warning.contains("2 項目中 1 項目"), "{}", warning);
+        assert!(warning.contains("blocked.txt"), "{}", warning);
+        assert!(from_dir.join("ok.txt").exists());
+
+        // 戻せた項目はやり直せ、戻せなかった項目は取り消しの履歴に残る
+        assert!(matches!(manager.last_undone(), Some(FileOperation::Move { moved }) if moved.len() == 1));
+        assert!(matches!(manager.last_done(), Some(FileOperation::Move { moved })
+            if moved == &vec![(from_dir.join("blocked.txt"), to_dir.join("blocked.txt"))]));
+    }
+
+    #[test]
+    fn test_history_depth_is_capped() {
+        let mut manager = OperationHistoryManager::new();
+        for i in 0..5 {
+            manager.push(FileOperation::Delete { original_path: PathBuf::from(format!("/tmp/{}", i)) });
+        }
+        manager.set_max_entries(3);
+        assert_eq!(manager.undo_len(), 3);
+        assert!(matches!(manager.last_done(), Some(FileOperation::Delete { original_path }) if original_path.ends_with("4")));
+
+        manager.push(FileOperation::Delete { original_path: PathBuf::from("/tmp/5") });
+        assert_eq!(manager.undo_len(), 3);
+
+        manager.set_max_entries(0);
+        assert_eq!(manager.max_entries(), 1);
+        assert_eq!(manager.undo_len(), 1);
+    }
+}
@@ -0,0 +1,600 @@
+//! 貼り付け（コピー・移動）のバックグラウンド実行
+//!
+//! 大きなフォルダの貼り付けで画面が固まらないよう、ワーカースレッドで
+//! ファイルを 1 件ずつ処理し、進み具合をチャネルで UI に送ります。
+//! 取り消すと処理中のファイルを終えたところで止まります。
+
+use crate::core::clipboard::{generate_copy_name_with_pattern, ClipboardMode};
+use crate::core::file_manager::{
+    resolve_conflict, ConflictDecision, ConflictPolicy, ConflictResolution, FileManager,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::sync::Arc;
+
+/// 貼り付けの内容
+#[derive(Debug, Clone)]
+pub struct PasteJob {
+    /// 貼り付け元のパス
+    pub src_paths: Vec<PathBuf>,
+    /// 貼り付け元ごとの貼り付け先のパス（`paste_destinations` の結果）
+    pub destinations: Vec<PathBuf>,
+    /// 貼り付け先のフォルダ
+    pub dest_dir: PathBuf,
+    /// コピーか移動か
+    pub mode: ClipboardMode,
+    /// 貼り付け先に同名の項目がある場合の扱い
+    pub conflict_policy: ConflictPolicy,
+    /// 上書き確認ダイアログで選んだ、貼り付け元ごとの処理（選んでいない項目は `conflict_policy` に従う）
+    pub resolutions: HashMap<PathBuf, ConflictResolution>,
+    /// 同じフォルダに貼り付けたときのコピー名の形式
+    pub copy_name_pattern: String,
+    /// コピー時に読み取り専用・隠し属性を引き継ぐか（Windowsのみ）
+    pub preserve_attributes: bool,
+}
+
+/// 貼り付けの進み具合
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasteProgress {
+    /// 処理を終えたファイル数
+    pub files_completed: usize,
+    /// 処理を終えたバイト数
+    pub bytes_copied: u64,
+    /// 処理中（直前に処理した）ファイルの名前
+    pub current_file: Option<String>,
+    /// 処理するファイル数の合計
+    pub total_files: usize,
+    /// 処理するバイト数の合計
+    pub total_bytes: u64,
+}
+
+impl PasteProgress {
+    /// 進み具合の割合（0.0〜1.0、合計が不明な場合は 0.0）
+    pub fn fraction(&self) -> f32 {
+        if self.total_bytes > 0 {
+            (self.bytes_copied as f64 / self.total_bytes as f64).min(1.0) as f32
+        } else if self.total_files > 0 {
+            (self.files_completed as f64 / self.total_files as f64).min(1.0) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 貼り付けの結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PasteOutcome {
+    /// 貼り付けた項目のパス（ハイライト用）
+    pub pasted_paths: Vec<PathBuf>,
+    /// 元に戻せる項目（貼り付け元, 貼り付け先）。コピーで上書きした項目は含まない
+    pub undoable_items: Vec<(PathBuf, PathBuf)>,
+    /// 失敗した項目のパス（貼り付け元・貼り付け先の両方）
+    pub failed_paths: Vec<PathBuf>,
+    /// 貼り付けに成功した項目数
+    pub success_count: usize,
+    /// 貼り付けに失敗した項目数
+    pub error_count: usize,
+    /// エラーメッセージ
+    pub errors: Vec<String>,
+    /// 貼り付け先の方が新しいためスキップした項目数
+    pub skipped_count: usize,
+    /// 上書き確認ダイアログでスキップを選んだ項目数
+    pub declined_count: usize,
+    /// 取り消されたか
+    pub cancelled: bool,
+    /// 処理を終えたファイル数
+    pub files_completed: usize,
+}
+
+/// ワーカースレッドから UI への通知
+#[derive(Debug, Clone)]
+pub enum PasteEvent {
+    /// 進み具合の更新
+    Progress(PasteProgress),
+    /// 完了（取り消しを含む）
+    Finished(PasteOutcome),
+}
+
+/// 処理するファイル数とバイト数の合計を数える（フォルダは配下のファイルを数える）
+fn count_files(paths: &[PathBuf], cancelled: &AtomicBool) -> (usize, u64) {
+    let mut files = 0;
+    let mut bytes = 0;
+    for path in paths {
+        for entry in walkdir::WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            if cancelled.load(Ordering::Relaxed) {
+                return (files, bytes);
+            }
+            if entry.file_type().is_file() {
+                files += 1;
+                bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+    (files, bytes)
+}
+
+/// ファイルを 1 件ずつコピーし、そのたびに進み具合を報告する
+///
+/// # 戻り値
+/// * `Ok(true)` - すべてコピーした
+/// * `Ok(false)` - 取り消されたため途中で止めた
+/// * `Err(String)` - エラーメッセージ
+fn copy_with_progress(
+    file_manager: &FileManager,
+    src: &Path,
+    dest: &Path,
+    cancelled: &AtomicBool,
+    progress: &mut PasteProgress,
+    report: &mut impl FnMut(&PasteProgress),
+) -> Result<bool, String> {
+    // 処理中のファイルは最後までコピーし、次のファイル・フォルダの前で止める
+    if cancelled.load(Ordering::Relaxed) {
+        return Ok(false);
+    }
+
+    if src.is_dir() {
+        std::fs::create_dir_all(dest).map_err(|e| format!("ディレクトリ作成失敗: {}", e))?;
+        for entry in std::fs::read_dir(src).map_err(|e| format!("ディレクトリ読み込み失敗: {}", e))? {
+            let entry = entry.map_err(|e| format!("エントリ読み込み失敗: {}", e))?;
+            let completed = copy_with_progress(
+                file_manager,
+                &entry.path(),
+                &dest.join(entry.file_name()),
+                cancelled,
+                progress,
+                report,
+            )?;
+            if !completed {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
+    }
+
+    file_manager.copy(src, dest)?;
+    progress.files_completed += 1;
+    progress.bytes_copied += src.metadata().map(|m| m.len()).unwrap_or(0);
+    progress.current_file = src.file_name().map(|name| name.to_string_lossy().to_string());
+    report(progress);
+    Ok(true)
+}
+
+/// 貼り付けを実行する（ワーカースレッドから呼ぶ）
+///
+/// # 引数
+/// * `job` - 貼り付けの内容
+/// * `cancelled` - true になった時点で、処理中のファイルを終えてから止める
+/// * `report` - ファイルを 1 件処理するたびに呼ばれる
+///
+/// # 戻り値
+/// 貼り付けの結果
+pub fn run_paste(job: &PasteJob, cancelled: &AtomicBool, mut report: impl FnMut(&PasteProgress)) -> PasteOutcome {
+    let file_manager = FileManager::new().with_preserve_attributes(job.preserve_attributes);
+    let mut outcome = PasteOutcome::default();
+    let mut progress = PasteProgress::default();
+
+    // 移動は名前の変更で済むことが多いため、項目単位で数える
+    (progress.total_files, progress.total_bytes) = match job.mode {
+        ClipboardMode::Copy => count_files(&job.src_paths, cancelled),
+        ClipboardMode::Cut => (job.src_paths.len(), 0),
+    };
+    report(&progress);
+
+    log::info!("=== ペースト実行開始 === モード: {:?}, ファイル数: {}, 宛先: {}",
+        job.mode, job.src_paths.len(), job.dest_dir.display());
+
+    for (idx, src_path) in job.src_paths.iter().enumerate() {
+        if cancelled.load(Ordering::Relaxed) {
+            outcome.cancelled = true;
+            break;
+        }
+
+        log::debug!("[{}/{}] 処理開始: {}", idx + 1, job.src_paths.len(), src_path.display());
+        let file_name = match src_path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => {
+                log::error!("ファイル名の取得に失敗: {}", src_path.display());
+                outcome.error_count += 1;
+                outcome.errors.push(format!("ファイル名の取得に失敗: {}", src_path.display()));
+                continue;
+            }
+        };
+
+        let mut dest_path = job.destinations[idx].clone();
+
+        if src_path == &dest_path {
+            dest_path = generate_copy_name_with_pattern(
+                src_path,
+                dest_path.parent().unwrap_or(&job.dest_dir),
+                &job.copy_name_pattern,
+            );
+        }
+
+        // フォルダ構成を再現する場合は途中のフォルダを作成
+        if let Some(parent) = dest_path.parent().filter(|p| !p.exists()) {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                log::error!("フォルダの作成に失敗: {} ({})", parent.display(), e);
+                outcome.failed_paths.push(src_path.clone());
+                outcome.error_count += 1;
+                outcome.errors.push(format!("「{}」の貼り付け先フォルダを作成できません: {}", file_name, e));
+                continue;
+            }
+        }
+
+        // 確認後に貼り付け先がなくなっていれば、選んだ処理に関係なく通常どおり貼り付ける
+        if dest_path.exists() && src_path != &dest_path {
+            match job.resolutions.get(src_path) {
+                Some(ConflictResolution::Skip) => {
+                    log::info!("「{}」はスキップが選ばれたため貼り付けません", file_name);
+                    outcome.declined_count += 1;
+                    continue;
+                }
+                Some(ConflictResolution::KeepBoth) => {
+                    dest_path = generate_copy_name_with_pattern(
+                        &dest_path,
+                        dest_path.parent().unwrap_or(&job.dest_dir),
+                        &job.copy_name_pattern,
+                    );
+                    log::info!("「{}」は両方残すため {} に貼り付けます", file_name, dest_path.display());
+                }
+                Some(ConflictResolution::Overwrite) => {
+                    log::warn!("「{}」は既に存在します。上書きします。", file_name);
+                }
+                None => {
+                    // 貼り付け先の方が新しい場合は、許可されていなければ上書きしない
+                    if resolve_conflict(job.conflict_policy, src_path, &dest_path) == ConflictDecision::Skip {
+                        log::info!("「{}」は貼り付け先の方が新しいためスキップします", file_name);
+                        outcome.skipped_count += 1;
+                        continue;
+                    }
+                    log::warn!("「{}」は既に存在します。上書きします。", file_name);
+                }
+            }
+        }
+
+        // 上書きしたコピーは削除すると元の項目も失われるため、取り消しの対象にしない
+        let overwriting = dest_path.exists();
+        let start_time = std::time::Instant::now();
+
+        match job.mode {
+            ClipboardMode::Copy => {
+                log::debug!("コピー開始: {} -> {}", src_path.display(), dest_path.display());
+                match copy_with_progress(&file_manager, src_path, &dest_path, cancelled, &mut progress, &mut report) {
+                    Ok(true) => {
+                        log::info!("「{}」をコピーしました (時間: {:?})", file_name, start_time.elapsed());
+                        if !overwriting {
+                            outcome.undoable_items.push((src_path.clone(), dest_path.clone()));
+                        }
+                        outcome.pasted_paths.push(dest_path);
+                        outcome.success_count += 1;
+                    }
+                    Ok(false) => {
+                        log::info!("「{}」のコピー中に取り消されました", file_name);
+                        // 途中までコピーしたフォルダも強調表示し、取り消しで削除できるようにする
+                        if src_path.is_dir() && dest_path.exists() {
+                            if !overwriting {
+                                outcome.undoable_items.push((src_path.clone(), dest_path.clone()));
+                            }
+                            outcome.pasted_paths.push(dest_path);
+                        }
+                        outcome.cancelled = true;
+                        break;
+                    }
+                    Err(e) => {
+                        log::error!("コピー失敗: {} (経過時間: {:?})", e, start_time.elapsed());
+                        outcome.failed_paths.extend([src_path.clone(), dest_path]);
+                        outcome.error_count += 1;
+                        outcome.errors.push(format!("「{}」のコピーに失敗: {}", file_name, e));
+                    }
+                }
+            }
+            ClipboardMode::Cut => {
+                log::debug!("移動開始: {} -> {}", src_path.display(), dest_path.display());
+                let file_size = src_path.metadata().map(|m| m.len()).unwrap_or(0);
+                if let Err(e) = file_manager.move_file(src_path, &dest_path) {
+                    log::error!("移動失敗: {} (経過時間: {:?})", e, start_time.elapsed());
+                    outcome.failed_paths.extend([src_path.clone(), dest_path]);
+                    outcome.error_count += 1;
+                    outcome.errors.push(format!("「{}」の移動に失敗: {}", file_name, e));
+                } else {
+                    log::info!("「{}」を移動しました (時間: {:?})", file_name, start_time.elapsed());
+                    outcome.undoable_items.push((src_path.clone(), dest_path.clone()));
+                    outcome.pasted_paths.push(dest_path);
+                    outcome.success_count += 1;
+                }
+                progress.files_completed += 1;
+                progress.bytes_copied += file_size;
+                progress.current_file = Some(file_name);
+                report(&progress);
+            }
+        }
+    }
+
+    outcome.files_completed = progress.files_completed;
+    log::info!("=== ペースト実行完了 === 成功: {}, 失敗: {}, 取り消し: {}",
+        outcome.success_count, outcome.error_count, outcome.cancelled);
+    outcome
+}
+
+/// バックグラウンドで実行する貼り付け
+///
+/// ドロップすると取り消します（アプリの終了時など）。
+#[derive(Debug)]
+pub struct PasteTask {
+    /// コピーか移動か
+    mode: ClipboardMode,
+    /// 最新の進み具合
+    progress: PasteProgress,
+    /// ワーカースレッドからの通知の受信チャネル
+    receiver: Receiver<PasteEvent>,
+    /// 取り消しフラグ（ワーカースレッドと共有）
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PasteTask {
+    /// ワーカースレッドで貼り付けを開始
+    pub fn spawn(job: PasteJob) -> Self {
+        let (sender, receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_cancelled = Arc::clone(&cancelled);
+        let mode = job.mode;
+
+        std::thread::spawn(move || {
+            let outcome = run_paste(&job, &worker_cancelled, |progress| {
+                // 受信側が破棄済みなら通知は捨てる
+                let _ = sender.send(PasteEvent::Progress(progress.clone()));
+            });
+            let _ = sender.send(PasteEvent::Finished(outcome));
+        });
+
+        Self::from_parts(mode, receiver, cancelled)
+    }
+
+    fn from_parts(mode: ClipboardMode, receiver: Receiver<PasteEvent>, cancelled: Arc<AtomicBool>) -> Self {
+        Self {
+            mode,
+            progress: PasteProgress::default(),
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// 届いた通知を反映し、完了していれば結果を返す
+    ///
+    /// 毎フレーム呼び出すことを想定しています。ワーカースレッドが結果を送らずに
+    /// 終了した場合は、取り消されたものとして空の結果を返します。
+    pub fn poll(&mut self) -> Option<PasteOutcome> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(PasteEvent::Progress(progress)) => self.progress = progress,
+                Ok(PasteEvent::Finished(outcome)) => return Some(outcome),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    log::error!("貼り付けのワーカースレッドが結果を返さずに終了しました");
+                    return Some(PasteOutcome {
+                        cancelled: true,
+                        files_completed: self.progress.files_completed,
+                        ..PasteOutcome::default()
+                    });
+                }
+            }
+        }
+    }
+
+    /// 取り消す（処理中のファイルを終えてから止まる）
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// 取り消しを要求済みか
+    pub fn is_cancelling(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// コピーか移動か
+    pub fn mode(&self) -> ClipboardMode {
+        self.mode
+    }
+
+    /// 最新の進み具合
+    pub fn progress(&self) -> &PasteProgress {
+        &self.progress
+    }
+}
+
+impl Drop for PasteTask {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    fn copy_job(src_paths: Vec<PathBuf>, dest_dir: &Path) -> PasteJob {
+        let destinations = src_paths
+            .iter()
+            .map(|src| dest_dir.join(src.file_name().unwrap()))
+            .collect();
+        PasteJob {
+            src_paths,
+            destinations,
+            dest_dir: dest_dir.to_path_buf(),
+            mode: ClipboardMode::Copy,
+            conflict_policy: ConflictPolicy::Overwrite,
+            resolutions: HashMap::new(),
+            copy_name_pattern: crate::core::clipboard::DEFAULT_COPY_NAME_PATTERN.to_string(),
+            preserve_attributes: false,
+        }
+    }
+
+    /// 3 ファイル（合計 60 バイト）を含むフォルダを作成
+    fn create_tree(root: &Path) -> PathBuf {
+        let folder = root.join("folder");
+        fs::create_dir_all(folder.join("sub")).unwrap();
+        fs::write(folder.join("a.txt"), vec![0u8; 10]).unwrap();
+        fs::write(folder.join("b.txt"), vec![0u8; 20]).unwrap();
+        fs::write(folder.join("sub/c.txt"), vec![0u8; 30]).unwrap();
+        folder
+    }
+
+    #[test]
+    fn test_run_paste_reports_each_file() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let folder = create_tree(src_dir.path());
+
+        let mut events = Vec::new();
+        let job = copy_job(vec![folder], dest_dir.path());
+        let outcome = run_paste(&job, &AtomicBool::new(false), |progress| events.push(progress.clone()));
+
+        // 最初に合計を通知し、その後ファイルごとに通知する
+        assert_eq!(events.len(), 4);
+        assert_eq!((events[0].total_files, events[0].total_bytes), (3, 60));
+        assert_eq!(events[0].files_completed, 0);
+        let last = events.last().unwrap();
+        assert_eq!((last.files_completed, last.bytes_copied), (3, 60));
+        assert_eq!(last.fraction(), 1.0);
+        assert!(events[1..].iter().all(|e| e.current_file.is_some()));
+
+        assert_eq!(outcome.success_count, 1);
+        assert_eq!(outcome.files_completed, 3);
+        assert!(!outcome.cancelled);
+        assert_eq!(outcome.pasted_paths, vec![dest_dir.path().join("folder")]);
+        assert!(dest_dir.path().join("folder/sub/c.txt").exists());
+    }
+
+    #[test]
+    fn test_run_paste_cancel_stops_after_current_file() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let folder = create_tree(src_dir.path());
+        let other = src_dir.path().join("other.txt");
+        fs::write(&other, "x").unwrap();
+
+        let cancelled = AtomicBool::new(false);
+        let job = copy_job(vec![folder.clone(), other], dest_dir.path());
+        let outcome = run_paste(&job, &cancelled, |progress| {
+            // 1 件目のファイルを終えたところで取り消す
+            if progress.files_completed == 1 {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+        });
+
+        assert!(outcome.cancelled);
+        assert_eq!(outcome.files_completed, 1);
+        assert_eq!(outcome.success_count, 0);
+        assert_eq!(outcome.error_count, 0);
+        // 途中までコピーしたフォルダは強調表示・取り消しの対象にする
+        let partial = dest_dir.path().join("folder");
+        assert_eq!(fs::read_dir(&partial).unwrap().count(), 1);
+        assert_eq!(outcome.pasted_paths, vec![partial.clone()]);
+        assert_eq!(outcome.undoable_items, vec![(folder, partial)]);
+        // 残りの項目には手を付けない
+        assert!(!dest_dir.path().join("other.txt").exists());
+    }
+
+    #[test]
+    fn test_run_paste_move_counts_items() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let file = src_dir.path().join("move.txt");
+        fs::write(&file, "12345").unwrap();
+
+        let mut job = copy_job(vec![file.clone()], dest_dir.path());
+        job.mode = ClipboardMode::Cut;
+        let mut last = PasteProgress::default();
+        let outcome = run_paste(&job, &AtomicBool::new(false), |progress| last = progress.clone());
+
+        assert_eq!(outcome.success_count, 1);
+        assert_eq!((last.files_completed, last.total_files, last.bytes_copied), (1, 1, 5));
+        assert!(!file.exists());
+        assert!(dest_dir.path().join("move.txt").exists());
+        assert_eq!(outcome.undoable_items, vec![(file, dest_dir.path().join("move.txt"))]);
+    }
+
+    #[test]
+    fn test_run_paste_honors_per_file_resolutions() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let names = ["overwrite.txt", "skip.txt", "keep.txt", "vanished.txt"];
+        for name in names {
+            fs::write(src_dir.path().join(name), "new").unwrap();
+            fs::write(dest_dir.path().join(name), "old").unwrap();
+        }
+        let src_paths: Vec<PathBuf> = names.iter().map(|name| src_dir.path().join(name)).collect();
+
+        let mut job = copy_job(src_paths.clone(), dest_dir.path());
+        job.resolutions = HashMap::from([
+            (src_paths[0].clone(), ConflictResolution::Overwrite),
+            (src_paths[1].clone(), ConflictResolution::Skip),
+            (src_paths[2].clone(), ConflictResolution::KeepBoth),
+            (src_paths[3].clone(), ConflictResolution::Skip),
+        ]);
+        // 確認した後で貼り付け先が消えた項目は通常どおり貼り付ける
+        fs::remove_file(dest_dir.path().join("vanished.txt")).unwrap();
+
+        let outcome = run_paste(&job, &AtomicBool::new(false), |_| {});
+
+        assert_eq!(outcome.success_count, 3);
+        assert_eq!(outcome.declined_count, 1);
+        assert_eq!(outcome.skipped_count, 0);
+        let read = |name: &str| fs::read_to_string(dest_dir.path().join(name)).unwrap();
+        assert_eq!(read("overwrite.txt"), "new");
+        assert_eq!(read("skip.txt"), "old");
+        assert_eq!(read("keep.txt"), "old");
+        assert_eq!(read("keep (コピー).txt"), "new");
+        assert_eq!(read("vanished.txt"), "new");
+        // 上書きした項目は取り消しの対象にしない
+        assert_eq!(
+            outcome.undoable_items,
+            vec![
+                (src_paths[2].clone(), dest_dir.path().join("keep (コピー).txt")),
+                (src_paths[3].clone(), dest_dir.path().join("vanished.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_task_poll_tracks_progress_until_finished() {
+        let (sender, receiver) = channel();
+        let mut task = PasteTask::from_parts(ClipboardMode::Copy, receiver, Arc::new(AtomicBool::new(false)));
+        assert_eq!(task.poll(), None);
+
+        let progress = PasteProgress { files_completed: 2, total_files: 5, ..PasteProgress::default() };
+        sender.send(PasteEvent::Progress(progress.clone())).unwrap();
+        assert_eq!(task.poll(), None);
+        assert_eq!(task.progress(), &progress);
+
+        task.cancel();
+        assert!(task.is_cancelling());
+        sender.send(PasteEvent::Finished(PasteOutcome { cancelled: true, ..PasteOutcome::default() })).unwrap();
+        assert!(task.poll().unwrap().cancelled);
+    }
+
+    #[test]
+    fn test_spawned_task_completes() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let file = src_dir.path().join("file.txt");
+        fs::write(&file, vec![0u8; 5]).unwrap();
+
+        let mut task = PasteTask::spawn(copy_job(vec![file], dest_dir.path()));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut outcome = None;
+        while outcome.is_none() && Instant::now() < deadline {
+            outcome = task.poll();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let outcome = outcome.unwrap();
+        assert_eq!(outcome.success_count, 1);
+        assert_eq!(task.progress().bytes_copied, 5);
+        assert!(dest_dir.path().join("file.txt").exists());
+    }
+}
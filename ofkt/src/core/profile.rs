@@ -0,0 +1,357 @@
+//! プロファイル（エイリアス・クイックアクセス・設定）のまとめてエクスポート/インポート
+//!
+//! 新しいPCへの移行用に、設定ディレクトリのJSONファイルを1つのZIPファイルにまとめます。
+
+use crate::data::models::{Config, FileAlias, QuickAccessEntry};
+use crate::data::storage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// バンドル形式のバージョン（互換性のない変更をしたら上げる）
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// バンドル内のマニフェストファイル名
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// バンドルに含めるファイル名
+pub const BUNDLE_FILES: [&str; 3] = ["aliases.json", "quick_access.json", "config.json"];
+
+/// バンドル内の (ファイル名, 内容) のリスト
+type BundleFiles = Vec<(String, Vec<u8>)>;
+
+/// バンドルのマニフェスト
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// バンドル形式のバージョン
+    pub version: u32,
+    /// 作成日時
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 含まれているファイル名
+    pub files: Vec<String>,
+}
+
+/// インポート時のファイルごとの変更内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    /// 新しく作成される
+    Create,
+    /// 内容が置き換えられる
+    Replace,
+    /// 内容が同じため変更なし
+    Unchanged,
+}
+
+/// インポートのプレビュー（ドライラン）結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportPreview {
+    /// バンドル形式のバージョン
+    pub version: u32,
+    /// (ファイル名, 変更内容) のリスト
+    pub changes: Vec<(String, FileChange)>,
+    /// インポート後のエイリアスの件数（バンドルに含まれない場合は None）
+    pub alias_count: Option<usize>,
+    /// インポート後のクイックアクセスの件数（バンドルに含まれない場合は None）
+    pub quick_access_count: Option<usize>,
+}
+
+/// 設定ディレクトリのプロファイルをZIPファイルにエクスポート
+///
+/// # 引数
+/// * `path` - 作成するZIPファイルのパス
+pub fn export_bundle(path: &Path) -> Result<(), String> {
+    export_bundle_from(&config_dir()?, path)
+}
+
+/// ZIPファイルのプロファイルを設定ディレクトリにインポート
+///
+/// # 戻り値
+/// 書き込んだファイルの変更内容
+pub fn import_bundle(path: &Path) -> Result<Vec<(String, FileChange)>, String> {
+    import_bundle_into(&config_dir()?, path)
+}
+
+/// インポートした場合の変更内容を確認する（ファイルは書き換えない）
+pub fn preview_bundle(path: &Path) -> Result<ImportPreview, String> {
+    preview_bundle_against(&config_dir()?, path)
+}
+
+fn config_dir() -> Result<std::path::PathBuf, String> {
+    storage::get_config_dir().map_err(|e| format!("設定ディレクトリを取得できません: {}", e))
+}
+
+/// 指定したディレクトリのプロファイルをZIPファイルにエクスポート
+///
+/// 存在しないファイルはバンドルに含めません。
+pub fn export_bundle_from(config_dir: &Path, path: &Path) -> Result<(), String> {
+    let file = fs::File::create(path)
+        .map_err(|e| format!("エクスポートファイルを作成できません: {} ({})", path.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut included = Vec::new();
+    for name in BUNDLE_FILES {
+        let source = config_dir.join(name);
+        if !source.exists() {
+            log::info!("バンドルに含めるファイルがありません: {}", source.display());
+            continue;
+        }
+        let contents = fs::read(&source)
+            .map_err(|e| format!("「{}」を読み込めません: {}", name, e))?;
+        write_entry(&mut zip, name, &contents, options)?;
+        included.push(name.to_string());
+    }
+
+    let manifest = BundleManifest {
+        version: BUNDLE_VERSION,
+        created_at: chrono::Utc::now(),
+        files: included,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("マニフェストのシリアライズに失敗: {}", e))?;
+    write_entry(&mut zip, MANIFEST_NAME, &manifest_json, options)?;
+
+    zip.finish().map_err(|e| format!("ZIPファイルの作成に失敗: {}", e))?;
+    log::info!("プロファイルをエクスポート: {}", path.display());
+    Ok(())
+}
+
+/// ZIPにファイルを1つ追加する
+fn write_entry(
+    zip: &mut zip::ZipWriter<fs::File>,
+    name: &str,
+    contents: &[u8],
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("「{}」の書き込みに失敗: {}", name, e))?;
+    zip.write_all(contents)
+        .map_err(|e| format!("「{}」の書き込みに失敗: {}", name, e))
+}
+
+/// 指定したディレクトリにZIPファイルのプロファイルをインポート
+///
+/// すべてのファイルを検証してから書き込むため、壊れたバンドルで一部だけ
+/// 置き換わることはありません。
+pub fn import_bundle_into(config_dir: &Path, path: &Path) -> Result<Vec<(String, FileChange)>, String> {
+    let (_, files) = read_bundle(path)?;
+    let changes = diff_files(config_dir, &files);
+
+    for (name, contents) in &files {
+        let dest = config_dir.join(name);
+        let temp = dest.with_extension("json.tmp");
+        fs::write(&temp, contents)
+            .and_then(|_| fs::rename(&temp, &dest))
+            .map_err(|e| format!("「{}」の書き込みに失敗: {}", name, e))?;
+    }
+
+    log::info!("プロファイルをインポート: {}", path.display());
+    Ok(changes)
+}
+
+/// 指定したディレクトリにインポートした場合の変更内容を確認する
+pub fn preview_bundle_against(config_dir: &Path, path: &Path) -> Result<ImportPreview, String> {
+    let (manifest, files) = read_bundle(path)?;
+    let count = |name: &str, parse: fn(&[u8]) -> Option<usize>| {
+        files.iter().find(|(n, _)| n == name).and_then(|(_, c)| parse(c))
+    };
+
+    Ok(ImportPreview {
+        version: manifest.version,
+        changes: diff_files(config_dir, &files),
+        alias_count: count("aliases.json", |c| serde_json::from_slice::<Vec<FileAlias>>(c).ok().map(|v| v.len())),
+        quick_access_count: count("quick_access.json", |c| {
+            serde_json::from_slice::<Vec<QuickAccessEntry>>(c).ok().map(|v| v.len())
+        }),
+    })
+}
+
+/// バンドルを読み込み、バージョンと各ファイルの内容を検証する
+fn read_bundle(path: &Path) -> Result<(BundleManifest, BundleFiles), String> {
+    let file = fs::File::open(path)
+        .map_err(|e| format!("バンドルを開けません: {} ({})", path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("ZIPファイルとして読み込めません: {}", e))?;
+
+    let manifest: BundleManifest = serde_json::from_slice(&read_entry(&mut archive, MANIFEST_NAME)?
+        .ok_or_else(|| "マニフェストが含まれていません".to_string())?)
+        .map_err(|e| format!("マニフェストの解析に失敗: {}", e))?;
+    if manifest.version > BUNDLE_VERSION {
+        return Err(format!(
+            "新しい形式のバンドルです（バージョン {}、対応: {} まで）",
+            manifest.version, BUNDLE_VERSION
+        ));
+    }
+
+    let mut files = Vec::new();
+    for name in BUNDLE_FILES {
+        let Some(contents) = read_entry(&mut archive, name)? else {
+            continue;
+        };
+        validate(name, &contents)?;
+        files.push((name.to_string(), contents));
+    }
+    Ok((manifest, files))
+}
+
+/// ZIP内のファイルを読み込む（存在しない場合は None）
+fn read_entry(archive: &mut zip::ZipArchive<fs::File>, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("「{}」を読み込めません: {}", name, e)),
+    };
+    let mut contents = Vec::new();
+    entry.read_to_end(&mut contents)
+        .map_err(|e| format!("「{}」を読み込めません: {}", name, e))?;
+    Ok(Some(contents))
+}
+
+/// ファイルの内容が読み込める形式か検証する
+fn validate(name: &str, contents: &[u8]) -> Result<(), String> {
+    let result = match name {
+        "aliases.json" => serde_json::from_slice::<Vec<FileAlias>>(contents).map(|_| ()),
+        "quick_access.json" => serde_json::from_slice::<Vec<QuickAccessEntry>>(contents).map(|_| ()),
+        "config.json" => serde_json::from_slice::<Config>(contents).map(|_| ()),
+        _ => Ok(()),
+    };
+    result.map_err(|e| format!("「{}」の内容が不正です: {}", name, e))
+}
+
+/// 現在のファイルとバンドルの内容を比較する
+fn diff_files(config_dir: &Path, files: &[(String, Vec<u8>)]) -> Vec<(String, FileChange)> {
+    files
+        .iter()
+        .map(|(name, contents)| {
+            let change = match fs::read(config_dir.join(name)) {
+                Ok(current) if &current == contents => FileChange::Unchanged,
+                Ok(_) => FileChange::Replace,
+                Err(_) => FileChange::Create,
+            };
+            (name.clone(), change)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const ALIASES: &str = r#"[{"id":"1","alias":"docs","path":"/home/user/docs","tags":[],"color":null,"created_at":"2025-01-01T00:00:00Z","last_accessed":"2025-01-01T00:00:00Z","is_favorite":false}]"#;
+    const QUICK_ACCESS: &str = r#"[{"id":"q","name":"work","path":"/work","added_at":0,"order":0,"is_system":false}]"#;
+
+    fn write_profile(dir: &Path) {
+        fs::write(dir.join("aliases.json"), ALIASES).unwrap();
+        fs::write(dir.join("quick_access.json"), QUICK_ACCESS).unwrap();
+        fs::write(dir.join("config.json"), include_str!("../../config/default_config.json")).unwrap();
+    }
+
+    #[test]
+    fn test_export_then_import_into_fresh_dir() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let bundle_dir = tempdir().unwrap();
+        let bundle = bundle_dir.path().join("profile.zip");
+        write_profile(source.path());
+
+        export_bundle_from(source.path(), &bundle).unwrap();
+        let changes = import_bundle_into(target.path(), &bundle).unwrap();
+
+        assert_eq!(changes.len(), 3);
+        assert!(changes.iter().all(|(_, change)| *change == FileChange::Create));
+        for name in BUNDLE_FILES {
+            assert_eq!(
+                fs::read(source.path().join(name)).unwrap(),
+                fs::read(target.path().join(name)).unwrap(),
+                "{} が一致しません", name
+            );
+        }
+
+        // 同じ内容を再インポートすると変更なし
+        let changes = import_bundle_into(target.path(), &bundle).unwrap();
+        assert!(changes.iter().all(|(_, change)| *change == FileChange::Unchanged));
+    }
+
+    #[test]
+    fn test_preview_does_not_write() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let bundle_dir = tempdir().unwrap();
+        let bundle = bundle_dir.path().join("profile.zip");
+        write_profile(source.path());
+        fs::write(target.path().join("aliases.json"), "[]").unwrap();
+
+        export_bundle_from(source.path(), &bundle).unwrap();
+        let preview = preview_bundle_against(target.path(), &bundle).unwrap();
+
+        assert_eq!(preview.version, BUNDLE_VERSION);
+        assert_eq!(preview.alias_count, Some(1));
+        assert_eq!(preview.quick_access_count, Some(1));
+        assert!(preview.changes.contains(&("aliases.json".to_string(), FileChange::Replace)));
+        assert!(preview.changes.contains(&("config.json".to_string(), FileChange::Create)));
+        // ドライランなので既存ファイルはそのまま
+        assert_eq!(fs::read_to_string(target.path().join("aliases.json")).unwrap(), "[]");
+        assert!(!target.path().join("config.json").exists());
+    }
+
+    #[test]
+    fn test_missing_files_are_skipped() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let bundle_dir = tempdir().unwrap();
+        let bundle = bundle_dir.path().join("profile.zip");
+        fs::write(source.path().join("aliases.json"), ALIASES).unwrap();
+
+        export_bundle_from(source.path(), &bundle).unwrap();
+        let changes = import_bundle_into(target.path(), &bundle).unwrap();
+
+        assert_eq!(changes, vec![("aliases.json".to_string(), FileChange::Create)]);
+        assert!(!target.path().join("config.json").exists());
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let bundle_dir = tempdir().unwrap();
+        let bundle = bundle_dir.path().join("future.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&bundle).unwrap());
+        zip.start_file(MANIFEST_NAME, zip::write::SimpleFileOptions::default()).unwrap();
+        let manifest = BundleManifest {
+            version: BUNDLE_VERSION + 1,
+            created_at: chrono::Utc::now(),
+            files: vec![],
+        };
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        zip.finish().unwrap();
+
+        let target = tempdir().unwrap();
+        let result = import_bundle_into(target.path(), &bundle);
+        assert!(result.unwrap_err().contains("新しい形式"));
+    }
+
+    #[test]
+    fn test_invalid_contents_are_rejected_without_writing() {
+        let bundle_dir = tempdir().unwrap();
+        let bundle = bundle_dir.path().join("broken.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&bundle).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("aliases.json", options).unwrap();
+        zip.write_all(ALIASES.as_bytes()).unwrap();
+        zip.start_file("config.json", options).unwrap();
+        zip.write_all(b"{ broken").unwrap();
+        zip.start_file(MANIFEST_NAME, options).unwrap();
+        let manifest = BundleManifest {
+            version: BUNDLE_VERSION,
+            created_at: chrono::Utc::now(),
+            files: vec!["aliases.json".to_string(), "config.json".to_string()],
+        };
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        zip.finish().unwrap();
+
+        let target = tempdir().unwrap();
+        assert!(import_bundle_into(target.path(), &bundle).is_err());
+        assert!(!target.path().join("aliases.json").exists());
+    }
+}
@@ -1,8 +1,9 @@
 use crate::data::models::QuickAccessEntry;
 use crate::data::storage;
+use crate::utils::path::remap_prefix;
 use anyhow::Result;
 use chrono::Utc;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// クイックアクセス管理
@@ -19,6 +20,11 @@ impl QuickAccessManager {
         }
     }
 
+    /// 既存のエントリからQuickAccessManagerを作成
+    pub fn from_entries(entries: Vec<QuickAccessEntry>) -> Self {
+        Self { entries }
+    }
+
     /// エントリを追加
     pub fn add_entry(
         &mut self,
@@ -80,6 +86,37 @@ impl QuickAccessManager {
         Ok(())
     }
 
+    /// エントリの表示名を変更
+    pub fn rename_entry(&mut self, id: &str, new_name: &str) -> Result<(), String> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err("名前を入力してください".to_string());
+        }
+
+        let entry = self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| format!("エントリID '{}' は存在しません", id))?;
+
+        entry.name = new_name.to_string();
+        Ok(())
+    }
+
+    /// リネーム・移動されたパス配下を指すエントリのパスを付け替える
+    ///
+    /// # 戻り値
+    /// パスを更新したエントリの件数
+    pub fn remap_paths(&mut self, from: &Path, to: &Path) -> usize {
+        let mut updated = 0;
+        for entry in &mut self.entries {
+            if let Some(new_path) = remap_prefix(&entry.path, from, to) {
+                entry.path = new_path;
+                updated += 1;
+            }
+        }
+        updated
+    }
+
     /// エントリ一覧を取得（order順）
     pub fn get_entries(&self) -> Vec<QuickAccessEntry> {
         let mut entries = self.entries.clone();
@@ -99,8 +136,186 @@ impl QuickAccessManager {
     }
 }
 
+/// ドロップされたパスからクイックアクセスに追加する名前とパスを決める
+///
+/// フォルダ名をエントリ名にします（ドライブのルートなど名前がない場合はパス全体）。
+///
+/// # 引数
+/// * `path` - ドロップされたパス
+///
+/// # 戻り値
+/// * `Ok((名前, パス))` - 追加できる場合
+/// * `Err(String)` - フォルダではない場合
+pub fn entry_for_dropped_path(path: &Path) -> Result<(String, PathBuf), String> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+
+    if !path.is_dir() {
+        return Err(format!("「{}」はフォルダではないため、クイックアクセスに追加できません", name));
+    }
+
+    Ok((name, path.to_path_buf()))
+}
+
+/// 選択した項目の親フォルダからクイックアクセスに追加する名前とパスを決める
+///
+/// # 引数
+/// * `path` - 選択した項目のパス
+///
+/// # 戻り値
+/// * `Ok((名前, パス))` - 親フォルダの名前とパス
+/// * `Err(String)` - ドライブのルートなど親フォルダがない場合
+pub fn entry_for_parent_folder(path: &Path) -> Result<(String, PathBuf), String> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| format!("「{}」には親フォルダがありません", path.display()))?;
+    entry_for_dropped_path(parent)
+}
+
 impl Default for QuickAccessManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_entry(id: &str, name: &str, order: u32, is_system: bool) -> QuickAccessEntry {
+        QuickAccessEntry {
+            id: id.to_string(),
+            name: name.to_string(),
+            path: PathBuf::from(format!("/quick/{}", name)),
+            added_at: Utc::now(),
+            order,
+            is_system,
+        }
+    }
+
+    fn create_manager() -> QuickAccessManager {
+        QuickAccessManager::from_entries(vec![
+            create_entry("home", "ホーム", 0, true),
+            create_entry("work", "仕事", 1, false),
+            create_entry("docs", "資料", 2, false),
+        ])
+    }
+
+    #[test]
+    fn test_remove_system_entry_is_refused() {
+        let mut manager = create_manager();
+
+        let result = manager.remove_entry_by_id("home");
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("システム項目"));
+        assert_eq!(manager.get_entries().len(), 3);
+    }
+
+    #[test]
+    fn test_remove_user_entry() {
+        let mut manager = create_manager();
+
+        manager.remove_entry_by_id("work").unwrap();
+
+        let entries = manager.get_entries();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.id != "work"));
+        // 並び順が詰め直される
+        assert_eq!(entries[1].id, "docs");
+        assert_eq!(entries[1].order, 1);
+    }
+
+    #[test]
+    fn test_remove_nonexistent_entry() {
+        let mut manager = create_manager();
+        assert!(manager.remove_entry_by_id("missing").is_err());
+    }
+
+    #[test]
+    fn test_rename_entry() {
+        let mut manager = create_manager();
+
+        manager.rename_entry("docs", "  共有資料 ").unwrap();
+        assert_eq!(manager.get_entries()[2].name, "共有資料");
+
+        // 空の名前は拒否
+        assert!(manager.rename_entry("docs", "   ").is_err());
+        assert!(manager.rename_entry("missing", "名前").is_err());
+    }
+
+    #[test]
+    fn test_entry_for_dropped_directory_uses_folder_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path().join("資料");
+        std::fs::create_dir(&folder).unwrap();
+
+        let (name, path) = entry_for_dropped_path(&folder).unwrap();
+        assert_eq!(name, "資料");
+        assert_eq!(path, folder);
+    }
+
+    #[test]
+    fn test_entry_for_dropped_file_is_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("memo.txt");
+        std::fs::write(&file, "memo").unwrap();
+
+        let err = entry_for_dropped_path(&file).unwrap_err();
+        assert!(err.contains("memo.txt"));
+        assert!(err.contains("フォルダではない"));
+
+        // 存在しないパスも拒否
+        assert!(entry_for_dropped_path(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_entry_for_parent_folder() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path().join("資料");
+        std::fs::create_dir(&folder).unwrap();
+        let file = folder.join("memo.txt");
+        std::fs::write(&file, "memo").unwrap();
+
+        let (name, path) = entry_for_parent_folder(&file).unwrap();
+        assert_eq!(name, "資料");
+        assert_eq!(path, folder);
+
+        // ドライブのルートや親のない相対パスは拒否
+        let root = temp_dir.path().ancestors().last().unwrap();
+        assert!(entry_for_parent_folder(root).unwrap_err().contains("親フォルダがありません"));
+        assert!(entry_for_parent_folder(Path::new("memo.txt")).is_err());
+    }
+
+    #[test]
+    fn test_add_parent_folder_uses_folder_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path().join("プロジェクト");
+        std::fs::create_dir(&folder).unwrap();
+        let file = folder.join("README.md");
+        std::fs::write(&file, "readme").unwrap();
+
+        let mut manager = QuickAccessManager::new();
+        let (name, path) = entry_for_parent_folder(&file).unwrap();
+        manager.add_entry(name, path).unwrap();
+
+        let entries = manager.get_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "プロジェクト");
+        assert_eq!(entries[0].path, folder.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_remap_paths_updates_descendants() {
+        let mut manager = create_manager();
+
+        let updated = manager.remap_paths(Path::new("/quick"), Path::new("/renamed"));
+
+        assert_eq!(updated, 3);
+        assert!(manager.get_entries().iter().all(|e| e.path.starts_with("/renamed")));
+        assert_eq!(manager.remap_paths(Path::new("/quick"), Path::new("/other")), 0);
+    }
+}
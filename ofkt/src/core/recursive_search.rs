@@ -0,0 +1,329 @@
+//! ディレクトリモードの再帰検索
+//!
+//! 検索ボックスに `**クエリ` と入力すると、現在のフォルダ配下をワーカースレッドでたどり、
+//! ファイル名をエイリアス名と同じ規則で採点した結果を一覧で返します。
+//! クエリが変わったら前の検索を取り消して新しく始めます。
+
+use crate::core::search::name_match_score;
+use crate::data::models::DirectoryEntry;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::sync::Arc;
+
+/// 再帰検索にするクエリの接頭辞
+pub const RECURSIVE_QUERY_PREFIX: &str = "**";
+
+/// 検索クエリが再帰検索（`**クエリ`）の場合、接頭辞を除いた検索語を返す
+pub fn parse_recursive_query(query: &str) -> Option<&str> {
+    query
+        .trim_start()
+        .strip_prefix(RECURSIVE_QUERY_PREFIX)
+        .map(str::trim)
+}
+
+/// 再帰検索の条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursiveSearchOptions {
+    /// たどる深さの上限（1 で直下のみ）
+    pub max_depth: usize,
+    /// たどらない名前（`node_modules`・`.git` など、大文字小文字は問わない）
+    pub ignore: Vec<String>,
+    /// 隠しファイル・フォルダも対象にするか
+    pub show_hidden: bool,
+    /// 結果の最大数（スコアの高い順に残す）
+    pub max_results: usize,
+}
+
+impl Default for RecursiveSearchOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 8,
+            ignore: vec!["node_modules".to_string(), ".git".to_string()],
+            show_hidden: false,
+            max_results: 500,
+        }
+    }
+}
+
+/// 再帰検索で一致した項目
+#[derive(Debug, Clone)]
+pub struct RecursiveMatch {
+    /// 一致した項目（`name` は検索したフォルダからの相対パス）
+    pub entry: DirectoryEntry,
+    /// 名前の一致スコア
+    pub score: f32,
+}
+
+/// 隠しファイル・フォルダか（`DirectoryEntry` と同じ判定）
+fn is_hidden(entry: &walkdir::DirEntry) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        entry.file_name().to_string_lossy().starts_with('.')
+    }
+}
+
+/// フォルダ配下から名前が検索語に一致する項目を探す
+///
+/// 除外する名前のフォルダの中はたどりません。シンボリックリンクはたどりません。
+/// 読み取れない項目は無視します。
+///
+/// # 引数
+/// * `root` - 検索するフォルダ
+/// * `query` - 検索語（空の場合は結果なし）
+/// * `options` - 検索の条件
+/// * `cancelled` - true になった時点で検索を打ち切る
+///
+/// # 戻り値
+/// スコアの高い順の結果（取り消された場合は None）
+pub fn search_tree(
+    root: &Path,
+    query: &str,
+    options: &RecursiveSearchOptions,
+    cancelled: &AtomicBool,
+) -> Option<Vec<RecursiveMatch>> {
+    if query.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let mut matched: Vec<RecursiveMatch> = Vec::new();
+    let walker = walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(options.max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !options.ignore.iter().any(|ignored| ignored.eq_ignore_ascii_case(&name))
+                && (options.show_hidden || !is_hidden(entry))
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let name = entry.file_name().to_string_lossy();
+        let Some(score) = name_match_score(&matcher, &name, query) else {
+            continue;
+        };
+        let path = entry.into_path();
+        let Ok(relative) = path.strip_prefix(root).map(|p| p.display().to_string()) else {
+            continue;
+        };
+        if let Ok(mut entry) = DirectoryEntry::from_path(path) {
+            entry.name = relative;
+            matched.push(RecursiveMatch { entry, score });
+        }
+    }
+
+    // スコアの高い順、同点は相対パスの短い順・名前順
+    matched.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.entry.path.components().count().cmp(&b.entry.path.components().count()))
+            .then_with(|| a.entry.path.cmp(&b.entry.path))
+    });
+    matched.truncate(options.max_results);
+    Some(matched)
+}
+
+/// バックグラウンドで実行する再帰検索
+///
+/// ドロップすると検索を取り消します（クエリが変わった場合など）。
+#[derive(Debug)]
+pub struct RecursiveSearchTask {
+    /// 検索したフォルダ
+    root: PathBuf,
+    /// 検索語
+    query: String,
+    /// 検索結果（検索中は None）
+    results: Option<Vec<RecursiveMatch>>,
+    /// 検索結果の受信チャネル
+    receiver: Receiver<Vec<RecursiveMatch>>,
+    /// 取り消しフラグ（ワーカースレッドと共有）
+    cancelled: Arc<AtomicBool>,
+}
+
+impl RecursiveSearchTask {
+    /// ワーカースレッドで検索を開始
+    pub fn spawn(root: PathBuf, query: String, options: RecursiveSearchOptions) -> Self {
+        let (sender, receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_cancelled = Arc::clone(&cancelled);
+        let worker_root = root.clone();
+        let worker_query = query.clone();
+
+        std::thread::spawn(move || {
+            match search_tree(&worker_root, &worker_query, &options, &worker_cancelled) {
+                Some(results) => {
+                    // 受信側が破棄済みなら結果は捨てる
+                    let _ = sender.send(results);
+                }
+                None => log::debug!("再帰検索を取り消しました: {}", worker_query),
+            }
+        });
+
+        Self::from_parts(root, query, receiver, cancelled)
+    }
+
+    fn from_parts(
+        root: PathBuf,
+        query: String,
+        receiver: Receiver<Vec<RecursiveMatch>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            root,
+            query,
+            results: None,
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// 検索結果を確認する
+    ///
+    /// 毎フレーム呼び出すことを想定しています。
+    ///
+    /// # 戻り値
+    /// この呼び出しで結果が届いた場合は true
+    pub fn poll(&mut self) -> bool {
+        if self.results.is_some() {
+            return false;
+        }
+        match self.receiver.try_recv() {
+            Ok(results) => {
+                self.results = Some(results);
+                true
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => {
+                // ワーカースレッドが結果を送らずに終了した（取り消し済み）
+                self.results = Some(Vec::new());
+                false
+            }
+        }
+    }
+
+    /// 検索中か
+    pub fn is_pending(&self) -> bool {
+        self.results.is_none()
+    }
+
+    /// 指定したフォルダ・検索語の検索か
+    pub fn is_for(&self, root: &Path, query: &str) -> bool {
+        self.root == root && self.query == query
+    }
+
+    /// 検索結果（検索中は空）
+    pub fn results(&self) -> &[RecursiveMatch] {
+        self.results.as_deref().unwrap_or_default()
+    }
+}
+
+impl Drop for RecursiveSearchTask {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    fn names(results: &[RecursiveMatch]) -> Vec<String> {
+        results
+            .iter()
+            .map(|m| m.entry.name.replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_recursive_query() {
+        assert_eq!(parse_recursive_query("**report"), Some("report"));
+        assert_eq!(parse_recursive_query("  ** report "), Some("report"));
+        assert_eq!(parse_recursive_query("**"), Some(""));
+        assert_eq!(parse_recursive_query("report"), None);
+        assert_eq!(parse_recursive_query("*report"), None);
+    }
+
+    #[test]
+    fn test_search_tree_respects_depth_and_ignore_list() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/2025/q1")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("report.txt"), "").unwrap();
+        fs::write(root.join("docs/2025/report.xlsx"), "").unwrap();
+        fs::write(root.join("docs/2025/q1/report_q1.xlsx"), "").unwrap();
+        fs::write(root.join("node_modules/pkg/report.js"), "").unwrap();
+        fs::write(root.join(".git/report"), "").unwrap();
+        fs::write(root.join("docs/invoice.pdf"), "").unwrap();
+
+        let options = RecursiveSearchOptions::default();
+        let results = search_tree(root, "report", &options, &AtomicBool::new(false)).unwrap();
+        // 除外したフォルダの中は探さず、スコアの高い順・浅い順に並ぶ
+        assert_eq!(
+            names(&results),
+            vec!["report.txt", "docs/2025/report.xlsx", "docs/2025/q1/report_q1.xlsx"]
+        );
+        assert_eq!(results[0].entry.path, root.join("report.txt"));
+        assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        let shallow = RecursiveSearchOptions { max_depth: 3, ..options.clone() };
+        let results = search_tree(root, "report", &shallow, &AtomicBool::new(false)).unwrap();
+        assert_eq!(names(&results), vec!["report.txt", "docs/2025/report.xlsx"]);
+
+        let limited = RecursiveSearchOptions { max_results: 1, ..options.clone() };
+        assert_eq!(search_tree(root, "report", &limited, &AtomicBool::new(false)).unwrap().len(), 1);
+
+        assert!(search_tree(root, "", &options, &AtomicBool::new(false)).unwrap().is_empty());
+        assert!(search_tree(root, "report", &options, &AtomicBool::new(true)).is_none());
+    }
+
+    #[test]
+    fn test_task_drop_signals_cancel() {
+        let (_sender, receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let task = RecursiveSearchTask::from_parts(PathBuf::from("/root"), "a".to_string(), receiver, Arc::clone(&cancelled));
+        assert!(task.is_pending());
+        assert!(task.is_for(Path::new("/root"), "a"));
+        assert!(!task.is_for(Path::new("/root"), "ab"));
+
+        drop(task);
+        assert!(cancelled.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_spawned_task_completes() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        fs::write(temp_dir.path().join("sub/memo.txt"), "").unwrap();
+
+        let mut task = RecursiveSearchTask::spawn(
+            temp_dir.path().to_path_buf(),
+            "memo".to_string(),
+            RecursiveSearchOptions::default(),
+        );
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !task.poll() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!task.is_pending());
+        assert_eq!(names(task.results()), vec!["sub/memo.txt"]);
+    }
+}
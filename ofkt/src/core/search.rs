@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
+use std::time::Instant;
 use crate::data::models::FileAlias;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
@@ -11,6 +12,11 @@ pub struct SearchResult {
     pub alias: FileAlias,
     pub score: f32,
     pub matched_field: MatchedField,
+    /// 一致した文字のバイト位置（昇順）
+    ///
+    /// `matched_field` の文字列（エイリアス名・パス・読み・メモ、タグの場合は一致した最初のタグ）
+    /// に対する位置です。タグだけで絞り込んだ場合など、検索語がない場合は空です。
+    pub match_indices: Vec<usize>,
 }
 
 /// マッチしたフィールド
@@ -19,6 +25,300 @@ pub enum MatchedField {
     Alias,
     Path,
     Tag,
+    /// メモ
+    Note,
+    /// 読み
+    Reading,
+}
+
+/// 最終アクセス日時によるブーストの段階設定
+///
+/// 最終アクセスから `near_days` 日未満なら `ScoringWeights::recency_7d`、
+/// `mid_days` 日未満なら `ScoringWeights::recency_30d` を加点します。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecencyConfig {
+    /// 「最近」とみなす日数
+    pub near_days: u32,
+    /// 「やや最近」とみなす日数
+    pub mid_days: u32,
+}
+
+impl Default for RecencyConfig {
+    fn default() -> Self {
+        Self {
+            near_days: 7,
+            mid_days: 30,
+        }
+    }
+}
+
+/// 最終スコアの重み付け
+///
+/// 最終アクセス日時の加点を受ける日数の区切りは `RecencyConfig` で変更できます
+/// （フィールド名の日数は既定の区切り）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoringWeights {
+    /// お気に入りの加点
+    pub favorite_boost: f32,
+    /// 「最近」（`RecencyConfig::near_days` 日未満）アクセスしたエイリアスの加点
+    pub recency_7d: f32,
+    /// 「やや最近」（`RecencyConfig::mid_days` 日未満）アクセスしたエイリアスの加点
+    pub recency_30d: f32,
+    /// 最終スコアの上限
+    pub max_score: f32,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            favorite_boost: 0.2,
+            recency_7d: 0.1,
+            recency_30d: 0.05,
+            max_score: 1.5,
+        }
+    }
+}
+
+/// 長期間アクセスされていないエイリアスへの減点設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StalePenalty {
+    /// 減点の対象にするまでの日数（最終アクセスからこの日数を超えたら減点）
+    pub threshold_days: u32,
+    /// 減点するスコア
+    pub penalty: f32,
+}
+
+/// 検索結果の最終スコアの計算方法
+///
+/// `SearchEngine::set_scorer` で差し替えると、マッチングはそのままに
+/// 順位付けだけを変えて比較できます。
+pub trait Scorer {
+    /// 最終スコアを計算
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - スコア計算対象のエイリアス
+    /// * `base` - 基本スコア（検索マッチングで得られたスコア、0.0〜1.0）
+    fn score(&self, alias: &FileAlias, base: f32) -> f32;
+}
+
+/// 既定のスコア計算
+///
+/// - 基本スコア: 0.0〜1.0
+/// - お気に入りブースト（既定値、`ScoringWeights` で変更可能）: +0.2
+/// - 最終アクセス日時ブースト（既定値、加点は `ScoringWeights`、日数は `RecencyConfig` で変更可能）:
+///   - 最近7日以内: +0.1
+///   - 最近30日以内: +0.05
+///   - それ以降: +0.0
+/// - 長期間未アクセスの減点（`stale_penalty` が設定されている場合のみ）:
+///   - 最終アクセスから指定日数を超えた場合: -減点値
+/// - 最終スコアは0.0〜1.5（上限は `ScoringWeights` で変更可能）に制限
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DefaultScorer {
+    /// 最終アクセス日時によるブーストの日数の区切り
+    pub recency: RecencyConfig,
+    /// 長期間アクセスされていないエイリアスへの減点（None の場合は減点しない）
+    pub stale_penalty: Option<StalePenalty>,
+    /// お気に入り・最終アクセス日時の加点とスコアの上限
+    pub weights: ScoringWeights,
+}
+
+impl Scorer for DefaultScorer {
+    fn score(&self, alias: &FileAlias, base: f32) -> f32 {
+        let mut final_score = base;
+
+        // お気に入りブースト
+        if alias.is_favorite {
+            final_score += self.weights.favorite_boost;
+        }
+
+        // 最終アクセス日時ブースト
+        // 時計のずれや不正なインポートで未来の日時になっている場合は「たった今」として扱う
+        let now = Utc::now();
+        let duration = now.signed_duration_since(alias.last_accessed).max(Duration::zero());
+
+        if duration < Duration::days(i64::from(self.recency.near_days)) {
+            final_score += self.weights.recency_7d;
+        } else if duration < Duration::days(i64::from(self.recency.mid_days)) {
+            final_score += self.weights.recency_30d;
+        }
+
+        // 長期間アクセスされていないエイリアスの減点
+        if let Some(stale) = self.stale_penalty {
+            if duration > Duration::days(i64::from(stale.threshold_days)) {
+                final_score -= stale.penalty;
+            }
+        }
+
+        // 0.0〜上限に制限
+        final_score.clamp(0.0, self.weights.max_score.max(0.0))
+    }
+}
+
+/// 開いた回数を重視するスコア計算
+///
+/// 基本スコアに `開いた回数 × per_open`（最大 `max_boost`）を加点します。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyScorer {
+    /// 1回開くごとの加点
+    pub per_open: f32,
+    /// 加点の上限
+    pub max_boost: f32,
+}
+
+impl Default for FrequencyScorer {
+    fn default() -> Self {
+        Self {
+            per_open: 0.02,
+            max_boost: 0.5,
+        }
+    }
+}
+
+impl Scorer for FrequencyScorer {
+    fn score(&self, alias: &FileAlias, base: f32) -> f32 {
+        let boost = (alias.open_count as f32 * self.per_open).min(self.max_boost);
+        (base + boost).clamp(0.0, 1.5)
+    }
+}
+
+/// `tag:` によるタグの絞り込みを含むクエリの解析結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TagFilterQuery {
+    /// 絞り込むタグ（すべてのタグを持つエイリアスに絞り込む、重複なし）
+    tags: Vec<String>,
+    /// タグ以外の検索語（空白1つでつないだもの）
+    keywords: String,
+}
+
+impl TagFilterQuery {
+    /// キャッシュのキー（タグの順番や空白の違いを無視する）
+    fn cache_key(&self) -> String {
+        let mut tags = self.tags.clone();
+        tags.sort();
+        let mut key: Vec<String> = tags.iter().map(|tag| format!("tag:{}", tag)).collect();
+        key.push(self.keywords.clone());
+        key.join(" ")
+    }
+}
+
+/// 階層パス解析の数値範囲キーワード（`06..09` など）
+///
+/// パスの階層に含まれる数字の並びが範囲内なら一致とみなします。数字の並びが範囲の桁数より
+/// 長い場合は末尾の桁で判定するため、`06..09` は `06`・`9` のほか `202506`〜`202509` にも一致します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NumericRange {
+    /// 下限（含む）
+    start: u64,
+    /// 上限（含む）
+    end: u64,
+    /// 範囲の桁数（下限・上限の長いほう）
+    width: usize,
+}
+
+impl NumericRange {
+    /// u64 に収まる桁数
+    const MAX_DIGITS: usize = 18;
+
+    /// `a..b` 形式のキーワードを解析（下限と上限が逆でもよい）
+    fn parse(keyword: &str) -> Option<Self> {
+        let (start, end) = keyword.split_once("..")?;
+        let is_number = |s: &str| {
+            !s.is_empty() && s.len() <= Self::MAX_DIGITS && s.bytes().all(|b| b.is_ascii_digit())
+        };
+        if !is_number(start) || !is_number(end) {
+            return None;
+        }
+        let (a, b) = (start.parse::<u64>().ok()?, end.parse::<u64>().ok()?);
+        Some(Self {
+            start: a.min(b),
+            end: a.max(b),
+            width: start.len().max(end.len()),
+        })
+    }
+
+    /// 値が範囲内か
+    fn contains(&self, value: u64) -> bool {
+        (self.start..=self.end).contains(&value)
+    }
+
+    /// パスの階層に範囲内の数字の並びが含まれるか
+    fn matches_component(&self, component: &str) -> bool {
+        component
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|digits| !digits.is_empty())
+            .any(|digits| {
+                let tail = &digits[digits.len().saturating_sub(self.width)..];
+                [digits, tail].iter().any(|d| {
+                    d.len() <= Self::MAX_DIGITS && d.parse::<u64>().is_ok_and(|value| self.contains(value))
+                })
+            })
+    }
+}
+
+/// 一致の種類（同点の結果はこの順に並べる）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchKind {
+    /// エイリアス名の完全一致・前方一致
+    Direct,
+    /// ファジーマッチ（フォルダ名の完全一致・読み・メモを含む）
+    Fuzzy,
+    /// 階層パス解析
+    Hierarchical,
+    /// AND 検索で追加した全キーワード一致
+    AllTerms,
+}
+
+/// 1 件のエイリアスに対する最良の一致
+#[derive(Debug, Clone)]
+struct AliasMatch {
+    /// エイリアスのインデックス
+    index: usize,
+    /// 最終スコアを計算する前のスコア
+    score: f32,
+    /// 一致したフィールド
+    matched_field: MatchedField,
+    /// 一致の種類
+    kind: MatchKind,
+}
+
+/// 名前の一致スコア（エイリアス名の判定と同じ規則）
+///
+/// 完全一致は 1.0、前方一致は 0.8、それ以外はファジーマッチ（0.7 以下）で判定します。
+/// 大文字小文字は区別しません。ディレクトリモードの再帰検索でファイル名の判定に使います。
+///
+/// # Arguments
+///
+/// * `matcher` - ファジーマッチャー
+/// * `name` - 判定する名前
+/// * `query` - 検索クエリ
+///
+/// # Returns
+///
+/// 一致する場合はスコア、一致しない場合は None
+pub fn name_match_score(matcher: &SkimMatcherV2, name: &str, query: &str) -> Option<f32> {
+    let name = name.to_lowercase();
+    let query = query.to_lowercase();
+    if name == query {
+        return Some(1.0);
+    }
+    if name.starts_with(&query) {
+        return Some(0.8);
+    }
+    matcher
+        .fuzzy_match(&name, &query)
+        .map(normalize_fuzzy_score)
+        .filter(|&score| score > 0.0)
+}
+
+/// fuzzy-matcher のスコアを 0.0〜0.7 の範囲に正規化
+fn normalize_fuzzy_score(score: i64) -> f32 {
+    // fuzzy-matcher のスコアは通常、0〜100程度の範囲
+    const MAX_FUZZY_SCORE: f32 = 100.0;
+    const TARGET_MAX: f32 = 0.7;
+
+    let normalized = (score as f32 / MAX_FUZZY_SCORE) * TARGET_MAX;
+    normalized.clamp(0.0, TARGET_MAX)
 }
 
 /// 検索エンジン
@@ -36,9 +336,15 @@ pub struct SearchEngine {
     /// キー: 検索クエリ, 値: 検索結果
     cache: HashMap<String, Vec<SearchResult>>,
 
+    /// キャッシュのキーの使用順（先頭が最も古い）
+    cache_order: VecDeque<String>,
+
     /// 最終検索クエリ
     last_query: Option<String>,
 
+    /// 直前の検索結果をキャッシュから返したか
+    last_from_cache: bool,
+
     /// キャッシュの最大サイズ
     max_cache_size: usize,
 
@@ -47,6 +353,46 @@ pub struct SearchEngine {
 
     /// ファジーマッチャー
     fuzzy_matcher: SkimMatcherV2,
+
+    /// エイリアス名に含まれる文字 → エイリアスのインデックス
+    /// （None の場合は全件を走査する）
+    name_index: Option<HashMap<char, Vec<usize>>>,
+
+    /// 正規化したタグ → エイリアスのインデックス（`tag:` 検索用）
+    tag_index: HashMap<String, Vec<usize>>,
+
+    /// 最終アクセス日時によるブーストの段階
+    recency: RecencyConfig,
+
+    /// 長期間アクセスされていないエイリアスへの減点（None の場合は減点しない）
+    stale_penalty: Option<StalePenalty>,
+
+    /// 最終スコアの重み付け（最終アクセス日時の加点は `recency` と揃えておく）
+    weights: ScoringWeights,
+
+    /// 最終スコアの計算方法
+    scorer: Box<dyn Scorer>,
+
+    /// `scorer` が既定のスコア計算か（差し替えられていない場合は設定変更に追従する）
+    uses_default_scorer: bool,
+
+    /// 空白区切りの全キーワードに一致するエイリアスだけを返すか（AND 検索）
+    match_all_terms: bool,
+
+    /// メモを検索対象に含めるか
+    search_notes: bool,
+
+    /// 読みを検索対象に含めるか
+    search_reading: bool,
+
+    /// 正規化（小文字化）せず、文字列をそのまま比較するか
+    raw_mode: bool,
+
+    /// 1回の検索で走査に使える時間（None の場合は制限しない）
+    time_budget: Option<std::time::Duration>,
+
+    /// 直前の検索が時間切れで途中までの結果になったか
+    last_truncated_by_budget: bool,
 }
 
 impl SearchEngine {
@@ -56,27 +402,87 @@ impl SearchEngine {
     /// デフォルトの検索結果上限
     const DEFAULT_MAX_RESULTS: usize = 100;
 
+    /// パスの階層名と完全一致した場合のスコア
+    /// （ファジーマッチの上限0.7より高く、前方一致の0.8より低い）
+    const EXACT_COMPONENT_SCORE: f32 = 0.75;
+
+    /// `*term*` 形式の部分一致検索のスコア
+    /// （ファジーマッチの上限0.7より高く、前方一致の0.8より低い）
+    const SUBSTRING_SCORE: f32 = 0.75;
+
+    /// AND 検索で全キーワードが名前・パス・タグのいずれかに含まれた場合のスコア
+    /// （階層パスの全キーワード一致と同じ）
+    const ALL_TERMS_SCORE: f32 = 0.9;
+
+    /// 読みに対する一致のスコアの重み（名前への一致より低くする）
+    const READING_WEIGHT: f32 = 0.8;
+
+    /// メモに検索語が含まれる場合のスコア（他のどの一致よりも低い）
+    const NOTE_SCORE: f32 = 0.3;
+
+    /// `tag:` 形式のタグ完全一致検索のスコア
+    const EXACT_TAG_SCORE: f32 = 1.0;
+
+    /// タグ完全一致検索のクエリ接頭辞
+    const TAG_QUERY_PREFIX: &'static str = "tag:";
+
+    /// キー入力ごとの検索に使う走査時間の目安（1フレームに収まる程度）
+    pub const DEFAULT_TIME_BUDGET: std::time::Duration = std::time::Duration::from_millis(8);
+
+    /// 走査中に経過時間を確認する間隔（エイリアス数）
+    const BUDGET_CHECK_INTERVAL: usize = 64;
+
     /// 新しい SearchEngine を作成
     pub fn new() -> Self {
         Self {
             aliases: Vec::new(),
             cache: HashMap::new(),
+            cache_order: VecDeque::new(),
             last_query: None,
+            last_from_cache: false,
             max_cache_size: Self::DEFAULT_CACHE_SIZE,
             max_results: Self::DEFAULT_MAX_RESULTS,
             fuzzy_matcher: SkimMatcherV2::default(),
+            name_index: Some(HashMap::new()),
+            tag_index: HashMap::new(),
+            recency: RecencyConfig::default(),
+            stale_penalty: None,
+            weights: ScoringWeights::default(),
+            scorer: Box::new(DefaultScorer::default()),
+            uses_default_scorer: true,
+            match_all_terms: false,
+            search_notes: false,
+            search_reading: true,
+            raw_mode: false,
+            time_budget: None,
+            last_truncated_by_budget: false,
         }
     }
 
     /// エイリアスリストを指定して SearchEngine を作成
     pub fn with_aliases(aliases: Vec<FileAlias>) -> Self {
         Self {
+            name_index: Some(Self::build_name_index(&aliases)),
+            tag_index: Self::build_tag_index(&aliases),
             aliases,
             cache: HashMap::new(),
+            cache_order: VecDeque::new(),
             last_query: None,
+            last_from_cache: false,
             max_cache_size: Self::DEFAULT_CACHE_SIZE,
             max_results: Self::DEFAULT_MAX_RESULTS,
             fuzzy_matcher: SkimMatcherV2::default(),
+            recency: RecencyConfig::default(),
+            stale_penalty: None,
+            weights: ScoringWeights::default(),
+            scorer: Box::new(DefaultScorer::default()),
+            uses_default_scorer: true,
+            match_all_terms: false,
+            search_notes: false,
+            search_reading: true,
+            raw_mode: false,
+            time_budget: None,
+            last_truncated_by_budget: false,
         }
     }
 
@@ -85,13 +491,35 @@ impl SearchEngine {
         Self {
             aliases: Vec::new(),
             cache: HashMap::new(),
+            cache_order: VecDeque::new(),
             last_query: None,
+            last_from_cache: false,
             max_cache_size: cache_size,
             max_results: Self::DEFAULT_MAX_RESULTS,
             fuzzy_matcher: SkimMatcherV2::default(),
+            name_index: Some(HashMap::new()),
+            tag_index: HashMap::new(),
+            recency: RecencyConfig::default(),
+            stale_penalty: None,
+            weights: ScoringWeights::default(),
+            scorer: Box::new(DefaultScorer::default()),
+            uses_default_scorer: true,
+            match_all_terms: false,
+            search_notes: false,
+            search_reading: true,
+            raw_mode: false,
+            time_budget: None,
+            last_truncated_by_budget: false,
         }
     }
 
+    /// スコアの重み付けを指定して SearchEngine を作成
+    pub fn with_weights(weights: ScoringWeights) -> Self {
+        let mut engine = Self::new();
+        engine.set_weights(weights);
+        engine
+    }
+
     /// 検索結果の上限を設定
     pub fn set_max_results(&mut self, max_results: usize) {
         self.max_results = max_results;
@@ -104,13 +532,256 @@ impl SearchEngine {
         self.max_results
     }
 
+    /// 1回の検索で走査に使える時間を設定
+    ///
+    /// 時間を超えた場合は走査を打ち切り、それまでに見つかった結果を返します
+    /// （`truncated_by_budget` が true になる）。None の場合は全件を走査します。
+    pub fn set_time_budget(&mut self, time_budget: Option<std::time::Duration>) {
+        self.time_budget = time_budget;
+    }
+
+    /// 1回の検索で走査に使える時間を取得
+    pub fn time_budget(&self) -> Option<std::time::Duration> {
+        self.time_budget
+    }
+
+    /// 直前の検索が時間切れで途中までの結果になったか
+    pub fn truncated_by_budget(&self) -> bool {
+        self.last_truncated_by_budget
+    }
+
+    /// 最終アクセス日時によるブーストの段階を設定
+    pub fn set_recency_config(&mut self, recency: RecencyConfig) {
+        if self.recency == recency {
+            return;
+        }
+        self.recency = recency;
+        self.refresh_default_scorer();
+        // スコアが変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// 最終アクセス日時によるブーストの段階を取得
+    pub fn recency_config(&self) -> RecencyConfig {
+        self.recency
+    }
+
+    /// スコアの重み付けを設定
+    pub fn set_weights(&mut self, weights: ScoringWeights) {
+        if self.weights == weights {
+            return;
+        }
+        self.weights = weights;
+        self.refresh_default_scorer();
+        // スコアが変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// スコアの重み付けを取得
+    pub fn weights(&self) -> ScoringWeights {
+        self.weights
+    }
+
+    /// 長期間アクセスされていないエイリアスへの減点を設定（None で無効）
+    pub fn set_stale_penalty(&mut self, stale_penalty: Option<StalePenalty>) {
+        if self.stale_penalty == stale_penalty {
+            return;
+        }
+        self.stale_penalty = stale_penalty;
+        self.refresh_default_scorer();
+        // スコアが変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// 最終スコアの計算方法を差し替える
+    ///
+    /// 差し替えている間、`set_recency_config`・`set_stale_penalty` の設定は
+    /// 保持されますがスコアには反映されません（`reset_scorer` で戻すと反映されます）。
+    pub fn set_scorer(&mut self, scorer: Box<dyn Scorer>) {
+        self.scorer = scorer;
+        self.uses_default_scorer = false;
+        // スコアが変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// 最終スコアの計算方法を既定に戻す
+    pub fn reset_scorer(&mut self) {
+        self.uses_default_scorer = true;
+        self.refresh_default_scorer();
+        self.clear_cache();
+    }
+
+    /// 既定のスコア計算を使っている場合、現在の設定で作り直す
+    fn refresh_default_scorer(&mut self) {
+        if self.uses_default_scorer {
+            self.scorer = Box::new(DefaultScorer {
+                recency: self.recency,
+                stale_penalty: self.stale_penalty,
+                weights: self.weights,
+            });
+        }
+    }
+
+    /// 長期間アクセスされていないエイリアスへの減点設定を取得
+    pub fn stale_penalty(&self) -> Option<StalePenalty> {
+        self.stale_penalty
+    }
+
+    /// 複数キーワードの検索を AND 検索にするか設定
+    ///
+    /// 有効な場合、空白で区切ったすべてのキーワードがエイリアス名・パス・タグの
+    /// いずれかに含まれるエイリアスだけを返します。
+    pub fn set_match_all_terms(&mut self, match_all_terms: bool) {
+        if self.match_all_terms == match_all_terms {
+            return;
+        }
+        self.match_all_terms = match_all_terms;
+        // 結果が変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// 複数キーワードの検索が AND 検索かどうかを取得
+    pub fn match_all_terms(&self) -> bool {
+        self.match_all_terms
+    }
+
+    /// メモを検索対象に含めるかを設定
+    ///
+    /// メモは自由記述のため、検索語を含む場合だけ低いスコアで一致とみなします。
+    pub fn set_search_notes(&mut self, search_notes: bool) {
+        if self.search_notes == search_notes {
+            return;
+        }
+        self.search_notes = search_notes;
+        // 結果が変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// メモを検索対象に含めるかを取得
+    pub fn search_notes(&self) -> bool {
+        self.search_notes
+    }
+
+    /// 読みを検索対象に含めるかを設定
+    ///
+    /// 読みへの一致は名前への一致より低いスコアになります。
+    pub fn set_search_reading(&mut self, search_reading: bool) {
+        if self.search_reading == search_reading {
+            return;
+        }
+        self.search_reading = search_reading;
+        // 結果が変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// 読みを検索対象に含めるかを取得
+    pub fn search_reading(&self) -> bool {
+        self.search_reading
+    }
+
+    /// 正規化せずに検索する（raw モード）かを設定
+    ///
+    /// 有効な場合は大文字小文字を区別し、クエリと名前・パス・タグをそのまま比較します。
+    /// 大文字小文字だけが異なるファイル名を区別したい場合に使います。
+    pub fn set_raw_mode(&mut self, raw_mode: bool) {
+        if self.raw_mode == raw_mode {
+            return;
+        }
+        self.raw_mode = raw_mode;
+        self.fuzzy_matcher = if raw_mode {
+            SkimMatcherV2::default().respect_case()
+        } else {
+            SkimMatcherV2::default()
+        };
+        // 結果が変わるためキャッシュをクリア
+        self.clear_cache();
+    }
+
+    /// 正規化せずに検索する（raw モード）かを取得
+    pub fn raw_mode(&self) -> bool {
+        self.raw_mode
+    }
+
+    /// 比較用に文字列を正規化（小文字化）する
+    ///
+    /// raw モードの場合はそのまま返します。
+    fn fold(&self, s: &str) -> String {
+        if self.raw_mode {
+            s.to_string()
+        } else {
+            s.to_lowercase()
+        }
+    }
+
     /// エイリアスリストを設定
     pub fn set_aliases(&mut self, aliases: Vec<FileAlias>) {
+        self.name_index = Some(Self::build_name_index(&aliases));
+        self.tag_index = Self::build_tag_index(&aliases);
         self.aliases = aliases;
         // エイリアスリストが変更されたらキャッシュをクリア
         self.clear_cache();
     }
 
+    /// エイリアス名の文字ごとの索引を作成
+    ///
+    /// エイリアス名（小文字化）に含まれる文字ごとにエイリアスを分類します。
+    /// 完全一致・前方一致・ファジーマッチのいずれも、クエリの先頭文字を
+    /// 名前に含まないエイリアスにはマッチしないため、検索時にそのバケットだけを
+    /// 名前の判定対象にできます。
+    fn build_name_index(aliases: &[FileAlias]) -> HashMap<char, Vec<usize>> {
+        let mut index: HashMap<char, Vec<usize>> = HashMap::new();
+        for (i, alias) in aliases.iter().enumerate() {
+            let mut seen: Vec<char> = Vec::new();
+            for c in alias.alias.to_lowercase().chars() {
+                if !seen.contains(&c) {
+                    seen.push(c);
+                    index.entry(c).or_default().push(i);
+                }
+            }
+        }
+        index
+    }
+
+    /// タグの索引を作成
+    ///
+    /// 正規化したタグごとにエイリアスを分類します（同じエイリアスは1回だけ登録）。
+    fn build_tag_index(aliases: &[FileAlias]) -> HashMap<String, Vec<usize>> {
+        let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, alias) in aliases.iter().enumerate() {
+            for tag in &alias.tags {
+                let entry = index.entry(Self::normalize_tag(tag)).or_default();
+                if entry.last() != Some(&i) {
+                    entry.push(i);
+                }
+            }
+        }
+        index
+    }
+
+    /// タグを比較用に正規化（前後の空白を除き小文字化）
+    fn normalize_tag(tag: &str) -> String {
+        tag.trim().to_lowercase()
+    }
+
+    /// クエリの先頭文字からエイリアス名を判定すべきエイリアスを求める
+    ///
+    /// # Returns
+    ///
+    /// エイリアスのインデックスごとに、名前の判定が必要なら true
+    fn name_candidates(&self, query_lower: &str) -> Vec<bool> {
+        // 索引は小文字化した名前で作っているため、raw モードでも小文字で引く（候補は広めになる）
+        let first = query_lower.chars().next().map(|c| c.to_lowercase().next().unwrap_or(c));
+        let (Some(index), Some(first)) = (&self.name_index, first) else {
+            return vec![true; self.aliases.len()];
+        };
+
+        let mut candidates = vec![false; self.aliases.len()];
+        for &i in index.get(&first).map(Vec::as_slice).unwrap_or_default() {
+            candidates[i] = true;
+        }
+        candidates
+    }
+
     /// エイリアスリストへの参照を取得
     pub fn aliases(&self) -> &[FileAlias] {
         &self.aliases
@@ -119,7 +790,9 @@ impl SearchEngine {
     /// キャッシュをクリア
     pub fn clear_cache(&mut self) {
         self.cache.clear();
+        self.cache_order.clear();
         self.last_query = None;
+        self.last_from_cache = false;
     }
 
     /// 最終検索クエリを取得
@@ -127,46 +800,19 @@ impl SearchEngine {
         self.last_query.as_deref()
     }
 
-    /// 最終スコアを計算
+    /// 直前の検索結果をキャッシュから返したか（新たに検索した場合は false）
+    pub fn last_from_cache(&self) -> bool {
+        self.last_from_cache
+    }
+
+    /// 最終スコアを計算（`Scorer` に委譲する）
     ///
     /// # Arguments
     ///
     /// * `alias` - スコア計算対象のエイリアス
     /// * `base_score` - 基本スコア（検索マッチングで得られたスコア）
-    ///
-    /// # Returns
-    ///
-    /// 最終スコア（最大値1.5）
-    ///
-    /// # スコアリング詳細
-    ///
-    /// - 基本スコア: 0.0〜1.0
-    /// - お気に入りブースト: +0.2
-    /// - 最終アクセス日時ブースト:
-    ///   - 最近7日以内: +0.1
-    ///   - 最近30日以内: +0.05
-    ///   - それ以降: +0.0
-    /// - 最終スコアは1.5に制限
     fn calculate_final_score(&self, alias: &FileAlias, base_score: f32) -> f32 {
-        let mut final_score = base_score;
-
-        // お気に入りブースト
-        if alias.is_favorite {
-            final_score += 0.2;
-        }
-
-        // 最終アクセス日時ブースト
-        let now = Utc::now();
-        let duration = now.signed_duration_since(alias.last_accessed);
-
-        if duration < Duration::days(7) {
-            final_score += 0.1;
-        } else if duration < Duration::days(30) {
-            final_score += 0.05;
-        }
-
-        // 最大値を1.5に制限
-        final_score.min(1.5)
+        self.scorer.score(alias, base_score)
     }
 
     /// エイリアスを検索
@@ -179,119 +825,415 @@ impl SearchEngine {
     ///
     /// 検索結果のベクター（スコアの高い順）
     pub fn search(&mut self, query: &str) -> Vec<SearchResult> {
+        self.search_with_limit(query, self.max_results)
+    }
+
+    /// 結果の上限を指定してエイリアスを検索
+    ///
+    /// `max_results` をこの呼び出しだけ上書きします。エンジンの設定は変更せず、
+    /// キャッシュもクリアしません（キャッシュには上限適用前の結果を保存し、取り出し時に切り詰める）。
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 検索クエリ
+    /// * `limit` - 返す結果の最大数
+    ///
+    /// # Returns
+    ///
+    /// 検索結果のベクター（スコアの高い順、最大 `limit` 件）
+    pub fn search_with_limit(&mut self, query: &str, limit: usize) -> Vec<SearchResult> {
         // 空のクエリチェック
         if query.is_empty() {
+            self.last_from_cache = false;
+            self.last_truncated_by_budget = false;
             return Vec::new();
         }
 
-        // キャッシュチェック
-        if let Some(cached_results) = self.cache.get(query) {
+        // キャッシュチェック（タグの絞り込みを含む場合は絞り込み条件ごとのキー）
+        let cache_key = self.parse_query_filters(query)
+            .map(|tag_query| tag_query.cache_key())
+            .unwrap_or_else(|| query.to_string());
+        if let Some(cached_results) = self.cache.get(&cache_key) {
+            let limited = cached_results.iter().take(limit).cloned().collect();
+            self.touch_cache_key(&cache_key);
             self.last_query = Some(query.to_string());
-            return cached_results.clone();
+            self.last_from_cache = true;
+            self.last_truncated_by_budget = false;
+            return limited;
         }
 
-        // 検索クエリを小文字に変換
-        let query_lower = query.to_lowercase();
-        let mut results = Vec::new();
-        let mut fuzzy_results = Vec::new();
-        let mut hierarchical_results = Vec::new();
+        let (mut results, truncated) = self.compute_results(query);
+        self.fill_match_indices(&mut results, query);
+        self.last_truncated_by_budget = truncated;
+        self.last_query = Some(query.to_string());
+        self.last_from_cache = false;
 
-        // 階層キーワードを抽出
-        let keywords = self.parse_hierarchical_query(query);
-        let use_hierarchical = keywords.len() >= 2;
+        // 途中までの結果はキャッシュしない（次回は改めて走査する）
+        if truncated {
+            return results.into_iter().take(limit).collect();
+        }
 
-        // エイリアスリストを走査
-        for alias in &self.aliases {
-            let alias_lower = alias.alias.to_lowercase();
-            let mut matched = false;
+        // キャッシュに保存（上限に達したら最も長く使われていないクエリから削除）
+        while self.cache.len() >= self.max_cache_size {
+            let Some(oldest) = self.cache_order.pop_front() else {
+                break;
+            };
+            self.cache.remove(&oldest);
+        }
+        let limited: Vec<SearchResult> = results.iter().take(limit).cloned().collect();
+        self.touch_cache_key(&cache_key);
+        self.cache.insert(cache_key, results);
 
-            // 完全一致チェック（スコア1.0）
-            if alias_lower == query_lower {
-                results.push(SearchResult {
-                    alias: alias.clone(),
-                    score: 1.0,
-                    matched_field: MatchedField::Alias,
-                });
-                continue;
-            }
-            // 前方一致チェック（スコア0.8）
-            else if alias_lower.starts_with(&query_lower) {
-                results.push(SearchResult {
-                    alias: alias.clone(),
-                    score: 0.8,
-                    matched_field: MatchedField::Alias,
-                });
-                continue;
-            }
-            // 完全一致・前方一致がない場合、ファジーマッチングを試行
-            else {
-                // エイリアス名に対するファジーマッチング
-                if let Some(score) = self.fuzzy_matcher.fuzzy_match(&alias_lower, &query_lower) {
-                    let normalized_score = self.normalize_fuzzy_score(score);
-                    if normalized_score > 0.0 {
-                        fuzzy_results.push(SearchResult {
-                            alias: alias.clone(),
-                            score: normalized_score,
-                            matched_field: MatchedField::Alias,
-                        });
-                        matched = true;
-                    }
-                }
+        limited
+    }
 
-                // パスに対するファジーマッチング（エイリアスでマッチしなかった場合のみ）
-                if !matched {
-                    let path_str = alias.path.to_string_lossy().to_lowercase();
-                    if let Some(score) = self.fuzzy_matcher.fuzzy_match(&path_str, &query_lower) {
-                        let normalized_score = self.normalize_fuzzy_score(score);
-                        if normalized_score > 0.0 {
-                            fuzzy_results.push(SearchResult {
-                                alias: alias.clone(),
-                                score: normalized_score,
-                                matched_field: MatchedField::Path,
-                            });
-                            matched = true;
-                        }
+    /// 各結果に一致した文字の位置を設定
+    ///
+    /// `tag:` の絞り込みとワイルドカードの `*` は除いた検索語で判定します。
+    fn fill_match_indices(&self, results: &mut [SearchResult], query: &str) {
+        let keywords = match self.parse_query_filters(query) {
+            Some(tag_query) => tag_query.keywords,
+            None => query.to_string(),
+        };
+        let terms: Vec<String> = keywords
+            .split_whitespace()
+            .map(|term| self.fold(term.trim_matches('*')))
+            .filter(|term| !term.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return;
+        }
+
+        for result in results.iter_mut() {
+            let alias = &result.alias;
+            result.match_indices = match result.matched_field {
+                MatchedField::Alias => self.highlight_indices(&alias.alias, &terms),
+                MatchedField::Path => self.highlight_indices(&alias.path.to_string_lossy(), &terms),
+                MatchedField::Tag => alias.tags
+                    .iter()
+                    .map(|tag| self.highlight_indices(tag, &terms))
+                    .find(|indices| !indices.is_empty())
+                    .unwrap_or_default(),
+                MatchedField::Reading => self.highlight_indices(alias.reading.as_deref().unwrap_or_default(), &terms),
+                MatchedField::Note => self.highlight_indices(alias.note.as_deref().unwrap_or_default(), &terms),
+            };
+        }
+    }
+
+    /// 文字列中で検索語に一致した文字のバイト位置
+    ///
+    /// 検索語全体が含まれる場合（完全一致・前方一致を含む）はその範囲、含まれない場合は
+    /// ファジーマッチで一致した文字を返します。検索語全体で一致しない場合は語ごとに判定します。
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - 判定する文字列
+    /// * `terms` - 小文字化済みの検索語（空でないこと）
+    ///
+    /// # Returns
+    ///
+    /// `text` の文字境界のバイト位置（昇順、重複なし）
+    fn highlight_indices(&self, text: &str, terms: &[String]) -> Vec<usize> {
+        // 小文字化で文字数が変わる場合に備え、小文字化後の文字ごとに元のバイト位置を記録する
+        let mut folded = String::with_capacity(text.len());
+        let mut origins = Vec::with_capacity(text.len());
+        for (byte, c) in text.char_indices() {
+            for folded_char in self.fold(c.encode_utf8(&mut [0; 4])).chars() {
+                folded.push(folded_char);
+                origins.push(byte);
+            }
+        }
+
+        let whole = terms.join(" ");
+        let mut char_indices = self.term_char_indices(&folded, &whole);
+        if char_indices.is_empty() && terms.len() > 1 {
+            char_indices = terms
+                .iter()
+                .flat_map(|term| self.term_char_indices(&folded, term))
+                .collect();
+        }
+
+        let bytes: std::collections::BTreeSet<usize> = char_indices
+            .into_iter()
+            .filter_map(|i| origins.get(i).copied())
+            .collect();
+        bytes.into_iter().collect()
+    }
+
+    /// 1 つの検索語に一致した文字の位置（文字単位）
+    fn term_char_indices(&self, folded: &str, term: &str) -> Vec<usize> {
+        if let Some(start) = folded.find(term) {
+            let start = folded[..start].chars().count();
+            return (start..start + term.chars().count()).collect();
+        }
+        self.fuzzy_matcher
+            .fuzzy_indices(folded, term)
+            .map(|(_, indices)| indices)
+            .unwrap_or_default()
+    }
+
+    /// キャッシュのキーを最近使用したものとして使用順の末尾に移動
+    fn touch_cache_key(&mut self, key: &str) {
+        self.cache_order.retain(|k| k != key);
+        self.cache_order.push_back(key.to_string());
+    }
+
+    /// 検索を実行し、上限を適用する前の全結果をスコア順で返す
+    ///
+    /// # Returns
+    ///
+    /// (検索結果, 時間切れで走査を打ち切ったか)
+    fn compute_results(&self, query: &str) -> (Vec<SearchResult>, bool) {
+        // tag:xxx を含むクエリはタグで絞り込み、残りの検索語を通常どおり判定する
+        if let Some(tag_query) = self.parse_query_filters(query) {
+            return self.compute_tag_filtered_results(&tag_query);
+        }
+
+        // 先頭が * のクエリは部分一致検索
+        if let Some(term) = self.parse_wildcard_query(query) {
+            let mut results = self.match_substring(&term);
+            self.finalize_results(&mut results);
+            return (results, false);
+        }
+
+        let deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        let mut truncated = false;
+
+        // 検索クエリを小文字に変換（raw モードではそのまま）
+        let query_lower = self.fold(query);
+
+        // 階層キーワードを抽出
+        let keywords = self.parse_hierarchical_query(query);
+        let use_hierarchical = keywords.len() >= 2;
+
+        // 名前の判定はクエリの先頭文字を含むエイリアスだけに絞る（パス・タグは全件）
+        let name_candidates = self.name_candidates(&query_lower);
+
+        // エイリアスリストを走査し、エイリアスごとに最良の一致を 1 件だけ記録する
+        let mut matches = Vec::new();
+        for (index, alias) in self.aliases.iter().enumerate() {
+            // 時間切れなら打ち切り、それまでの結果を返す
+            if Self::budget_exceeded(deadline, index) {
+                truncated = true;
+                break;
+            }
+
+            let hierarchical_keywords = use_hierarchical.then_some(keywords.as_slice());
+            if let Some((score, matched_field, kind)) =
+                self.match_alias(alias, name_candidates[index], &query_lower, hierarchical_keywords)
+            {
+                matches.push(AliasMatch { index, score, matched_field, kind });
+            }
+        }
+
+        // AND 検索: 一部のキーワードにしか一致しないものを除き、
+        // 語順の違いなどで上記の判定から漏れた全一致のエイリアスを追加する
+        if self.match_all_terms && use_hierarchical {
+            let terms: Vec<String> = keywords.iter().map(|k| self.fold(k)).collect();
+            matches.retain(|m| self.all_terms_field(&self.aliases[m.index], &terms).is_some());
+            // 時間切れの場合は追加の走査をしない
+            if !truncated {
+                let matched: HashSet<usize> = matches.iter().map(|m| m.index).collect();
+                for (index, alias) in self.aliases.iter().enumerate() {
+                    if matched.contains(&index) {
+                        continue;
+                    }
+                    if let Some(matched_field) = self.all_terms_field(alias, &terms) {
+                        matches.push(AliasMatch {
+                            index,
+                            score: Self::ALL_TERMS_SCORE,
+                            matched_field,
+                            kind: MatchKind::AllTerms,
+                        });
                     }
                 }
+            }
+        }
 
-                // タグに対するファジーマッチング（エイリアス・パスでマッチしなかった場合のみ）
-                if !matched {
-                    for tag in &alias.tags {
-                        let tag_lower = tag.to_lowercase();
-                        if let Some(score) = self.fuzzy_matcher.fuzzy_match(&tag_lower, &query_lower) {
-                            let normalized_score = self.normalize_fuzzy_score(score);
-                            if normalized_score > 0.0 {
-                                fuzzy_results.push(SearchResult {
-                                    alias: alias.clone(),
-                                    score: normalized_score,
-                                    matched_field: MatchedField::Tag,
-                                });
-                                matched = true;
-                                break; // タグの場合、最初にマッチしたもので十分
-                            }
-                        }
-                    }
+        // 同点の場合に完全一致・前方一致 → ファジー → 階層 → AND 検索の順になるよう
+        // 一致の種類で並べてから（安定ソート）、まとめてスコアを計算して並べ替える
+        matches.sort_by_key(|m| m.kind);
+        let mut results: Vec<SearchResult> = matches
+            .into_iter()
+            .map(|m| SearchResult {
+                alias: self.aliases[m.index].clone(),
+                score: m.score,
+                matched_field: m.matched_field,
+                match_indices: Vec::new(),
+            })
+            .collect();
+
+        self.finalize_results(&mut results);
+        (results, truncated)
+    }
+
+    /// 1 件のエイリアスに対する最良の一致を判定
+    ///
+    /// エイリアス名の完全一致・前方一致 → エイリアス名のファジーマッチ → パス → タグ →
+    /// 読み・メモ → 階層パスの順に判定し、最初に一致したものを返します。
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - 判定対象のエイリアス
+    /// * `name_candidate` - エイリアス名を判定するか（クエリの先頭文字を含むか）
+    /// * `query_lower` - 小文字化済みの検索クエリ
+    /// * `keywords` - 階層パス解析のキーワード（2 語以上の場合のみ）
+    ///
+    /// # Returns
+    ///
+    /// (最終スコアを計算する前のスコア, 一致したフィールド, 一致の種類)、一致しない場合は None
+    fn match_alias(
+        &self,
+        alias: &FileAlias,
+        name_candidate: bool,
+        query_lower: &str,
+        keywords: Option<&[String]>,
+    ) -> Option<(f32, MatchedField, MatchKind)> {
+        if name_candidate {
+            let alias_lower = self.fold(&alias.alias);
+
+            // 完全一致（スコア1.0）・前方一致（スコア0.8）
+            if alias_lower == query_lower {
+                return Some((1.0, MatchedField::Alias, MatchKind::Direct));
+            }
+            if alias_lower.starts_with(query_lower) {
+                return Some((0.8, MatchedField::Alias, MatchKind::Direct));
+            }
+
+            // 完全一致・前方一致がない場合、エイリアス名に対するファジーマッチングを試行
+            if let Some(score) = self.fuzzy_score(&alias_lower, query_lower) {
+                return Some((score, MatchedField::Alias, MatchKind::Fuzzy));
+            }
+        }
+
+        // パスに対するマッチング
+        // フォルダ名と完全一致する場合はファジーマッチより優先する
+        if self.has_exact_path_component(&alias.path, query_lower) {
+            return Some((Self::EXACT_COMPONENT_SCORE, MatchedField::Path, MatchKind::Fuzzy));
+        }
+        let path_str = self.fold(&alias.path.to_string_lossy());
+        if let Some(score) = self.fuzzy_score(&path_str, query_lower) {
+            return Some((score, MatchedField::Path, MatchKind::Fuzzy));
+        }
+
+        // タグに対するファジーマッチング（最初にマッチしたもので十分）
+        if let Some(score) = alias.tags.iter().find_map(|tag| self.fuzzy_score(&self.fold(tag), query_lower)) {
+            return Some((score, MatchedField::Tag, MatchKind::Fuzzy));
+        }
+
+        // 読み・メモに対するマッチング（設定で有効な場合）
+        if let Some((score, matched_field)) = self.match_secondary_fields(alias, query_lower) {
+            return Some((score, matched_field, MatchKind::Fuzzy));
+        }
+
+        // 階層パス解析
+        let score = self.match_hierarchical_path(&alias.path, keywords?)?;
+        Some((score, MatchedField::Path, MatchKind::Hierarchical))
+    }
+
+    /// ファジーマッチの正規化済みスコア（0 以下は一致なしとみなす）
+    fn fuzzy_score(&self, target: &str, query_lower: &str) -> Option<f32> {
+        self.fuzzy_matcher
+            .fuzzy_match(target, query_lower)
+            .map(|score| self.normalize_fuzzy_score(score))
+            .filter(|&score| score > 0.0)
+    }
+
+    /// 走査の時間切れを判定（`BUDGET_CHECK_INTERVAL` 件ごとに経過時間を確認する）
+    fn budget_exceeded(deadline: Option<Instant>, index: usize) -> bool {
+        index > 0
+            && index.is_multiple_of(Self::BUDGET_CHECK_INTERVAL)
+            && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// 読み・メモに対するマッチング
+    ///
+    /// 読みは前方一致・ファジーマッチで判定し、スコアに `READING_WEIGHT` を掛けます。
+    /// メモは検索語を含む場合のみ `NOTE_SCORE` で一致とみなします。
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - 判定対象のエイリアス
+    /// * `query_lower` - 小文字化済みの検索クエリ
+    ///
+    /// # Returns
+    ///
+    /// (スコア, 一致したフィールド)、一致しない場合は None
+    fn match_secondary_fields(&self, alias: &FileAlias, query_lower: &str) -> Option<(f32, MatchedField)> {
+        if self.search_reading {
+            if let Some(reading) = alias.reading.as_deref().filter(|r| !r.is_empty()) {
+                let reading_lower = self.fold(reading);
+                let score = if reading_lower.starts_with(query_lower) {
+                    Some(0.8)
+                } else {
+                    self.fuzzy_score(&reading_lower, query_lower)
+                };
+                if let Some(score) = score {
+                    return Some((score * Self::READING_WEIGHT, MatchedField::Reading));
+                }
+            }
+        }
+
+        if self.search_notes {
+            if let Some(note) = alias.note.as_deref() {
+                if self.fold(note).contains(query_lower) {
+                    return Some((Self::NOTE_SCORE, MatchedField::Note));
                 }
             }
+        }
+
+        None
+    }
 
-            // 階層パス解析（完全一致・前方一致・ファジーマッチがない場合のみ）
-            if !matched && use_hierarchical {
-                if let Some(score) = self.match_hierarchical_path(&alias.path, &keywords) {
-                    hierarchical_results.push(SearchResult {
-                        alias: alias.clone(),
-                        score,
-                        matched_field: MatchedField::Path,
-                    });
+    /// すべてのキーワードがエイリアス名・パス・タグのいずれかに含まれるか判定
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - 判定対象のエイリアス
+    /// * `terms` - 小文字化済みのキーワード
+    ///
+    /// # Returns
+    ///
+    /// 全キーワードが含まれる場合は表示用の一致箇所（エイリアス名 → パス → タグの順）、
+    /// 含まれないキーワードがある場合は None
+    fn all_terms_field(&self, alias: &FileAlias, terms: &[String]) -> Option<MatchedField> {
+        let name = self.fold(&alias.alias);
+        let path = self.fold(&alias.path.to_string_lossy());
+        let tags: Vec<String> = alias.tags.iter().map(|t| self.fold(t)).collect();
+
+        let mut in_name = false;
+        let mut in_path = false;
+        for term in terms {
+            // 数値範囲はパスの階層だけで判定する
+            if let Some(range) = NumericRange::parse(term) {
+                if !path.split(['/', '\\']).any(|c| range.matches_component(c)) {
+                    return None;
                 }
+                in_path = true;
+                continue;
             }
+            let name_hit = name.contains(term.as_str());
+            let path_hit = path.contains(term.as_str());
+            if !name_hit && !path_hit && !tags.iter().any(|t| t.contains(term.as_str())) {
+                return None;
+            }
+            in_name |= name_hit;
+            in_path |= path_hit;
         }
 
-        // 完全一致・前方一致、ファジーマッチ、階層マッチの結果をマージ
-        results.extend(fuzzy_results);
-        results.extend(hierarchical_results);
+        Some(if in_name {
+            MatchedField::Alias
+        } else if in_path {
+            MatchedField::Path
+        } else {
+            MatchedField::Tag
+        })
+    }
 
+    /// 各結果のスコアを最終スコアに更新し、スコア順（降順）に並べる
+    fn finalize_results(&self, results: &mut [SearchResult]) {
         // 各 SearchResult の score を最終スコアに更新
-        for result in &mut results {
+        for result in results.iter_mut() {
             result.score = self.calculate_final_score(&result.alias, result.score);
         }
 
@@ -299,20 +1241,188 @@ impl SearchEngine {
         results.sort_by(|a, b| {
             b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
         });
+    }
 
-        // 検索結果の上限を適用
-        results.truncate(self.max_results);
+    /// クエリから `tag:xxx` 形式の絞り込みを取り出す
+    ///
+    /// 接頭辞の大文字小文字は問いません。`tag: xxx` のように値が空白の後にある場合は
+    /// 次の語を値とみなし、値のない `tag:` は無視します。
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 検索クエリ
+    ///
+    /// # Returns
+    ///
+    /// `tag:` を含む場合は正規化したタグ（raw モードでは前後の空白を除いただけのタグ）と
+    /// 残りの検索語、含まない場合は None
+    fn parse_query_filters(&self, query: &str) -> Option<TagFilterQuery> {
+        let mut tags: Vec<String> = Vec::new();
+        let mut keywords = Vec::new();
+        let mut has_tag_token = false;
+
+        let mut tokens = query.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            let Some(value) = Self::strip_tag_prefix(token) else {
+                keywords.push(token);
+                continue;
+            };
+            has_tag_token = true;
 
-        // キャッシュに保存（サイズ制限考慮）
-        if self.cache.len() >= self.max_cache_size {
-            // キャッシュサイズが上限に達したら、最も古いエントリを削除
-            // 簡易実装: 全クリア
-            self.cache.clear();
+            let value = if value.is_empty() {
+                match tokens.next_if(|next| Self::strip_tag_prefix(next).is_none()) {
+                    Some(next) => next,
+                    None => continue,
+                }
+            } else {
+                value
+            };
+            let tag = if self.raw_mode {
+                value.to_string()
+            } else {
+                Self::normalize_tag(value)
+            };
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        has_tag_token.then(|| TagFilterQuery {
+            tags,
+            keywords: keywords.join(" "),
+        })
+    }
+
+    /// 語が `tag:` で始まる場合、接頭辞を除いた値を返す
+    fn strip_tag_prefix(token: &str) -> Option<&str> {
+        let prefix = token.get(..Self::TAG_QUERY_PREFIX.len())?;
+        prefix
+            .eq_ignore_ascii_case(Self::TAG_QUERY_PREFIX)
+            .then(|| &token[Self::TAG_QUERY_PREFIX.len()..])
+    }
+
+    /// タグで絞り込んだ検索
+    ///
+    /// 検索語がない場合は、すべてのタグを持つエイリアスをスコア `EXACT_TAG_SCORE` で返します。
+    /// 検索語がある場合は通常の検索結果のうち、すべてのタグを持つものだけを返します。
+    fn compute_tag_filtered_results(&self, tag_query: &TagFilterQuery) -> (Vec<SearchResult>, bool) {
+        // 値のない `tag:` だけのクエリは絞り込みなし
+        if tag_query.tags.is_empty() {
+            if tag_query.keywords.is_empty() {
+                return (Vec::new(), false);
+            }
+            return self.compute_results(&tag_query.keywords);
+        }
+
+        let candidates = self.tag_filter_candidates(&tag_query.tags);
+        if tag_query.keywords.is_empty() {
+            let mut results: Vec<SearchResult> = candidates
+                .iter()
+                .map(|&i| SearchResult {
+                    alias: self.aliases[i].clone(),
+                    score: Self::EXACT_TAG_SCORE,
+                    matched_field: MatchedField::Tag,
+                    match_indices: Vec::new(),
+                })
+                .collect();
+            self.finalize_results(&mut results);
+            return (results, false);
+        }
+
+        let allowed: HashSet<&str> = candidates
+            .iter()
+            .map(|&i| self.aliases[i].id.as_str())
+            .collect();
+        let (mut results, truncated) = self.compute_results(&tag_query.keywords);
+        results.retain(|result| allowed.contains(result.alias.id.as_str()));
+        (results, truncated)
+    }
+
+    /// すべてのタグを持つエイリアスのインデックス（索引を引くだけで全件走査しない）
+    fn tag_filter_candidates(&self, tags: &[String]) -> Vec<usize> {
+        let mut candidates: Option<Vec<usize>> = None;
+        for tag in tags {
+            let matched = self.match_exact_tag(tag);
+            candidates = Some(match candidates {
+                Some(current) => current.into_iter().filter(|i| matched.contains(i)).collect(),
+                None => matched,
+            });
+        }
+        candidates.unwrap_or_default()
+    }
+
+    /// タグが完全一致するエイリアスのインデックス
+    ///
+    /// raw モードでは、索引で絞り込んだ後にタグをそのまま比較します。
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - `parse_query_filters` で取り出したタグ（空の場合は結果なし）
+    fn match_exact_tag(&self, tag: &str) -> Vec<usize> {
+        self.tag_index
+            .get(&Self::normalize_tag(tag))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(|&i| !self.raw_mode || self.aliases[i].tags.iter().any(|t| t.trim() == tag))
+            .collect()
+    }
+
+    /// ワイルドカード付きクエリから部分一致の検索語を取り出す
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - 検索クエリ
+    ///
+    /// # Returns
+    ///
+    /// `*term` または `*term*` の形式なら小文字化した `term`（raw モードではそのまま）、それ以外は None
+    fn parse_wildcard_query(&self, query: &str) -> Option<String> {
+        let rest = query.trim().strip_prefix('*')?;
+        let term = rest.strip_suffix('*').unwrap_or(rest).trim();
+        if term.is_empty() {
+            // "*" だけのクエリは全件一致になるため扱わない
+            return Some(String::new());
+        }
+        Some(self.fold(term))
+    }
+
+    /// エイリアス名・パス・タグに対する部分一致検索
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - 小文字化済みの検索語（空の場合は結果なし）
+    ///
+    /// # Returns
+    ///
+    /// スコア `SUBSTRING_SCORE` の検索結果（エイリアス名 → パス → タグの順に判定）
+    fn match_substring(&self, term: &str) -> Vec<SearchResult> {
+        if term.is_empty() {
+            return Vec::new();
         }
-        self.cache.insert(query.to_string(), results.clone());
-        self.last_query = Some(query.to_string());
 
-        results
+        self.aliases
+            .iter()
+            .filter_map(|alias| {
+                let matched_field = if self.fold(&alias.alias).contains(term) {
+                    MatchedField::Alias
+                } else if self.fold(&alias.path.to_string_lossy()).contains(term) {
+                    MatchedField::Path
+                } else if alias.tags.iter().any(|tag| self.fold(tag).contains(term)) {
+                    MatchedField::Tag
+                } else {
+                    return None;
+                };
+
+                Some(SearchResult {
+                    alias: alias.clone(),
+                    score: Self::SUBSTRING_SCORE,
+                    matched_field,
+                    match_indices: Vec::new(),
+                })
+            })
+            .collect()
     }
 
     /// ファジーマッチのスコアを0.0〜0.7の範囲に正規化
@@ -325,13 +1435,23 @@ impl SearchEngine {
     ///
     /// 0.0〜0.7の範囲に正規化された f32 のスコア
     fn normalize_fuzzy_score(&self, score: i64) -> f32 {
-        // fuzzy-matcher のスコアは通常、0〜100程度の範囲
-        // これを0.0〜0.7の範囲に正規化
-        const MAX_FUZZY_SCORE: f32 = 100.0;
-        const TARGET_MAX: f32 = 0.7;
+        normalize_fuzzy_score(score)
+    }
 
-        let normalized = (score as f32 / MAX_FUZZY_SCORE) * TARGET_MAX;
-        normalized.max(0.0).min(TARGET_MAX)
+    /// パスの階層のいずれかがクエリと完全一致するか判定
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - 判定対象のパス
+    /// * `query_lower` - 小文字化済みの検索クエリ
+    ///
+    /// # Returns
+    ///
+    /// いずれかの階層名（大文字小文字を区別しない）がクエリと等しい場合 true
+    fn has_exact_path_component(&self, path: &Path, query_lower: &str) -> bool {
+        path.to_string_lossy()
+            .split(['/', '\\'])
+            .any(|component| self.fold(component) == query_lower)
     }
 
     /// クエリを階層キーワードに分割
@@ -359,6 +1479,9 @@ impl SearchEngine {
 
     /// パスの階層でキーワードをマッチング
     ///
+    /// `06..09` のような `a..b` 形式のキーワードは数値の範囲として判定します（`NumericRange`）。
+    /// 範囲に一致する階層がない場合は、他のキーワードが一致していても一致なしとします。
+    ///
     /// # Arguments
     ///
     /// * `path` - マッチング対象のパス
@@ -386,8 +1509,8 @@ impl SearchEngine {
         // パスを階層に分割（/ または \ で分割）
         let path_str = path.to_string_lossy();
         let components: Vec<String> = path_str
-            .split(|c| c == '/' || c == '\\')
-            .map(|s| s.to_lowercase())
+            .split(['/', '\\'])
+            .map(|s| self.fold(s))
             .collect();
 
         if components.is_empty() {
@@ -397,15 +1520,19 @@ impl SearchEngine {
         // 各キーワードが階層のどこかにマッチするかチェック
         let mut matched_count = 0;
         for keyword in keywords {
-            let keyword_lower = keyword.to_lowercase();
-            let mut found = false;
-
-            for component in &components {
-                if component.contains(&keyword_lower) {
-                    found = true;
-                    break;
+            // `a..b` は数値の範囲として判定し、範囲外なら他のキーワードに関係なく一致しない
+            let found = match NumericRange::parse(keyword) {
+                Some(range) => {
+                    if !components.iter().any(|component| range.matches_component(component)) {
+                        return None;
+                    }
+                    true
                 }
-            }
+                None => {
+                    let keyword_lower = self.fold(keyword);
+                    components.iter().any(|component| component.contains(&keyword_lower))
+                }
+            };
 
             if found {
                 matched_count += 1;
@@ -438,6 +1565,7 @@ impl Default for SearchEngine {
 mod tests {
     use super::*;
     use chrono::Utc;
+    use crate::data::models::OpenAction;
     use std::path::PathBuf;
 
     fn create_test_alias(alias: &str, path: &str) -> FileAlias {
@@ -453,6 +1581,14 @@ mod tests {
             created_at: now,
             last_accessed: now - Duration::days(100),
             is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         }
     }
 
@@ -612,6 +1748,54 @@ mod tests {
         assert_eq!(results1[0].score, results2[0].score);
     }
 
+    #[test]
+    fn test_cache_evicts_least_recently_used_query() {
+        let aliases = vec![
+            create_test_alias("alpha", "/a"),
+            create_test_alias("beta", "/b"),
+            create_test_alias("gamma", "/c"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+        engine.max_cache_size = 2;
+
+        engine.search("alpha");
+        engine.search("beta");
+        engine.search("gamma");
+
+        // 上限を超えたら最も古い alpha だけが消える
+        assert_eq!(engine.cache.len(), 2);
+        assert!(!engine.cache.contains_key("alpha"));
+        assert!(engine.cache.contains_key("beta"));
+        assert!(engine.cache.contains_key("gamma"));
+
+        engine.search("beta");
+        assert!(engine.last_from_cache());
+        engine.search("alpha");
+        assert!(!engine.last_from_cache());
+    }
+
+    #[test]
+    fn test_cache_hit_marks_query_as_recently_used() {
+        let aliases = vec![
+            create_test_alias("alpha", "/a"),
+            create_test_alias("beta", "/b"),
+            create_test_alias("gamma", "/c"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+        engine.max_cache_size = 2;
+
+        engine.search("alpha");
+        engine.search("beta");
+        // alpha を使い直すと、次に追い出されるのは beta
+        engine.search("alpha");
+        assert!(engine.last_from_cache());
+        engine.search("gamma");
+
+        assert!(engine.cache.contains_key("alpha"));
+        assert!(!engine.cache.contains_key("beta"));
+        assert_eq!(engine.cache_order, VecDeque::from(["alpha".to_string(), "gamma".to_string()]));
+    }
+
     #[test]
     fn test_cache_invalidation() {
         let aliases = vec![
@@ -631,6 +1815,48 @@ mod tests {
         assert_eq!(engine.last_query(), None);
     }
 
+    #[test]
+    fn test_last_from_cache() {
+        let aliases = vec![
+            create_test_alias("test", "/path/to/test"),
+            create_test_alias("other", "/path/to/other"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+        assert!(!engine.last_from_cache());
+
+        // 初回は新たに検索する
+        engine.search("test");
+        assert!(!engine.last_from_cache());
+
+        // 同じクエリはキャッシュから返す
+        engine.search("test");
+        assert!(engine.last_from_cache());
+
+        // 別のクエリは新たに検索する
+        engine.search("other");
+        assert!(!engine.last_from_cache());
+
+        // キャッシュ済みのクエリに戻るとキャッシュから返す
+        engine.search("test");
+        assert!(engine.last_from_cache());
+    }
+
+    #[test]
+    fn test_last_from_cache_after_invalidation() {
+        let mut engine = SearchEngine::with_aliases(vec![
+            create_test_alias("test", "/path/to/test"),
+        ]);
+        engine.search("test");
+        engine.search("test");
+        assert!(engine.last_from_cache());
+
+        // エイリアスの変更でキャッシュが消えると、同じクエリでも新たに検索する
+        engine.set_aliases(vec![create_test_alias("test", "/path/to/test")]);
+        assert!(!engine.last_from_cache());
+        engine.search("test");
+        assert!(!engine.last_from_cache());
+    }
+
     #[test]
     fn test_no_match() {
         let aliases = vec![
@@ -1058,6 +2284,61 @@ mod tests {
         assert_eq!(results[0].score, 0.9);
     }
 
+    #[test]
+    fn test_numeric_range_parse() {
+        assert_eq!(NumericRange::parse("06..09"), Some(NumericRange { start: 6, end: 9, width: 2 }));
+        // 下限と上限が逆でもよい
+        assert_eq!(NumericRange::parse("12..3"), Some(NumericRange { start: 3, end: 12, width: 2 }));
+        // 範囲でない語はそのまま
+        assert_eq!(NumericRange::parse("202506"), None);
+        assert_eq!(NumericRange::parse("06.."), None);
+        assert_eq!(NumericRange::parse("a..b"), None);
+        assert_eq!(NumericRange::parse("v1.2..3"), None);
+    }
+
+    #[test]
+    fn test_match_hierarchical_path_numeric_range() {
+        let engine = SearchEngine::new();
+        let keywords = vec!["2025".to_string(), "06..09".to_string()];
+
+        for month in ["202506", "202507", "202509"] {
+            let path = format!("/経理/試算表/{}", month);
+            assert_eq!(engine.match_hierarchical_path(Path::new(&path), &keywords), Some(0.9), "{}", month);
+        }
+        // 範囲外の月は他のキーワードが一致しても除外する
+        for month in ["202505", "202510", "202412"] {
+            let path = format!("/経理/試算表/{}", month);
+            assert_eq!(engine.match_hierarchical_path(Path::new(&path), &keywords), None, "{}", month);
+        }
+        // 月だけのフォルダ名や数字以外を含むフォルダ名も数字の並びで判定する
+        assert!(engine.match_hierarchical_path(Path::new("/2025年度/08月"), &keywords).is_some());
+        assert!(engine.match_hierarchical_path(Path::new("/2025年度/11月"), &keywords).is_none());
+    }
+
+    #[test]
+    fn test_numeric_range_in_search() {
+        let mut engine = SearchEngine::with_aliases(vec![
+            create_test_alias("6月", "/経理/試算表/202506"),
+            create_test_alias("9月", "/経理/試算表/202509"),
+            create_test_alias("10月", "/経理/試算表/202510"),
+            create_test_alias("前年", "/経理/試算表/202407"),
+        ]);
+
+        let names = |results: Vec<SearchResult>| {
+            let mut names: Vec<String> = results.into_iter().map(|r| r.alias.alias).collect();
+            names.sort();
+            names
+        };
+        // 範囲外の月は除外し、範囲内でも年が一致しないものは部分一致として後ろに並ぶ
+        let results = engine.search("2025 06..09");
+        assert_eq!(results.last().unwrap().alias.alias, "前年");
+        assert_eq!(names(results), vec!["6月", "9月", "前年"]);
+
+        // AND 検索ではすべてのキーワードが一致するものだけ
+        engine.set_match_all_terms(true);
+        assert_eq!(names(engine.search("2025 06..09")), vec!["6月", "9月"]);
+    }
+
     #[test]
     fn test_hierarchical_match_requires_multiple_keywords() {
         // 階層マッチは2つ以上のキーワードが必要
@@ -1364,28 +2645,969 @@ mod tests {
         assert!(results.len() <= 20);
     }
 
-    // テストデータ生成関数（ベンチマークと同じ）
-    fn generate_test_data(count: usize) -> Vec<FileAlias> {
-        let mut aliases = Vec::new();
+    #[test]
+    fn test_search_with_limit() {
+        let aliases = generate_test_data(200);
+        let mut engine = SearchEngine::with_aliases(aliases);
 
-        for i in 0..count {
-            let alias = match i % 5 {
-                0 => format!("config_{}", i),
-                1 => format!("document_{}", i),
-                2 => format!("report_{}", i),
-                3 => format!("試算表_{}", i),
-                _ => format!("file_{}", i),
-            };
+        let results = engine.search_with_limit("config", 5);
+        assert_eq!(results.len(), 5);
 
-            let path = match i % 3 {
-                0 => format!("C:/Documents/{}/file.txt", i),
-                1 => format!("C:/Projects/{}/code.rs", i),
-                _ => format!("C:/Data/{}/data.xlsx", i),
-            };
+        // エンジンの設定は変更されない
+        assert_eq!(engine.max_results(), 100);
+    }
 
-            aliases.push(create_test_alias(&alias, &path));
+    #[test]
+    fn test_search_with_limit_slices_cached_results() {
+        let aliases = generate_test_data(200);
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 全件を取得してキャッシュさせる
+        let full = engine.search("config");
+        assert_eq!(full.len(), 40);
+
+        // 同じクエリを異なる上限で取得（キャッシュから切り出し）
+        let top3 = engine.search_with_limit("config", 3);
+        let top10 = engine.search_with_limit("config", 10);
+        assert_eq!(top3.len(), 3);
+        assert_eq!(top10.len(), 10);
+        assert_eq!(engine.last_query(), Some("config"));
+
+        // 先頭の並びは全件の結果と一致する
+        for (limited, original) in top10.iter().zip(full.iter()) {
+            assert_eq!(limited.alias.id, original.alias.id);
+            assert_eq!(limited.score, original.score);
+        }
+        for (limited, original) in top3.iter().zip(top10.iter()) {
+            assert_eq!(limited.alias.id, original.alias.id);
         }
+    }
 
-        aliases
+    #[test]
+    fn test_search_with_small_limit_does_not_truncate_cache() {
+        let aliases = generate_test_data(200);
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 小さい上限で最初に検索しても、後から大きい上限で全件取得できる
+        let first = engine.search_with_limit("config", 2);
+        assert_eq!(first.len(), 2);
+
+        let larger = engine.search_with_limit("config", 50);
+        assert_eq!(larger.len(), 40);
+    }
+
+    #[test]
+    fn test_exact_path_component_outranks_fuzzy_path() {
+        let aliases = vec![
+            // パスの途中に "会計" と部分的に似た文字列があるだけ
+            create_test_alias("fuzzy", "C:/2025年度/会計資料一覧/fuzzy.xlsx"),
+            // フォルダ名が "会計" と完全一致
+            create_test_alias("exact", "C:/2025年度/会計/試算表.xlsx"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        let results = engine.search("会計");
+        assert_eq!(results.len(), 2);
+
+        // 完全一致した階層を持つパスが上位に来ること
+        assert_eq!(results[0].alias.alias, "exact");
+        assert_eq!(results[0].matched_field, MatchedField::Path);
+        assert_eq!(results[0].score, 0.75);
+
+        // ファジーマッチは0.7以下
+        assert_eq!(results[1].alias.alias, "fuzzy");
+        assert!(results[1].score <= 0.7);
+    }
+
+    #[test]
+    fn test_exact_path_component_below_prefix_match() {
+        let aliases = vec![
+            create_test_alias("projects_old", "C:/archive/projects_old"),
+            create_test_alias("code", "C:/Work/Projects/code.rs"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        let results = engine.search("projects");
+        assert_eq!(results.len(), 2);
+
+        // エイリアスの前方一致（0.8）が階層完全一致（0.75）より上位
+        assert_eq!(results[0].alias.alias, "projects_old");
+        assert_eq!(results[0].score, 0.8);
+        // 大文字小文字を区別しない
+        assert_eq!(results[1].alias.alias, "code");
+        assert_eq!(results[1].score, 0.75);
+    }
+
+    #[test]
+    fn test_has_exact_path_component() {
+        let engine = SearchEngine::new();
+        assert!(engine.has_exact_path_component(Path::new("C:/2025年度/会計/試算表"), "会計"));
+        assert!(engine.has_exact_path_component(Path::new("C:\\Users\\Docs"), "docs"));
+        assert!(!engine.has_exact_path_component(Path::new("C:/2025年度/会計資料"), "会計"));
+    }
+
+    #[test]
+    fn test_wildcard_substring_match() {
+        let aliases = vec![
+            create_test_alias("configure", "/path/to/configure"),
+            create_test_alias("fxixgx", "/path/to/other"),
+            create_test_alias("test", "/path/to/test"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        let results = engine.search("*fig*");
+
+        // 部分一致で "configure" がヒットし、ファジーマッチより高いスコアになる
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "configure");
+        assert_eq!(results[0].matched_field, MatchedField::Alias);
+        assert_eq!(results[0].score, 0.75);
+
+        // 通常のクエリではファジーマッチ（0.7以下）
+        let fuzzy = engine.search("fig");
+        let fuzzy_configure = fuzzy.iter().find(|r| r.alias.alias == "configure").unwrap();
+        assert!(fuzzy_configure.score <= 0.7);
+        assert!(results[0].score > fuzzy_configure.score);
+        // ファジーマッチでは部分列でもヒットするが、部分一致ではヒットしない
+        assert!(fuzzy.iter().any(|r| r.alias.alias == "fxixgx"));
+    }
+
+    #[test]
+    fn test_wildcard_leading_only() {
+        let aliases = vec![
+            create_test_alias("configure", "/path/to/configure"),
+            create_test_alias("report", "/data/settings/report.xlsx"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 末尾の * は省略可能
+        let results = engine.search("*FIG");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "configure");
+
+        // パスに対する部分一致
+        let results = engine.search("*settings");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "report");
+        assert_eq!(results[0].matched_field, MatchedField::Path);
+    }
+
+    #[test]
+    fn test_wildcard_below_prefix_match() {
+        let aliases = vec![
+            create_test_alias("figure", "/path/to/figure"),
+            create_test_alias("configure", "/path/to/configure"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 通常検索の前方一致（0.8）は部分一致（0.75）より上位
+        let prefix = engine.search("fig");
+        let substring = engine.search("*fig*");
+        assert_eq!(prefix[0].alias.alias, "figure");
+        assert!(prefix[0].score > substring.iter().find(|r| r.alias.alias == "configure").unwrap().score);
+    }
+
+    #[test]
+    fn test_wildcard_only_returns_empty() {
+        let aliases = vec![create_test_alias("configure", "/path/to/configure")];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        assert!(engine.search("*").is_empty());
+        assert!(engine.search("**").is_empty());
+    }
+
+    // テストデータ生成関数（ベンチマークと同じ）
+    fn generate_test_data(count: usize) -> Vec<FileAlias> {
+        let mut aliases = Vec::new();
+
+        for i in 0..count {
+            let alias = match i % 5 {
+                0 => format!("config_{}", i),
+                1 => format!("document_{}", i),
+                2 => format!("report_{}", i),
+                3 => format!("試算表_{}", i),
+                _ => format!("file_{}", i),
+            };
+
+            let path = match i % 3 {
+                0 => format!("C:/Documents/{}/file.txt", i),
+                1 => format!("C:/Projects/{}/code.rs", i),
+                _ => format!("C:/Data/{}/data.xlsx", i),
+            };
+
+            aliases.push(create_test_alias(&alias, &path));
+        }
+
+        aliases
+    }
+
+    /// 索引を使わない検索結果（比較用）
+    fn search_without_index(aliases: &[FileAlias], query: &str) -> Vec<(String, MatchedField)> {
+        let mut engine = SearchEngine::with_aliases(aliases.to_vec());
+        engine.name_index = None;
+        engine.search(query).into_iter().map(|r| (r.alias.id, r.matched_field)).collect()
+    }
+
+    #[test]
+    fn test_name_index_matches_unindexed_search() {
+        let mut aliases = vec![
+            create_test_alias("report", "/work/docs/report.xlsx"),
+            create_test_alias("Reports2024", "/archive/2024"),
+            create_test_alias("試算表", "C:/2025年度/会計/試算表/202506"),
+            create_test_alias("music", "/home/user/Music"),
+            create_test_alias("prep", "/tmp/repo"),
+            create_test_alias("budget", "/finance/budget.xlsx"),
+        ];
+        aliases[3].tags = vec!["audio".to_string()];
+
+        let mut engine = SearchEngine::with_aliases(aliases.clone());
+        for query in ["report", "rep", "r", "試算", "202506", "audio", "xlsx", "mu", "試算表 202506", "zzz"] {
+            let indexed: Vec<(String, MatchedField)> = engine
+                .search(query)
+                .into_iter()
+                .map(|r| (r.alias.id, r.matched_field))
+                .collect();
+            assert_eq!(indexed, search_without_index(&aliases, query), "query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_name_index_buckets_by_contained_chars() {
+        let aliases = vec![
+            create_test_alias("Alpha", "/a"),
+            create_test_alias("beta", "/b"),
+        ];
+        let index = SearchEngine::build_name_index(&aliases);
+
+        // 小文字化した文字で分類され、同じ文字は1回だけ登録される
+        assert_eq!(index.get(&'a'), Some(&vec![0, 1]));
+        assert_eq!(index.get(&'l'), Some(&vec![0]));
+        assert_eq!(index.get(&'b'), Some(&vec![1]));
+        assert!(!index.contains_key(&'A'));
+    }
+
+    #[test]
+    fn test_name_candidates_skip_names_without_leading_char() {
+        let aliases = vec![
+            create_test_alias("docs", "/x"),
+            create_test_alias("music", "/y"),
+        ];
+        let engine = SearchEngine::with_aliases(aliases);
+
+        assert_eq!(engine.name_candidates("do"), vec![true, false]);
+        assert_eq!(engine.name_candidates("z"), vec![false, false]);
+    }
+
+    #[test]
+    fn test_set_aliases_rebuilds_name_index() {
+        let mut engine = SearchEngine::new();
+        engine.set_aliases(vec![create_test_alias("zeta", "/z")]);
+
+        let results = engine.search("zeta");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_field, MatchedField::Alias);
+    }
+
+    /// タグの完全一致を全件走査で求める（比較用）
+    fn brute_force_tag_ids(aliases: &[FileAlias], tag: &str) -> Vec<String> {
+        let mut ids: Vec<String> = aliases
+            .iter()
+            .filter(|a| a.tags.iter().any(|t| t.trim().to_lowercase() == tag))
+            .map(|a| a.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_tag_query_matches_brute_force_scan() {
+        let mut aliases = generate_test_data(60);
+        for (i, alias) in aliases.iter_mut().enumerate() {
+            alias.tags = match i % 4 {
+                0 => vec!["work".to_string()],
+                1 => vec!["Work ".to_string(), "urgent".to_string()],
+                2 => vec!["homework".to_string()],
+                _ => vec![],
+            };
+        }
+
+        let mut engine = SearchEngine::with_aliases(aliases.clone());
+        engine.set_max_results(1000);
+        for tag in ["work", "urgent", "homework", "wor", "none"] {
+            let mut indexed: Vec<String> = engine
+                .search(&format!("tag:{}", tag))
+                .into_iter()
+                .map(|r| r.alias.id)
+                .collect();
+            indexed.sort();
+            assert_eq!(indexed, brute_force_tag_ids(&aliases, tag), "tag: {}", tag);
+        }
+    }
+
+    #[test]
+    fn test_tag_query_is_exact_and_case_insensitive() {
+        let mut work = create_test_alias("report", "/docs/report");
+        work.tags = vec!["Work".to_string()];
+        let mut homework = create_test_alias("math", "/school/math");
+        homework.tags = vec!["homework".to_string()];
+        let mut engine = SearchEngine::with_aliases(vec![work, homework]);
+
+        let results = engine.search("TAG: work ");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "report");
+        assert_eq!(results[0].matched_field, MatchedField::Tag);
+
+        // 接頭辞のないクエリは従来どおりタグもファジーマッチする
+        let fuzzy = engine.search("homewrk");
+        assert!(fuzzy.iter().any(|r| r.alias.alias == "math" && r.matched_field == MatchedField::Tag));
+
+        assert!(engine.search("tag:").is_empty());
+    }
+
+    #[test]
+    fn test_name_match_score_uses_alias_name_rules() {
+        let matcher = SkimMatcherV2::default();
+
+        assert_eq!(name_match_score(&matcher, "Report.PDF", "report.pdf"), Some(1.0));
+        assert_eq!(name_match_score(&matcher, "report_2025.xlsx", "REPORT"), Some(0.8));
+        let fuzzy = name_match_score(&matcher, "monthly_report.xlsx", "mrpt").unwrap();
+        assert!(fuzzy > 0.0 && fuzzy <= 0.7);
+        assert_eq!(name_match_score(&matcher, "invoice.pdf", "zzz"), None);
+    }
+
+    #[test]
+    fn test_match_indices_within_matched_text() {
+        let mut engine = SearchEngine::with_aliases(vec![
+            create_test_alias("Report", "/docs/a"),
+            create_test_alias("monthly_report", "/docs/b"),
+            create_test_alias("月次試算表", "/docs/c"),
+            create_test_alias("memo", "/work/Invoices/2025"),
+        ]);
+        let indices_of = |results: &[SearchResult], alias: &str| {
+            results.iter().find(|r| r.alias.alias == alias).unwrap().match_indices.clone()
+        };
+
+        // 完全一致・前方一致は一致した範囲をすべて含む
+        let results = engine.search("report");
+        assert_eq!(indices_of(&results, "Report"), vec![0, 1, 2, 3, 4, 5]);
+        let results = engine.search("mon");
+        assert_eq!(indices_of(&results, "monthly_report"), vec![0, 1, 2]);
+
+        // ファジーマッチは一致した文字だけ
+        let results = engine.search("mrpt");
+        let indices = indices_of(&results, "monthly_report");
+        assert_eq!(indices.len(), 4);
+        assert_eq!(indices[0], 0);
+
+        // マルチバイト文字はバイト位置で返す
+        let results = engine.search("試算");
+        assert_eq!(indices_of(&results, "月次試算表"), vec![6, 9]);
+
+        // パスで一致した場合はパスの文字列に対する位置
+        let results = engine.search("invoices");
+        let result = results.iter().find(|r| r.alias.alias == "memo").unwrap();
+        assert_eq!(result.matched_field, MatchedField::Path);
+        let path = result.alias.path.to_string_lossy();
+        let start = path.find("Invoices").unwrap();
+        assert_eq!(result.match_indices, (start..start + "Invoices".len()).collect::<Vec<_>>());
+
+        // すべての位置が元の文字列の範囲内の文字境界にある
+        for query in ["report", "mrpt", "試算", "invoices", "*port*", "docs 2025"] {
+            for result in engine.search(query) {
+                let text = match result.matched_field {
+                    MatchedField::Alias => result.alias.alias.clone(),
+                    MatchedField::Path => result.alias.path.to_string_lossy().to_string(),
+                    _ => continue,
+                };
+                assert!(!result.match_indices.is_empty(), "{} / {}", query, text);
+                for &i in &result.match_indices {
+                    assert!(i < text.len() && text.is_char_boundary(i), "{} / {} / {}", query, text, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_query_filters_extracts_tags() {
+        let engine = SearchEngine::new();
+        let parsed = |query: &str| engine.parse_query_filters(query);
+
+        // tag: の語を取り出し、残りの語を通常の検索語としてつなぐ
+        let query = parsed("tag:work 試算表").unwrap();
+        assert_eq!(query.tags, vec!["work"]);
+        assert_eq!(query.keywords, "試算表");
+
+        // 複数指定・位置・接頭辞の大文字小文字・値が次の語にある形式
+        let query = parsed("月次  TAG:Work 試算表 tag: 2025 tag:work").unwrap();
+        assert_eq!(query.tags, vec!["work", "2025"]);
+        assert_eq!(query.keywords, "月次 試算表");
+
+        // 値のない tag: は絞り込みにならない
+        let query = parsed("試算表 tag:").unwrap();
+        assert!(query.tags.is_empty());
+        assert_eq!(query.keywords, "試算表");
+
+        // tag: を含まないクエリ・語の途中の tag: は対象外
+        assert_eq!(parsed("試算表"), None);
+        assert_eq!(parsed("mytag:work"), None);
+    }
+
+    fn tagged_alias(alias: &str, path: &str, tags: &[&str]) -> FileAlias {
+        let mut alias = create_test_alias(alias, path);
+        alias.tags = tags.iter().map(|t| t.to_string()).collect();
+        alias
+    }
+
+    #[test]
+    fn test_tag_filter_with_keywords() {
+        let mut engine = SearchEngine::with_aliases(vec![
+            tagged_alias("report", "/work/report.pdf", &["Work", "2025"]),
+            tagged_alias("report_old", "/work/report_old.pdf", &["work", "2024"]),
+            tagged_alias("report_home", "/home/report.pdf", &["home"]),
+            tagged_alias("invoice", "/work/invoice.pdf", &["work", "invoice"]),
+        ]);
+
+        // タグで絞り込んだ上で、残りの検索語を名前・パスに対して判定する
+        let names = |results: Vec<SearchResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.alias.alias).collect()
+        };
+        assert_eq!(names(engine.search("tag:work report")), vec!["report", "report_old"]);
+
+        // 複数の tag: はすべてを満たすものに絞り込む（大文字小文字は問わない）
+        assert_eq!(names(engine.search("TAG:WORK report Tag:2025")), vec!["report"]);
+        assert_eq!(names(engine.search("tag:work tag:invoice")), vec!["invoice"]);
+        assert!(engine.search("tag:home tag:work").is_empty());
+
+        // 値のない tag: は無視する
+        assert_eq!(engine.search("report tag:").len(), engine.search("report").len());
+
+        // 結果の上限も適用される
+        engine.set_max_results(1);
+        assert_eq!(engine.search("tag:work report").len(), 1);
+    }
+
+    #[test]
+    fn test_tag_filter_scores_go_through_scorer() {
+        let mut favorite = tagged_alias("report_a", "/a/report.pdf", &["work"]);
+        favorite.is_favorite = true;
+        let plain = tagged_alias("report_b", "/b/report.pdf", &["work"]);
+        let mut engine = SearchEngine::with_aliases(vec![plain, favorite]);
+
+        let results = engine.search("tag:work report");
+        assert_eq!(results[0].alias.alias, "report_a");
+        assert!((results[0].score - (0.8 + 0.2)).abs() < 1e-6);
+        assert!((results[1].score - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tag_filter_cache_key_includes_tags() {
+        let mut engine = SearchEngine::with_aliases(vec![
+            tagged_alias("report", "/work/report.pdf", &["work"]),
+            tagged_alias("report_home", "/home/report.pdf", &["home"]),
+        ]);
+
+        assert_eq!(engine.search("tag:work report").len(), 1);
+        // 語順・空白・大文字小文字が違っても同じ絞り込みならキャッシュを使う
+        assert_eq!(engine.search("report  TAG:Work").len(), 1);
+        assert!(engine.last_from_cache());
+        assert_eq!(engine.last_query(), Some("report  TAG:Work"));
+
+        // 絞り込みが違えば別のキャッシュ
+        let results = engine.search("tag:home report");
+        assert!(!engine.last_from_cache());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "report_home");
+        engine.search("report");
+        assert!(!engine.last_from_cache());
+    }
+
+    #[test]
+    fn test_tag_filter_with_hierarchical_path() {
+        let mut engine = SearchEngine::with_aliases(vec![
+            tagged_alias("a1", "/projects/docs/2025/report.txt", &["work"]),
+            tagged_alias("a2", "/projects/docs/old/report.txt", &["archive"]),
+        ]);
+
+        // 絞り込みなしでは両方が階層パスに一致する
+        assert_eq!(engine.search("docs report").len(), 2);
+
+        let results = engine.search("tag:work docs report");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "a1");
+        assert_eq!(results[0].matched_field, MatchedField::Path);
+    }
+
+    #[test]
+    fn test_tag_index_registers_alias_once() {
+        let mut alias = create_test_alias("a", "/a");
+        alias.tags = vec!["x".to_string(), "X".to_string()];
+        let index = SearchEngine::build_tag_index(&[alias]);
+
+        assert_eq!(index.get("x"), Some(&vec![0]));
+    }
+
+    #[test]
+    fn test_stale_penalty_applies_past_threshold() {
+        let mut engine = SearchEngine::new();
+        engine.set_stale_penalty(Some(StalePenalty { threshold_days: 180, penalty: 0.3 }));
+
+        let mut alias = create_test_alias("old", "/old");
+        alias.last_accessed = Utc::now() - Duration::days(181);
+
+        let final_score = engine.calculate_final_score(&alias, 0.8);
+        assert!((final_score - 0.5).abs() < 0.001);
+
+        // 減点で0未満にはならない
+        assert_eq!(engine.calculate_final_score(&alias, 0.1), 0.0);
+    }
+
+    #[test]
+    fn test_stale_penalty_not_applied_before_threshold() {
+        let mut engine = SearchEngine::new();
+        engine.set_stale_penalty(Some(StalePenalty { threshold_days: 180, penalty: 0.3 }));
+
+        let mut alias = create_test_alias("recent", "/recent");
+        alias.last_accessed = Utc::now() - Duration::days(179);
+
+        let final_score = engine.calculate_final_score(&alias, 0.8);
+        assert!((final_score - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_stale_penalty_disabled_by_default() {
+        let engine = SearchEngine::new();
+        assert!(engine.stale_penalty().is_none());
+
+        let mut alias = create_test_alias("old", "/old");
+        alias.last_accessed = Utc::now() - Duration::days(1000);
+
+        let final_score = engine.calculate_final_score(&alias, 0.8);
+        assert!((final_score - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_stale_penalty_pushes_unused_alias_down() {
+        let mut stale = create_test_alias("report_old", "/a");
+        stale.last_accessed = Utc::now() - Duration::days(365);
+        let mut used = create_test_alias("report_new", "/b");
+        used.last_accessed = Utc::now() - Duration::days(60);
+
+        let mut engine = SearchEngine::with_aliases(vec![stale, used]);
+        engine.set_stale_penalty(Some(StalePenalty { threshold_days: 180, penalty: 0.2 }));
+
+        let results = engine.search("report");
+        assert_eq!(results[0].alias.alias, "report_new");
+        assert_eq!(results[1].alias.alias, "report_old");
+    }
+
+    #[test]
+    fn test_recency_config_defaults_match_previous_tiers() {
+        let recency = RecencyConfig::default();
+        assert_eq!(recency.near_days, 7);
+        assert_eq!(recency.mid_days, 30);
+        assert_eq!(SearchEngine::new().recency_config(), recency);
+
+        let weights = ScoringWeights::default();
+        assert_eq!(weights.recency_7d, 0.1);
+        assert_eq!(weights.recency_30d, 0.05);
+    }
+
+    #[test]
+    fn test_custom_recency_boundaries_change_tier() {
+        let mut alias = create_test_alias("test", "/path/to/test");
+        alias.last_accessed = Utc::now() - Duration::days(20);
+
+        // 既定値では20日前は「やや最近」
+        let mut engine = SearchEngine::new();
+        assert_eq!(engine.calculate_final_score(&alias, 0.5), 0.55);
+
+        // 月次の締めに合わせて「最近」を31日に広げる
+        engine.set_recency_config(RecencyConfig {
+            near_days: 31,
+            mid_days: 92,
+        });
+        assert_eq!(engine.calculate_final_score(&alias, 0.5), 0.6);
+
+        // 100日前は四半期（92日）も過ぎているので加点なし
+        alias.last_accessed = Utc::now() - Duration::days(100);
+        assert_eq!(engine.calculate_final_score(&alias, 0.5), 0.5);
+
+        // 「最近」を短くすると20日前は加点なし
+        engine.set_recency_config(RecencyConfig {
+            near_days: 3,
+            mid_days: 14,
+        });
+        alias.last_accessed = Utc::now() - Duration::days(20);
+        assert_eq!(engine.calculate_final_score(&alias, 0.5), 0.5);
+    }
+
+    #[test]
+    fn test_scoring_weights_default_and_custom() {
+        let mut favorite = create_test_alias("test", "/path/to/test");
+        favorite.is_favorite = true;
+        favorite.last_accessed = Utc::now() - Duration::days(3);
+
+        // 既定値は従来どおり（お気に入り +0.2、7日以内 +0.1）
+        let engine = SearchEngine::new();
+        assert_eq!(engine.weights(), ScoringWeights::default());
+        assert!((engine.calculate_final_score(&favorite, 0.5) - 0.8).abs() < 1e-6);
+
+        // よく使うエイリアスを強く優先し、上限も広げる
+        let weights = ScoringWeights {
+            favorite_boost: 1.0,
+            recency_7d: 0.3,
+            recency_30d: 0.2,
+            max_score: 3.0,
+        };
+        let mut engine = SearchEngine::with_weights(weights);
+        assert!((engine.calculate_final_score(&favorite, 0.9) - 2.2).abs() < 1e-6);
+
+        // 「やや最近」の加点も重み付けから取る
+        favorite.last_accessed = Utc::now() - Duration::days(20);
+        assert!((engine.calculate_final_score(&favorite, 0.5) - 1.7).abs() < 1e-6);
+
+        // 日数の区切りを変えても加点は重み付けのまま
+        engine.set_recency_config(RecencyConfig { near_days: 31, ..RecencyConfig::default() });
+        assert_eq!(engine.recency_config().near_days, 31);
+        assert_eq!(engine.weights(), weights);
+        assert!((engine.calculate_final_score(&favorite, 0.5) - 1.8).abs() < 1e-6);
+
+        // 上限で頭打ちになる
+        engine.set_weights(ScoringWeights { max_score: 1.0, ..weights });
+        assert_eq!(engine.calculate_final_score(&favorite, 0.5), 1.0);
+    }
+
+    #[test]
+    fn test_set_weights_clears_cache() {
+        let mut engine = SearchEngine::with_aliases(vec![create_test_alias("report", "/report")]);
+        engine.search("report");
+        assert!(!engine.cache.is_empty());
+
+        // 同じ重み付けではキャッシュを残す
+        engine.set_weights(ScoringWeights::default());
+        assert!(!engine.cache.is_empty());
+
+        engine.set_weights(ScoringWeights { favorite_boost: 0.5, ..ScoringWeights::default() });
+        assert!(engine.cache.is_empty());
+    }
+
+    #[test]
+    fn test_set_recency_config_clears_cache() {
+        let mut engine = SearchEngine::with_aliases(vec![create_test_alias("report", "/report")]);
+        engine.search("report");
+        assert!(!engine.cache.is_empty());
+
+        engine.set_recency_config(RecencyConfig { near_days: 1, ..RecencyConfig::default() });
+        assert!(engine.cache.is_empty());
+    }
+
+    #[test]
+    fn test_future_last_accessed_gets_at_most_near_boost() {
+        let engine = SearchEngine::new();
+        let mut alias = create_test_alias("test", "/path/to/test");
+
+        alias.last_accessed = Utc::now() + Duration::days(365);
+        let future_score = engine.calculate_final_score(&alias, 0.5);
+
+        alias.last_accessed = Utc::now();
+        let now_score = engine.calculate_final_score(&alias, 0.5);
+
+        assert_eq!(future_score, now_score);
+        assert_eq!(future_score, 0.6);
+
+        // 「最近」の段階を無効にしても未来の日時は減点・加点の対象外
+        let mut engine = SearchEngine::new();
+        engine.set_recency_config(RecencyConfig { near_days: 0, mid_days: 0 });
+        engine.set_stale_penalty(Some(StalePenalty { threshold_days: 0, penalty: 0.2 }));
+        alias.last_accessed = Utc::now() + Duration::days(365);
+        assert_eq!(engine.calculate_final_score(&alias, 0.5), 0.5);
+    }
+
+    #[test]
+    fn test_match_all_terms_excludes_partial_matches() {
+        let aliases = vec![
+            create_test_alias("monthly", "/docs/report/2024/summary.xlsx"),
+            create_test_alias("old", "/docs/report/2023/summary.xlsx"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 従来の検索では一部のキーワードだけに一致するエイリアスも含まれる
+        let or_results = engine.search("report 2024");
+        assert!(or_results.iter().any(|r| r.alias.alias == "old"));
+        assert!(or_results.iter().any(|r| r.alias.alias == "monthly"));
+
+        engine.set_match_all_terms(true);
+        let and_results = engine.search("report 2024");
+        assert_eq!(and_results.len(), 1);
+        assert_eq!(and_results[0].alias.alias, "monthly");
+    }
+
+    #[test]
+    fn test_match_all_terms_across_fields_and_order() {
+        let mut tagged = create_test_alias("2024 budget", "/work/finance.xlsx");
+        tagged.tags = vec!["Report".to_string()];
+        let aliases = vec![tagged, create_test_alias("budget", "/work/plan.xlsx")];
+        let mut engine = SearchEngine::with_aliases(aliases);
+        engine.set_match_all_terms(true);
+
+        // 語順が違っても、名前とタグに分かれていても一致する
+        let results = engine.search("report 2024");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "2024 budget");
+        assert_eq!(results[0].matched_field, MatchedField::Alias);
+    }
+
+    #[test]
+    fn test_each_alias_appears_once() {
+        // 名前・パス・タグ・メモ・階層パスのすべてに一致しうるエイリアス
+        let mut everywhere = create_test_alias("report 2024", "/report/2024/report.txt");
+        everywhere.tags = vec!["report".to_string(), "2024".to_string()];
+        everywhere.note = Some("report 2024".to_string());
+        let aliases = vec![everywhere, create_test_alias("report", "/docs/2024/a.txt")];
+        let mut engine = SearchEngine::with_aliases(aliases);
+        engine.set_search_notes(true);
+
+        for match_all_terms in [false, true] {
+            engine.set_match_all_terms(match_all_terms);
+            for query in ["report", "report 2024", "2024 report", "rep"] {
+                let results = engine.search(query);
+                let ids: HashSet<&str> = results.iter().map(|r| r.alias.id.as_str()).collect();
+                assert_eq!(ids.len(), results.len(), "query: {}", query);
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_all_terms_single_term_unchanged() {
+        let aliases = vec![
+            create_test_alias("report", "/docs/report.txt"),
+            create_test_alias("reptile", "/docs/zoo.txt"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+        let before = engine.search("rep").len();
+
+        engine.set_match_all_terms(true);
+        assert!(engine.match_all_terms());
+        assert_eq!(engine.search("rep").len(), before);
+    }
+
+    #[test]
+    fn test_raw_mode_distinguishes_case() {
+        let aliases = vec![
+            create_test_alias("Config", "/work/a.toml"),
+            create_test_alias("config", "/work/b.toml"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 通常は大文字小文字を区別しない
+        assert!(!engine.raw_mode());
+        let results = engine.search("Config");
+        assert_eq!(results.len(), 2);
+
+        // raw モードでは文字列をそのまま比較する（切り替えでキャッシュもクリア）
+        engine.set_raw_mode(true);
+        assert!(engine.raw_mode());
+        assert_eq!(engine.last_query(), None);
+        let results = engine.search("Config");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "Config");
+        assert_eq!(results[0].score, 1.0);
+        let results = engine.search("config");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "config");
+
+        // 元に戻すと再び同一視する
+        engine.set_raw_mode(false);
+        assert_eq!(engine.search("config").len(), 2);
+    }
+
+    #[test]
+    fn test_raw_mode_tag_and_wildcard_queries() {
+        let mut upper = create_test_alias("upper", "/work/Report.txt");
+        upper.tags = vec!["Work".to_string()];
+        let mut lower = create_test_alias("lower", "/work/report.txt");
+        lower.tags = vec!["work".to_string()];
+        let mut engine = SearchEngine::with_aliases(vec![upper, lower]);
+
+        assert_eq!(engine.search("tag:Work").len(), 2);
+        assert_eq!(engine.search("*Report*").len(), 2);
+
+        engine.set_raw_mode(true);
+        let results = engine.search("tag:Work");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "upper");
+        let results = engine.search("*Report*");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].alias.alias, "upper");
+    }
+
+    #[test]
+    fn test_search_reading_toggle() {
+        let mut alias = create_test_alias("報告書", "/work/a.txt");
+        alias.reading = Some("ほうこくしょ".to_string());
+        let mut engine = SearchEngine::with_aliases(vec![alias]);
+
+        // 既定では読みも検索対象
+        assert!(engine.search_reading());
+        let results = engine.search("ほうこく");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_field, MatchedField::Reading);
+        // 名前の前方一致より低いスコア
+        assert!(results[0].score < 0.8);
+
+        engine.set_search_reading(false);
+        assert!(engine.search("ほうこく").is_empty());
+    }
+
+    #[test]
+    fn test_search_notes_toggle() {
+        let mut alias = create_test_alias("quarterly", "/work/q.xlsx");
+        alias.note = Some("四半期の売上をまとめたもの".to_string());
+        let mut engine = SearchEngine::with_aliases(vec![alias]);
+
+        // 既定ではメモは検索対象外
+        assert!(!engine.search_notes());
+        assert!(engine.search("売上").is_empty());
+
+        engine.set_search_notes(true);
+        let results = engine.search("売上");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_field, MatchedField::Note);
+        assert_eq!(results[0].score, SearchEngine::NOTE_SCORE);
+
+        engine.set_search_notes(false);
+        assert!(engine.search("売上").is_empty());
+    }
+
+    #[test]
+    fn test_secondary_fields_rank_below_name_matches() {
+        let named = create_test_alias("memo", "/work/memo.txt");
+        let mut noted = create_test_alias("other", "/work/other.txt");
+        noted.note = Some("meeting memo".to_string());
+        let mut engine = SearchEngine::with_aliases(vec![noted, named]);
+        engine.set_search_notes(true);
+
+        let results = engine.search("memo");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].alias.alias, "memo");
+        assert_eq!(results[1].alias.alias, "other");
+        assert_eq!(results[1].matched_field, MatchedField::Note);
+    }
+
+    /// 名前の短いエイリアスほど上位にするテスト用のスコア計算
+    struct ShortNameScorer;
+
+    impl Scorer for ShortNameScorer {
+        fn score(&self, alias: &FileAlias, _base: f32) -> f32 {
+            1.0 / alias.alias.chars().count() as f32
+        }
+    }
+
+    #[test]
+    fn test_custom_scorer_changes_ranking() {
+        let aliases = vec![
+            create_test_alias("report", "/docs/report"),
+            create_test_alias("report_archive_2020", "/docs/archive"),
+            create_test_alias("rep", "/docs/rep"),
+        ];
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        // 既定では完全一致が最上位
+        let results = engine.search("report");
+        assert_eq!(results[0].alias.alias, "report");
+        assert_eq!(results.len(), 2);
+
+        // スコア計算だけを差し替える（マッチングとキャッシュの仕組みはそのまま）
+        engine.set_scorer(Box::new(ShortNameScorer));
+        let results = engine.search("rep");
+        let names: Vec<&str> = results.iter().map(|r| r.alias.alias.as_str()).collect();
+        assert_eq!(names, vec!["rep", "report", "report_archive_2020"]);
+        assert_eq!(results[0].score, 1.0 / 3.0);
+
+        engine.reset_scorer();
+        let results = engine.search("rep");
+        assert_eq!(results[0].alias.alias, "rep");
+        assert!(results[0].score >= 1.0);
+    }
+
+    #[test]
+    fn test_set_scorer_clears_cache_and_keeps_settings() {
+        let mut engine = SearchEngine::with_aliases(vec![create_test_alias("test", "/test")]);
+        engine.search("test");
+        assert!(!engine.cache.is_empty());
+
+        engine.set_scorer(Box::new(FrequencyScorer::default()));
+        assert!(engine.cache.is_empty());
+
+        // 差し替え中に変更した設定は、既定に戻したときに反映される
+        let stale = StalePenalty { threshold_days: 90, penalty: 0.2 };
+        engine.set_stale_penalty(Some(stale));
+        assert_eq!(engine.calculate_final_score(&create_test_alias("a", "/a"), 0.8), 0.8);
+        engine.reset_scorer();
+        let final_score = engine.calculate_final_score(&create_test_alias("a", "/a"), 0.8);
+        assert!((final_score - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_frequency_scorer_prefers_often_opened() {
+        let mut rarely = create_test_alias("notes_rarely", "/notes/rarely");
+        rarely.open_count = 1;
+        let mut often = create_test_alias("notes_often", "/notes/often");
+        often.open_count = 50;
+        let mut engine = SearchEngine::with_aliases(vec![rarely, often]);
+        engine.set_scorer(Box::new(FrequencyScorer::default()));
+
+        let results = engine.search("notes");
+        assert_eq!(results[0].alias.alias, "notes_often");
+        // 加点は上限で頭打ちになる
+        let scorer = FrequencyScorer::default();
+        assert_eq!(scorer.score(&results[0].alias, 0.8), 0.8 + scorer.max_boost);
+    }
+
+    #[test]
+    fn test_time_budget_truncates_scan() {
+        let aliases: Vec<FileAlias> = (0..5000)
+            .map(|i| create_test_alias(&format!("project_{}", i), &format!("/work/project_{}", i)))
+            .collect();
+        let mut engine = SearchEngine::with_aliases(aliases);
+
+        let full = engine.search_with_limit("proj", usize::MAX);
+        assert!(!engine.truncated_by_budget());
+        assert_eq!(full.len(), 5000);
+
+        engine.clear_cache();
+        engine.set_time_budget(Some(std::time::Duration::from_nanos(1)));
+        let partial = engine.search_with_limit("proj", usize::MAX);
+        assert!(engine.truncated_by_budget());
+        assert!(!partial.is_empty());
+        assert!(partial.len() < full.len());
+
+        // 途中までの結果は全件検索の結果の一部で、スコア順に並んでいる
+        let full_ids: std::collections::HashSet<&str> =
+            full.iter().map(|r| r.alias.id.as_str()).collect();
+        assert!(partial.iter().all(|r| full_ids.contains(r.alias.id.as_str())));
+        assert!(partial.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_truncated_results_are_not_cached() {
+        let aliases: Vec<FileAlias> = (0..1000)
+            .map(|i| create_test_alias(&format!("doc_{}", i), &format!("/docs/doc_{}", i)))
+            .collect();
+        let mut engine = SearchEngine::with_aliases(aliases);
+        engine.set_time_budget(Some(std::time::Duration::from_nanos(1)));
+
+        engine.search("doc");
+        assert!(engine.truncated_by_budget());
+        engine.search("doc");
+        assert!(!engine.last_from_cache());
+
+        // 制限を外すと全件を走査し、結果をキャッシュする
+        engine.set_time_budget(None);
+        assert_eq!(engine.search_with_limit("doc", usize::MAX).len(), 1000);
+        assert!(!engine.truncated_by_budget());
+        engine.search("doc");
+        assert!(engine.last_from_cache());
+        assert!(!engine.truncated_by_budget());
     }
 }
@@ -0,0 +1,185 @@
+//! テキストファイルのプレビュー（文字コードの自動判定付き）
+//!
+//! 日本語環境では Shift_JIS のテキストファイルも多いため、UTF-8 として読めない場合は
+//! 内容から文字コードを推定してデコードします。
+
+use encoding_rs::{Encoding, UTF_8};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// プレビューとして読み込む最大バイト数
+pub const TEXT_PREVIEW_MAX_BYTES: usize = 16 * 1024;
+
+/// 内容をコピーする際に読み込む最大バイト数
+pub const TEXT_COPY_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+/// バイナリファイルかどうかを判定するために調べる先頭のバイト数
+const BINARY_CHECK_BYTES: usize = 8 * 1024;
+
+/// デコード済みのテキスト
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedText {
+    /// デコードした文字列
+    pub text: String,
+    /// 判定した文字コード名（例: "UTF-8", "Shift_JIS"）
+    pub encoding: &'static str,
+    /// 上限に達したため途中までしか読み込んでいないか
+    pub truncated: bool,
+}
+
+/// バイト列の文字コードを判定して文字列にデコードする
+///
+/// BOM があればそれに従い、なければ UTF-8 として妥当かを確認し、
+/// 妥当でない場合は内容から文字コードを推定します。
+///
+/// # 引数
+/// * `bytes` - デコードするバイト列
+/// * `truncated` - バイト列がファイルの途中で切れているか（末尾の不完全な文字を許容する）
+pub fn decode_text(bytes: &[u8], truncated: bool) -> DecodedText {
+    let encoding = detect_encoding(bytes, truncated);
+    let (text, _) = match Encoding::for_bom(bytes) {
+        Some((_, bom_length)) => encoding.decode_without_bom_handling(&bytes[bom_length..]),
+        None => encoding.decode_without_bom_handling(bytes),
+    };
+
+    DecodedText {
+        text: text.into_owned(),
+        encoding: encoding.name(),
+        truncated,
+    }
+}
+
+/// バイト列の文字コードを判定する
+fn detect_encoding(bytes: &[u8], truncated: bool) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return UTF_8,
+        // 途中で切った末尾の文字が不完全なだけなら UTF-8 とみなす
+        Err(e) if truncated && e.error_len().is_none() => return UTF_8,
+        Err(_) => {}
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, !truncated);
+    detector.guess(None, true)
+}
+
+/// バイナリファイルらしいかを判定する（先頭に NUL 文字を含む場合）
+///
+/// UTF-16 のテキストも NUL 文字を含むため、BOM がある場合はテキストとして扱います。
+pub fn is_probably_binary(bytes: &[u8]) -> bool {
+    if Encoding::for_bom(bytes).is_some() {
+        return false;
+    }
+    bytes.iter().take(BINARY_CHECK_BYTES).any(|&b| b == 0)
+}
+
+/// テキストファイルを先頭から最大 `max_bytes` バイトまで読み込んでデコードする
+///
+/// # 戻り値
+/// * `Ok(Some(DecodedText))` - デコードしたテキスト
+/// * `Ok(None)` - バイナリファイルのためテキストとして扱わない場合
+/// * `Err(String)` - 読み込みに失敗した場合
+pub fn read_text(path: &Path, max_bytes: usize) -> Result<Option<DecodedText>, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("ファイルを開けません: {} ({})", path.display(), e))?;
+
+    // 上限を超えるかを判定するため1バイト多く読む
+    let mut bytes = Vec::new();
+    file.take(max_bytes as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("ファイルの読み込みに失敗しました: {} ({})", path.display(), e))?;
+
+    let truncated = bytes.len() > max_bytes;
+    bytes.truncate(max_bytes);
+
+    if is_probably_binary(&bytes) {
+        return Ok(None);
+    }
+    Ok(Some(decode_text(&bytes, truncated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// 「日本語のテキストです。」の Shift_JIS 表現
+    const SHIFT_JIS_SAMPLE: &[u8] = &[
+        0x93, 0xfa, 0x96, 0x7b, 0x8c, 0xea, 0x82, 0xcc, 0x83, 0x65, 0x83, 0x4c,
+        0x83, 0x58, 0x83, 0x67, 0x82, 0xc5, 0x82, 0xb7, 0x81, 0x42,
+    ];
+
+    #[test]
+    fn test_decode_shift_jis() {
+        let decoded = decode_text(SHIFT_JIS_SAMPLE, false);
+        assert_eq!(decoded.encoding, "Shift_JIS");
+        assert_eq!(decoded.text, "日本語のテキストです。");
+        assert!(!decoded.truncated);
+    }
+
+    #[test]
+    fn test_decode_utf8() {
+        let decoded = decode_text("日本語のテキストです。".as_bytes(), false);
+        assert_eq!(decoded.encoding, "UTF-8");
+        assert_eq!(decoded.text, "日本語のテキストです。");
+    }
+
+    #[test]
+    fn test_decode_utf8_with_bom() {
+        let mut bytes = vec![0xef, 0xbb, 0xbf];
+        bytes.extend_from_slice("テキスト".as_bytes());
+        let decoded = decode_text(&bytes, false);
+        assert_eq!(decoded.encoding, "UTF-8");
+        assert_eq!(decoded.text, "テキスト");
+    }
+
+    #[test]
+    fn test_decode_truncated_utf8_keeps_encoding() {
+        // 「あい」の途中（「い」の1バイト目まで）で切れている
+        let bytes = &"あい".as_bytes()[..4];
+        let decoded = decode_text(bytes, true);
+        assert_eq!(decoded.encoding, "UTF-8");
+        assert!(decoded.text.starts_with('あ'));
+    }
+
+    #[test]
+    fn test_is_probably_binary() {
+        assert!(is_probably_binary(&[0x89, b'P', b'N', b'G', 0x00, 0x01]));
+        assert!(!is_probably_binary(SHIFT_JIS_SAMPLE));
+        // BOM 付きの UTF-16 は NUL を含んでもテキスト
+        assert!(!is_probably_binary(&[0xff, 0xfe, b'a', 0x00]));
+    }
+
+    #[test]
+    fn test_read_text_from_shift_jis_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sjis.txt");
+        fs::write(&path, SHIFT_JIS_SAMPLE).unwrap();
+
+        let decoded = read_text(&path, TEXT_PREVIEW_MAX_BYTES).unwrap().unwrap();
+        assert_eq!(decoded.text, "日本語のテキストです。");
+        assert!(!decoded.truncated);
+    }
+
+    #[test]
+    fn test_read_text_truncates_and_skips_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let text_path = temp_dir.path().join("long.txt");
+        fs::write(&text_path, "abcdefghij").unwrap();
+        let decoded = read_text(&text_path, 4).unwrap().unwrap();
+        assert_eq!(decoded.text, "abcd");
+        assert!(decoded.truncated);
+
+        let binary_path = temp_dir.path().join("data.bin");
+        fs::write(&binary_path, [0u8, 1, 2, 3]).unwrap();
+        assert_eq!(read_text(&binary_path, 4).unwrap(), None);
+
+        assert!(read_text(&temp_dir.path().join("missing.txt"), 4).is_err());
+    }
+}
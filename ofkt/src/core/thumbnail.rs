@@ -0,0 +1,430 @@
+//! 画像のサムネイルキャッシュ
+//!
+//! 縮小画像の生成は時間がかかるため、ワーカースレッドで作成してキャッシュします。
+//! キャッシュはパスと更新日時をキーにし、件数・メモリ使用量の上限を超えたら
+//! 最も長く使われていないものから破棄します。
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// サムネイルの長辺の既定サイズ（ピクセル）
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 128;
+
+/// キャッシュのメモリ使用量の既定上限（バイト）
+pub const DEFAULT_MEMORY_BUDGET: usize = 32 * 1024 * 1024;
+
+/// キャッシュの既定の最大件数
+pub const DEFAULT_MAX_ENTRIES: usize = 256;
+
+/// サムネイルを作成できる画像の拡張子
+const SUPPORTED_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp", "ico", "tif", "tiff"];
+
+/// サムネイルを作成できる画像ファイルか（拡張子で判定）
+pub fn is_supported_image(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| SUPPORTED_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// キャッシュのキー（パス + 更新日時）
+///
+/// ファイルが更新されるとキーが変わるため、古いサムネイルは使われなくなります。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThumbnailKey {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+impl ThumbnailKey {
+    /// ファイルの現在の更新日時からキーを作成
+    ///
+    /// # 戻り値
+    /// キー（ファイルのメタデータを読めない場合は None）
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// 縮小済みの画像（RGBA、アルファは非乗算）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// メモリ使用量（バイト）
+    pub fn byte_size(&self) -> usize {
+        self.rgba.len()
+    }
+}
+
+/// 画像を読み込み、縦横比を保って長辺が `size` 以下になるよう縮小する
+pub fn generate_thumbnail(path: &Path, size: u32) -> Result<Thumbnail, String> {
+    let image = image::open(path)
+        .map_err(|e| format!("画像の読み込みに失敗しました: {}: {}", path.display(), e))?;
+    let rgba = image.thumbnail(size, size).to_rgba8();
+    Ok(Thumbnail {
+        width: rgba.width(),
+        height: rgba.height(),
+        rgba: rgba.into_raw(),
+    })
+}
+
+/// サムネイルの取得状況
+#[derive(Debug, Clone)]
+pub enum ThumbnailStatus {
+    /// 作成中（プレースホルダーを表示する）
+    Loading,
+    /// 作成済み
+    Ready(Arc<Thumbnail>),
+    /// 作成できない（画像でない・読み込みに失敗した）
+    Unavailable,
+}
+
+/// サムネイル作成用のワーカースレッドとの通信路
+struct Worker {
+    requests: Sender<ThumbnailKey>,
+    results: Receiver<(ThumbnailKey, Result<Thumbnail, String>)>,
+}
+
+impl Worker {
+    fn spawn(size: u32) -> Self {
+        let (request_sender, request_receiver) = channel::<ThumbnailKey>();
+        let (result_sender, result_receiver) = channel();
+
+        std::thread::spawn(move || {
+            // 送信側（キャッシュ）が破棄されるとループを抜ける
+            for key in request_receiver {
+                let result = generate_thumbnail(&key.path, size);
+                if result_sender.send((key, result)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests: request_sender,
+            results: result_receiver,
+        }
+    }
+}
+
+/// バックグラウンドで作成したサムネイルのキャッシュ
+///
+/// UI からは毎フレーム [`ThumbnailCache::request`] を呼び出し、
+/// `Loading` の間はプレースホルダーを表示します。
+pub struct ThumbnailCache {
+    /// サムネイルの長辺のサイズ
+    size: u32,
+    /// メモリ使用量の上限（バイト）
+    memory_budget: usize,
+    /// 最大件数
+    max_entries: usize,
+    /// 作成済みのサムネイル
+    entries: HashMap<ThumbnailKey, Arc<Thumbnail>>,
+    /// 使用順（先頭が最も長く使われていないもの）
+    lru: VecDeque<ThumbnailKey>,
+    /// 現在のメモリ使用量（バイト）
+    memory_used: usize,
+    /// 作成中のキー
+    pending: HashSet<ThumbnailKey>,
+    /// 作成に失敗したキー（同じファイルで再試行しない）
+    failed: HashSet<ThumbnailKey>,
+    /// ワーカースレッド（最初の作成要求で起動する）
+    worker: Option<Worker>,
+}
+
+impl std::fmt::Debug for ThumbnailCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThumbnailCache")
+            .field("entries", &self.entries.len())
+            .field("memory_used", &self.memory_used)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl Default for ThumbnailCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_THUMBNAIL_SIZE, DEFAULT_MEMORY_BUDGET, DEFAULT_MAX_ENTRIES)
+    }
+}
+
+impl ThumbnailCache {
+    /// 新しい ThumbnailCache を作成
+    ///
+    /// # 引数
+    /// * `size` - サムネイルの長辺のサイズ（ピクセル）
+    /// * `memory_budget` - メモリ使用量の上限（バイト）
+    /// * `max_entries` - 最大件数
+    pub fn new(size: u32, memory_budget: usize, max_entries: usize) -> Self {
+        Self {
+            size,
+            memory_budget,
+            max_entries,
+            entries: HashMap::new(),
+            lru: VecDeque::new(),
+            memory_used: 0,
+            pending: HashSet::new(),
+            failed: HashSet::new(),
+            worker: None,
+        }
+    }
+
+    /// サムネイルを要求する
+    ///
+    /// 作成済みならそれを返し、未作成ならワーカースレッドに作成を依頼して `Loading` を返します。
+    pub fn request(&mut self, path: &Path) -> ThumbnailStatus {
+        self.poll();
+
+        if !is_supported_image(path) {
+            return ThumbnailStatus::Unavailable;
+        }
+        let Some(key) = ThumbnailKey::for_path(path) else {
+            return ThumbnailStatus::Unavailable;
+        };
+
+        if let Some(thumbnail) = self.get(&key) {
+            return ThumbnailStatus::Ready(thumbnail);
+        }
+        if self.failed.contains(&key) {
+            return ThumbnailStatus::Unavailable;
+        }
+        if !self.pending.contains(&key) {
+            let size = self.size;
+            let worker = self.worker.get_or_insert_with(|| Worker::spawn(size));
+            if worker.requests.send(key.clone()).is_err() {
+                log::error!("サムネイル作成スレッドが終了しています");
+                return ThumbnailStatus::Unavailable;
+            }
+            self.pending.insert(key);
+        }
+        ThumbnailStatus::Loading
+    }
+
+    /// ワーカースレッドで作成が完了したサムネイルをキャッシュに取り込む
+    pub fn poll(&mut self) {
+        let Some(worker) = self.worker.as_ref() else {
+            return;
+        };
+        let results: Vec<_> = worker.results.try_iter().collect();
+        for (key, result) in results {
+            self.pending.remove(&key);
+            match result {
+                Ok(thumbnail) => self.insert(key, thumbnail),
+                Err(e) => {
+                    log::debug!("{}", e);
+                    self.failed.insert(key);
+                }
+            }
+        }
+    }
+
+    /// 作成中のサムネイルがあるか（再描画の要否の判定用）
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// キャッシュ済みのサムネイルを取得し、最近使ったものとして記録する
+    pub fn get(&mut self, key: &ThumbnailKey) -> Option<Arc<Thumbnail>> {
+        let thumbnail = self.entries.get(key)?.clone();
+        self.touch(key);
+        Some(thumbnail)
+    }
+
+    /// キャッシュ済みの件数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// キャッシュが空か
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 現在のメモリ使用量（バイト）
+    pub fn memory_used(&self) -> usize {
+        self.memory_used
+    }
+
+    /// サムネイルをキャッシュに追加する
+    ///
+    /// 同じパスの古い（更新日時の異なる）サムネイルは破棄し、
+    /// 上限を超えた分は最も長く使われていないものから破棄します。
+    fn insert(&mut self, key: ThumbnailKey, thumbnail: Thumbnail) {
+        let outdated: Vec<ThumbnailKey> = self.entries
+            .keys()
+            .filter(|k| k.path == key.path)
+            .cloned()
+            .collect();
+        for old in &outdated {
+            self.remove(old);
+        }
+        self.failed.retain(|k| k.path != key.path);
+
+        self.memory_used += thumbnail.byte_size();
+        self.entries.insert(key.clone(), Arc::new(thumbnail));
+        self.lru.push_back(key);
+
+        // 追加したもの自体は残す
+        while self.entries.len() > 1
+            && (self.memory_used > self.memory_budget || self.entries.len() > self.max_entries)
+        {
+            let Some(oldest) = self.lru.front().cloned() else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+
+    /// サムネイルをキャッシュから削除する
+    fn remove(&mut self, key: &ThumbnailKey) {
+        if let Some(thumbnail) = self.entries.remove(key) {
+            self.memory_used -= thumbnail.byte_size();
+        }
+        self.lru.retain(|k| k != key);
+    }
+
+    /// 最近使ったものとして使用順の末尾に移す
+    fn touch(&mut self, key: &ThumbnailKey) {
+        if let Some(pos) = self.lru.iter().position(|k| k == key) {
+            if let Some(key) = self.lru.remove(pos) {
+                self.lru.push_back(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    fn key(path: &str, secs: u64) -> ThumbnailKey {
+        ThumbnailKey {
+            path: PathBuf::from(path),
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+        }
+    }
+
+    fn thumbnail(bytes: usize) -> Thumbnail {
+        Thumbnail { width: 1, height: 1, rgba: vec![0; bytes] }
+    }
+
+    #[test]
+    fn test_key_changes_when_mtime_changes() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("photo.png");
+        fs::write(&path, "x").unwrap();
+
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000)).unwrap();
+        let before = ThumbnailKey::for_path(&path).unwrap();
+        assert_eq!(before, ThumbnailKey::for_path(&path).unwrap());
+
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(2_000)).unwrap();
+        let after = ThumbnailKey::for_path(&path).unwrap();
+        assert_ne!(before, after);
+        assert!(ThumbnailKey::for_path(&temp_dir.path().join("missing.png")).is_none());
+    }
+
+    #[test]
+    fn test_newer_mtime_replaces_old_thumbnail() {
+        let mut cache = ThumbnailCache::new(DEFAULT_THUMBNAIL_SIZE, 1024, 10);
+        cache.insert(key("/img/a.png", 1), thumbnail(10));
+        cache.insert(key("/img/b.png", 1), thumbnail(10));
+
+        cache.insert(key("/img/a.png", 2), thumbnail(20));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.memory_used(), 30);
+        assert!(cache.get(&key("/img/a.png", 1)).is_none());
+        assert!(cache.get(&key("/img/a.png", 2)).is_some());
+    }
+
+    #[test]
+    fn test_lru_eviction_by_count() {
+        let mut cache = ThumbnailCache::new(DEFAULT_THUMBNAIL_SIZE, 1024, 2);
+        cache.insert(key("/img/a.png", 1), thumbnail(10));
+        cache.insert(key("/img/b.png", 1), thumbnail(10));
+        // a を使うと b が最も長く使われていないものになる
+        assert!(cache.get(&key("/img/a.png", 1)).is_some());
+
+        cache.insert(key("/img/c.png", 1), thumbnail(10));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("/img/b.png", 1)).is_none());
+        assert!(cache.get(&key("/img/a.png", 1)).is_some());
+        assert!(cache.get(&key("/img/c.png", 1)).is_some());
+    }
+
+    #[test]
+    fn test_lru_eviction_by_memory_budget() {
+        let mut cache = ThumbnailCache::new(DEFAULT_THUMBNAIL_SIZE, 100, 10);
+        cache.insert(key("/img/a.png", 1), thumbnail(40));
+        cache.insert(key("/img/b.png", 1), thumbnail(40));
+        cache.insert(key("/img/c.png", 1), thumbnail(40));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.memory_used(), 80);
+        assert!(cache.get(&key("/img/a.png", 1)).is_none());
+
+        // 上限より大きい1件だけは残す
+        cache.insert(key("/img/huge.png", 1), thumbnail(500));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.memory_used(), 500);
+    }
+
+    #[test]
+    fn test_request_generates_in_background() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("wide.png");
+        image::RgbaImage::new(300, 150).save(&path).unwrap();
+        let text = temp_dir.path().join("note.txt");
+        fs::write(&text, "x").unwrap();
+
+        let mut cache = ThumbnailCache::default();
+        assert!(matches!(cache.request(&text), ThumbnailStatus::Unavailable));
+        assert!(matches!(cache.request(&path), ThumbnailStatus::Loading));
+        assert!(cache.has_pending());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let thumbnail = loop {
+            match cache.request(&path) {
+                ThumbnailStatus::Ready(thumbnail) => break thumbnail,
+                ThumbnailStatus::Loading if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                other => panic!("サムネイルを作成できませんでした: {:?}", other),
+            }
+        };
+        // 縦横比を保って長辺を縮小する
+        assert_eq!((thumbnail.width, thumbnail.height), (128, 64));
+        assert_eq!(thumbnail.rgba.len(), 128 * 64 * 4);
+        assert!(!cache.has_pending());
+    }
+
+    #[test]
+    fn test_broken_image_is_unavailable() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("broken.png");
+        fs::write(&path, "not an image").unwrap();
+
+        let mut cache = ThumbnailCache::default();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while matches!(cache.request(&path), ThumbnailStatus::Loading) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(matches!(cache.request(&path), ThumbnailStatus::Unavailable));
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! 前回開いた後にエイリアスのリンク先が更新されたかの判定
+//!
+//! エイリアスの最終アクセス日時（`last_accessed`）とリンク先の更新日時を比べ、
+//! 開いた後に変更があったエイリアスに印を付けるために使います。
+//! 更新日時は一定時間キャッシュし、描画のたびにファイルシステムへ問い合わせないようにします。
+
+use crate::data::models::FileAlias;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// 更新日時を再取得するまでの間隔
+pub const DEFAULT_MTIME_TTL: Duration = Duration::from_secs(30);
+
+/// 更新日時が最終アクセス日時より後か
+///
+/// 更新日時が不明な場合（リンク先が存在しないなど）は false を返します。
+pub fn modified_since_opened(alias: &FileAlias, modified: Option<DateTime<Utc>>) -> bool {
+    modified.is_some_and(|modified| modified > alias.last_accessed)
+}
+
+/// リンク先の更新日時を取得（取得できない場合は None）
+fn read_modified(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+/// パスごとの更新日時のキャッシュ
+#[derive(Debug, Clone)]
+pub struct ModifiedTimeCache {
+    /// パス → (更新日時, 取得した時刻)
+    entries: HashMap<PathBuf, (Option<DateTime<Utc>>, Instant)>,
+    /// 更新日時を再取得するまでの間隔
+    ttl: Duration,
+}
+
+impl Default for ModifiedTimeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MTIME_TTL)
+    }
+}
+
+impl ModifiedTimeCache {
+    /// 再取得の間隔を指定してキャッシュを作成
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl,
+        }
+    }
+
+    /// エイリアスのリンク先が前回開いた後に更新されたか
+    pub fn has_unseen_changes(&mut self, alias: &FileAlias) -> bool {
+        let modified = self.modified_with(&alias.path, Instant::now(), read_modified);
+        modified_since_opened(alias, modified)
+    }
+
+    /// キャッシュ済みの更新日時を返し、期限切れなら `lookup` で取得し直す
+    ///
+    /// # 引数
+    /// * `path` - 対象のパス
+    /// * `now` - 現在時刻
+    /// * `lookup` - 更新日時の取得方法
+    pub fn modified_with(
+        &mut self,
+        path: &Path,
+        now: Instant,
+        lookup: impl FnOnce(&Path) -> Option<DateTime<Utc>>,
+    ) -> Option<DateTime<Utc>> {
+        if let Some((modified, fetched_at)) = self.entries.get(path) {
+            if now.saturating_duration_since(*fetched_at) < self.ttl {
+                return *modified;
+            }
+        }
+
+        let modified = lookup(path);
+        self.entries.insert(path.to_path_buf(), (modified, now));
+        modified
+    }
+
+    /// パスのキャッシュを取り除く（次回は取得し直す）
+    pub fn invalidate(&mut self, path: &Path) {
+        self.entries.remove(path);
+    }
+
+    /// キャッシュをすべて取り除く
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::OpenAction;
+    use chrono::TimeZone;
+    use std::cell::Cell;
+
+    fn alias_opened_at(last_accessed: DateTime<Utc>) -> FileAlias {
+        FileAlias {
+            id: "id-1".to_string(),
+            alias: "report".to_string(),
+            path: PathBuf::from("/work/report.txt"),
+            tags: vec![],
+            color: None,
+            created_at: last_accessed,
+            last_accessed,
+            is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        }
+    }
+
+    #[test]
+    fn test_modified_since_opened() {
+        let opened = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+        let alias = alias_opened_at(opened);
+
+        assert!(modified_since_opened(&alias, Some(opened + chrono::Duration::minutes(1))));
+        assert!(!modified_since_opened(&alias, Some(opened - chrono::Duration::minutes(1))));
+        assert!(!modified_since_opened(&alias, Some(opened)));
+        // リンク先が見つからない場合は印を付けない
+        assert!(!modified_since_opened(&alias, None));
+    }
+
+    #[test]
+    fn test_cache_reuses_lookup_until_ttl_expires() {
+        let mut cache = ModifiedTimeCache::new(Duration::from_secs(30));
+        let path = Path::new("/work/report.txt");
+        let first = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+        let second = first + chrono::Duration::hours(1);
+        let lookups = Cell::new(0);
+        let start = Instant::now();
+
+        let lookup = |modified| {
+            let lookups = &lookups;
+            move |_: &Path| {
+                lookups.set(lookups.get() + 1);
+                Some(modified)
+            }
+        };
+
+        assert_eq!(cache.modified_with(path, start, lookup(first)), Some(first));
+        // 期限内はファイルシステムに問い合わせない
+        assert_eq!(
+            cache.modified_with(path, start + Duration::from_secs(10), lookup(second)),
+            Some(first)
+        );
+        assert_eq!(lookups.get(), 1);
+
+        // 期限切れ後は取得し直す
+        assert_eq!(
+            cache.modified_with(path, start + Duration::from_secs(30), lookup(second)),
+            Some(second)
+        );
+        assert_eq!(lookups.get(), 2);
+
+        // 取り除いたパスは期限内でも取得し直す
+        cache.invalidate(path);
+        cache.modified_with(path, start + Duration::from_secs(31), lookup(second));
+        assert_eq!(lookups.get(), 3);
+    }
+}
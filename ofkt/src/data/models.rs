@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 /// ファイルエイリアス
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FileAlias {
     pub id: String,
     pub alias: String,
@@ -15,6 +16,55 @@ pub struct FileAlias {
     pub last_accessed: DateTime<Utc>,
     #[serde(default)]
     pub is_favorite: bool,
+    /// エイリアスを開いたときの動作
+    #[serde(default)]
+    pub open_action: OpenAction,
+    /// 登録時のボリューム情報（リムーバブルドライブのドライブレター変更への追従用）
+    #[serde(default)]
+    pub volume: Option<VolumeId>,
+    /// エイリアスから開いた回数
+    #[serde(default)]
+    pub open_count: u32,
+    /// メモ（説明文）
+    #[serde(default)]
+    pub note: Option<String>,
+    /// 読み（例: 漢字の名前のふりがな）
+    #[serde(default)]
+    pub reading: Option<String>,
+    /// 手動で並べ替えた順番（小さいほど先、保存時に 0 からの連番に振り直す）
+    #[serde(default)]
+    pub order: u32,
+    /// パスが見つからないか（読み込み時に検証する一時的な状態で、保存しない）
+    #[serde(skip)]
+    pub is_broken: bool,
+    /// ネットワークパスに接続できず、パスがあるか確認できないか（保存しない）
+    #[serde(skip)]
+    pub is_unreachable: bool,
+}
+
+/// エイリアスが置かれているボリュームの識別情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeId {
+    /// 登録時のマウント位置（例: `E:\`）
+    pub root: PathBuf,
+    /// ボリュームラベル
+    #[serde(default)]
+    pub label: Option<String>,
+    /// ボリュームシリアル番号
+    #[serde(default)]
+    pub serial: Option<u32>,
+}
+
+/// エイリアスを開いたときの動作
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OpenAction {
+    /// 既定のアプリケーションで開く（フォルダの場合はディレクトリモードで移動）
+    #[default]
+    Open,
+    /// 親フォルダを開いて項目を選択状態で表示
+    RevealInFolder,
+    /// 指定したプログラムで開く
+    OpenWith(String),
 }
 
 /// ファイル履歴
@@ -164,6 +214,10 @@ pub struct Config {
     pub theme: ThemeConfig,
     pub search: SearchConfig,
     pub file_operations: FileOperationConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub keybindings: KeybindingConfig,
 }
 
 /// ウィンドウ設定
@@ -176,6 +230,9 @@ pub struct WindowConfig {
     pub always_on_top: bool,
     #[serde(default = "default_decorations")]
     pub decorations: bool,
+    /// Escキーを2回押したとみなす間隔（ミリ秒、0で無効）。2回押すとウィンドウを隠す
+    #[serde(default = "default_double_escape_ms")]
+    pub double_escape_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -188,6 +245,10 @@ fn default_decorations() -> bool {
     true
 }
 
+fn default_double_escape_ms() -> u64 {
+    400
+}
+
 /// ホットキー設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotkeyConfig {
@@ -226,6 +287,141 @@ pub struct SearchConfig {
     pub search_paths: bool,
     pub search_aliases: bool,
     pub case_sensitive: bool,
+    /// 起動時に前回の検索クエリをモードごとに復元するか
+    #[serde(default)]
+    pub remember_last_query: bool,
+    /// エイリアスモードで最後に使った検索クエリ
+    #[serde(default)]
+    pub last_alias_query: Option<String>,
+    /// ディレクトリモードで最後に使った検索クエリ
+    #[serde(default)]
+    pub last_directory_query: Option<String>,
+    /// 長期間アクセスされていないエイリアスの検索順位を下げるか
+    #[serde(default)]
+    pub stale_penalty_enabled: bool,
+    /// 順位を下げるまでの未アクセス日数
+    #[serde(default = "default_stale_threshold_days")]
+    pub stale_threshold_days: u32,
+    /// 順位を下げる際に減点するスコア
+    #[serde(default = "default_stale_penalty")]
+    pub stale_penalty: f32,
+    /// 空白区切りの複数キーワードをすべて含むエイリアスだけを検索結果にするか
+    #[serde(default)]
+    pub match_all_terms: bool,
+    /// エイリアスのメモを検索対象に含めるか
+    #[serde(default)]
+    pub search_notes: bool,
+    /// エイリアスの読みを検索対象に含めるか
+    #[serde(default = "default_search_reading")]
+    pub search_reading: bool,
+    /// ディレクトリモードの再帰検索（`**クエリ`）でたどる深さの上限
+    #[serde(default = "default_recursive_max_depth")]
+    pub recursive_max_depth: usize,
+    /// ディレクトリモードの再帰検索でたどらないフォルダ名
+    #[serde(default = "default_recursive_ignore")]
+    pub recursive_ignore: Vec<String>,
+    /// フォルダを移動してもディレクトリモードの検索クエリを消さずに絞り込みを続けるか
+    #[serde(default)]
+    pub keep_filter_on_navigate: bool,
+    /// エイリアス検索で1回の走査に使える時間（ミリ秒、None の場合は全件を走査する）
+    #[serde(default)]
+    pub search_time_budget_ms: Option<u64>,
+}
+
+fn default_search_reading() -> bool {
+    true
+}
+
+fn default_recursive_max_depth() -> usize {
+    8
+}
+
+fn default_recursive_ignore() -> Vec<String> {
+    vec!["node_modules".to_string(), ".git".to_string()]
+}
+
+fn default_stale_threshold_days() -> u32 {
+    180
+}
+
+fn default_stale_penalty() -> f32 {
+    0.2
+}
+
+/// 表示設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// エイリアス一覧の各行に表示する列（name, size, modified, tags, path）
+    #[serde(default = "default_row_columns")]
+    pub row_columns: Vec<String>,
+    /// 新しく開いたフォルダの既定のソートキー（name, size, type, modified）
+    #[serde(default = "default_sort_key")]
+    pub default_sort_key: String,
+    /// 新しく開いたフォルダの既定のソート順（ascending, descending）
+    #[serde(default = "default_sort_order")]
+    pub default_sort_order: String,
+    /// ディレクトリモードのサイドバーの幅（前回終了時の幅を復元する）
+    #[serde(default = "default_sidebar_width")]
+    pub sidebar_width: f32,
+    /// ディレクトリモードのサイドバーに最初から表示するエイリアスの件数
+    #[serde(default = "default_sidebar_alias_limit")]
+    pub sidebar_alias_limit: usize,
+    /// サイドバーのエイリアス一覧を「もっと見る」で展開しているか
+    #[serde(default)]
+    pub sidebar_aliases_expanded: bool,
+    /// タグ別表示で折りたたんでいるタグ名
+    #[serde(default)]
+    pub collapsed_tags: HashSet<String>,
+    /// トップバーに表示するタブと並び順（alias, directory, recent。先頭が起動時のモード）
+    #[serde(default = "default_browse_tabs")]
+    pub browse_tabs: Vec<String>,
+    /// 見つからないネットワークパス（UNC）のエイリアスを、壊れているとせず「確認できない」と表示するか
+    #[serde(default = "default_network_path_as_unknown")]
+    pub network_path_as_unknown: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            row_columns: default_row_columns(),
+            default_sort_key: default_sort_key(),
+            default_sort_order: default_sort_order(),
+            sidebar_width: default_sidebar_width(),
+            sidebar_alias_limit: default_sidebar_alias_limit(),
+            sidebar_aliases_expanded: false,
+            collapsed_tags: HashSet::new(),
+            browse_tabs: default_browse_tabs(),
+            network_path_as_unknown: default_network_path_as_unknown(),
+        }
+    }
+}
+
+fn default_network_path_as_unknown() -> bool {
+    true
+}
+
+fn default_sort_key() -> String {
+    "name".to_string()
+}
+
+fn default_sort_order() -> String {
+    "ascending".to_string()
+}
+
+fn default_sidebar_width() -> f32 {
+    200.0
+}
+
+fn default_sidebar_alias_limit() -> usize {
+    10
+}
+
+fn default_browse_tabs() -> Vec<String> {
+    vec!["alias".to_string(), "directory".to_string()]
+}
+
+fn default_row_columns() -> Vec<String> {
+    vec!["name".to_string(), "path".to_string()]
 }
 
 /// ファイル操作設定
@@ -234,6 +430,78 @@ pub struct FileOperationConfig {
     pub confirm_delete: bool,
     pub use_trash: bool,
     pub default_open_action: String,
+    /// 別々のフォルダから選んだ項目を貼り付けるとき、共通の親からのフォルダ構成を再現するか
+    #[serde(default)]
+    pub preserve_structure: bool,
+    /// 貼り付けで作成される項目数がこれを超える場合に確認する（0 は確認しない）
+    #[serde(default = "default_large_paste_threshold")]
+    pub large_paste_threshold: usize,
+    /// 同じフォルダに貼り付けたときのコピー名の形式（`{name}`・`{ext}`・`{n}` が使える）
+    #[serde(default = "default_copy_name_pattern")]
+    pub copy_name_pattern: String,
+    /// 貼り付け先に同名の項目がある場合の扱い（overwrite, overwrite_only_if_older）
+    #[serde(default = "default_conflict_policy")]
+    pub conflict_policy: String,
+    /// 元に戻せる操作の数（古い操作から取り消せなくなる）
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    /// 貼り付け（コピー）時に読み取り専用・隠し属性を引き継ぐか（Windowsのみ）
+    #[serde(default)]
+    pub preserve_attributes: bool,
+}
+
+/// アプリ内のファイル操作のキー割り当て設定
+///
+/// "Ctrl+C" のように修飾キーとキーを `+` でつなげて指定する（空文字列で無効）。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeybindingConfig {
+    /// ファイルのコピー
+    #[serde(default = "default_copy_keybinding")]
+    pub copy: String,
+    /// ファイルの切り取り
+    #[serde(default = "default_cut_keybinding")]
+    pub cut: String,
+    /// ファイルの貼り付け
+    #[serde(default = "default_paste_keybinding")]
+    pub paste: String,
+}
+
+impl Default for KeybindingConfig {
+    fn default() -> Self {
+        Self {
+            copy: default_copy_keybinding(),
+            cut: default_cut_keybinding(),
+            paste: default_paste_keybinding(),
+        }
+    }
+}
+
+fn default_copy_keybinding() -> String {
+    "Ctrl+C".to_string()
+}
+
+fn default_cut_keybinding() -> String {
+    "Ctrl+X".to_string()
+}
+
+fn default_paste_keybinding() -> String {
+    "Ctrl+V".to_string()
+}
+
+fn default_large_paste_threshold() -> usize {
+    1000
+}
+
+fn default_copy_name_pattern() -> String {
+    crate::core::clipboard::DEFAULT_COPY_NAME_PATTERN.to_string()
+}
+
+fn default_conflict_policy() -> String {
+    "overwrite".to_string()
+}
+
+fn default_history_limit() -> usize {
+    crate::core::operation_history::OperationHistoryManager::DEFAULT_MAX_ENTRIES
 }
 
 #[cfg(test)]
@@ -253,6 +521,14 @@ mod tests {
             created_at: now,
             last_accessed: now,
             is_favorite: true,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         };
 
         assert_eq!(alias.id, "test-id");
@@ -310,6 +586,14 @@ mod tests {
             created_at: now,
             last_accessed: now,
             is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         };
 
         // JSON シリアライズ
@@ -324,6 +608,54 @@ mod tests {
         assert_eq!(deserialized.path, alias.path);
     }
 
+    #[test]
+    fn test_file_alias_open_action_default() {
+        // open_action を含まない既存のJSONは Open として読み込まれる
+        let json = r#"{
+            "id": "test-id",
+            "alias": "test",
+            "path": "/path/to/file",
+            "color": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "last_accessed": "2024-01-01T00:00:00Z"
+        }"#;
+        let alias: FileAlias = serde_json::from_str(json).unwrap();
+        assert_eq!(alias.open_action, OpenAction::Open);
+        // volume を含まない場合は None
+        assert!(alias.volume.is_none());
+        // open_count を含まない場合は 0
+        assert_eq!(alias.open_count, 0);
+        // order を含まない場合は 0
+        assert_eq!(alias.order, 0);
+    }
+
+    #[test]
+    fn test_file_alias_open_action_serialization() {
+        let now = Utc::now();
+        let alias = FileAlias {
+            id: "test-id".to_string(),
+            alias: "test".to_string(),
+            path: PathBuf::from("/path/to/file"),
+            tags: vec![],
+            color: None,
+            created_at: now,
+            last_accessed: now,
+            is_favorite: false,
+            open_action: OpenAction::OpenWith("notepad.exe".to_string()),
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        };
+
+        let json = serde_json::to_string(&alias).unwrap();
+        let deserialized: FileAlias = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.open_action, OpenAction::OpenWith("notepad.exe".to_string()));
+    }
+
     #[test]
     fn test_file_alias_with_empty_tags() {
         let now = Utc::now();
@@ -336,6 +668,14 @@ mod tests {
             created_at: now,
             last_accessed: now,
             is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         };
 
         assert_eq!(alias.tags.len(), 0);
@@ -360,6 +700,7 @@ mod tests {
             position: WindowPosition { x: 100.0, y: 100.0 },
             always_on_top: true,
             decorations: false,
+            double_escape_ms: 400,
         };
 
         assert_eq!(window_config.width, 800.0);
@@ -426,6 +767,19 @@ mod tests {
             search_paths: true,
             search_aliases: true,
             case_sensitive: false,
+            remember_last_query: false,
+            last_alias_query: None,
+            last_directory_query: None,
+            stale_penalty_enabled: false,
+            stale_threshold_days: 180,
+            stale_penalty: 0.2,
+            match_all_terms: false,
+            search_notes: false,
+            search_reading: true,
+            recursive_max_depth: 8,
+            recursive_ignore: vec!["node_modules".to_string(), ".git".to_string()],
+            keep_filter_on_navigate: false,
+            search_time_budget_ms: None,
         };
 
         assert_eq!(search_config.incremental, true);
@@ -441,6 +795,12 @@ mod tests {
             confirm_delete: true,
             use_trash: true,
             default_open_action: "open".to_string(),
+            preserve_structure: false,
+            large_paste_threshold: 1000,
+            copy_name_pattern: "{name} (コピー){ext}".to_string(),
+            conflict_policy: "overwrite".to_string(),
+            history_limit: 50,
+            preserve_attributes: false,
         };
 
         assert_eq!(file_op_config.confirm_delete, true);
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::PathBuf;
-use crate::data::models::{Config, FileAlias, FileHistory, QuickAccessEntry};
+use std::path::{Path, PathBuf};
+use crate::data::models::{Config, FileAlias, FileHistory, OpenAction, QuickAccessEntry};
 
 /// 設定ディレクトリのパスを取得
 /// Linux: $HOME/.config/ofkt
@@ -20,6 +20,30 @@ pub fn get_config_dir() -> Result<PathBuf> {
     Ok(config_dir)
 }
 
+/// 書き込みできるかの確認に使う一時ファイルの名前
+const WRITE_PROBE_FILE_NAME: &str = ".ofkt_write_test";
+
+/// ディレクトリに書き込めるか確認する
+///
+/// 一時ファイルを作成してすぐに削除します。読み取り専用のドライブや
+/// アクセス権限がない場合はエラーを返します。
+pub fn check_dir_writable(dir: &Path) -> Result<()> {
+    let probe_path = dir.join(WRITE_PROBE_FILE_NAME);
+    fs::write(&probe_path, b"")
+        .with_context(|| format!("{} に書き込めません", dir.display()))?;
+    let _ = fs::remove_file(&probe_path);
+    Ok(())
+}
+
+/// 設定ディレクトリを取得し、書き込めるか確認する
+///
+/// 起動時に1回だけ呼び出します（保存のたびに確認はしません）。
+pub fn get_writable_config_dir() -> Result<PathBuf> {
+    let config_dir = get_config_dir()?;
+    check_dir_writable(&config_dir)?;
+    Ok(config_dir)
+}
+
 /// 設定ファイルのパスを取得
 pub fn get_config_path() -> Result<PathBuf> {
     Ok(get_config_dir()?.join("config.json"))
@@ -123,6 +147,14 @@ fn create_sample_aliases() -> Result<Vec<FileAlias>> {
             created_at: now,
             last_accessed: now,
             is_favorite: true,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         });
     }
 
@@ -137,6 +169,14 @@ fn create_sample_aliases() -> Result<Vec<FileAlias>> {
             created_at: now,
             last_accessed: now,
             is_favorite: true,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         });
     }
 
@@ -151,6 +191,14 @@ fn create_sample_aliases() -> Result<Vec<FileAlias>> {
             created_at: now,
             last_accessed: now,
             is_favorite: true,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         });
     }
 
@@ -176,6 +224,17 @@ pub fn save_aliases(aliases: &[FileAlias]) -> Result<()> {
     Ok(())
 }
 
+/// エイリアスを指定したファイルにエクスポート
+pub fn export_aliases(aliases: &[FileAlias], path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(aliases)
+        .context("エイリアスのシリアライズに失敗しました")?;
+
+    fs::write(path, json)
+        .with_context(|| format!("エクスポートファイルの書き込みに失敗しました: {}", path.display()))?;
+
+    Ok(())
+}
+
 /// 履歴ファイルを読み込む
 pub fn load_history() -> Result<Vec<FileHistory>> {
     let history_path = get_history_path()?;
@@ -365,6 +424,20 @@ mod tests {
         assert!(config_dir.ends_with("ofkt"));
     }
 
+    #[test]
+    fn test_check_dir_writable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(check_dir_writable(temp_dir.path()).is_ok());
+        // 確認用の一時ファイルは残さない
+        assert!(!temp_dir.path().join(WRITE_PROBE_FILE_NAME).exists());
+
+        // ディレクトリの代わりにファイルがある（書き込めない場所の代わり）
+        let not_a_dir = temp_dir.path().join("file.txt");
+        fs::write(&not_a_dir, "").unwrap();
+        let error = check_dir_writable(&not_a_dir).unwrap_err();
+        assert!(error.to_string().contains("書き込めません"));
+    }
+
     #[test]
     fn test_get_config_path() {
         let config_path = get_config_path();
@@ -572,6 +645,14 @@ mod tests {
                 created_at: now,
                 last_accessed: now,
                 is_favorite: true,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
             },
             FileAlias {
                 id: uuid::Uuid::new_v4().to_string(),
@@ -582,6 +663,14 @@ mod tests {
                 created_at: now,
                 last_accessed: now,
                 is_favorite: false,
+                open_action: OpenAction::Open,
+                volume: None,
+                open_count: 0,
+                note: None,
+                reading: None,
+                order: 0,
+                is_broken: false,
+                is_unreachable: false,
             },
         ];
 
@@ -849,4 +938,40 @@ mod tests {
                 alias_names.contains(&"ダウンロード") ||
                 alias_names.contains(&"デスクトップ"));
     }
+
+    #[test]
+    fn test_export_aliases() {
+        let temp_dir = env::temp_dir().join(format!("ofkt_export_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&temp_dir).unwrap();
+        let export_path = temp_dir.join("aliases_export.json");
+
+        let now = chrono::Utc::now();
+        let test_aliases = vec![FileAlias {
+            id: uuid::Uuid::new_v4().to_string(),
+            alias: "export_test".to_string(),
+            path: PathBuf::from("/path/to/export"),
+            tags: vec![],
+            color: None,
+            created_at: now,
+            last_accessed: now,
+            is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        }];
+
+        export_aliases(&test_aliases, &export_path).unwrap();
+
+        let contents = fs::read_to_string(&export_path).unwrap();
+        let exported: Vec<FileAlias> = serde_json::from_str(&contents).unwrap();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].alias, "export_test");
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
 }
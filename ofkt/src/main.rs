@@ -14,11 +14,22 @@ fn main() -> Result<()> {
 
     info!("Ofkt 起動中...");
 
+    // 設定のウィンドウサイズ・位置を、配置先モニターの作業領域に収める
+    let config = match data::storage::load_config() {
+        Ok(config) => Some(config),
+        Err(e) => {
+            log::warn!("設定の読み込みに失敗（既定のウィンドウ配置を使用）: {}", e);
+            None
+        }
+    };
+    let geometry = platform::monitor::initial_window_geometry(config.as_ref().map(|c| &c.window));
+    info!("初期ウィンドウ配置: 位置 {:?}, サイズ {:?}", geometry.position, geometry.size);
+
     // eframe の NativeOptions を設定
     let native_options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
-            .with_inner_size([300.0, 1080.0])  // 幅300px、高さ画面全体
-            .with_position([1620.0, 0.0])      // 初期位置
+            .with_inner_size(geometry.size)
+            .with_position(geometry.position)
             .with_resizable(true)               // リサイズ可能
             .with_decorations(true)             // ウィンドウ装飾あり
             .with_transparent(false),           // 透明度なし
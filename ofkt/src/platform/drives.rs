@@ -1,4 +1,5 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::data::models::VolumeId;
 
 #[derive(Debug, Clone)]
 pub struct DriveInfo {
@@ -109,3 +110,237 @@ pub fn get_quick_access() -> Vec<DriveInfo> {
 
     quick
 }
+
+/// マウント中のボリューム情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountedVolume {
+    /// マウント位置（例: `F:\`）
+    pub root: PathBuf,
+    /// ボリュームラベル
+    pub label: Option<String>,
+    /// ボリュームシリアル番号
+    pub serial: Option<u32>,
+    /// リムーバブルドライブか
+    pub removable: bool,
+}
+
+/// マウント中のボリュームを列挙する
+///
+/// テストではダミー実装に差し替えて使用します。
+pub trait DriveEnumerator {
+    /// 現在マウントされているボリュームの一覧を取得
+    fn mounted_volumes(&self) -> Vec<MountedVolume>;
+}
+
+/// システムのドライブを列挙する DriveEnumerator
+pub struct SystemDriveEnumerator;
+
+impl DriveEnumerator for SystemDriveEnumerator {
+    #[cfg(target_os = "windows")]
+    fn mounted_volumes(&self) -> Vec<MountedVolume> {
+        use windows::Win32::Storage::FileSystem::GetVolumeInformationW;
+        use windows::core::PCWSTR;
+
+        get_drives()
+            .into_iter()
+            .map(|drive| {
+                let root_wide: Vec<u16> = drive.path
+                    .to_string_lossy()
+                    .encode_utf16()
+                    .chain(Some(0))
+                    .collect();
+                let mut label_buffer = [0u16; 261];
+                let mut serial = 0u32;
+
+                let result = unsafe {
+                    GetVolumeInformationW(
+                        PCWSTR(root_wide.as_ptr()),
+                        Some(&mut label_buffer),
+                        Some(&mut serial),
+                        None,
+                        None,
+                        None,
+                    )
+                };
+
+                let (label, serial) = match result {
+                    Ok(()) => {
+                        let len = label_buffer.iter().position(|&c| c == 0).unwrap_or(label_buffer.len());
+                        let label = String::from_utf16_lossy(&label_buffer[..len]);
+                        (Some(label).filter(|l| !l.is_empty()), Some(serial))
+                    }
+                    // メディアが挿入されていないドライブなど
+                    Err(_) => (None, None),
+                };
+
+                MountedVolume {
+                    root: drive.path,
+                    label,
+                    serial,
+                    removable: drive.drive_type == DriveType::Removable,
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn mounted_volumes(&self) -> Vec<MountedVolume> {
+        Vec::new()
+    }
+}
+
+/// パスが置かれているリムーバブルボリュームの識別情報を取得
+///
+/// ラベルもシリアル番号も取得できない場合や、固定ドライブ上のパスの場合は None を返します。
+///
+/// # 引数
+/// * `path` - 対象のパス
+/// * `enumerator` - ボリュームの列挙方法
+pub fn volume_for_path(path: &Path, enumerator: &dyn DriveEnumerator) -> Option<VolumeId> {
+    enumerator
+        .mounted_volumes()
+        .into_iter()
+        .filter(|volume| volume.removable && path.starts_with(&volume.root))
+        .filter(|volume| volume.label.is_some() || volume.serial.is_some())
+        .max_by_key(|volume| volume.root.components().count())
+        .map(|volume| VolumeId {
+            root: volume.root,
+            label: volume.label,
+            serial: volume.serial,
+        })
+}
+
+/// 登録時のボリューム情報から現在のパスを解決
+///
+/// シリアル番号が一致するボリュームを優先し、なければラベルが一致する
+/// ボリュームを探して、登録時のマウント位置を現在のマウント位置に置き換えます。
+/// 見つからない場合は保存されたパスをそのまま返します。
+///
+/// # 引数
+/// * `path` - 保存されたパス
+/// * `volume` - 登録時のボリューム情報
+/// * `enumerator` - ボリュームの列挙方法
+///
+/// # 戻り値
+/// 現在のマウント位置に基づくパス
+pub fn resolve_volume_path(
+    path: &Path,
+    volume: Option<&VolumeId>,
+    enumerator: &dyn DriveEnumerator,
+) -> PathBuf {
+    let Some(volume) = volume else {
+        return path.to_path_buf();
+    };
+    let Ok(relative) = path.strip_prefix(&volume.root) else {
+        return path.to_path_buf();
+    };
+
+    let mounted = enumerator.mounted_volumes();
+    let by_serial = volume.serial.and_then(|serial| {
+        mounted.iter().find(|m| m.serial == Some(serial))
+    });
+    let by_label = || {
+        volume.label.as_ref().and_then(|label| {
+            mounted.iter().find(|m| {
+                m.label.as_ref().is_some_and(|l| l.eq_ignore_ascii_case(label))
+            })
+        })
+    };
+
+    match by_serial.or_else(by_label) {
+        Some(current) => current.root.join(relative),
+        None => path.to_path_buf(),
+    }
+}
+
+/// エイリアスに保存されたパスから現在の実際のパスを解決
+///
+/// 環境変数と先頭の `~` を展開したうえで、ボリュームのマウント位置の変更に追従します。
+pub fn resolve_alias_path(
+    path: &Path,
+    volume: Option<&VolumeId>,
+    enumerator: &dyn DriveEnumerator,
+) -> PathBuf {
+    resolve_volume_path(&crate::utils::path::expand_path(path), volume, enumerator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 固定のボリューム一覧を返すテスト用の列挙子
+    struct FakeDrives(Vec<MountedVolume>);
+
+    impl DriveEnumerator for FakeDrives {
+        fn mounted_volumes(&self) -> Vec<MountedVolume> {
+            self.0.clone()
+        }
+    }
+
+    fn mounted(root: &str, label: Option<&str>, serial: Option<u32>) -> MountedVolume {
+        MountedVolume {
+            root: PathBuf::from(root),
+            label: label.map(|l| l.to_string()),
+            serial,
+            removable: true,
+        }
+    }
+
+    fn volume_id(root: &str, label: Option<&str>, serial: Option<u32>) -> VolumeId {
+        VolumeId {
+            root: PathBuf::from(root),
+            label: label.map(|l| l.to_string()),
+            serial,
+        }
+    }
+
+    #[test]
+    fn test_resolve_label_to_current_mount() {
+        // 登録時は /mnt/e に、現在は /mnt/f にマウントされている
+        let drives = FakeDrives(vec![
+            mounted("/mnt/c", Some("SYSTEM"), Some(1)),
+            mounted("/mnt/f", Some("USB_WORK"), None),
+        ]);
+        let volume = volume_id("/mnt/e", Some("usb_work"), None);
+
+        let resolved = resolve_volume_path(Path::new("/mnt/e/docs/report.txt"), Some(&volume), &drives);
+        assert_eq!(resolved, PathBuf::from("/mnt/f/docs/report.txt"));
+    }
+
+    #[test]
+    fn test_resolve_prefers_serial_over_label() {
+        // 同じラベルのドライブが2つある場合はシリアル番号で区別する
+        let drives = FakeDrives(vec![
+            mounted("/mnt/f", Some("USB"), Some(100)),
+            mounted("/mnt/g", Some("USB"), Some(200)),
+        ]);
+        let volume = volume_id("/mnt/e", Some("USB"), Some(200));
+
+        let resolved = resolve_volume_path(Path::new("/mnt/e/data"), Some(&volume), &drives);
+        assert_eq!(resolved, PathBuf::from("/mnt/g/data"));
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_stored_path() {
+        let drives = FakeDrives(vec![mounted("/mnt/f", Some("OTHER"), Some(1))]);
+        let volume = volume_id("/mnt/e", Some("USB_WORK"), Some(2));
+        let stored = Path::new("/mnt/e/docs");
+
+        // 一致するボリュームがない
+        assert_eq!(resolve_volume_path(stored, Some(&volume), &drives), stored);
+        // ボリューム情報がない
+        assert_eq!(resolve_volume_path(stored, None, &drives), stored);
+    }
+
+    #[test]
+    fn test_volume_for_path_only_removable() {
+        let mut fixed = mounted("/mnt/c", Some("SYSTEM"), Some(1));
+        fixed.removable = false;
+        let drives = FakeDrives(vec![fixed, mounted("/mnt/e", Some("USB_WORK"), Some(2))]);
+
+        let volume = volume_for_path(Path::new("/mnt/e/docs"), &drives).unwrap();
+        assert_eq!(volume, volume_id("/mnt/e", Some("USB_WORK"), Some(2)));
+
+        assert!(volume_for_path(Path::new("/mnt/c/Users"), &drives).is_none());
+    }
+}
@@ -4,6 +4,7 @@ pub mod hotkey;
 pub mod edge_detector;
 pub mod autostart;
 pub mod drives;
+pub mod monitor;
 
 // Re-export for convenience
 pub use system_tray::{SystemTray, TrayEvent};
@@ -11,4 +12,8 @@ pub use theme_detector::detect_system_theme;
 pub use hotkey::HotkeyManager;
 pub use edge_detector::EdgeDetector;
 pub use autostart::AutostartManager;
-pub use drives::{DriveInfo, DriveType, get_drives, get_wsl_distributions, get_quick_access};
+pub use drives::{
+    DriveInfo, DriveType, get_drives, get_wsl_distributions, get_quick_access,
+    DriveEnumerator, MountedVolume, SystemDriveEnumerator, resolve_alias_path, resolve_volume_path,
+    volume_for_path,
+};
@@ -0,0 +1,205 @@
+//! モニターの作業領域と起動時のウィンドウ配置
+
+use crate::data::models::WindowConfig;
+
+/// 設定がない場合のウィンドウサイズ（幅, 高さ）
+pub const DEFAULT_WINDOW_SIZE: [f32; 2] = [300.0, 1080.0];
+
+/// 設定がない場合のウィンドウ位置（x, y）
+pub const DEFAULT_WINDOW_POSITION: [f32; 2] = [1620.0, 0.0];
+
+/// ウィンドウの最小サイズ（作業領域に収める際もこれより小さくしない）
+pub const MIN_WINDOW_SIZE: f32 = 100.0;
+
+/// モニターの作業領域（タスクバーを除いた領域）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkArea {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// 起動時のウィンドウの位置とサイズ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+}
+
+impl WindowGeometry {
+    /// 設定からウィンドウの位置とサイズを決める
+    ///
+    /// 設定がない場合や、幅・高さ・座標が不正な値（0以下や数値でない値）の場合は既定値を使います。
+    pub fn from_config(config: Option<&WindowConfig>) -> Self {
+        let size = config
+            .filter(|c| is_valid_length(c.width) && is_valid_length(c.height))
+            .map(|c| [c.width, c.height])
+            .unwrap_or(DEFAULT_WINDOW_SIZE);
+        let position = config
+            .filter(|c| c.position.x.is_finite() && c.position.y.is_finite())
+            .map(|c| [c.position.x, c.position.y])
+            .unwrap_or(DEFAULT_WINDOW_POSITION);
+        Self { position, size }
+    }
+
+    /// ウィンドウを作業領域に収める
+    ///
+    /// 作業領域より大きい場合は作業領域の大きさまで縮め、
+    /// はみ出す場合は作業領域の内側に収まるよう位置をずらします。
+    pub fn clamp_to_work_area(self, area: &WorkArea) -> Self {
+        let width = self.size[0].min(area.width).max(MIN_WINDOW_SIZE);
+        let height = self.size[1].min(area.height).max(MIN_WINDOW_SIZE);
+        let x = clamp_axis(self.position[0], width, area.x, area.width);
+        let y = clamp_axis(self.position[1], height, area.y, area.height);
+        Self {
+            position: [x, y],
+            size: [width, height],
+        }
+    }
+}
+
+fn is_valid_length(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+/// 1軸分の位置を [start, start + extent - length] の範囲に収める
+///
+/// 作業領域よりウィンドウが大きい場合は先頭に揃えます。
+fn clamp_axis(position: f32, length: f32, start: f32, extent: f32) -> f32 {
+    let max = (start + extent - length).max(start);
+    position.clamp(start, max)
+}
+
+/// 指定した座標に最も近いモニターの作業領域を取得する
+///
+/// 座標は物理ピクセル単位です。取得できない場合は None を返します。
+#[cfg(target_os = "windows")]
+pub fn work_area_at(x: f32, y: f32) -> Option<WorkArea> {
+    use winapi::shared::windef::POINT;
+    use winapi::um::winuser::{GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTONEAREST};
+
+    unsafe {
+        let point = POINT { x: x as i32, y: y as i32 };
+        let monitor = MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST);
+        if monitor.is_null() {
+            return None;
+        }
+
+        let mut info: MONITORINFO = std::mem::zeroed();
+        info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+        if GetMonitorInfoW(monitor, &mut info) == 0 {
+            return None;
+        }
+
+        let rect = info.rcWork;
+        Some(WorkArea {
+            x: rect.left as f32,
+            y: rect.top as f32,
+            width: (rect.right - rect.left) as f32,
+            height: (rect.bottom - rect.top) as f32,
+        })
+    }
+}
+
+/// 指定した座標に最も近いモニターの作業領域を取得する（Windows以外では取得しない）
+#[cfg(not(target_os = "windows"))]
+pub fn work_area_at(_x: f32, _y: f32) -> Option<WorkArea> {
+    None
+}
+
+/// 起動時のウィンドウの位置とサイズを決める
+///
+/// 設定の値（なければ既定値）を、その位置にあるモニターの作業領域に収めます。
+pub fn initial_window_geometry(config: Option<&WindowConfig>) -> WindowGeometry {
+    let geometry = WindowGeometry::from_config(config);
+    match work_area_at(geometry.position[0], geometry.position[1]) {
+        Some(area) => geometry.clamp_to_work_area(&area),
+        None => geometry,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::WindowPosition;
+
+    fn window_config(width: f32, height: f32, x: f32, y: f32) -> WindowConfig {
+        WindowConfig {
+            width,
+            height,
+            position: WindowPosition { x, y },
+            always_on_top: false,
+            decorations: true,
+            double_escape_ms: 400,
+        }
+    }
+
+    const FULL_HD: WorkArea = WorkArea { x: 0.0, y: 0.0, width: 1920.0, height: 1040.0 };
+
+    #[test]
+    fn test_from_config_uses_defaults_when_unset() {
+        let geometry = WindowGeometry::from_config(None);
+        assert_eq!(geometry.size, DEFAULT_WINDOW_SIZE);
+        assert_eq!(geometry.position, DEFAULT_WINDOW_POSITION);
+
+        // 不正なサイズ・座標は既定値に置き換える
+        let config = window_config(0.0, f32::NAN, f32::INFINITY, 10.0);
+        let geometry = WindowGeometry::from_config(Some(&config));
+        assert_eq!(geometry.size, DEFAULT_WINDOW_SIZE);
+        assert_eq!(geometry.position, DEFAULT_WINDOW_POSITION);
+
+        let config = window_config(400.0, 800.0, 100.0, 50.0);
+        let geometry = WindowGeometry::from_config(Some(&config));
+        assert_eq!(geometry.size, [400.0, 800.0]);
+        assert_eq!(geometry.position, [100.0, 50.0]);
+    }
+
+    #[test]
+    fn test_clamp_keeps_geometry_inside_work_area() {
+        let geometry = WindowGeometry { position: [100.0, 50.0], size: [400.0, 800.0] };
+        assert_eq!(geometry.clamp_to_work_area(&FULL_HD), geometry);
+    }
+
+    #[test]
+    fn test_clamp_shrinks_oversized_window() {
+        // 既定の高さ1080はタスクバーを除いた高さ1040に収める
+        let geometry = WindowGeometry { position: [1620.0, 0.0], size: [300.0, 1080.0] };
+        let clamped = geometry.clamp_to_work_area(&FULL_HD);
+        assert_eq!(clamped.size, [300.0, 1040.0]);
+        assert_eq!(clamped.position, [1620.0, 0.0]);
+    }
+
+    #[test]
+    fn test_clamp_moves_window_back_onto_monitor() {
+        // 小さいモニターでは右端からはみ出す位置を左に寄せる
+        let area = WorkArea { x: 0.0, y: 0.0, width: 1366.0, height: 728.0 };
+        let geometry = WindowGeometry { position: [1620.0, 0.0], size: [300.0, 1080.0] };
+        let clamped = geometry.clamp_to_work_area(&area);
+        assert_eq!(clamped.size, [300.0, 728.0]);
+        assert_eq!(clamped.position, [1066.0, 0.0]);
+
+        // 左上にはみ出す位置は作業領域の左上に揃える
+        let geometry = WindowGeometry { position: [-500.0, -20.0], size: [300.0, 600.0] };
+        assert_eq!(geometry.clamp_to_work_area(&area).position, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_clamp_on_secondary_monitor_with_offset() {
+        // 左側に置いた2台目のモニター（負の座標、上部にタスクバー）
+        let area = WorkArea { x: -1280.0, y: 40.0, width: 1280.0, height: 984.0 };
+        let geometry = WindowGeometry { position: [-100.0, 0.0], size: [300.0, 1080.0] };
+        let clamped = geometry.clamp_to_work_area(&area);
+        assert_eq!(clamped.size, [300.0, 984.0]);
+        assert_eq!(clamped.position, [-300.0, 40.0]);
+    }
+
+    #[test]
+    fn test_clamp_respects_minimum_size() {
+        let area = WorkArea { x: 0.0, y: 0.0, width: 50.0, height: 50.0 };
+        let geometry = WindowGeometry { position: [0.0, 0.0], size: [300.0, 600.0] };
+        let clamped = geometry.clamp_to_work_area(&area);
+        assert_eq!(clamped.size, [MIN_WINDOW_SIZE, MIN_WINDOW_SIZE]);
+        assert_eq!(clamped.position, [0.0, 0.0]);
+    }
+}
@@ -1,4 +1,53 @@
 use crate::ui::theme::Theme;
+use std::time::{Duration, Instant};
+
+/// システムテーマの検出結果のキャッシュ
+///
+/// レジストリの読み取りを毎フレーム行わないよう、一定間隔でのみ検出し直します。
+#[derive(Debug, Clone)]
+pub struct SystemThemeCache {
+    /// 検出し直す間隔
+    interval: Duration,
+    /// 前回の検出結果と検出した時刻
+    cached: Option<(Theme, Instant)>,
+}
+
+impl Default for SystemThemeCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_INTERVAL)
+    }
+}
+
+impl SystemThemeCache {
+    /// 既定の検出間隔
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, cached: None }
+    }
+
+    /// 検出し直す間隔
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// システムテーマを取得（前回の検出から間隔が空いていれば検出し直す）
+    pub fn get(&mut self, now: Instant) -> Theme {
+        self.get_with(now, detect_system_theme)
+    }
+
+    /// 検出関数を指定してシステムテーマを取得
+    pub fn get_with(&mut self, now: Instant, detect: impl FnOnce() -> Theme) -> Theme {
+        match self.cached {
+            Some((theme, detected_at)) if now.duration_since(detected_at) < self.interval => theme,
+            _ => {
+                let theme = detect();
+                self.cached = Some((theme, now));
+                theme
+            }
+        }
+    }
+}
 
 /// システムテーマを検出
 pub fn detect_system_theme() -> Theme {
@@ -73,3 +122,28 @@ fn read_windows_theme() -> anyhow::Result<Theme> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_theme_cache_detects_only_after_interval() {
+        let mut cache = SystemThemeCache::new(Duration::from_secs(2));
+        let start = Instant::now();
+        let mut calls = 0;
+
+        let theme = cache.get_with(start, || { calls += 1; Theme::Dark });
+        assert_eq!(theme, Theme::Dark);
+
+        // 間隔内はキャッシュを返し、検出し直さない
+        let theme = cache.get_with(start + Duration::from_secs(1), || { calls += 1; Theme::Light });
+        assert_eq!(theme, Theme::Dark);
+        assert_eq!(calls, 1);
+
+        // 間隔が過ぎたら検出し直し、OSの変更を拾う
+        let theme = cache.get_with(start + Duration::from_secs(2), || { calls += 1; Theme::Light });
+        assert_eq!(theme, Theme::Light);
+        assert_eq!(calls, 2);
+    }
+}
@@ -0,0 +1,83 @@
+//! トップバーに表示するモード切替タブ
+
+use crate::app::state::BrowseMode;
+
+/// デフォルトのタブ（エイリアス + ディレクトリ）
+pub fn default_browse_tabs() -> Vec<BrowseMode> {
+    vec![BrowseMode::Alias, BrowseMode::Directory]
+}
+
+/// 設定のタブ名リストをタブのリストに変換
+///
+/// 不明なタブ名と重複は無視します。有効なタブが1つもない場合はデフォルトのタブを使います。
+/// 先頭のタブが起動時のモードになります。
+///
+/// # 引数
+/// * `names` - 設定ファイルのタブ名リスト
+///
+/// # 戻り値
+/// 表示順のタブリスト（空にはならない）
+pub fn parse_browse_tabs(names: &[String]) -> Vec<BrowseMode> {
+    let mut tabs: Vec<BrowseMode> = Vec::new();
+    for name in names {
+        match BrowseMode::parse(name) {
+            Some(mode) if !tabs.contains(&mode) => tabs.push(mode),
+            Some(_) => {}
+            None => log::warn!("不明なタブを無視しました: {}", name),
+        }
+    }
+
+    if tabs.is_empty() {
+        return default_browse_tabs();
+    }
+    tabs
+}
+
+/// Ctrl+Tab で切り替える次のモード
+///
+/// 現在のモードが表示するタブにない場合は先頭のタブに切り替えます。
+pub fn next_browse_mode(tabs: &[BrowseMode], current: BrowseMode) -> BrowseMode {
+    match tabs.iter().position(|mode| *mode == current) {
+        Some(index) => tabs[(index + 1) % tabs.len()],
+        None => tabs.first().copied().unwrap_or(current),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_browse_tabs_keeps_order_and_skips_invalid() {
+        let tabs = parse_browse_tabs(&names(&["Recent", "alias", "unknown", "recent"]));
+        assert_eq!(tabs, vec![BrowseMode::Recent, BrowseMode::Alias]);
+        // 先頭のタブが起動時のモード
+        assert_eq!(tabs[0], BrowseMode::Recent);
+    }
+
+    #[test]
+    fn test_parse_browse_tabs_falls_back_to_default() {
+        assert_eq!(parse_browse_tabs(&[]), default_browse_tabs());
+        assert_eq!(parse_browse_tabs(&names(&["unknown"])), default_browse_tabs());
+    }
+
+    #[test]
+    fn test_browse_mode_config_name_round_trip() {
+        for mode in BrowseMode::ALL {
+            assert_eq!(BrowseMode::parse(mode.config_name()), Some(mode));
+        }
+    }
+
+    #[test]
+    fn test_next_browse_mode_cycles_enabled_tabs() {
+        let tabs = vec![BrowseMode::Directory, BrowseMode::Recent];
+        assert_eq!(next_browse_mode(&tabs, BrowseMode::Directory), BrowseMode::Recent);
+        assert_eq!(next_browse_mode(&tabs, BrowseMode::Recent), BrowseMode::Directory);
+        // 非表示のタブからは先頭のタブへ
+        assert_eq!(next_browse_mode(&tabs, BrowseMode::Alias), BrowseMode::Directory);
+    }
+}
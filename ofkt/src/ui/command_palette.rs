@@ -0,0 +1,328 @@
+use eframe::egui;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+/// コマンドパレットから実行できるアクション
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaletteAction {
+    /// 現在のディレクトリに新しいフォルダを作成
+    NewFolder,
+    /// ライト/ダークテーマを切り替え
+    ToggleTheme,
+    /// 設定画面を開く
+    OpenSettings,
+    /// 選択中のファイル/フォルダをZIPに圧縮
+    Compress,
+    /// エイリアスをJSONファイルにエクスポート
+    ExportAliases,
+    /// エイリアス・クイックアクセス・設定をZIPファイルにエクスポート
+    ExportProfile,
+    /// 選択中のZIPファイルからプロファイルをインポート（変更内容を確認してから）
+    ImportProfile,
+    /// エイリアス/ディレクトリモードを切り替え
+    SwitchBrowseMode,
+    /// エイリアス追加ダイアログを開く
+    AddAlias,
+    /// パスが見つからないエイリアスを確認する
+    ValidateAliases,
+    /// 直前の操作を元に戻す
+    Undo,
+    /// 元に戻した操作をやり直す
+    Redo,
+    /// 現在のディレクトリを再読み込み
+    Reload,
+    /// 常に最前面に表示を切り替え
+    ToggleAlwaysOnTop,
+}
+
+/// コマンドパレットに登録されたコマンド
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+    /// 実行するアクション
+    pub action: PaletteAction,
+    /// 表示名（検索対象）
+    pub name: String,
+    /// 検索用の追加キーワード（英語名など）
+    pub keywords: String,
+}
+
+/// コマンドレジストリ
+///
+/// パレットに表示するコマンドの一覧を保持します。
+/// 新しいアクションは `register` で追加できます。
+pub struct CommandRegistry {
+    commands: Vec<PaletteCommand>,
+    fuzzy_matcher: SkimMatcherV2,
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandRegistry {
+    /// 既定のコマンドを登録した CommandRegistry を作成
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(PaletteAction::NewFolder, "新しいフォルダ", "new folder mkdir");
+        registry.register(PaletteAction::ToggleTheme, "テーマを切り替え", "toggle theme dark light");
+        registry.register(PaletteAction::OpenSettings, "設定を開く", "open settings preferences");
+        registry.register(PaletteAction::Compress, "ZIPに圧縮", "compress zip archive");
+        registry.register(PaletteAction::ExportAliases, "エイリアスをエクスポート", "export aliases");
+        registry.register(PaletteAction::ExportProfile, "プロファイルをエクスポート", "export profile backup zip");
+        registry.register(PaletteAction::ImportProfile, "プロファイルをインポート", "import profile restore zip");
+        registry.register(PaletteAction::SwitchBrowseMode, "モードを切り替え", "switch mode alias directory");
+        registry.register(PaletteAction::AddAlias, "エイリアスを追加", "add alias");
+        registry.register(PaletteAction::ValidateAliases, "壊れたエイリアスを確認", "validate broken aliases check");
+        registry.register(PaletteAction::Undo, "元に戻す", "undo");
+        registry.register(PaletteAction::Redo, "やり直し", "redo");
+        registry.register(PaletteAction::Reload, "再読み込み", "reload refresh");
+        registry.register(PaletteAction::ToggleAlwaysOnTop, "常に最前面に表示を切り替え", "always on top pin window");
+        registry
+    }
+
+    /// コマンドが登録されていない CommandRegistry を作成
+    pub fn empty() -> Self {
+        Self {
+            commands: Vec::new(),
+            fuzzy_matcher: SkimMatcherV2::default(),
+        }
+    }
+
+    /// コマンドを登録
+    ///
+    /// # 引数
+    /// * `action` - 実行するアクション
+    /// * `name` - 表示名
+    /// * `keywords` - 検索用の追加キーワード
+    pub fn register(&mut self, action: PaletteAction, name: impl Into<String>, keywords: impl Into<String>) {
+        self.commands.push(PaletteCommand {
+            action,
+            name: name.into(),
+            keywords: keywords.into(),
+        });
+    }
+
+    /// 登録済みのコマンド一覧を取得
+    pub fn commands(&self) -> &[PaletteCommand] {
+        &self.commands
+    }
+
+    /// クエリでコマンドを絞り込む
+    ///
+    /// 表示名とキーワードに対してあいまい検索を行い、スコアの高い順に返します。
+    /// クエリが空の場合は登録順に全件を返します。
+    ///
+    /// # 引数
+    /// * `query` - 検索クエリ
+    ///
+    /// # 戻り値
+    /// マッチしたコマンドのリスト
+    pub fn filter(&self, query: &str) -> Vec<&PaletteCommand> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return self.commands.iter().collect();
+        }
+
+        let mut matched: Vec<(i64, &PaletteCommand)> = self.commands
+            .iter()
+            .filter_map(|command| {
+                let name_score = self.fuzzy_matcher.fuzzy_match(&command.name.to_lowercase(), &query);
+                let keyword_score = self.fuzzy_matcher.fuzzy_match(&command.keywords.to_lowercase(), &query);
+                name_score.max(keyword_score).map(|score| (score, command))
+            })
+            .collect();
+
+        // スコア降順（同点は登録順を維持）
+        matched.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matched.into_iter().map(|(_, command)| command).collect()
+    }
+}
+
+/// コマンドパレットの操作結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteOutcome {
+    /// アクションを実行
+    Execute(PaletteAction),
+    /// パレットを閉じる
+    Cancel,
+}
+
+/// コマンドパレットコンポーネント
+pub struct CommandPalette {
+    registry: CommandRegistry,
+    id: egui::Id,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandPalette {
+    /// 新しい CommandPalette を作成
+    pub fn new() -> Self {
+        Self {
+            registry: CommandRegistry::new(),
+            id: egui::Id::new("command_palette_input"),
+        }
+    }
+
+    /// コマンドレジストリを取得
+    pub fn registry(&self) -> &CommandRegistry {
+        &self.registry
+    }
+
+    /// コマンドレジストリを可変で取得（コマンド追加用）
+    pub fn registry_mut(&mut self) -> &mut CommandRegistry {
+        &mut self.registry
+    }
+
+    /// コマンドパレットを描画
+    ///
+    /// # 引数
+    /// * `ctx` - egui コンテキスト
+    /// * `query` - 検索クエリ
+    /// * `selected_index` - 選択中の候補インデックス
+    ///
+    /// # 戻り値
+    /// 実行またはキャンセルされた場合は Some(PaletteOutcome) を返す
+    pub fn render(
+        &self,
+        ctx: &egui::Context,
+        query: &mut String,
+        selected_index: &mut usize,
+    ) -> Option<PaletteOutcome> {
+        let mut outcome = None;
+
+        let candidates = self.registry.filter(query);
+        if *selected_index >= candidates.len() {
+            *selected_index = candidates.len().saturating_sub(1);
+        }
+
+        // キー操作
+        ctx.input(|i| {
+            if i.key_pressed(egui::Key::Escape) {
+                outcome = Some(PaletteOutcome::Cancel);
+            }
+            if i.key_pressed(egui::Key::ArrowDown) && *selected_index + 1 < candidates.len() {
+                *selected_index += 1;
+            }
+            if i.key_pressed(egui::Key::ArrowUp) {
+                *selected_index = selected_index.saturating_sub(1);
+            }
+            if i.key_pressed(egui::Key::Enter) {
+                if let Some(command) = candidates.get(*selected_index) {
+                    outcome = Some(PaletteOutcome::Execute(command.action));
+                }
+            }
+        });
+
+        egui::Window::new("コマンドパレット")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(query)
+                        .id(self.id)
+                        .hint_text("コマンドを検索...")
+                        .desired_width(ui.available_width())
+                );
+                response.request_focus();
+
+                ui.separator();
+
+                if candidates.is_empty() {
+                    ui.label("一致するコマンドがありません");
+                }
+
+                for (index, command) in candidates.iter().enumerate() {
+                    if ui.selectable_label(index == *selected_index, &command.name).clicked() {
+                        outcome = Some(PaletteOutcome::Execute(command.action));
+                    }
+                }
+            });
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_has_default_commands() {
+        let registry = CommandRegistry::new();
+        let actions: Vec<PaletteAction> = registry.commands().iter().map(|c| c.action).collect();
+
+        assert!(actions.contains(&PaletteAction::NewFolder));
+        assert!(actions.contains(&PaletteAction::ToggleTheme));
+        assert!(actions.contains(&PaletteAction::OpenSettings));
+        assert!(actions.contains(&PaletteAction::Compress));
+        assert!(actions.contains(&PaletteAction::ExportAliases));
+        assert!(actions.contains(&PaletteAction::ExportProfile));
+        assert!(actions.contains(&PaletteAction::ImportProfile));
+    }
+
+    #[test]
+    fn test_filter_empty_query_returns_all() {
+        let registry = CommandRegistry::new();
+        let results = registry.filter("");
+
+        assert_eq!(results.len(), registry.commands().len());
+        // 登録順が維持される
+        assert_eq!(results[0].action, PaletteAction::NewFolder);
+    }
+
+    #[test]
+    fn test_filter_by_japanese_name() {
+        let registry = CommandRegistry::new();
+        let results = registry.filter("テーマ");
+
+        assert!(!results.is_empty());
+        assert_eq!(results[0].action, PaletteAction::ToggleTheme);
+    }
+
+    #[test]
+    fn test_filter_by_keyword() {
+        let registry = CommandRegistry::new();
+        let results = registry.filter("zip");
+
+        assert!(!results.is_empty());
+        assert_eq!(results[0].action, PaletteAction::Compress);
+    }
+
+    #[test]
+    fn test_filter_is_case_insensitive() {
+        let registry = CommandRegistry::new();
+        let results = registry.filter("EXPORT");
+
+        assert!(!results.is_empty());
+        assert_eq!(results[0].action, PaletteAction::ExportAliases);
+    }
+
+    #[test]
+    fn test_filter_no_match() {
+        let registry = CommandRegistry::new();
+        let results = registry.filter("xyzxyzxyz");
+
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_register_custom_command() {
+        let mut registry = CommandRegistry::empty();
+        assert!(registry.commands().is_empty());
+
+        registry.register(PaletteAction::Reload, "再読み込み", "reload");
+        let results = registry.filter("reload");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].action, PaletteAction::Reload);
+    }
+}
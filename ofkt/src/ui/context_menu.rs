@@ -22,6 +22,12 @@ pub enum MenuAction {
     Properties,
     /// 選択したアイテムの新しいエイリアスを作成
     AddAlias,
+    /// フォルダでターミナルを開く
+    OpenTerminal,
+    /// 同じフォルダにリンク（フォルダはジャンクション、ファイルはハードリンク）を作成
+    CreateLink,
+    /// 親フォルダをクイックアクセスに追加
+    AddParentToQuickAccess,
 }
 
 /// コンテキストメニューコンポーネント
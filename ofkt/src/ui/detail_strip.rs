@@ -0,0 +1,137 @@
+//! 選択中のエイリアスの詳細（解決後のパス・タグ・存在状態）を表示する帯
+
+use crate::data::models::FileAlias;
+use eframe::egui;
+use std::path::Path;
+
+/// 詳細表示の帯に表示する内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasDetail {
+    /// エイリアス名
+    pub name: String,
+    /// 環境変数などを展開した後の実際のパス
+    pub resolved_path: String,
+    /// エイリアスに保存されているパス
+    pub stored_path: String,
+    /// 解決後のパスが保存されているパスと異なるか
+    pub path_changed: bool,
+    /// カンマ区切りのタグ（タグがなければ空文字列）
+    pub tags: String,
+    /// 解決後のパスが存在するか
+    pub exists: bool,
+}
+
+impl AliasDetail {
+    /// エイリアスと解決後のパスから表示内容を組み立てる
+    ///
+    /// # 引数
+    /// * `alias` - 選択中のエイリアス
+    /// * `resolved` - 環境変数やボリュームの変更を解決した後のパス
+    /// * `exists` - 解決後のパスが存在するか
+    pub fn new(alias: &FileAlias, resolved: &Path, exists: bool) -> Self {
+        Self {
+            name: alias.alias.clone(),
+            resolved_path: resolved.display().to_string(),
+            stored_path: alias.path.display().to_string(),
+            path_changed: resolved != alias.path,
+            tags: alias.tags.join(", "),
+            exists,
+        }
+    }
+
+    /// 存在状態の表示文字列
+    pub fn status_label(&self) -> &'static str {
+        if self.exists {
+            "✔ 存在します"
+        } else {
+            "⚠ 見つかりません"
+        }
+    }
+}
+
+/// 詳細表示の帯を描画する
+pub fn render(ui: &mut egui::Ui, detail: &AliasDetail) {
+    let path_label = ui.add(
+        egui::Label::new(egui::RichText::new(&detail.resolved_path).monospace().small())
+            .truncate(),
+    );
+    if detail.path_changed {
+        path_label.on_hover_text(format!("登録されたパス: {}", detail.stored_path));
+    } else {
+        path_label.on_hover_text(detail.resolved_path.as_str());
+    }
+
+    ui.horizontal(|ui| {
+        let status = egui::RichText::new(detail.status_label()).small();
+        if detail.exists {
+            ui.label(status.weak());
+        } else {
+            ui.label(status.color(ui.visuals().warn_fg_color));
+        }
+
+        if !detail.tags.is_empty() {
+            ui.separator();
+            ui.add(
+                egui::Label::new(egui::RichText::new(format!("🏷 {}", detail.tags)).small())
+                    .truncate(),
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::path::expand_path_with;
+    use std::path::PathBuf;
+
+    fn alias(path: &str, tags: &[&str]) -> FileAlias {
+        FileAlias {
+            id: "id-1".to_string(),
+            alias: "プロジェクト".to_string(),
+            path: PathBuf::from(path),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            color: None,
+            created_at: chrono::Utc::now(),
+            last_accessed: chrono::Utc::now(),
+            is_favorite: false,
+            open_action: crate::data::models::OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        }
+    }
+
+    #[test]
+    fn test_detail_with_tags_and_expanded_path() {
+        let alias = alias("${PROJECT}/docs", &["work", "重要"]);
+        let resolved = expand_path_with(
+            &alias.path,
+            |name| (name == "PROJECT").then(|| "/work/project".to_string()),
+            None,
+        );
+
+        let detail = AliasDetail::new(&alias, &resolved, false);
+        assert_eq!(detail.name, "プロジェクト");
+        assert_eq!(detail.resolved_path, "/work/project/docs");
+        assert_eq!(detail.stored_path, "${PROJECT}/docs");
+        assert!(detail.path_changed);
+        assert_eq!(detail.tags, "work, 重要");
+        assert!(!detail.exists);
+        assert_eq!(detail.status_label(), "⚠ 見つかりません");
+    }
+
+    #[test]
+    fn test_detail_without_tags_and_unchanged_path() {
+        let alias = alias("/work/report.txt", &[]);
+        let detail = AliasDetail::new(&alias, &alias.path, true);
+        assert_eq!(detail.resolved_path, "/work/report.txt");
+        assert!(!detail.path_changed);
+        assert_eq!(detail.tags, "");
+        assert_eq!(detail.status_label(), "✔ 存在します");
+    }
+}
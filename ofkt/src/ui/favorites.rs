@@ -57,6 +57,7 @@ impl Default for FavoritesView {
 mod tests {
     use super::*;
     use std::path::PathBuf;
+    use crate::data::models::OpenAction;
     use chrono::Utc;
 
     fn create_test_alias(alias: &str, path: &str, is_favorite: bool) -> FileAlias {
@@ -70,6 +71,14 @@ mod tests {
             created_at: now,
             last_accessed: now,
             is_favorite,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         }
     }
 
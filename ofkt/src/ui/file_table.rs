@@ -0,0 +1,338 @@
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use std::path::PathBuf;
+use crate::core::directory_browser::{SortKey, SortOrder};
+use crate::data::models::DirectoryEntry;
+use crate::ui::file_tree::{show_failed_badge, EntryRowContext, CUT_ENTRY_OPACITY, PASTED_ENTRY_FILL};
+
+/// テーブル表示の列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableColumn {
+    /// 名前
+    Name,
+    /// サイズ
+    Size,
+    /// 種類
+    Type,
+    /// 更新日時
+    Modified,
+}
+
+impl TableColumn {
+    /// 表示順の全列
+    pub const ALL: [TableColumn; 4] = [
+        TableColumn::Name,
+        TableColumn::Size,
+        TableColumn::Type,
+        TableColumn::Modified,
+    ];
+
+    /// 列に対応するソートキーを取得
+    pub fn sort_key(&self) -> SortKey {
+        match self {
+            TableColumn::Name => SortKey::Name,
+            TableColumn::Size => SortKey::Size,
+            TableColumn::Type => SortKey::Type,
+            TableColumn::Modified => SortKey::Modified,
+        }
+    }
+
+    /// 列見出しのラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            TableColumn::Name => "名前",
+            TableColumn::Size => "サイズ",
+            TableColumn::Type => "種類",
+            TableColumn::Modified => "更新日時",
+        }
+    }
+}
+
+/// 列見出しクリック時の新しいソート設定を計算
+///
+/// 現在と同じ列をクリックした場合は昇順/降順を反転し、
+/// 別の列をクリックした場合はその列の昇順にする。
+///
+/// # 引数
+/// * `current_key` - 現在のソートキー
+/// * `current_order` - 現在のソート順
+/// * `clicked` - クリックされた列
+///
+/// # 戻り値
+/// 新しい (ソートキー, ソート順)
+pub fn sort_for_header_click(
+    current_key: SortKey,
+    current_order: SortOrder,
+    clicked: TableColumn,
+) -> (SortKey, SortOrder) {
+    let key = clicked.sort_key();
+    if key == current_key {
+        (key, current_order.reversed())
+    } else {
+        (key, SortOrder::Ascending)
+    }
+}
+
+/// テーブル表示の操作結果
+#[derive(Debug, Clone, Default)]
+pub struct FileTableResponse {
+    /// クリックで選択されたパス
+    pub selected_path: Option<PathBuf>,
+    /// ダブルクリックで開くパス
+    pub open_path: Option<PathBuf>,
+    /// 右クリックされたか
+    pub is_right_click: bool,
+    /// クリックされた列見出し
+    pub clicked_column: Option<TableColumn>,
+}
+
+/// ディレクトリモード用のテーブル表示コンポーネント
+pub struct FileTableView {
+    /// 行の高さ
+    row_height: f32,
+    /// 前回の描画時に選択されていた行（選択が変わったときだけスクロールするため）
+    last_selected_index: Option<usize>,
+}
+
+impl Default for FileTableView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileTableView {
+    /// 新しい FileTableView を作成
+    pub fn new() -> Self {
+        Self {
+            row_height: 20.0,
+            last_selected_index: None,
+        }
+    }
+
+    /// 選択が前回の描画から変わった場合、スクロールして表示する行を返す
+    ///
+    /// 毎フレームスクロールするとマウスホイールで選択行から離れられないため、
+    /// キー操作などで選択が変わったフレームだけスクロールします。
+    fn take_scroll_target(&mut self, selected_index: Option<usize>) -> Option<usize> {
+        if selected_index == self.last_selected_index {
+            return None;
+        }
+        self.last_selected_index = selected_index;
+        selected_index
+    }
+
+    /// エントリをテーブル形式で描画
+    ///
+    /// # 引数
+    /// * `ui` - egui UI コンテキスト
+    /// * `entries` - 表示するエントリ
+    /// * `sort_key` - 現在のソートキー（見出しの矢印表示用）
+    /// * `sort_order` - 現在のソート順（見出しの矢印表示用）
+    /// * `context` - 選択・切り取り・ペースト直後・エラーバッジなど行の表示に使う状態
+    ///
+    /// # 戻り値
+    /// FileTableResponse - クリック・見出し操作の結果
+    pub fn render(
+        &mut self,
+        ui: &mut egui::Ui,
+        entries: &[DirectoryEntry],
+        sort_key: SortKey,
+        sort_order: SortOrder,
+        context: EntryRowContext<'_>,
+    ) -> FileTableResponse {
+        let mut result = FileTableResponse::default();
+
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(160.0).at_least(60.0).clip(true))
+            .column(Column::initial(70.0).at_least(40.0))
+            .column(Column::initial(60.0).at_least(40.0))
+            .column(Column::remainder().at_least(80.0));
+
+        // 選択が変わったときだけ、その行が見えるようにスクロール
+        if let Some(index) = self.take_scroll_target(context.selected_index) {
+            table = table.scroll_to_row(index, None);
+        }
+
+        table
+            .header(self.row_height, |mut header| {
+                for column in TableColumn::ALL {
+                    header.col(|ui| {
+                        let arrow = if column.sort_key() == sort_key {
+                            match sort_order {
+                                SortOrder::Ascending => " ▲",
+                                SortOrder::Descending => " ▼",
+                            }
+                        } else {
+                            ""
+                        };
+                        if ui.button(format!("{}{}", column.label(), arrow)).clicked() {
+                            result.clicked_column = Some(column);
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(self.row_height, entries.len(), |mut row| {
+                    let index = row.index();
+                    let entry = &entries[index];
+                    let is_selected = context.selected_index == Some(index);
+                    row.set_selected(is_selected || context.multi_selected.contains(&entry.path));
+                    let is_pasted = context.is_pasted(&entry.path) && !is_selected;
+
+                    // 切り取り中の行は薄く表示
+                    let opacity = if context.clipboard.is_pending_cut(&entry.path) {
+                        CUT_ENTRY_OPACITY
+                    } else {
+                        1.0
+                    };
+
+                    row.col(|ui| {
+                        ui.multiply_opacity(opacity);
+                        // ペースト直後の行は名前欄を緑背景で表示
+                        if is_pasted {
+                            ui.painter().rect_filled(ui.max_rect(), 0.0, PASTED_ENTRY_FILL);
+                        }
+                        let icon = if entry.is_directory {
+                            if entry.is_wsl_path() { "🐧" } else { "📁" }
+                        } else {
+                            "📄"
+                        };
+                        ui.label(format!("{} {}", icon, entry.name));
+                        show_failed_badge(ui, context.failed_badges, &entry.path);
+                    });
+                    row.col(|ui| {
+                        ui.multiply_opacity(opacity);
+                        if let Some(size) = entry.size.filter(|_| entry.is_file()) {
+                            ui.label(format_size(size));
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.multiply_opacity(opacity);
+                        ui.label(entry_type_label(entry));
+                    });
+                    row.col(|ui| {
+                        ui.multiply_opacity(opacity);
+                        if let Some(modified) = entry.modified {
+                            let local = modified.with_timezone(&chrono::Local);
+                            ui.label(local.format("%Y/%m/%d %H:%M").to_string());
+                        }
+                    });
+
+                    let response = row.response();
+                    // シングルクリック → 選択のみ
+                    if response.clicked() {
+                        result.selected_path = Some(entry.path.clone());
+                    }
+                    // ダブルクリック → 開く
+                    if response.double_clicked() {
+                        result.open_path = Some(entry.path.clone());
+                    }
+                    // 右クリック
+                    if response.secondary_clicked() {
+                        result.selected_path = Some(entry.path.clone());
+                        result.is_right_click = true;
+                    }
+                });
+            });
+
+        result
+    }
+}
+
+/// 種類列の表示文字列を取得
+fn entry_type_label(entry: &DirectoryEntry) -> String {
+    if entry.is_directory {
+        return "フォルダ".to_string();
+    }
+    entry.path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_uppercase())
+        .unwrap_or_else(|| "ファイル".to_string())
+}
+
+/// バイト数を読みやすい単位に変換
+pub(crate) fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_sort_key_mapping() {
+        assert_eq!(TableColumn::Name.sort_key(), SortKey::Name);
+        assert_eq!(TableColumn::Size.sort_key(), SortKey::Size);
+        assert_eq!(TableColumn::Type.sort_key(), SortKey::Type);
+        assert_eq!(TableColumn::Modified.sort_key(), SortKey::Modified);
+    }
+
+    #[test]
+    fn test_header_click_same_column_toggles_order() {
+        let (key, order) = sort_for_header_click(SortKey::Name, SortOrder::Ascending, TableColumn::Name);
+        assert_eq!(key, SortKey::Name);
+        assert_eq!(order, SortOrder::Descending);
+
+        let (key, order) = sort_for_header_click(SortKey::Name, SortOrder::Descending, TableColumn::Name);
+        assert_eq!(key, SortKey::Name);
+        assert_eq!(order, SortOrder::Ascending);
+    }
+
+    #[test]
+    fn test_header_click_other_column_resets_to_ascending() {
+        let (key, order) = sort_for_header_click(SortKey::Name, SortOrder::Descending, TableColumn::Size);
+        assert_eq!(key, SortKey::Size);
+        assert_eq!(order, SortOrder::Ascending);
+
+        let (key, order) = sort_for_header_click(SortKey::Size, SortOrder::Ascending, TableColumn::Modified);
+        assert_eq!(key, SortKey::Modified);
+        assert_eq!(order, SortOrder::Ascending);
+    }
+
+    #[test]
+    fn test_scroll_only_when_selection_changes() {
+        let mut view = FileTableView::new();
+        assert_eq!(view.take_scroll_target(Some(3)), Some(3));
+        // 同じ選択のままではスクロールしない（ホイールで離れられるように）
+        assert_eq!(view.take_scroll_target(Some(3)), None);
+        assert_eq!(view.take_scroll_target(Some(4)), Some(4));
+        assert_eq!(view.take_scroll_target(None), None);
+        assert_eq!(view.take_scroll_target(Some(4)), Some(4));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KB");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(1024 * 1024), "1.0 MB");
+    }
+
+    #[test]
+    fn test_entry_type_label() {
+        let dir = DirectoryEntry::new("dir".to_string(), PathBuf::from("/tmp/dir"), true, None, None, false, false);
+        assert_eq!(entry_type_label(&dir), "フォルダ");
+
+        let file = DirectoryEntry::new("a.txt".to_string(), PathBuf::from("/tmp/a.txt"), false, Some(1), None, false, false);
+        assert_eq!(entry_type_label(&file), "TXT");
+
+        let no_ext = DirectoryEntry::new("README".to_string(), PathBuf::from("/tmp/README"), false, Some(1), None, false, false);
+        assert_eq!(entry_type_label(&no_ext), "ファイル");
+    }
+}
@@ -1,8 +1,121 @@
 use eframe::egui;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::app::state::{FailedPathBadges, PastedFileHighlight};
+use crate::core::clipboard::ClipboardState;
+use crate::core::unseen_changes::ModifiedTimeCache;
 use crate::data::models::FileAlias;
 use crate::data::models::DirectoryEntry;
+use crate::ui::row_columns::{assemble_row, default_row_columns, RowColumn, RowData};
+use crate::utils::path::display_path;
+
+/// 切り取り済み（ペースト待ち）エントリの不透明度
+pub const CUT_ENTRY_OPACITY: f32 = 0.45;
+
+/// ペースト直後のエントリの背景色
+pub(crate) const PASTED_ENTRY_FILL: egui::Color32 = egui::Color32::from_rgb(200, 255, 200);
+
+/// ディレクトリ表示の各行の描画に使う、フレームごとの状態（ツリー表示・テーブル表示で共通）
+#[derive(Clone, Copy)]
+pub struct EntryRowContext<'a> {
+    /// 選択中のインデックス
+    pub selected_index: Option<usize>,
+    /// 複数選択されているパス
+    pub multi_selected: &'a HashSet<PathBuf>,
+    /// ペースト直後のハイライト情報
+    pub pasted_highlight: Option<&'a PastedFileHighlight>,
+    /// 切り取り中のエントリを薄く表示するためのクリップボード状態
+    pub clipboard: &'a ClipboardState,
+    /// 操作に失敗した行のエラーバッジ
+    pub failed_badges: &'a FailedPathBadges,
+}
+
+impl EntryRowContext<'_> {
+    /// ペースト直後のハイライト対象か
+    pub(crate) fn is_pasted(&self, path: &Path) -> bool {
+        self.pasted_highlight.is_some_and(|h| h.contains(path))
+    }
+}
+
+/// 操作に失敗した行にエラーバッジを表示
+pub(crate) fn show_failed_badge(ui: &mut egui::Ui, failed_badges: &FailedPathBadges, path: &Path) {
+    if failed_badges.should_badge(path, std::time::Instant::now()) {
+        ui.colored_label(ui.visuals().error_fg_color, "⚠")
+            .on_hover_text("直前の操作に失敗しました");
+    }
+}
+
+/// 行をドラッグ可能にし、ドラッグ開始時にパスをドラッグ＆ドロップのペイロードに設定
+///
+/// ドロップ先では `response.dnd_release_payload::<PathBuf>()` で受け取れます。
+fn make_path_draggable(response: egui::Response, path: &Path) -> egui::Response {
+    let response = response.interact(egui::Sense::drag());
+    if response.drag_started() {
+        egui::DragAndDrop::set_payload(&response.ctx, path.to_path_buf());
+    }
+    response
+}
+
+/// タグ別表示のグループ
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagGroup {
+    /// タグ名（タグのないエイリアスのグループは None）
+    pub tag: Option<String>,
+    /// グループに含まれるエイリアスのインデックス（元の並び順）
+    pub indices: Vec<usize>,
+}
+
+/// エイリアスをタグごとのグループに分ける
+///
+/// 複数のタグを持つエイリアスはそれぞれのグループに含まれます。
+/// グループはタグ名順に並べ、タグのないエイリアスのグループを最後に置きます。
+pub fn group_aliases_by_tag(items: &[FileAlias]) -> Vec<TagGroup> {
+    let mut tagged: std::collections::BTreeMap<&str, Vec<usize>> = std::collections::BTreeMap::new();
+    let mut untagged = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        if item.tags.is_empty() {
+            untagged.push(index);
+            continue;
+        }
+        for tag in &item.tags {
+            let indices = tagged.entry(tag.as_str()).or_default();
+            // 同じタグが重複して付いていても1回だけ含める
+            if indices.last() != Some(&index) {
+                indices.push(index);
+            }
+        }
+    }
+
+    let mut groups: Vec<TagGroup> = tagged
+        .into_iter()
+        .map(|(tag, indices)| TagGroup { tag: Some(tag.to_string()), indices })
+        .collect();
+    if !untagged.is_empty() {
+        groups.push(TagGroup { tag: None, indices: untagged });
+    }
+    groups
+}
+
+/// お気に入りの行の背景色
+const FAVORITE_ROW_TINT: egui::Color32 = egui::Color32::from_rgba_premultiplied(40, 32, 0, 40);
+
+/// 複数選択中の行の背景色（選択色を薄くしてフォーカス中の行と区別する）
+pub(crate) fn multi_selected_row_fill(ui: &egui::Ui, is_multi_selected: bool) -> egui::Color32 {
+    if is_multi_selected {
+        ui.visuals().selection.bg_fill.gamma_multiply(0.4)
+    } else {
+        egui::Color32::TRANSPARENT
+    }
+}
+
+/// お気に入りとして行の背景に色を付けるか
+///
+/// お気に入りはアイコンを ⭐ にしたうえで、選択中でなければ背景にも色を付けます
+/// （選択中は選択の色と重ならないようにする）。
+pub fn highlight_as_favorite(alias: &FileAlias, is_selected: bool) -> bool {
+    alias.is_favorite && !is_selected
+}
 
 /// ファイルツリー表示コンポーネント
 pub struct FileTreeView {
@@ -11,6 +124,15 @@ pub struct FileTreeView {
 
     /// アイテムの高さ（px）
     item_height: f32,
+
+    /// エイリアス行に表示する列
+    row_columns: Vec<RowColumn>,
+
+    /// リンク先の更新日時のキャッシュ（未確認の変更の印に使う）
+    modified_cache: ModifiedTimeCache,
+
+    /// 右クリックメニューで「修復」が選ばれたエイリアスのID
+    repair_request: Option<String>,
 }
 
 impl Default for FileTreeView {
@@ -28,9 +150,22 @@ impl FileTreeView {
         Self {
             expanded_nodes: HashSet::new(),
             item_height: Self::DEFAULT_ITEM_HEIGHT,
+            row_columns: default_row_columns(),
+            modified_cache: ModifiedTimeCache::default(),
+            repair_request: None,
         }
     }
 
+    /// 右クリックメニューで「修復」が選ばれたエイリアスのIDを取り出す
+    pub fn take_repair_request(&mut self) -> Option<String> {
+        self.repair_request.take()
+    }
+
+    /// エイリアス行に表示する列を設定
+    pub fn set_row_columns(&mut self, columns: Vec<RowColumn>) {
+        self.row_columns = columns;
+    }
+
     /// ツリーを描画（仮想化対応）
     ///
     /// # 戻り値
@@ -103,6 +238,69 @@ impl FileTreeView {
         (selected_result, open_result)
     }
 
+    /// エイリアスのリストをタグごとのグループに分けてレンダリング
+    ///
+    /// # 引数
+    /// * `collapsed_tags` - 折りたたんでいるタグ名
+    ///
+    /// # 戻り値
+    /// (シングルクリックで選択されたインデックス, ダブルクリックで開くインデックス,
+    ///  折りたたみ状態を切り替えたタグ名)
+    pub fn render_grouped_by_tag(
+        &mut self,
+        ui: &mut egui::Ui,
+        items: &[FileAlias],
+        selected_index: Option<usize>,
+        collapsed_tags: &HashSet<String>,
+    ) -> (Option<usize>, Option<usize>, Option<String>) {
+        let mut selected_result = None;
+        let mut open_result = None;
+        let mut toggled_tag = None;
+
+        for group in group_aliases_by_tag(items) {
+            // お気に入りをグループの上部に表示
+            let mut indices = group.indices;
+            indices.sort_by_key(|&index| !items[index].is_favorite);
+
+            let (id, title, open) = match group.tag {
+                Some(ref tag) => (
+                    format!("tag_group_{}", tag),
+                    format!("🏷 {} ({})", tag, indices.len()),
+                    !collapsed_tags.contains(tag),
+                ),
+                None => (
+                    "tag_group_untagged".to_string(),
+                    format!("タグなし ({})", indices.len()),
+                    true,
+                ),
+            };
+
+            let response = egui::CollapsingHeader::new(title)
+                .id_salt(id)
+                .open(Some(open))
+                .show(ui, |ui| {
+                    for &index in &indices {
+                        let (selected, open) = self.render_item(ui, &items[index], index, selected_index);
+                        if selected.is_some() {
+                            selected_result = selected;
+                        }
+                        if open.is_some() {
+                            open_result = open;
+                        }
+                    }
+                });
+
+            // タグのないグループは折りたたみ状態を保存しない
+            if response.header_response.clicked() {
+                if let Some(tag) = group.tag {
+                    toggled_tag = Some(tag);
+                }
+            }
+        }
+
+        (selected_result, open_result, toggled_tag)
+    }
+
     /// 個別のアイテムを描画（再帰的）
     ///
     /// # 戻り値
@@ -117,39 +315,97 @@ impl FileTreeView {
         let is_expanded = self.is_expanded(&item.id);
         let is_folder = item.path.is_dir();
         let is_selected = selected_index == Some(index);
+        let has_unseen_changes = self.modified_cache.has_unseen_changes(item);
         let mut selected = None;
         let mut open = None;
 
-        ui.horizontal(|ui| {
-            // 展開/折りたたみアイコン（フォルダのみ）
-            if is_folder {
-                let icon = if is_expanded { "▼" } else { "▶" };
-                if ui.button(icon).clicked() {
-                    self.toggle_expansion(&item.id);
+        // お気に入りの行は背景に色を付ける（選択中は選択の色を優先）
+        let row_fill = if highlight_as_favorite(item, is_selected) {
+            FAVORITE_ROW_TINT
+        } else {
+            egui::Color32::TRANSPARENT
+        };
+
+        egui::Frame::none().fill(row_fill).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                // 展開/折りたたみアイコン（フォルダのみ）
+                if is_folder {
+                    let icon = if is_expanded { "▼" } else { "▶" };
+                    if ui.button(icon).clicked() {
+                        self.toggle_expansion(&item.id);
+                    }
+                } else {
+                    ui.add_space(20.0);
                 }
-            } else {
-                ui.add_space(20.0);
-            }
 
-            // アイコン
-            let icon = self.get_icon(item);
-            ui.label(icon);
+                // アイコン
+                let icon = self.get_icon(item);
+                ui.label(icon);
+
+                // パスが見つからない（移動・削除された）エイリアスは赤い印を付ける
+                if item.is_broken {
+                    ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                        .on_hover_text("パスが見つかりません（移動または削除された可能性があります）");
+                } else if item.is_unreachable {
+                    ui.colored_label(ui.visuals().warn_fg_color, "?")
+                        .on_hover_text("ネットワークパスに接続できないため確認できません");
+                }
 
-            // エイリアス名（選択可能）
-            let response = ui.selectable_label(is_selected, &item.alias);
+                // 前回開いた後にリンク先が更新されていれば印を付ける
+                if has_unseen_changes {
+                    ui.label(egui::RichText::new("●").small().color(ui.visuals().warn_fg_color))
+                        .on_hover_text("前回開いた後に更新されています");
+                }
 
-            // シングルクリック → 選択のみ
-            if response.clicked() {
-                selected = Some(index);
-            }
+                // 設定された列を固定幅で表示（サイズ・更新日時がある場合のみメタデータを取得）
+                let needs_metadata = self.row_columns
+                    .iter()
+                    .any(|c| matches!(c, RowColumn::Size | RowColumn::Modified));
+                let data = RowData::from_alias(item, needs_metadata);
+
+                for (column, text) in assemble_row(&self.row_columns, &data) {
+                    if column == RowColumn::Name {
+                        // エイリアス名（選択可能、パスが見つからない場合は警告色）
+                        let text = if item.is_broken {
+                            egui::RichText::new(text).color(ui.visuals().error_fg_color)
+                        } else {
+                            egui::RichText::new(text)
+                        };
+                        let label = egui::SelectableLabel::new(is_selected, text);
+                        let response = match column.fixed_width() {
+                            Some(width) => ui.add_sized([width, self.item_height], label),
+                            None => ui.add(label),
+                        }
+                        .on_hover_text(display_path(&item.path));
 
-            // ダブルクリック → 開く
-            if response.double_clicked() {
-                open = Some(index);
-            }
+                        // シングルクリック → 選択のみ
+                        if response.clicked() {
+                            selected = Some(index);
+                        }
+
+                        // ダブルクリック → 開く
+                        if response.double_clicked() {
+                            open = Some(index);
+                        }
 
-            // パス
-            ui.label(format!("-> {}", item.path.display()));
+                        // 壊れたエイリアスは右クリックから修復できる
+                        if item.is_broken || item.is_unreachable {
+                            response.context_menu(|ui| {
+                                if ui.button("修復").clicked() {
+                                    self.repair_request = Some(item.id.clone());
+                                    ui.close_menu();
+                                }
+                            });
+                        }
+                    } else {
+                        let label = egui::Label::new(text).truncate();
+                        match column.fixed_width() {
+                            Some(width) => ui.add_sized([width, self.item_height], label),
+                            None => ui.add(label),
+                        };
+                    }
+                }
+            });
         });
 
         (selected, open)
@@ -272,8 +528,8 @@ impl FileTreeView {
     /// - `entry`: レンダリングするディレクトリエントリ
     /// - `flat_index`: グローバルフラットインデックスのアキュムレータ
     /// - `expanded_dirs`: 展開されているディレクトリのセット
-    /// - `selected_index`: 選択されているインデックス
     /// - `level`: 階層レベル（0 = ルート）
+    /// - `context`: 選択・切り取り・ペースト直後・エラーバッジなど行の表示に使う状態
     ///
     /// # 戻り値
     /// (シングルクリックで選択されたパス, ダブルクリックで開くパス, 右クリックかどうか)
@@ -283,9 +539,8 @@ impl FileTreeView {
         entry: &DirectoryEntry,
         flat_index: &mut usize,
         expanded_dirs: &mut HashSet<PathBuf>,
-        selected_index: Option<usize>,
         level: usize,
-        pasted_highlight: Option<&crate::app::state::PastedFileHighlight>,
+        context: EntryRowContext<'_>,
     ) -> (Option<PathBuf>, Option<PathBuf>, bool) {
         // ディレクトリのみ処理
         if !entry.is_directory {
@@ -297,18 +552,24 @@ impl FileTreeView {
         *flat_index += 1;  // 次のアイテムのためにインクリメント
 
         let is_expanded = expanded_dirs.contains(&entry.path);
-        let is_selected = selected_index == Some(current_index);
+        let is_selected = context.selected_index == Some(current_index);
         let icon = if is_expanded { "▼" } else { "▶" };
         let mut selected_result: Option<PathBuf> = None;
         let mut open_result: Option<PathBuf> = None;
         let mut is_right_click = false;
 
         // ペースト直後のハイライト判定
-        let is_pasted = pasted_highlight
-            .map(|h| h.contains(&entry.path))
-            .unwrap_or(false);
+        let is_pasted = context.is_pasted(&entry.path);
+
+        let is_cut = context.clipboard.is_pending_cut(&entry.path);
+        let row_fill = multi_selected_row_fill(ui, context.multi_selected.contains(&entry.path));
+
+        egui::Frame::none().fill(row_fill).show(ui, |ui| ui.horizontal(|ui| {
+            // 切り取り中は薄く表示
+            if is_cut {
+                ui.multiply_opacity(CUT_ENTRY_OPACITY);
+            }
 
-        ui.horizontal(|ui| {
             // 階層レベルに応じたインデント
             ui.add_space(level as f32 * 20.0);
 
@@ -329,7 +590,7 @@ impl FileTreeView {
                 // ペースト直後: 緑背景（事前に設定）
                 ui.scope(|ui| {
                     // 背景色を設定
-                    ui.visuals_mut().widgets.inactive.weak_bg_fill = egui::Color32::from_rgb(200, 255, 200);
+                    ui.visuals_mut().widgets.inactive.weak_bg_fill = PASTED_ENTRY_FILL;
                     ui.selectable_label(is_selected, label)
                 }).inner
             } else if is_pasted && is_selected {
@@ -344,6 +605,10 @@ impl FileTreeView {
                 ui.selectable_label(is_selected, label)
             };
 
+            // クイックアクセスなどへドラッグできるようにする
+            let response = make_path_draggable(response, &entry.path);
+            show_failed_badge(ui, context.failed_badges, &entry.path);
+
             // シングルクリック → 選択のみ
             if response.clicked() {
                 selected_result = Some(entry.path.clone());
@@ -357,7 +622,7 @@ impl FileTreeView {
                 selected_result = Some(entry.path.clone());
                 is_right_click = true;
             }
-        });
+        }));
 
         // 展開されている場合、サブアイテムを再帰的に表示
         if is_expanded {
@@ -386,9 +651,8 @@ impl FileTreeView {
                                 sub_entry,
                                 flat_index,  // アキュムレータを渡す（インクリメントされ続ける）
                                 expanded_dirs,
-                                selected_index,  // 選択状態を渡す
                                 level + 1,  // 階層レベルを1つ増やす
-                                pasted_highlight,  // ハイライト情報を渡す
+                                context,  // 選択状態・ハイライト情報を渡す
                             );
 
                             if sub_selected.is_some() {
@@ -401,8 +665,12 @@ impl FileTreeView {
                         } else {
                             // ファイルはシンプルに表示
                             ui.horizontal(|ui| {
+                                if context.clipboard.is_pending_cut(&sub_entry.path) {
+                                    ui.multiply_opacity(CUT_ENTRY_OPACITY);
+                                }
                                 ui.add_space((level + 1) as f32 * 20.0);
                                 let response = ui.label(format!("📄 {}", sub_entry.name));
+                                show_failed_badge(ui, context.failed_badges, &sub_entry.path);
 
                                 // シングルクリック → 選択のみ
                                 if response.clicked() {
@@ -433,7 +701,7 @@ impl FileTreeView {
     /// - `ui`: egui UI コンテキスト
     /// - `entries`: レンダリングするエントリのリスト
     /// - `expanded_dirs`: 展開されているディレクトリのセット
-    /// - `selected_index`: 選択されているインデックス
+    /// - `context`: 選択・切り取り・ペースト直後・エラーバッジなど行の表示に使う状態
     ///
     /// # 戻り値
     /// (シングルクリックで選択されたパス, ダブルクリックで開くパス, 右クリックかどうか, 総アイテム数)
@@ -442,8 +710,7 @@ impl FileTreeView {
         ui: &mut egui::Ui,
         entries: &[DirectoryEntry],
         expanded_dirs: &mut HashSet<PathBuf>,
-        selected_index: Option<usize>,
-        pasted_highlight: Option<&crate::app::state::PastedFileHighlight>,
+        context: EntryRowContext<'_>,
     ) -> (Option<PathBuf>, Option<PathBuf>, bool, usize) {
         let mut selected_result: Option<PathBuf> = None;
         let mut open_result: Option<PathBuf> = None;
@@ -451,7 +718,7 @@ impl FileTreeView {
         let mut flat_index = 0;  // アキュムレータを初期化
 
         for entry in entries.iter() {
-            let is_selected = selected_index == Some(flat_index);
+            let is_selected = context.selected_index == Some(flat_index);
 
             if entry.is_directory {
                 // ディレクトリは render_tree_node() に委譲
@@ -460,9 +727,8 @@ impl FileTreeView {
                     entry,
                     &mut flat_index,  // アキュムレータを渡す
                     expanded_dirs,
-                    selected_index,
                     0,  // ルートレベル（階層 = 0）
-                    pasted_highlight,  // ハイライト情報を渡す
+                    context,  // 選択状態・ハイライト情報を渡す
                 );
 
                 if sub_selected.is_some() {
@@ -474,9 +740,15 @@ impl FileTreeView {
                 }
             } else {
                 // ファイルは従来通りの処理
-                ui.horizontal(|ui| {
+                let row_fill = multi_selected_row_fill(ui, context.multi_selected.contains(&entry.path));
+                egui::Frame::none().fill(row_fill).show(ui, |ui| ui.horizontal(|ui| {
+                    // 切り取り中は薄く表示
+                    if context.clipboard.is_pending_cut(&entry.path) {
+                        ui.multiply_opacity(CUT_ENTRY_OPACITY);
+                    }
                     let label = format!("📄 {}", entry.name);
-                    let response = ui.selectable_label(is_selected, label);
+                    let response = make_path_draggable(ui.selectable_label(is_selected, label), &entry.path);
+                    show_failed_badge(ui, context.failed_badges, &entry.path);
 
                     // シングルクリック → 選択のみ
                     if response.clicked() {
@@ -491,7 +763,7 @@ impl FileTreeView {
                         selected_result = Some(entry.path.clone());
                         is_right_click = true;
                     }
-                });
+                }));
                 flat_index += 1;  // ファイルもカウント
             }
         }
@@ -499,3 +771,70 @@ impl FileTreeView {
         (selected_result, open_result, is_right_click, flat_index)  // 総アイテム数を返す
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias_with_tags(name: &str, tags: &[&str]) -> FileAlias {
+        FileAlias {
+            id: name.to_string(),
+            alias: name.to_string(),
+            path: PathBuf::from(format!("/work/{}", name)),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            color: None,
+            created_at: chrono::Utc::now(),
+            last_accessed: chrono::Utc::now(),
+            is_favorite: false,
+            open_action: crate::data::models::OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
+        }
+    }
+
+    #[test]
+    fn test_favorite_row_style() {
+        let mut favorite = alias_with_tags("fav", &[]);
+        favorite.is_favorite = true;
+        let normal = alias_with_tags("normal", &[]);
+
+        assert!(highlight_as_favorite(&favorite, false));
+        // 選択中は選択の色を優先して背景色を付けない
+        assert!(!highlight_as_favorite(&favorite, true));
+        assert!(!highlight_as_favorite(&normal, false));
+        assert!(!highlight_as_favorite(&normal, true));
+    }
+
+    #[test]
+    fn test_group_aliases_by_tag() {
+        let items = vec![
+            alias_with_tags("report", &["work"]),
+            alias_with_tags("photo", &["private", "work"]),
+            alias_with_tags("memo", &[]),
+            alias_with_tags("dup", &["work", "work"]),
+        ];
+
+        let groups = group_aliases_by_tag(&items);
+        assert_eq!(
+            groups,
+            vec![
+                TagGroup { tag: Some("private".to_string()), indices: vec![1] },
+                TagGroup { tag: Some("work".to_string()), indices: vec![0, 1, 3] },
+                TagGroup { tag: None, indices: vec![2] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_aliases_by_tag_without_untagged() {
+        let items = vec![alias_with_tags("report", &["work"])];
+        let groups = group_aliases_by_tag(&items);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].tag.as_deref(), Some("work"));
+    }
+}
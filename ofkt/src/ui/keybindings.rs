@@ -0,0 +1,255 @@
+//! アプリ内のファイル操作（コピー・切り取り・貼り付け）のキー割り当て
+//!
+//! 設定ファイルの "Ctrl+Shift+C" のような文字列を解析し、
+//! 押されたキーに対応するファイル操作を判定します。
+
+use crate::data::models::KeybindingConfig;
+use eframe::egui;
+
+/// キー割り当ての対象となるファイル操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    /// コピー
+    Copy,
+    /// 切り取り
+    Cut,
+    /// 貼り付け
+    Paste,
+}
+
+impl FileAction {
+    /// 設定画面での表示順の全操作
+    pub const ALL: [FileAction; 3] = [FileAction::Copy, FileAction::Cut, FileAction::Paste];
+
+    /// 設定画面のラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileAction::Copy => "コピー",
+            FileAction::Cut => "切り取り",
+            FileAction::Paste => "貼り付け",
+        }
+    }
+
+    /// 設定から割り当ての文字列を取得
+    pub fn binding<'a>(&self, config: &'a KeybindingConfig) -> &'a str {
+        match self {
+            FileAction::Copy => &config.copy,
+            FileAction::Cut => &config.cut,
+            FileAction::Paste => &config.paste,
+        }
+    }
+
+    /// 設定の割り当ての文字列を変更できる参照を取得
+    pub fn binding_mut<'a>(&self, config: &'a mut KeybindingConfig) -> &'a mut String {
+        match self {
+            FileAction::Copy => &mut config.copy,
+            FileAction::Cut => &mut config.cut,
+            FileAction::Paste => &mut config.paste,
+        }
+    }
+}
+
+/// 修飾キーとキーの組み合わせ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyCombo {
+    /// Ctrl（macOS では Cmd）
+    pub ctrl: bool,
+    /// Shift
+    pub shift: bool,
+    /// Alt
+    pub alt: bool,
+    /// キー
+    pub key: egui::Key,
+}
+
+impl KeyCombo {
+    /// "Ctrl+Shift+C" 形式の文字列を解析
+    ///
+    /// 修飾キー（Ctrl・Shift・Alt）とキーを `+` でつなげて指定します。
+    /// 大文字小文字と各部分の前後の空白は無視します。
+    ///
+    /// # エラー
+    /// 修飾キーやキーが不明な場合、キーがない場合はエラーメッセージを返す
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let (key_name, modifiers) = parts
+            .split_last()
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| format!("キーが指定されていません: {}", text))?;
+
+        let mut combo = Self {
+            ctrl: false,
+            shift: false,
+            alt: false,
+            key: parse_key(key_name).ok_or_else(|| format!("無効なキー: {}", key_name))?,
+        };
+        for modifier in modifiers {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => combo.ctrl = true,
+                "shift" => combo.shift = true,
+                "alt" => combo.alt = true,
+                _ => return Err(format!("無効な修飾キー: {}", modifier)),
+            }
+        }
+        Ok(combo)
+    }
+
+    /// 押されたキーと修飾キーがこの組み合わせと一致するか（余分な修飾キーがあれば一致しない）
+    pub fn matches(&self, key: egui::Key, modifiers: egui::Modifiers) -> bool {
+        self.key == key
+            && self.ctrl == modifiers.command
+            && self.shift == modifiers.shift
+            && self.alt == modifiers.alt
+    }
+}
+
+/// キー名から egui のキーを取得（"c"・"F2"・"delete" など）
+fn parse_key(name: &str) -> Option<egui::Key> {
+    let mut chars = name.chars();
+    let capitalized = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+    egui::Key::from_name(name)
+        .or_else(|| egui::Key::from_name(&capitalized))
+        .or_else(|| egui::Key::from_name(&name.to_uppercase()))
+}
+
+/// ファイル操作のキー割り当て
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileKeybindings {
+    /// 操作ごとの割り当て（空文字列で無効にした操作は含まない）
+    bindings: Vec<(FileAction, KeyCombo)>,
+}
+
+impl Default for FileKeybindings {
+    fn default() -> Self {
+        Self::from_config(&KeybindingConfig::default())
+    }
+}
+
+impl FileKeybindings {
+    /// 設定からキー割り当てを作成
+    ///
+    /// 解析できない割り当ては警告を出して既定の割り当てを使います。
+    pub fn from_config(config: &KeybindingConfig) -> Self {
+        let defaults = KeybindingConfig::default();
+        let bindings = FileAction::ALL
+            .into_iter()
+            .filter_map(|action| {
+                let text = action.binding(config);
+                if text.trim().is_empty() {
+                    return None;
+                }
+                let combo = KeyCombo::parse(text).or_else(|e| {
+                    log::warn!("キー割り当てを解析できないため既定値を使います: {}", e);
+                    KeyCombo::parse(action.binding(&defaults))
+                });
+                combo.ok().map(|combo| (action, combo))
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    /// 押されたキーに割り当てられた操作を取得
+    pub fn action_for(&self, key: egui::Key, modifiers: egui::Modifiers) -> Option<FileAction> {
+        self.bindings
+            .iter()
+            .find(|(_, combo)| combo.matches(key, modifiers))
+            .map(|(action, _)| *action)
+    }
+
+    /// 入力イベントに割り当てられた操作を取得
+    ///
+    /// Ctrl+C/X/V は OS によって Key ではなく Copy/Cut/Paste イベントとして届くため、
+    /// それぞれ C/X/V キーが押されたものとして判定します。
+    ///
+    /// # 引数
+    /// * `event` - 入力イベント
+    /// * `modifiers` - イベント発生時の修飾キー
+    pub fn action_for_event(&self, event: &egui::Event, modifiers: egui::Modifiers) -> Option<FileAction> {
+        match event {
+            egui::Event::Key { key, pressed: true, modifiers, .. } => self.action_for(*key, *modifiers),
+            egui::Event::Copy => self.action_for(egui::Key::C, modifiers),
+            egui::Event::Cut => self.action_for(egui::Key::X, modifiers),
+            egui::Event::Paste(_) => self.action_for(egui::Key::V, modifiers),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_event(key: egui::Key, modifiers: egui::Modifiers) -> egui::Event {
+        egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        }
+    }
+
+    #[test]
+    fn test_parse_key_combo() {
+        let combo = KeyCombo::parse("Ctrl+Shift+C").unwrap();
+        assert!(combo.ctrl && combo.shift && !combo.alt);
+        assert_eq!(combo.key, egui::Key::C);
+
+        // 大文字小文字・空白は無視する
+        assert_eq!(KeyCombo::parse(" control + c ").unwrap(), KeyCombo::parse("Ctrl+C").unwrap());
+        assert_eq!(KeyCombo::parse("alt+f2").unwrap().key, egui::Key::F2);
+        assert_eq!(KeyCombo::parse("Delete").unwrap().key, egui::Key::Delete);
+    }
+
+    #[test]
+    fn test_parse_key_combo_errors() {
+        assert!(KeyCombo::parse("").is_err());
+        assert!(KeyCombo::parse("Ctrl+").is_err());
+        assert!(KeyCombo::parse("Hyper+C").is_err());
+        assert!(KeyCombo::parse("Ctrl+NoSuchKey").is_err());
+    }
+
+    #[test]
+    fn test_dispatch_remapped_bindings() {
+        let config = KeybindingConfig {
+            copy: "Ctrl+Shift+C".to_string(),
+            cut: "".to_string(),
+            paste: "invalid+V".to_string(),
+        };
+        let bindings = FileKeybindings::from_config(&config);
+        let ctrl = egui::Modifiers::COMMAND;
+        let ctrl_shift = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+
+        // Ctrl+C はテキスト欄に任せ、Ctrl+Shift+C でファイルをコピーする
+        assert_eq!(bindings.action_for_event(&egui::Event::Copy, ctrl), None);
+        assert_eq!(bindings.action_for_event(&egui::Event::Copy, ctrl_shift), Some(FileAction::Copy));
+        assert_eq!(
+            bindings.action_for_event(&key_event(egui::Key::C, ctrl_shift), egui::Modifiers::NONE),
+            Some(FileAction::Copy)
+        );
+
+        // 空文字列で無効にした操作は反応しない
+        assert_eq!(bindings.action_for_event(&egui::Event::Cut, ctrl), None);
+
+        // 解析できない割り当ては既定の Ctrl+V になる
+        assert_eq!(
+            bindings.action_for_event(&egui::Event::Paste(String::new()), ctrl),
+            Some(FileAction::Paste)
+        );
+        assert_eq!(bindings.action_for_event(&key_event(egui::Key::V, ctrl), ctrl), Some(FileAction::Paste));
+    }
+
+    #[test]
+    fn test_default_bindings_match_ctrl_shortcuts() {
+        let bindings = FileKeybindings::default();
+        let ctrl = egui::Modifiers::COMMAND;
+
+        assert_eq!(bindings.action_for(egui::Key::C, ctrl), Some(FileAction::Copy));
+        assert_eq!(bindings.action_for(egui::Key::X, ctrl), Some(FileAction::Cut));
+        assert_eq!(bindings.action_for(egui::Key::V, ctrl), Some(FileAction::Paste));
+        assert_eq!(bindings.action_for(egui::Key::C, egui::Modifiers::NONE), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! パネル配置（分割幅・表示件数）に関する設定値
+
+/// サイドバーの最小幅
+pub const MIN_SIDEBAR_WIDTH: f32 = 120.0;
+
+/// サイドバーの最大幅
+pub const MAX_SIDEBAR_WIDTH: f32 = 600.0;
+
+/// サイドバーの幅を有効な範囲に収める
+///
+/// 設定ファイルが手で編集された場合などに備え、数値でない値は最小幅として扱います。
+pub fn clamp_sidebar_width(width: f32) -> f32 {
+    if width.is_finite() {
+        width.clamp(MIN_SIDEBAR_WIDTH, MAX_SIDEBAR_WIDTH)
+    } else {
+        MIN_SIDEBAR_WIDTH
+    }
+}
+
+/// サイドバーに表示するエイリアスの件数を決める
+///
+/// 展開していない場合は `limit` 件まで、展開している場合はすべてを表示します。
+/// `limit` が 0 の場合も最低1件は表示します。
+///
+/// # 引数
+/// * `total` - 絞り込み後のエイリアスの件数
+/// * `limit` - 設定の表示件数
+/// * `expanded` - 「もっと見る」で展開しているか
+pub fn sidebar_alias_display_count(total: usize, limit: usize, expanded: bool) -> usize {
+    if expanded {
+        total
+    } else {
+        total.min(limit.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_sidebar_width_within_range() {
+        assert_eq!(clamp_sidebar_width(250.0), 250.0);
+        assert_eq!(clamp_sidebar_width(MIN_SIDEBAR_WIDTH), MIN_SIDEBAR_WIDTH);
+        assert_eq!(clamp_sidebar_width(MAX_SIDEBAR_WIDTH), MAX_SIDEBAR_WIDTH);
+    }
+
+    #[test]
+    fn test_clamp_sidebar_width_out_of_range() {
+        assert_eq!(clamp_sidebar_width(10.0), MIN_SIDEBAR_WIDTH);
+        assert_eq!(clamp_sidebar_width(-50.0), MIN_SIDEBAR_WIDTH);
+        assert_eq!(clamp_sidebar_width(5000.0), MAX_SIDEBAR_WIDTH);
+        assert_eq!(clamp_sidebar_width(f32::NAN), MIN_SIDEBAR_WIDTH);
+        assert_eq!(clamp_sidebar_width(f32::INFINITY), MIN_SIDEBAR_WIDTH);
+    }
+
+    #[test]
+    fn test_sidebar_alias_display_count_collapsed() {
+        assert_eq!(sidebar_alias_display_count(25, 10, false), 10);
+        assert_eq!(sidebar_alias_display_count(3, 10, false), 3);
+        assert_eq!(sidebar_alias_display_count(0, 10, false), 0);
+        assert_eq!(sidebar_alias_display_count(25, 0, false), 1);
+    }
+
+    #[test]
+    fn test_sidebar_alias_display_count_expanded_shows_all() {
+        assert_eq!(sidebar_alias_display_count(25, 10, true), 25);
+        assert_eq!(sidebar_alias_display_count(3, 10, true), 3);
+    }
+}
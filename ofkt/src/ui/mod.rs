@@ -1,9 +1,17 @@
 pub mod theme;
 pub mod search_bar;
 pub mod file_tree;
+pub mod file_table;
+pub mod row_columns;
 pub mod context_menu;
 pub mod settings;
 pub mod history;
 pub mod favorites;
+pub mod command_palette;
+pub mod shortcut_help;
+pub mod layout;
+pub mod detail_strip;
+pub mod browse_tabs;
+pub mod keybindings;
 
 // このモジュールは実装予定です
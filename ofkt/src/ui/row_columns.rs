@@ -0,0 +1,263 @@
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use crate::data::models::FileAlias;
+use crate::ui::file_table::format_size;
+
+/// エイリアス一覧の各行に表示する列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowColumn {
+    /// エイリアス名（常に表示）
+    Name,
+    /// ファイルサイズ
+    Size,
+    /// 更新日時
+    Modified,
+    /// タグ
+    Tags,
+    /// パス
+    Path,
+}
+
+impl RowColumn {
+    /// 設定画面での表示順の全列
+    pub const ALL: [RowColumn; 5] = [
+        RowColumn::Name,
+        RowColumn::Size,
+        RowColumn::Modified,
+        RowColumn::Tags,
+        RowColumn::Path,
+    ];
+
+    /// 設定ファイル上の名前から列を取得
+    ///
+    /// 大文字小文字と前後の空白は無視します。
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "name" => Some(RowColumn::Name),
+            "size" => Some(RowColumn::Size),
+            "modified" => Some(RowColumn::Modified),
+            "tags" => Some(RowColumn::Tags),
+            "path" => Some(RowColumn::Path),
+            _ => None,
+        }
+    }
+
+    /// 設定ファイル上の名前を取得
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            RowColumn::Name => "name",
+            RowColumn::Size => "size",
+            RowColumn::Modified => "modified",
+            RowColumn::Tags => "tags",
+            RowColumn::Path => "path",
+        }
+    }
+
+    /// 設定画面のラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            RowColumn::Name => "名前",
+            RowColumn::Size => "サイズ",
+            RowColumn::Modified => "更新日時",
+            RowColumn::Tags => "タグ",
+            RowColumn::Path => "パス",
+        }
+    }
+
+    /// 列の固定幅（None は残り幅をすべて使う）
+    ///
+    /// 行ごとに幅が変わって表示がガタつかないよう、パス以外は固定幅にする。
+    pub fn fixed_width(&self) -> Option<f32> {
+        match self {
+            RowColumn::Name => Some(140.0),
+            RowColumn::Size => Some(64.0),
+            RowColumn::Modified => Some(110.0),
+            RowColumn::Tags => Some(100.0),
+            RowColumn::Path => None,
+        }
+    }
+}
+
+/// デフォルトの表示列（名前 + パス）
+pub fn default_row_columns() -> Vec<RowColumn> {
+    vec![RowColumn::Name, RowColumn::Path]
+}
+
+/// 設定の列名リストを列のリストに変換
+///
+/// 不明な列名と重複は無視し、名前列が含まれていない場合は先頭に追加します。
+///
+/// # 引数
+/// * `names` - 設定ファイルの列名リスト
+///
+/// # 戻り値
+/// 表示順の列リスト
+pub fn parse_row_columns(names: &[String]) -> Vec<RowColumn> {
+    let mut columns: Vec<RowColumn> = Vec::new();
+    for name in names {
+        match RowColumn::parse(name) {
+            Some(column) if !columns.contains(&column) => columns.push(column),
+            Some(_) => {}
+            None => log::warn!("不明な表示列を無視しました: {}", name),
+        }
+    }
+
+    if !columns.contains(&RowColumn::Name) {
+        columns.insert(0, RowColumn::Name);
+    }
+    columns
+}
+
+/// 1行分の表示データ
+#[derive(Debug, Clone)]
+pub struct RowData<'a> {
+    /// 表示名
+    pub name: &'a str,
+    /// パス
+    pub path: &'a Path,
+    /// ファイルサイズ（フォルダや取得できない場合は None）
+    pub size: Option<u64>,
+    /// 更新日時
+    pub modified: Option<DateTime<Utc>>,
+    /// タグ
+    pub tags: &'a [String],
+}
+
+impl<'a> RowData<'a> {
+    /// エイリアスから表示データを作成
+    ///
+    /// `needs_metadata` が false の場合はファイルシステムにアクセスしない。
+    pub fn from_alias(alias: &'a FileAlias, needs_metadata: bool) -> Self {
+        let metadata = if needs_metadata {
+            std::fs::metadata(&alias.path).ok()
+        } else {
+            None
+        };
+
+        let size = metadata
+            .as_ref()
+            .filter(|m| m.is_file())
+            .map(|m| m.len());
+        let modified = metadata
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .map(DateTime::<Utc>::from);
+
+        Self {
+            name: &alias.alias,
+            path: &alias.path,
+            size,
+            modified,
+            tags: &alias.tags,
+        }
+    }
+}
+
+/// 列ごとの表示文字列を組み立てる
+///
+/// # 引数
+/// * `columns` - 表示する列
+/// * `data` - 行の表示データ
+///
+/// # 戻り値
+/// (列, 表示文字列) のリスト（columns と同じ順序）
+pub fn assemble_row(columns: &[RowColumn], data: &RowData) -> Vec<(RowColumn, String)> {
+    columns
+        .iter()
+        .map(|column| {
+            let text = match column {
+                RowColumn::Name => data.name.to_string(),
+                RowColumn::Size => data.size.map(format_size).unwrap_or_default(),
+                RowColumn::Modified => data
+                    .modified
+                    .map(|m| m.with_timezone(&chrono::Local).format("%Y/%m/%d %H:%M").to_string())
+                    .unwrap_or_default(),
+                RowColumn::Tags => data.tags.join(", "),
+                RowColumn::Path => data.path.display().to_string(),
+            };
+            (*column, text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_column_names() {
+        assert_eq!(RowColumn::parse("name"), Some(RowColumn::Name));
+        assert_eq!(RowColumn::parse(" Size "), Some(RowColumn::Size));
+        assert_eq!(RowColumn::parse("MODIFIED"), Some(RowColumn::Modified));
+        assert_eq!(RowColumn::parse("tags"), Some(RowColumn::Tags));
+        assert_eq!(RowColumn::parse("path"), Some(RowColumn::Path));
+        assert_eq!(RowColumn::parse("owner"), None);
+    }
+
+    #[test]
+    fn test_config_name_round_trip() {
+        for column in RowColumn::ALL {
+            assert_eq!(RowColumn::parse(column.config_name()), Some(column));
+        }
+    }
+
+    #[test]
+    fn test_parse_row_columns_keeps_order_and_skips_invalid() {
+        let names = vec![
+            "name".to_string(),
+            "modified".to_string(),
+            "unknown".to_string(),
+            "size".to_string(),
+            "modified".to_string(), // 重複
+        ];
+        let columns = parse_row_columns(&names);
+        assert_eq!(columns, vec![RowColumn::Name, RowColumn::Modified, RowColumn::Size]);
+    }
+
+    #[test]
+    fn test_parse_row_columns_always_includes_name() {
+        let columns = parse_row_columns(&["path".to_string()]);
+        assert_eq!(columns, vec![RowColumn::Name, RowColumn::Path]);
+
+        let columns = parse_row_columns(&[]);
+        assert_eq!(columns, vec![RowColumn::Name]);
+    }
+
+    #[test]
+    fn test_assemble_row() {
+        let path = PathBuf::from("/projects/report.xlsx");
+        let tags = vec!["work".to_string(), "2025".to_string()];
+        let data = RowData {
+            name: "report",
+            path: &path,
+            size: Some(2048),
+            modified: None,
+            tags: &tags,
+        };
+
+        let columns = vec![RowColumn::Name, RowColumn::Size, RowColumn::Tags, RowColumn::Path];
+        let row = assemble_row(&columns, &data);
+
+        assert_eq!(row.len(), 4);
+        assert_eq!(row[0], (RowColumn::Name, "report".to_string()));
+        assert_eq!(row[1], (RowColumn::Size, "2.0 KB".to_string()));
+        assert_eq!(row[2], (RowColumn::Tags, "work, 2025".to_string()));
+        assert_eq!(row[3], (RowColumn::Path, path.display().to_string()));
+    }
+
+    #[test]
+    fn test_assemble_row_missing_metadata_is_empty() {
+        let path = PathBuf::from("/projects/folder");
+        let data = RowData {
+            name: "folder",
+            path: &path,
+            size: None,
+            modified: None,
+            tags: &[],
+        };
+
+        let row = assemble_row(&[RowColumn::Size, RowColumn::Modified, RowColumn::Tags], &data);
+        assert!(row.iter().all(|(_, text)| text.is_empty()));
+    }
+}
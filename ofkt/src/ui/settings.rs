@@ -1,5 +1,12 @@
 use eframe::egui;
+use crate::core::directory_browser::{SortKey, SortOrder};
+use crate::core::file_manager::ConflictPolicy;
 use crate::data::models::Config;
+use crate::app::state::BrowseMode;
+use crate::ui::browse_tabs::parse_browse_tabs;
+use crate::ui::keybindings::{FileAction, KeyCombo};
+use crate::ui::row_columns::{parse_row_columns, RowColumn};
+use crate::ui::theme::{parse_hex_color, to_hex_color, DEFAULT_FOCUS_COLOR};
 
 /// 設定画面でのアクション
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -16,6 +23,8 @@ pub struct Settings {
     config: Config,
     /// ホットキー入力用の一時文字列
     temp_hotkey: String,
+    /// 再帰検索の除外フォルダ入力用の一時文字列（カンマ区切り）
+    temp_recursive_ignore: String,
 }
 
 impl Settings {
@@ -26,10 +35,12 @@ impl Settings {
     pub fn new(config: Config) -> Self {
         // ホットキー設定から初期値を構築
         let temp_hotkey = Self::build_hotkey_string(&config);
+        let temp_recursive_ignore = config.search.recursive_ignore.join(", ");
 
         Self {
             config,
             temp_hotkey,
+            temp_recursive_ignore,
         }
     }
 
@@ -82,6 +93,10 @@ impl Settings {
 
             ui.checkbox(&mut self.config.window.always_on_top, "常に最前面に表示");
             ui.checkbox(&mut self.config.window.decorations, "ウィンドウ装飾を表示");
+            ui.horizontal(|ui| {
+                ui.label("Escキー2回でウィンドウを隠す間隔 (ms、0で無効):");
+                ui.add(egui::DragValue::new(&mut self.config.window.double_escape_ms).range(0..=2000));
+            });
         });
 
         ui.add_space(10.0);
@@ -92,6 +107,19 @@ impl Settings {
             ui.radio_value(&mut self.config.theme.mode, "system".to_string(), "システム設定に従う");
             ui.radio_value(&mut self.config.theme.mode, "light".to_string(), "ライトモード");
             ui.radio_value(&mut self.config.theme.mode, "dark".to_string(), "ダークモード");
+
+            let mut use_accent = self.config.theme.custom_accent_color.is_some();
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut use_accent, "フォーカス枠の色を指定").changed() {
+                    self.config.theme.custom_accent_color = use_accent.then(|| to_hex_color(DEFAULT_FOCUS_COLOR));
+                }
+                if let Some(hex) = self.config.theme.custom_accent_color.as_mut() {
+                    let mut color = parse_hex_color(hex).unwrap_or(DEFAULT_FOCUS_COLOR);
+                    if ui.color_edit_button_srgba(&mut color).changed() {
+                        *hex = to_hex_color(color);
+                    }
+                }
+            });
         });
 
         ui.add_space(10.0);
@@ -140,6 +168,100 @@ impl Settings {
             ui.checkbox(&mut self.config.search.search_paths, "パスを検索対象に含める");
             ui.checkbox(&mut self.config.search.search_aliases, "エイリアスを検索対象に含める");
             ui.checkbox(&mut self.config.search.case_sensitive, "大文字小文字を区別");
+            ui.checkbox(&mut self.config.search.remember_last_query, "前回の検索クエリを起動時に復元");
+            ui.checkbox(&mut self.config.search.keep_filter_on_navigate, "フォルダを移動しても検索クエリを残す");
+            ui.checkbox(&mut self.config.search.match_all_terms, "複数キーワードをすべて含むものだけを表示（AND 検索）");
+            ui.checkbox(&mut self.config.search.search_reading, "エイリアスの読みを検索対象に含める");
+            ui.checkbox(&mut self.config.search.search_notes, "エイリアスのメモを検索対象に含める");
+            ui.checkbox(&mut self.config.search.stale_penalty_enabled, "長期間使っていないエイリアスの順位を下げる");
+            if self.config.search.stale_penalty_enabled {
+                ui.horizontal(|ui| {
+                    ui.label("未使用の日数:");
+                    ui.add(egui::Slider::new(&mut self.config.search.stale_threshold_days, 30..=730));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("減点:");
+                    ui.add(egui::Slider::new(&mut self.config.search.stale_penalty, 0.05..=0.5));
+                });
+            }
+            let mut limit_search_time = self.config.search.search_time_budget_ms.is_some();
+            if ui.checkbox(&mut limit_search_time, "検索に時間がかかる場合は途中までの結果を表示").changed() {
+                self.config.search.search_time_budget_ms = limit_search_time
+                    .then_some(crate::core::search::SearchEngine::DEFAULT_TIME_BUDGET.as_millis() as u64);
+            }
+            if let Some(budget_ms) = self.config.search.search_time_budget_ms.as_mut() {
+                ui.horizontal(|ui| {
+                    ui.label("走査時間の上限（ミリ秒）:");
+                    ui.add(egui::Slider::new(budget_ms, 1..=100));
+                });
+            }
+            ui.label("ディレクトリの再帰検索（検索ボックスに **名前 と入力）:");
+            ui.horizontal(|ui| {
+                ui.label("たどる深さ:");
+                ui.add(egui::Slider::new(&mut self.config.search.recursive_max_depth, 1..=32));
+            });
+            ui.horizontal(|ui| {
+                ui.label("除外するフォルダ（カンマ区切り）:");
+                ui.text_edit_singleline(&mut self.temp_recursive_ignore);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        // 表示設定セクション
+        ui.group(|ui| {
+            ui.label("表示設定");
+            ui.label("エイリアス一覧に表示する列:");
+            ui.horizontal_wrapped(|ui| {
+                let current = parse_row_columns(&self.config.display.row_columns);
+                for column in RowColumn::ALL {
+                    let mut enabled = current.contains(&column);
+                    // 名前列は常に表示
+                    let response = ui.add_enabled(
+                        column != RowColumn::Name,
+                        egui::Checkbox::new(&mut enabled, column.label()),
+                    );
+                    if response.changed() {
+                        self.set_row_column(column, enabled);
+                    }
+                }
+            });
+
+            ui.label("表示するタブ（先頭のタブで起動）:");
+            ui.horizontal_wrapped(|ui| {
+                let current = parse_browse_tabs(&self.config.display.browse_tabs);
+                for mode in BrowseMode::ALL {
+                    let mut enabled = current.contains(&mode);
+                    // 最後の1つは非表示にできない
+                    let response = ui.add_enabled(
+                        !(enabled && current.len() == 1),
+                        egui::Checkbox::new(&mut enabled, mode.label()),
+                    );
+                    if response.changed() {
+                        self.set_browse_tab(mode, enabled);
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("フォルダの既定の並び順:");
+                for key in SortKey::ALL {
+                    ui.radio_value(&mut self.config.display.default_sort_key, key.config_name().to_string(), key.label());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("既定の順序:");
+                ui.radio_value(&mut self.config.display.default_sort_order, SortOrder::Ascending.config_name().to_string(), "昇順");
+                ui.radio_value(&mut self.config.display.default_sort_order, SortOrder::Descending.config_name().to_string(), "降順");
+            });
+            ui.horizontal(|ui| {
+                ui.label("サイドバーに表示するエイリアスの件数:");
+                ui.add(egui::DragValue::new(&mut self.config.display.sidebar_alias_limit).range(1..=100));
+            });
+            ui.checkbox(
+                &mut self.config.display.network_path_as_unknown,
+                "接続できないネットワークパスのエイリアスは「確認できない」と表示（壊れているとしない）",
+            );
         });
 
         ui.add_space(10.0);
@@ -149,6 +271,37 @@ impl Settings {
             ui.label("ファイル操作設定");
             ui.checkbox(&mut self.config.file_operations.confirm_delete, "削除前に確認");
             ui.checkbox(&mut self.config.file_operations.use_trash, "ゴミ箱に移動");
+            ui.checkbox(&mut self.config.file_operations.preserve_structure, "別々のフォルダから貼り付けるときにフォルダ構成を保つ");
+            ui.checkbox(&mut self.config.file_operations.preserve_attributes, "コピー時に読み取り専用・隠し属性を引き継ぐ（Windows）");
+            ui.horizontal(|ui| {
+                ui.label("貼り付ける項目数がこれを超えたら確認（0 で確認しない）:");
+                ui.add(egui::DragValue::new(&mut self.config.file_operations.large_paste_threshold).range(0..=1_000_000));
+            });
+            ui.horizontal(|ui| {
+                ui.label("元に戻せる操作の数:");
+                ui.add(egui::DragValue::new(&mut self.config.file_operations.history_limit).range(1..=500));
+            });
+            ui.horizontal(|ui| {
+                ui.label("同名のファイルがあるとき:");
+                for policy in ConflictPolicy::ALL {
+                    ui.radio_value(&mut self.config.file_operations.conflict_policy, policy.config_name().to_string(), policy.label());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("同じフォルダに貼り付けたときの名前:");
+                ui.text_edit_singleline(&mut self.config.file_operations.copy_name_pattern);
+            });
+            ui.horizontal(|ui| {
+                for preset in ["{name} (コピー){ext}", "{name} ({n}){ext}", "{name}_copy{ext}"] {
+                    ui.selectable_value(&mut self.config.file_operations.copy_name_pattern, preset.to_string(), preset);
+                }
+            });
+            if !crate::core::clipboard::is_valid_copy_name_pattern(&self.config.file_operations.copy_name_pattern) {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "{name} を含み、/ や \\ を含まない形式にしてください（正しくない場合は既定の形式を使います）",
+                );
+            }
 
             ui.horizontal(|ui| {
                 ui.label("デフォルト開き方:");
@@ -158,6 +311,25 @@ impl Settings {
             });
         });
 
+        ui.add_space(10.0);
+
+        // キー割り当て設定セクション
+        ui.group(|ui| {
+            ui.label("キー割り当て（例: Ctrl+Shift+C、空欄で無効）");
+            for action in FileAction::ALL {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", action.label()));
+                    ui.text_edit_singleline(action.binding_mut(&mut self.config.keybindings));
+                    let binding = action.binding(&self.config.keybindings);
+                    if !binding.trim().is_empty() {
+                        if let Err(e) = KeyCombo::parse(binding) {
+                            ui.colored_label(ui.visuals().warn_fg_color, format!("{}（既定の割り当てを使います）", e));
+                        }
+                    }
+                });
+            }
+        });
+
         ui.add_space(20.0);
 
         // 保存/キャンセルボタン
@@ -165,6 +337,7 @@ impl Settings {
             if ui.button("保存").clicked() {
                 // ホットキー文字列を解析して設定に反映
                 self.parse_hotkey_string();
+                self.parse_recursive_ignore_string();
                 action = Some(SettingsAction::Save);
             }
             if ui.button("キャンセル").clicked() {
@@ -192,6 +365,54 @@ impl Settings {
         }
     }
 
+    /// 再帰検索の除外フォルダ文字列を解析して設定に反映
+    fn parse_recursive_ignore_string(&mut self) {
+        self.config.search.recursive_ignore = self.temp_recursive_ignore
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+    }
+
+    /// 表示列の有効/無効を切り替える
+    ///
+    /// 既存の列の並び順は維持し、新しく有効にした列は末尾に追加する。
+    fn set_row_column(&mut self, column: RowColumn, enabled: bool) {
+        let mut columns = parse_row_columns(&self.config.display.row_columns);
+        if enabled {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        } else if column != RowColumn::Name {
+            columns.retain(|c| *c != column);
+        }
+
+        self.config.display.row_columns = columns
+            .iter()
+            .map(|c| c.config_name().to_string())
+            .collect();
+    }
+
+    /// タブの表示/非表示を切り替える
+    ///
+    /// 既存のタブの並び順は維持し、新しく表示したタブは末尾に追加する。
+    /// すべてのタブを非表示にはできない。
+    fn set_browse_tab(&mut self, mode: BrowseMode, enabled: bool) {
+        let mut tabs = parse_browse_tabs(&self.config.display.browse_tabs);
+        if enabled {
+            if !tabs.contains(&mode) {
+                tabs.push(mode);
+            }
+        } else if tabs.len() > 1 {
+            tabs.retain(|m| *m != mode);
+        }
+
+        self.config.display.browse_tabs = tabs
+            .iter()
+            .map(|m| m.config_name().to_string())
+            .collect();
+    }
+
     /// 現在の設定を取得
     pub fn get_config(&self) -> &Config {
         &self.config
@@ -200,6 +421,7 @@ impl Settings {
     /// 設定を更新
     pub fn update_config(&mut self, config: Config) {
         self.temp_hotkey = Self::build_hotkey_string(&config);
+        self.temp_recursive_ignore = config.search.recursive_ignore.join(", ");
         self.config = config;
     }
 }
@@ -218,6 +440,7 @@ mod tests {
                 position: WindowPosition { x: 0.0, y: 0.0 },
                 always_on_top: true,
                 decorations: true,
+                double_escape_ms: 400,
             },
             hotkey: HotkeyConfig {
                 enabled: true,
@@ -243,12 +466,33 @@ mod tests {
                 search_paths: true,
                 search_aliases: true,
                 case_sensitive: false,
+                remember_last_query: false,
+                last_alias_query: None,
+                last_directory_query: None,
+                stale_penalty_enabled: false,
+                stale_threshold_days: 180,
+                stale_penalty: 0.2,
+                match_all_terms: false,
+                search_notes: false,
+                search_reading: true,
+                recursive_max_depth: 8,
+                recursive_ignore: vec!["node_modules".to_string(), ".git".to_string()],
+                keep_filter_on_navigate: false,
+                search_time_budget_ms: None,
             },
             file_operations: FileOperationConfig {
                 confirm_delete: true,
                 use_trash: true,
                 default_open_action: "open".to_string(),
+                preserve_structure: false,
+                large_paste_threshold: 1000,
+                copy_name_pattern: "{name} (コピー){ext}".to_string(),
+                conflict_policy: "overwrite".to_string(),
+                history_limit: 50,
+                preserve_attributes: false,
             },
+            display: DisplayConfig::default(),
+            keybindings: KeybindingConfig::default(),
         }
     }
 
@@ -281,6 +525,18 @@ mod tests {
         assert_eq!(settings.config.hotkey.key, "F4");
     }
 
+    #[test]
+    fn test_parse_recursive_ignore_string() {
+        let config = create_test_config();
+        let mut settings = Settings::new(config);
+        assert_eq!(settings.temp_recursive_ignore, "node_modules, .git");
+
+        settings.temp_recursive_ignore = " target ,, .git,".to_string();
+        settings.parse_recursive_ignore_string();
+
+        assert_eq!(settings.config.search.recursive_ignore, vec!["target".to_string(), ".git".to_string()]);
+    }
+
     #[test]
     fn test_update_config() {
         let config = create_test_config();
@@ -296,6 +552,50 @@ mod tests {
         assert_eq!(settings.temp_hotkey, "Ctrl+Shift+P");
     }
 
+    #[test]
+    fn test_set_browse_tab() {
+        let config = create_test_config();
+        let mut settings = Settings::new(config);
+
+        // 表示したタブは末尾に追加
+        settings.set_browse_tab(BrowseMode::Recent, true);
+        assert_eq!(
+            settings.config.display.browse_tabs,
+            vec!["alias".to_string(), "directory".to_string(), "recent".to_string()]
+        );
+
+        // 非表示にしたタブは削除（先頭のタブが起動時のモードになる）
+        settings.set_browse_tab(BrowseMode::Alias, false);
+        settings.set_browse_tab(BrowseMode::Directory, false);
+        assert_eq!(settings.config.display.browse_tabs, vec!["recent".to_string()]);
+
+        // 最後の1つは非表示にできない
+        settings.set_browse_tab(BrowseMode::Recent, false);
+        assert_eq!(settings.config.display.browse_tabs, vec!["recent".to_string()]);
+    }
+
+    #[test]
+    fn test_set_row_column() {
+        let config = create_test_config();
+        let mut settings = Settings::new(config);
+        assert_eq!(settings.config.display.row_columns, vec!["name".to_string(), "path".to_string()]);
+
+        // 有効にした列は末尾に追加
+        settings.set_row_column(RowColumn::Size, true);
+        assert_eq!(
+            settings.config.display.row_columns,
+            vec!["name".to_string(), "path".to_string(), "size".to_string()]
+        );
+
+        // 無効にした列は削除
+        settings.set_row_column(RowColumn::Path, false);
+        assert_eq!(settings.config.display.row_columns, vec!["name".to_string(), "size".to_string()]);
+
+        // 名前列は無効にできない
+        settings.set_row_column(RowColumn::Name, false);
+        assert_eq!(settings.config.display.row_columns, vec!["name".to_string(), "size".to_string()]);
+    }
+
     #[test]
     fn test_settings_action_enum() {
         let save_action = SettingsAction::Save;
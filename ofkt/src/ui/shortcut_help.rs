@@ -0,0 +1,180 @@
+use eframe::egui;
+
+/// ショートカットが有効な範囲
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutScope {
+    /// どのモードでも有効
+    Global,
+    /// エイリアスモード
+    Alias,
+    /// ディレクトリモード
+    Directory,
+}
+
+impl ShortcutScope {
+    /// ヘルプでの表示順の全範囲
+    pub const ALL: [ShortcutScope; 3] = [
+        ShortcutScope::Global,
+        ShortcutScope::Alias,
+        ShortcutScope::Directory,
+    ];
+
+    /// 見出しのラベルを取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutScope::Global => "共通",
+            ShortcutScope::Alias => "エイリアスモード",
+            ShortcutScope::Directory => "ディレクトリモード",
+        }
+    }
+}
+
+/// キーボードショートカットの定義
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    /// 有効な範囲
+    pub scope: ShortcutScope,
+    /// キーの表記
+    pub keys: &'static str,
+    /// 動作の説明
+    pub description: &'static str,
+}
+
+const fn shortcut(scope: ShortcutScope, keys: &'static str, description: &'static str) -> Shortcut {
+    Shortcut { scope, keys, description }
+}
+
+/// 実装済みのショートカット一覧
+///
+/// キー操作を追加・変更した場合はこの表も更新すること（ヘルプ画面はこの表から生成される）。
+pub const SHORTCUTS: &[Shortcut] = &[
+    shortcut(ShortcutScope::Global, "F1 / ?", "ショートカット一覧を表示"),
+    shortcut(ShortcutScope::Global, "Ctrl+Tab", "エイリアス/ディレクトリモードを切り替え"),
+    shortcut(ShortcutScope::Global, "Ctrl+Shift+P", "コマンドパレットを開く"),
+    shortcut(ShortcutScope::Global, "Tab / Shift+Tab", "サイドバーとメインパネルのフォーカスを切り替え"),
+    shortcut(ShortcutScope::Global, "Ctrl+F", "検索バーにフォーカス"),
+    shortcut(ShortcutScope::Global, "Ctrl+Z", "元に戻す"),
+    shortcut(ShortcutScope::Global, "Ctrl+Y / Ctrl+Shift+Z", "やり直し"),
+    shortcut(ShortcutScope::Global, "Esc", "ダイアログ・メニューを閉じる / 選択と検索をクリア"),
+    shortcut(ShortcutScope::Global, "Esc Esc", "ウィンドウを隠す"),
+    shortcut(ShortcutScope::Alias, "↑ / ↓", "エイリアスを選択"),
+    shortcut(ShortcutScope::Alias, "Enter", "選択中のエイリアスを開く"),
+    shortcut(ShortcutScope::Alias, "Ctrl+D", "クイックアクセスに追加"),
+    shortcut(ShortcutScope::Directory, "↑ / ↓", "項目を選択"),
+    shortcut(ShortcutScope::Directory, "Enter", "フォルダに移動 / ファイルを開く"),
+    shortcut(ShortcutScope::Directory, "→", "フォルダを展開"),
+    shortcut(ShortcutScope::Directory, "←", "フォルダを折りたたむ / 親を選択"),
+    shortcut(ShortcutScope::Directory, "Backspace", "親フォルダに移動"),
+    shortcut(ShortcutScope::Directory, "Alt+←", "戻る"),
+    shortcut(ShortcutScope::Directory, "Alt+→", "進む"),
+    shortcut(ShortcutScope::Directory, "マウスの戻る/進むボタン", "戻る / 進む"),
+    shortcut(ShortcutScope::Directory, "Ctrl+D", "クイックアクセスに追加"),
+    shortcut(ShortcutScope::Directory, "Ctrl+クリック", "項目を選択に加える / 外す"),
+    shortcut(ShortcutScope::Directory, "Shift+クリック", "範囲を選択"),
+    shortcut(ShortcutScope::Directory, "Ctrl+A", "表示中の項目をすべて選択"),
+    shortcut(ShortcutScope::Directory, "Delete", "選択中の項目を削除"),
+    shortcut(ShortcutScope::Directory, "Ctrl+C", "コピー"),
+    shortcut(ShortcutScope::Directory, "Ctrl+X", "切り取り"),
+    shortcut(ShortcutScope::Directory, "Ctrl+V", "貼り付け"),
+    shortcut(ShortcutScope::Directory, "Ctrl+Shift+V", "テキストとしてコピーしたパスを貼り付け"),
+];
+
+/// 指定した範囲のショートカットを取得
+pub fn shortcuts_for(scope: ShortcutScope) -> impl Iterator<Item = &'static Shortcut> {
+    SHORTCUTS.iter().filter(move |s| s.scope == scope)
+}
+
+/// ヘルプを開くキーが押されたか
+///
+/// `?` はテキスト入力中に誤って反応しないよう、テキスト入力にフォーカスがない場合のみ扱う。
+pub fn help_requested(ctx: &egui::Context) -> bool {
+    let typing = ctx.wants_keyboard_input();
+    ctx.input(|i| {
+        i.key_pressed(egui::Key::F1) || (!typing && i.key_pressed(egui::Key::Questionmark))
+    })
+}
+
+/// ショートカット一覧ウィンドウを描画
+///
+/// # 引数
+/// * `ctx` - egui コンテキスト
+/// * `open` - 表示状態（閉じるボタン・Esc で false になる）
+pub fn render_shortcut_help(ctx: &egui::Context, open: &mut bool) {
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        *open = false;
+        return;
+    }
+
+    egui::Window::new("キーボードショートカット")
+        .open(open)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            for scope in ShortcutScope::ALL {
+                ui.heading(scope.label());
+                egui::Grid::new(("shortcut_help", scope.label()))
+                    .num_columns(2)
+                    .spacing([24.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for shortcut in shortcuts_for(scope) {
+                            ui.monospace(shortcut.keys);
+                            ui.label(shortcut.description);
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(8.0);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has(scope: ShortcutScope, keys: &str) -> bool {
+        shortcuts_for(scope).any(|s| s.keys == keys)
+    }
+
+    #[test]
+    fn test_table_contains_implemented_shortcuts() {
+        assert!(has(ShortcutScope::Global, "F1 / ?"));
+        assert!(has(ShortcutScope::Global, "Ctrl+Tab"));
+        assert!(has(ShortcutScope::Global, "Ctrl+F"));
+        assert!(has(ShortcutScope::Global, "Ctrl+Shift+P"));
+        assert!(has(ShortcutScope::Global, "Ctrl+Z"));
+
+        assert!(has(ShortcutScope::Alias, "↑ / ↓"));
+        assert!(has(ShortcutScope::Alias, "Enter"));
+        assert!(has(ShortcutScope::Alias, "Ctrl+D"));
+
+        assert!(has(ShortcutScope::Directory, "Alt+←"));
+        assert!(has(ShortcutScope::Directory, "Alt+→"));
+        assert!(has(ShortcutScope::Directory, "Backspace"));
+        assert!(has(ShortcutScope::Directory, "Ctrl+D"));
+        assert!(has(ShortcutScope::Directory, "Ctrl+V"));
+    }
+
+    #[test]
+    fn test_every_scope_has_entries() {
+        for scope in ShortcutScope::ALL {
+            assert!(shortcuts_for(scope).count() > 0, "{:?} が空です", scope);
+        }
+    }
+
+    #[test]
+    fn test_no_duplicate_keys_within_scope() {
+        for scope in ShortcutScope::ALL {
+            let keys: Vec<&str> = shortcuts_for(scope).map(|s| s.keys).collect();
+            for (i, key) in keys.iter().enumerate() {
+                assert!(!keys[i + 1..].contains(key), "{:?} で {} が重複しています", scope, key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_entries_have_description() {
+        assert!(SHORTCUTS.iter().all(|s| !s.keys.is_empty() && !s.description.is_empty()));
+    }
+}
@@ -1,5 +1,53 @@
 use eframe::egui;
 
+/// フォーカス枠の既定の色（アクセントカラー未設定時）
+pub const DEFAULT_FOCUS_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 150, 255);
+
+/// フォーカス枠の太さ
+pub const FOCUS_STROKE_WIDTH: f32 = 2.0;
+
+/// エイリアスの色として選べる色（`#RRGGBB` 形式）
+pub const ALIAS_COLOR_PALETTE: [&str; 8] = [
+    "#3B82F6", // 青
+    "#10B981", // 緑
+    "#F59E0B", // オレンジ
+    "#EF4444", // 赤
+    "#8B5CF6", // 紫
+    "#EC4899", // ピンク
+    "#14B8A6", // 青緑
+    "#6B7280", // グレー
+];
+
+/// `#RRGGBB` 形式（`#` は省略可）の色を解析
+pub fn parse_hex_color(s: &str) -> Option<egui::Color32> {
+    let hex = s.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(egui::Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// 色を `#RRGGBB` 形式の文字列に変換
+pub fn to_hex_color(color: egui::Color32) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
+
+/// テーマを egui に適用し直す必要があるか
+///
+/// 毎フレーム `set_visuals` すると描画がちらつくことがあるため、
+/// 適用したいテーマが前回適用したものと変わった場合のみ適用し直します。
+/// egui 側で表示中の配色が入れ替わった場合（`visuals_dark_mode` が食い違う場合）も適用し直します。
+///
+/// # 引数
+/// * `applied` - 前回適用したテーマ（まだ適用していなければ None）
+/// * `desired` - 適用したいテーマ
+/// * `visuals_dark_mode` - egui で現在使われている配色がダークか
+pub fn should_reapply_theme(applied: Option<Theme>, desired: Theme, visuals_dark_mode: bool) -> bool {
+    applied != Some(desired) || visuals_dark_mode != (desired == Theme::Dark)
+}
+
 /// テーマ（ライト/ダーク）
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Theme {
@@ -38,4 +86,54 @@ impl Theme {
             Theme::Dark => "dark",
         }
     }
+
+    /// フォーカスのあるパネルの枠線
+    ///
+    /// # 引数
+    /// * `accent` - 設定のアクセントカラー（`#RRGGBB`。未設定・不正な値の場合は既定の色）
+    pub fn focus_stroke(accent: Option<&str>) -> egui::Stroke {
+        let color = accent.and_then(parse_hex_color).unwrap_or(DEFAULT_FOCUS_COLOR);
+        egui::Stroke::new(FOCUS_STROKE_WIDTH, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_stroke_uses_configured_accent() {
+        let stroke = Theme::focus_stroke(Some("#3B82F6"));
+        assert_eq!(stroke.color, egui::Color32::from_rgb(0x3B, 0x82, 0xF6));
+        assert_eq!(stroke.width, FOCUS_STROKE_WIDTH);
+    }
+
+    #[test]
+    fn test_focus_stroke_falls_back_to_default() {
+        assert_eq!(Theme::focus_stroke(None).color, DEFAULT_FOCUS_COLOR);
+        assert_eq!(Theme::focus_stroke(Some("blue")).color, DEFAULT_FOCUS_COLOR);
+        assert_eq!(Theme::focus_stroke(Some("#12345")).color, DEFAULT_FOCUS_COLOR);
+    }
+
+    #[test]
+    fn test_should_reapply_theme_only_on_change() {
+        // 初回は必ず適用する
+        assert!(should_reapply_theme(None, Theme::Dark, true));
+        // 同じテーマのままなら適用し直さない
+        assert!(!should_reapply_theme(Some(Theme::Dark), Theme::Dark, true));
+        assert!(!should_reapply_theme(Some(Theme::Light), Theme::Light, false));
+        // システムテーマや設定が変わった
+        assert!(should_reapply_theme(Some(Theme::Dark), Theme::Light, true));
+        assert!(should_reapply_theme(Some(Theme::Light), Theme::Dark, false));
+        // egui 側の配色が入れ替わっていた
+        assert!(should_reapply_theme(Some(Theme::Dark), Theme::Dark, false));
+    }
+
+    #[test]
+    fn test_hex_color_round_trip() {
+        assert_eq!(parse_hex_color("3b82f6"), Some(egui::Color32::from_rgb(0x3B, 0x82, 0xF6)));
+        assert_eq!(to_hex_color(egui::Color32::from_rgb(0x3B, 0x82, 0xF6)), "#3B82F6");
+        assert_eq!(parse_hex_color(&to_hex_color(DEFAULT_FOCUS_COLOR)), Some(DEFAULT_FOCUS_COLOR));
+        assert_eq!(parse_hex_color("#GG0000"), None);
+    }
 }
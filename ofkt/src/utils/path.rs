@@ -26,9 +26,262 @@ pub fn paths_equal(a: &Path, b: &Path) -> bool {
     }
 }
 
+/// `from` 配下のパスを `to` 配下に付け替える
+///
+/// `path` が `from` 自身またはその配下であれば付け替えたパスを返し、それ以外は None を返す。
+/// 文字列として前方一致するだけのパス（`/a/bc` に対する `/a/b` など）は対象外。
+pub fn remap_prefix(path: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    let rest = path.strip_prefix(from).ok()?;
+    if rest.as_os_str().is_empty() {
+        Some(to.to_path_buf())
+    } else {
+        Some(to.join(rest))
+    }
+}
+
 /// パスのリストを正規化する
 pub fn normalize_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
     paths.iter()
         .filter_map(|p| normalize_path(p).ok())
         .collect()
 }
+
+/// `path` を `base` からの相対表記にした表示用の文字列を返す
+///
+/// `path` が `base` 配下にない場合は絶対パスのまま、`base` と同じ場合は `.` を返す。
+/// Windowsでは大文字小文字を区別しない。
+pub fn relativize(path: &Path, base: &Path) -> String {
+    relativize_with(path, base, cfg!(target_os = "windows"))
+}
+
+fn relativize_with(path: &Path, base: &Path, case_insensitive: bool) -> String {
+    match strip_base(path, base, case_insensitive) {
+        Some(rest) if rest.as_os_str().is_empty() => ".".to_string(),
+        Some(rest) => rest.display().to_string(),
+        None => path.display().to_string(),
+    }
+}
+
+/// ツールチップ・ステータス表示用のパス（ホームフォルダ配下は `~` からの表記）
+pub fn display_path(path: &Path) -> String {
+    match dirs::home_dir() {
+        Some(home) => display_path_under_home(path, &home, cfg!(target_os = "windows")),
+        None => path.display().to_string(),
+    }
+}
+
+fn display_path_under_home(path: &Path, home: &Path, case_insensitive: bool) -> String {
+    match strip_base(path, home, case_insensitive) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~{}{}", std::path::MAIN_SEPARATOR, rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// パス中の環境変数（`%VAR%` / `${VAR}`）と先頭の `~` を展開する
+///
+/// 定義されていない環境変数はそのまま残す。
+pub fn expand_path(path: &Path) -> PathBuf {
+    expand_path_with(path, |name| std::env::var(name).ok(), dirs::home_dir().as_deref())
+}
+
+pub(crate) fn expand_path_with(path: &Path, lookup: impl Fn(&str) -> Option<String>, home: Option<&Path>) -> PathBuf {
+    let text = path.to_string_lossy();
+    if !text.contains(['%', '$', '~']) {
+        return path.to_path_buf();
+    }
+
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest: &str = &text;
+
+    // 先頭の `~` はホームフォルダ
+    if let Some(home) = home {
+        if rest == "~" {
+            return home.to_path_buf();
+        }
+        if let Some(after) = rest.strip_prefix("~/").or_else(|| rest.strip_prefix("~\\")) {
+            expanded.push_str(&home.to_string_lossy());
+            expanded.push(std::path::MAIN_SEPARATOR);
+            rest = after;
+        }
+    }
+
+    while let Some(ch) = rest.chars().next() {
+        let variable = if let Some(after) = rest.strip_prefix('%') {
+            after.find('%').map(|end| (&after[..end], &after[end + 1..]))
+        } else if let Some(after) = rest.strip_prefix("${") {
+            after.find('}').map(|end| (&after[..end], &after[end + 1..]))
+        } else {
+            None
+        };
+
+        match variable.and_then(|(name, after)| {
+            (!name.is_empty()).then(|| lookup(name)).flatten().map(|value| (value, after))
+        }) {
+            Some((value, after)) => {
+                expanded.push_str(&value);
+                rest = after;
+            }
+            None => {
+                expanded.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+
+    PathBuf::from(expanded)
+}
+
+/// 同じ場所を指すパスかを比べるためのキー
+///
+/// 環境変数と `~` を展開し、存在するパスはシンボリックリンクなどを解決したうえで、
+/// 区切り文字を `/` にそろえ、末尾の区切り文字を除き、大文字小文字を区別しない形にします。
+/// `C:\Work\` と `c:/work` は同じキーになります。
+pub fn path_key(path: &Path) -> String {
+    let expanded = expand_path(path);
+    let resolved = expanded.canonicalize().unwrap_or(expanded);
+    let mut text = resolved.to_string_lossy().replace('\\', "/");
+    // Windows の canonicalize が付ける `//?/`（ネットワークパスは `//?/UNC/`）は取り除く
+    if let Some(rest) = text.strip_prefix("//?/UNC/") {
+        text = format!("//{}", rest);
+    } else if let Some(rest) = text.strip_prefix("//?/") {
+        text = rest.to_string();
+    }
+    let trimmed = text.trim_end_matches('/');
+    let key = if trimmed.is_empty() { "/" } else { trimmed };
+    key.to_lowercase()
+}
+
+/// ネットワーク上のパス（`\\server\share` 形式の UNC パス）か
+///
+/// `\\?\UNC\` で始まるパスも含みます。`\\?\C:\` のようなローカルドライブは含みません。
+pub fn is_network_path(path: &Path) -> bool {
+    let text = path.to_string_lossy();
+    if let Some(rest) = text.strip_prefix(r"\\?\").or_else(|| text.strip_prefix(r"\\.\")) {
+        return rest.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case(r"UNC\"));
+    }
+    text.starts_with(r"\\") || text.starts_with("//")
+}
+
+/// `base` の階層を `path` の先頭から取り除く（`base` 配下でなければ None）
+fn strip_base(path: &Path, base: &Path, case_insensitive: bool) -> Option<PathBuf> {
+    let mut components = path.components();
+    for base_component in base.components() {
+        let component = components.next()?;
+        let same = if case_insensitive {
+            component.as_os_str().to_string_lossy().to_lowercase()
+                == base_component.as_os_str().to_string_lossy().to_lowercase()
+        } else {
+            component == base_component
+        };
+        if !same {
+            return None;
+        }
+    }
+    Some(components.as_path().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relativize_under_base() {
+        let rel = relativize(Path::new("/work/project/src/main.rs"), Path::new("/work/project"));
+        assert_eq!(PathBuf::from(rel), PathBuf::from("src").join("main.rs"));
+    }
+
+    #[test]
+    fn test_relativize_not_under_base() {
+        assert_eq!(relativize(Path::new("/work/other/a.txt"), Path::new("/work/project")), "/work/other/a.txt");
+        // 文字列として前方一致するだけのパスは対象外
+        assert_eq!(relativize(Path::new("/work/projects/a.txt"), Path::new("/work/project")), "/work/projects/a.txt");
+    }
+
+    #[test]
+    fn test_relativize_equal_path() {
+        assert_eq!(relativize(Path::new("/work/project"), Path::new("/work/project")), ".");
+        assert_eq!(relativize(Path::new("/work/project/"), Path::new("/work/project")), ".");
+    }
+
+    #[test]
+    fn test_relativize_case_insensitive() {
+        assert_eq!(relativize_with(Path::new("/Work/Project/a.txt"), Path::new("/work/project"), true), "a.txt");
+        assert_eq!(
+            relativize_with(Path::new("/Work/Project/a.txt"), Path::new("/work/project"), false),
+            "/Work/Project/a.txt"
+        );
+    }
+
+    #[test]
+    fn test_display_path_under_home() {
+        let home = Path::new("/home/user");
+        assert_eq!(display_path_under_home(Path::new("/home/user"), home, false), "~");
+        assert_eq!(
+            display_path_under_home(Path::new("/home/user/docs"), home, false),
+            format!("~{}docs", std::path::MAIN_SEPARATOR)
+        );
+        assert_eq!(display_path_under_home(Path::new("/tmp/docs"), home, false), "/tmp/docs");
+    }
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "USERPROFILE" => Some("C:\\Users\\taro".to_string()),
+            "PROJECT" => Some("/work/project".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_path_variables() {
+        assert_eq!(
+            expand_path_with(Path::new("%USERPROFILE%\\Documents"), lookup, None),
+            PathBuf::from("C:\\Users\\taro\\Documents")
+        );
+        assert_eq!(
+            expand_path_with(Path::new("${PROJECT}/src"), lookup, None),
+            PathBuf::from("/work/project/src")
+        );
+        // 定義されていない変数・対になっていない記号はそのまま
+        assert_eq!(
+            expand_path_with(Path::new("%UNKNOWN%/100%/${PROJECT"), lookup, None),
+            PathBuf::from("%UNKNOWN%/100%/${PROJECT")
+        );
+        // 展開するものがなければそのまま
+        assert_eq!(expand_path_with(Path::new("/plain/path"), lookup, None), PathBuf::from("/plain/path"));
+    }
+
+    #[test]
+    fn test_expand_path_home() {
+        let home = Path::new("/home/taro");
+        assert_eq!(expand_path_with(Path::new("~"), lookup, Some(home)), PathBuf::from("/home/taro"));
+        assert_eq!(
+            expand_path_with(Path::new("~/docs"), lookup, Some(home)),
+            home.join("docs")
+        );
+        // 先頭以外の `~` は展開しない
+        assert_eq!(expand_path_with(Path::new("/tmp/~x"), lookup, Some(home)), PathBuf::from("/tmp/~x"));
+    }
+
+    #[test]
+    fn test_is_network_path() {
+        assert!(is_network_path(Path::new(r"\\fileserver\share\docs")));
+        assert!(is_network_path(Path::new("//fileserver/share")));
+        assert!(is_network_path(Path::new(r"\\?\UNC\fileserver\share")));
+        assert!(!is_network_path(Path::new(r"\\?\C:\work")));
+        assert!(!is_network_path(Path::new(r"C:\work")));
+        assert!(!is_network_path(Path::new("/home/taro")));
+    }
+
+    #[test]
+    fn test_path_key_normalizes_case_and_separators() {
+        assert_eq!(path_key(Path::new(r"C:\Work\Docs\")), "c:/work/docs");
+        assert_eq!(path_key(Path::new("c:/work/docs")), "c:/work/docs");
+
+        // 存在するパスは解決してから比べる
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("Project");
+        std::fs::create_dir(&dir).unwrap();
+        assert_eq!(path_key(&dir.join(".").join("..").join("Project")), path_key(&dir));
+    }
+}
@@ -1,7 +1,7 @@
 // 統合テスト: ofkt の主要機能を統合的にテスト
 
 use ofkt::core::{AliasManager, FileManager, SearchEngine};
-use ofkt::data::models::FileAlias;
+use ofkt::data::models::{FileAlias, OpenAction};
 use std::path::PathBuf;
 use chrono::Utc;
 
@@ -92,6 +92,14 @@ fn test_search_with_tags() {
         created_at: now,
         last_accessed: now,
         is_favorite: false,
+        open_action: OpenAction::Open,
+        volume: None,
+        open_count: 0,
+        note: None,
+        reading: None,
+        order: 0,
+        is_broken: false,
+        is_unreachable: false,
     };
 
     let alias2 = FileAlias {
@@ -103,6 +111,14 @@ fn test_search_with_tags() {
         created_at: now,
         last_accessed: now,
         is_favorite: false,
+        open_action: OpenAction::Open,
+        volume: None,
+        open_count: 0,
+        note: None,
+        reading: None,
+        order: 0,
+        is_broken: false,
+        is_unreachable: false,
     };
 
     let mut search_engine = SearchEngine::with_aliases(vec![alias1, alias2]);
@@ -130,6 +146,14 @@ fn test_hierarchical_search() {
         created_at: now,
         last_accessed: now,
         is_favorite: false,
+        open_action: OpenAction::Open,
+        volume: None,
+        open_count: 0,
+        note: None,
+        reading: None,
+        order: 0,
+        is_broken: false,
+        is_unreachable: false,
     };
 
     let mut search_engine = SearchEngine::with_aliases(vec![alias]);
@@ -183,6 +207,14 @@ fn test_search_engine_cache() {
         created_at: now,
         last_accessed: now,
         is_favorite: false,
+        open_action: OpenAction::Open,
+        volume: None,
+        open_count: 0,
+        note: None,
+        reading: None,
+        order: 0,
+        is_broken: false,
+        is_unreachable: false,
     };
 
     let mut search_engine = SearchEngine::with_aliases(vec![alias]);
@@ -216,6 +248,14 @@ fn test_max_results_configuration() {
             created_at: now,
             last_accessed: now,
             is_favorite: false,
+            open_action: OpenAction::Open,
+            volume: None,
+            open_count: 0,
+            note: None,
+            reading: None,
+            order: 0,
+            is_broken: false,
+            is_unreachable: false,
         });
     }
 